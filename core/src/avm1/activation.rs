@@ -1195,9 +1195,35 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// Print the frames of a movie clip, as requested by the `print` functions.
+    fn print_target(
+        &mut self,
+        target: Value<'gc>,
+        print_as_bitmap: bool,
+        bounding_box: &str,
+    ) -> Result<(), Error<'gc>> {
+        let start = self.target_clip_or_root();
+        match self.resolve_target_display_object(start, target, false)? {
+            Some(target) => crate::print_job::print_movie_clip(
+                &mut self.context,
+                target,
+                bounding_box,
+                print_as_bitmap,
+            ),
+            None => avm_warn!(self, "print: Target not found"),
+        }
+        Ok(())
+    }
+
     fn action_get_url(&mut self, action: GetUrl) -> Result<FrameControl<'gc>, Error<'gc>> {
         let target = action.target.decode(self.encoding());
         let url = action.url.decode(self.encoding());
+        if let Some((print_as_bitmap, bounding_box)) = globals::print_job::parse_print_url(&url) {
+            let target = AvmString::new(self.context.gc_context, target.into_owned());
+            self.print_target(target.into(), print_as_bitmap, &bounding_box)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         if target.starts_with(WStr::from_units(b"_level")) && target.len() > 6 {
             match target[6..].parse::<i32>() {
@@ -1257,6 +1283,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return Ok(FrameControl::Continue);
         }
 
+        // The `print` functions are compiled to a `getURL` with a special URL.
+        if let Some((print_as_bitmap, bounding_box)) = globals::print_job::parse_print_url(&url) {
+            self.print_target(target_val, print_as_bitmap, &bounding_box)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         let level_target = if target.starts_with(WStr::from_units(b"_level")) && target.len() >= 6 {
             match target[6..].parse::<f64>() {
@@ -30,33 +30,38 @@ pub(crate) mod displacement_map_filter;
 pub(crate) mod drop_shadow_filter;
 pub(crate) mod error;
 mod external_interface;
+pub(crate) mod file_reference;
 mod function;
 pub(crate) mod glow_filter;
 pub(crate) mod gradient_filter;
 mod key;
 mod load_vars;
-mod local_connection;
+pub(crate) mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
 pub(crate) mod movie_clip;
 mod movie_clip_loader;
+pub(crate) mod net_connection;
 pub(crate) mod netstream;
 pub(crate) mod number;
 mod object;
 mod point;
+pub(crate) mod print_job;
 mod rectangle;
 mod selection;
 pub(crate) mod shared_object;
 pub(crate) mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
 pub(crate) mod system_security;
 pub(crate) mod text_field;
 mod text_format;
+pub(crate) mod text_snapshot;
 pub(crate) mod transform;
 mod video;
 pub(crate) mod xml;
@@ -511,6 +516,7 @@ pub struct SystemPrototypes<'gc> {
     pub convolution_filter: Object<'gc>,
     pub gradient_bevel_filter: Object<'gc>,
     pub gradient_glow_filter: Object<'gc>,
+    pub text_snapshot: Object<'gc>,
 }
 
 /// Initialize default global scope and builtins for an AVM1 instance.
@@ -535,6 +541,8 @@ pub fn create_globals<'gc>(
     let sound_proto = sound::create_proto(context, object_proto, function_proto);
 
     let text_field_proto = text_field::create_proto(context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(context, object_proto, function_proto);
+    let text_snapshot_proto = text_snapshot::create_proto(context, object_proto, function_proto);
     let text_format_proto = text_format::create_proto(context, object_proto, function_proto);
 
     let array_proto = array::create_proto(context, object_proto, function_proto);
@@ -578,9 +586,19 @@ pub fn create_globals<'gc>(
         movie_clip_loader_proto,
     );
 
+    let file_reference_proto = file_reference::create_proto(
+        context,
+        object_proto,
+        function_proto,
+        array_proto,
+        broadcaster_functions,
+    );
+
     let video_proto = video::create_proto(context, object_proto, function_proto);
+    let net_connection_proto = net_connection::create_proto(context, object_proto, function_proto);
     let netstream_proto = netstream::create_proto(context, object_proto, function_proto);
     let xml_socket_proto = xml_socket::create_proto(context, object_proto, function_proto);
+    let print_job_proto = print_job::create_proto(context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(context, object_proto, function_proto);
@@ -653,6 +671,13 @@ pub fn create_globals<'gc>(
         function_proto,
         text_field_proto,
     );
+    let style_sheet = style_sheet::create_class(context, style_sheet_proto, function_proto);
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let text_format = FunctionObject::constructor(
         gc_context,
         Executable::Native(text_format::constructor),
@@ -660,6 +685,7 @@ pub fn create_globals<'gc>(
         function_proto,
         text_format_proto,
     );
+    let text_snapshot = text_snapshot::create_class(context, text_snapshot_proto, function_proto);
     let array = array::create_array_object(context, array_proto, function_proto);
     let xmlnode = FunctionObject::constructor(
         gc_context,
@@ -673,14 +699,20 @@ pub fn create_globals<'gc>(
     let number = number::create_number_object(context, number_proto, function_proto);
     let boolean = boolean::create_boolean_object(context, boolean_proto, function_proto);
     let date = date::create_constructor(context, object_proto, function_proto);
+    let net_connection =
+        net_connection::create_class(context, net_connection_proto, function_proto);
     let netstream = netstream::create_class(context, netstream_proto, function_proto);
     let xml_socket = xml_socket::create_class(context, xml_socket_proto, function_proto);
+    let print_job = print_job::create_class(context, print_job_proto, function_proto);
+    let file_reference =
+        file_reference::create_class(context, file_reference_proto, function_proto);
 
     let flash = ScriptObject::new(gc_context, Some(object_proto));
 
     let geom = ScriptObject::new(gc_context, Some(object_proto));
     let filters = ScriptObject::new(gc_context, Some(object_proto));
     let display = ScriptObject::new(gc_context, Some(object_proto));
+    let net = ScriptObject::new(gc_context, Some(object_proto));
 
     let matrix = matrix::create_matrix_object(context, matrix_proto, function_proto);
     let point = point::create_point_object(context, point_proto, function_proto);
@@ -704,6 +736,13 @@ pub fn create_globals<'gc>(
     flash.define_value(gc_context, "geom", geom.into(), Attribute::empty());
     flash.define_value(gc_context, "filters", filters.into(), Attribute::empty());
     flash.define_value(gc_context, "display", display.into(), Attribute::empty());
+    flash.define_value(gc_context, "net", net.into(), Attribute::empty());
+    net.define_value(
+        gc_context,
+        "FileReference",
+        file_reference.into(),
+        Attribute::empty(),
+    );
     geom.define_value(gc_context, "Matrix", matrix.into(), Attribute::empty());
     geom.define_value(gc_context, "Point", point.into(), Attribute::empty());
     geom.define_value(
@@ -922,6 +961,12 @@ pub fn create_globals<'gc>(
         text_format.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "TextSnapshot",
+        text_snapshot.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "XML", xml.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "String", string.into(), Attribute::DONT_ENUM);
@@ -1051,6 +1096,12 @@ pub fn create_globals<'gc>(
         )),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "NetConnection",
+        net_connection.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(
         gc_context,
         "NetStream",
@@ -1063,6 +1114,12 @@ pub fn create_globals<'gc>(
         xml_socket.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "PrintJob",
+        print_job.into(),
+        Attribute::DONT_ENUM,
+    );
 
     define_properties_on(GLOBAL_DECLS, context, globals, function_proto);
 
@@ -1110,6 +1167,7 @@ pub fn create_globals<'gc>(
             convolution_filter,
             gradient_bevel_filter,
             gradient_glow_filter,
+            text_snapshot: text_snapshot_proto,
         },
         globals.into(),
         broadcaster_functions,
@@ -0,0 +1,410 @@
+//! `flash.net.FileReference` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::object::NativeObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{
+    Activation, ArrayObject, Error, ExecutionReason, Object, ScriptObject, TObject, Value,
+};
+use crate::backend::file_dialog::{FileFilter, SelectedFile};
+use crate::backend::navigator::Request;
+use crate::context::GcContext;
+use crate::file_reference::{
+    multipart_body, FileReferenceEvent, FileReferenceKind, FileSaveSource,
+};
+use crate::string::AvmString;
+use chrono::{DateTime, Utc};
+use gc_arena::{Collect, Gc};
+use std::cell::{Ref, RefCell};
+use url::form_urlencoded;
+
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+struct FileReferenceData {
+    /// The picked file, if any.
+    file: RefCell<Option<SelectedFile>>,
+}
+
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct FileReference<'gc>(Gc<'gc, FileReferenceData>);
+
+impl<'gc> FileReference<'gc> {
+    pub fn file(&self) -> Ref<'_, Option<SelectedFile>> {
+        self.0.file.borrow()
+    }
+
+    pub fn set_file(&self, file: SelectedFile) {
+        self.0.file.replace(Some(file));
+    }
+
+    pub fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::FileReference(file_reference) = object.native() {
+                return Some(file_reference);
+            }
+        }
+        None
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "creationDate" => property(get_creation_date);
+    "creator" => property(get_creator);
+    "modificationDate" => property(get_modification_date);
+    "name" => property(get_name);
+    "size" => property(get_size);
+    "type" => property(get_type);
+    "browse" => method(browse);
+    "cancel" => method(cancel);
+    "download" => method(download);
+    "upload" => method(upload);
+};
+
+fn date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    date: Option<DateTime<Utc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(date) = date else {
+        return Ok(Value::Undefined);
+    };
+
+    let millis = date.timestamp_millis() as f64;
+    let date_constructor = activation.context.avm1.prototypes().date_constructor;
+    date_constructor.construct(activation, &[millis.into()])
+}
+
+fn get_creation_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = FileReference::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+    let creation_date = file_reference
+        .file()
+        .as_ref()
+        .and_then(|file| file.creation_date);
+    date(activation, creation_date)
+}
+
+fn get_creator<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The creator is only known on Macintosh.
+    Ok(Value::Null)
+}
+
+fn get_modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = FileReference::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+    let modification_date = file_reference
+        .file()
+        .as_ref()
+        .and_then(|file| file.modification_date);
+    date(activation, modification_date)
+}
+
+fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(file_reference) = FileReference::cast(this.into()) {
+        if let Some(file) = file_reference.file().as_ref() {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, &file.name).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(file_reference) = FileReference::cast(this.into()) {
+        if let Some(file) = file_reference.file().as_ref() {
+            return Ok(file.data.len().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(file_reference) = FileReference::cast(this.into()) {
+        if let Some(extension) = file_reference
+            .file()
+            .as_ref()
+            .and_then(|file| file.extension())
+        {
+            let file_type = format!(".{extension}");
+            return Ok(AvmString::new_utf8(activation.context.gc_context, file_type).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if FileReference::cast(this.into()).is_none() {
+        return Ok(Value::Undefined);
+    }
+
+    let mut filters = Vec::new();
+    if let Some(Value::Object(type_list)) = args.get(0) {
+        let length = type_list.length(activation)?;
+        for i in 0..length {
+            let Value::Object(filter) = type_list.get_element(activation, i) else {
+                continue;
+            };
+            let description = filter.get("description", activation)?;
+            let extension = filter.get("extension", activation)?;
+            // Both are required for the filter to be valid.
+            if matches!(description, Value::Undefined) || matches!(extension, Value::Undefined) {
+                return Ok(false.into());
+            }
+            let description = description.coerce_to_string(activation)?;
+            let extension = extension.coerce_to_string(activation)?;
+            let mac_type = match filter.get("macType", activation)? {
+                Value::Undefined | Value::Null => None,
+                mac_type => Some(mac_type.coerce_to_string(activation)?),
+            };
+            filters.push(FileFilter::new(
+                &description.to_utf8_lossy(),
+                &extension.to_utf8_lossy(),
+                mac_type
+                    .map(|mac_type| mac_type.to_utf8_lossy().into_owned())
+                    .as_deref(),
+            ));
+        }
+    }
+
+    let dialog = activation.context.file_dialog.browse(&filters);
+    let future = activation.context.load_manager.browse_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm1(this),
+        dialog,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .load_manager
+        .cancel_file_reference(FileReferenceKind::Avm1(this));
+    Ok(Value::Undefined)
+}
+
+fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if FileReference::cast(this.into()).is_none() {
+        return Ok(Value::Undefined);
+    }
+
+    let url = match args.get(0) {
+        Some(Value::String(url)) if !url.is_empty() => url.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+
+    let default_file_name = match args.get(1) {
+        Some(Value::Undefined | Value::Null) | None => {
+            // Suggest the name of the downloaded file.
+            let path = url.split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default().to_string()
+        }
+        Some(name) => name
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned(),
+    };
+
+    let dialog = activation.context.file_dialog.save(&default_file_name);
+    let future = activation.context.load_manager.save_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm1(this),
+        dialog,
+        FileSaveSource::Download(Request::get(url)),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = FileReference::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let url = match args.get(0) {
+        Some(Value::String(url)) if !url.is_empty() => url.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+
+    let field_name = match args.get(1) {
+        Some(Value::Undefined | Value::Null) | None => "Filedata".to_string(),
+        Some(name) => name
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned(),
+    };
+
+    // `postData` holds URL-encoded variables, sent as fields of the form.
+    let variables: Vec<_> = match this.get("postData", activation)? {
+        Value::Undefined | Value::Null => Vec::new(),
+        post_data => {
+            let post_data = post_data
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned();
+            form_urlencoded::parse(post_data.as_bytes())
+                .into_owned()
+                .collect()
+        }
+    };
+
+    let body = match file_reference.file().as_ref() {
+        Some(file) => multipart_body(&variables, &field_name, file),
+        // A file must be picked first.
+        None => return Ok(false.into()),
+    };
+
+    let future = activation.context.load_manager.upload_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm1(this),
+        Request::post(url, Some(body)),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+/// Call the listeners of a `FileReference` with the progress of an operation.
+pub fn broadcast<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    event: FileReferenceEvent<'_>,
+) {
+    let mut args = vec![];
+    let name = match event {
+        FileReferenceEvent::Select => "onSelect",
+        FileReferenceEvent::Cancel => "onCancel",
+        FileReferenceEvent::Open => "onOpen",
+        FileReferenceEvent::Progress {
+            bytes_loaded,
+            bytes_total,
+        } => {
+            args.push(bytes_loaded.into());
+            args.push(bytes_total.into());
+            "onProgress"
+        }
+        FileReferenceEvent::Complete => "onComplete",
+        FileReferenceEvent::HttpStatus(status) => {
+            args.push(status.into());
+            "onHTTPError"
+        }
+        FileReferenceEvent::IoError => "onIOError",
+        FileReferenceEvent::UploadCompleteData(data) => {
+            args.push(AvmString::new_utf8_bytes(activation.context.gc_context, data).into());
+            "onUploadCompleteData"
+        }
+    };
+
+    let mut broadcast_args = vec![name.into(), this.into()];
+    broadcast_args.extend(args);
+    let _ = this.call_method(
+        "broadcastMessage".into(),
+        &broadcast_args,
+        activation,
+        ExecutionReason::Special,
+    );
+}
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file_reference = FileReference(Gc::new(
+        activation.gc(),
+        FileReferenceData {
+            file: RefCell::new(None),
+        },
+    ));
+    this.set_native(activation.gc(), NativeObject::FileReference(file_reference));
+
+    let listeners = ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        [this.into()],
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "_listeners",
+        Value::Object(listeners.into()),
+        Attribute::DONT_ENUM,
+    );
+
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+) -> Object<'gc> {
+    let file_reference_proto = ScriptObject::new(context.gc_context, Some(proto));
+    broadcaster_functions.initialize(context.gc_context, file_reference_proto.into(), array_proto);
+    define_properties_on(PROTO_DECLS, context, file_reference_proto, fn_proto);
+    file_reference_proto.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    file_reference_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        file_reference_proto,
+    )
+}
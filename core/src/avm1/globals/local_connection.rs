@@ -2,38 +2,176 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::shared_object::{deserialize_value, new_lso};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, Value};
+use crate::avm1::{Attribute, ExecutionReason, Object, ScriptObject, TObject, Value};
+use crate::backend::local_connection::LocalConnectionMessage;
 use crate::context::GcContext;
 use crate::display_object::TDisplayObject;
+use crate::local_connection::{LocalConnectionKind, LocalConnections, MAX_MESSAGE_SIZE};
 use crate::string::AvmString;
+use std::collections::BTreeMap;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "domain" => method(domain; DONT_DELETE | READ_ONLY);
+    "connect" => method(connect; DONT_DELETE | READ_ONLY);
+    "close" => method(close; DONT_DELETE | READ_ONLY);
+    "send" => method(send; DONT_DELETE | READ_ONLY);
 };
 
+fn movie_domain(activation: &mut Activation<'_, '_>) -> Option<String> {
+    LocalConnections::get_domain(activation.base_clip().movie().url())
+}
+
 pub fn domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = activation.base_clip().movie();
-
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(activation) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(Value::String(name)) = args.get(0) else {
+        return Ok(false.into());
+    };
+    if name.is_empty() {
+        return Ok(false.into());
+    }
+
+    let domain = movie_domain(activation).unwrap_or_else(|| "localhost".to_string());
+    let context = &mut activation.context;
+    let connected = context.local_connections.connect(
+        context.local_connection,
+        LocalConnectionKind::Avm1(this),
+        &domain,
+        &name.to_utf8_lossy(),
+    );
+    Ok(connected.into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let context = &mut activation.context;
+    context
+        .local_connections
+        .close(context.local_connection, LocalConnectionKind::Avm1(this));
+    Ok(Value::Undefined)
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let [Value::String(name), Value::String(method_name), ..] = args else {
+        return Ok(false.into());
+    };
+    if name.is_empty() || method_name.is_empty() {
+        return Ok(false.into());
+    }
+
+    let arguments = ScriptObject::new(activation.context.gc_context, None);
+    for (index, arg) in args.iter().skip(2).enumerate() {
+        arguments.define_value(
+            activation.context.gc_context,
+            AvmString::new_utf8(activation.context.gc_context, index.to_string()),
+            *arg,
+            Attribute::empty(),
+        );
+    }
+    let mut lso = new_lso(activation, "", arguments.into());
+    let arguments = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    if arguments.len() > MAX_MESSAGE_SIZE {
+        return Ok(false.into());
+    }
+
+    let message = LocalConnectionMessage {
+        sender_domain: movie_domain(activation).unwrap_or_else(|| "localhost".to_string()),
+        method_name: method_name.to_utf8_lossy().into_owned(),
+        arguments,
+    };
+    let context = &mut activation.context;
+    context.local_connections.send(
+        context.local_connection,
+        LocalConnectionKind::Avm1(this),
+        &name.to_utf8_lossy(),
+        message,
+    );
+    Ok(true.into())
+}
+
+/// Deliver a message to a connected `LocalConnection`, calling the method on the object itself.
+pub fn receive_message<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    domain: &str,
+    message: &LocalConnectionMessage,
+) {
+    if message.sender_domain != domain {
+        // Other domains must be accepted by the `allowDomain` callback.
+        let sender_domain =
+            AvmString::new_utf8(activation.context.gc_context, &message.sender_domain);
+        let is_allowed = this
+            .call_method(
+                "allowDomain".into(),
+                &[sender_domain.into()],
+                activation,
+                ExecutionReason::Special,
+            )
+            .map_or(false, |allowed| allowed.as_bool(activation.swf_version()));
+
+        if !is_allowed {
+            tracing::warn!(
+                "LocalConnection: Ignoring message from disallowed domain {}",
+                message.sender_domain
+            );
+            return;
+        }
+    }
+
+    let mut reader = flash_lso::read::Reader::default();
+    let Ok(lso) = reader.parse(&message.arguments) else {
+        tracing::warn!("LocalConnection: Unable to parse message arguments");
+        return;
+    };
+
+    let mut reference_cache = BTreeMap::default();
+    let mut arguments = Vec::new();
+    for element in &lso.body {
+        let Ok(index) = element.name.parse::<usize>() else {
+            continue;
+        };
+        if arguments.len() <= index {
+            arguments.resize(index + 1, Value::Undefined);
+        }
+        arguments[index] = deserialize_value(
+            activation,
+            element.value(),
+            &reader.amf0_decoder,
+            &mut reference_cache,
+        );
+    }
+
+    let method_name = AvmString::new_utf8(activation.context.gc_context, &message.method_name);
+    let _ = this.call_method(
+        method_name,
+        &arguments,
+        activation,
+        ExecutionReason::Special,
+    );
 }
 
 pub fn constructor<'gc>(
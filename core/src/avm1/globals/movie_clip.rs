@@ -3,7 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::matrix::gradient_object_to_matrix;
-use crate::avm1::globals::{self, bitmap_filter, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
+use crate::avm1::globals::{self, bitmap_filter, text_snapshot, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{self, ArrayObject, Object, ScriptObject, TObject, Value};
@@ -85,6 +85,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "getNextHighestDepth" => method(mc_method!(get_next_highest_depth); DONT_ENUM | DONT_DELETE | VERSION_7);
     "getRect" => method(mc_method!(get_rect); DONT_ENUM | DONT_DELETE | VERSION_8);
     "getSWFVersion" => method(mc_method!(get_swf_version); DONT_ENUM | DONT_DELETE);
+    "getTextSnapshot" => method(mc_method!(get_text_snapshot); DONT_ENUM | DONT_DELETE | VERSION_6);
     "getURL" => method(mc_method!(get_url); DONT_ENUM | DONT_DELETE);
     "globalToLocal" => method(mc_method!(global_to_local); DONT_ENUM | DONT_DELETE);
     "gotoAndPlay" => method(mc_method!(goto_and_play); DONT_ENUM | DONT_DELETE);
@@ -115,6 +116,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "enabled" => bool(true; DONT_ENUM);
     "_lockroot" => property(mc_getter!(lock_root), mc_setter!(set_lock_root); DONT_DELETE | DONT_ENUM);
     "scrollRect" => property(mc_getter!(scroll_rect), mc_setter!(set_scroll_rect); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "scale9Grid" => property(mc_getter!(scale9_grid), mc_setter!(set_scale9_grid); DONT_DELETE | DONT_ENUM | VERSION_8);
     "transform" => property(mc_getter!(transform), mc_setter!(set_transform); DONT_ENUM | VERSION_8);
     "useHandCursor" => bool(true; DONT_ENUM);
     // NOTE: `focusEnabled` is not a built-in property of MovieClip.
@@ -190,6 +192,31 @@ fn set_scroll_rect<'gc>(
     Ok(())
 }
 
+fn scale9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(grid) = this.scaling_grid() {
+        new_rectangle(activation, grid)
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_scale9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let grid = if let Value::Object(object) = value {
+        object_to_rectangle(activation, object)?
+    } else {
+        None
+    };
+    this.set_scaling_grid(activation.context.gc_context, grid);
+    Ok(())
+}
+
 #[allow(clippy::comparison_chain)]
 pub fn hit_test<'gc>(
     movie_clip: MovieClip<'gc>,
@@ -1506,6 +1533,14 @@ fn get_swf_version<'gc>(
     })
 }
 
+fn get_text_snapshot<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(text_snapshot::create(activation, movie_clip.into()).into())
+}
+
 pub fn get_url<'gc>(
     _movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc>,
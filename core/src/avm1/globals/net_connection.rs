@@ -0,0 +1,527 @@
+//! `NetConnection` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::shared_object::{deserialize_value, serialize_value};
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, ExecutionReason, Object, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::backend::navigator::Request;
+use crate::context::GcContext;
+use crate::net_connection::{
+    read_packet, write_arguments, write_packet, write_value, AmfHeader, AmfMessage,
+    NetConnectionCall, AMF_CONTENT_TYPE,
+};
+use crate::string::AvmString;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use gc_arena::{Collect, Gc};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Collect)]
+#[collect(require_static)]
+struct NetConnectionData {
+    /// The URI given to `connect`, if any.
+    uri: RefCell<Option<String>>,
+
+    /// Whether the connection is open, which is only the case for
+    /// connections to `null` as Flash Media Server isn't supported.
+    is_connected: Cell<bool>,
+
+    /// The URL of the Flash Remoting gateway, if connected to one.
+    gateway_url: RefCell<Option<String>>,
+
+    /// The encoded headers sent with every call.
+    headers: RefCell<Vec<AmfHeader<Vec<u8>>>>,
+
+    /// The identifier of the next call, used to match it with its response.
+    next_response_id: Cell<u32>,
+}
+
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct NetConnection<'gc>(Gc<'gc, NetConnectionData>);
+
+impl<'gc> NetConnection<'gc> {
+    pub fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::NetConnection(net_connection) = object.native() {
+                return Some(net_connection);
+            }
+        }
+        None
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.0.is_connected.get()
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "isConnected" => property(get_is_connected);
+    "uri" => property(get_uri);
+    "addHeader" => method(add_header; DONT_ENUM | DONT_DELETE);
+    "call" => method(call; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let data = NetConnectionData {
+        next_response_id: Cell::new(1),
+        ..Default::default()
+    };
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::NetConnection(NetConnection(Gc::new(activation.context.gc_context, data))),
+    );
+    Ok(this.into())
+}
+
+/// Call the `onStatus` handler of an object with an info object.
+fn on_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    code: &'static str,
+    level: &'static str,
+) -> Result<(), Error<'gc>> {
+    let info_object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    info_object.set("code", code.into(), activation)?;
+    info_object.set("level", level.into(), activation)?;
+    this.call_method(
+        "onStatus".into(),
+        &[info_object.into()],
+        activation,
+        ExecutionReason::Special,
+    )?;
+    Ok(())
+}
+
+fn get_is_connected<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        return Ok(net_connection.is_connected().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_uri<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        if let Some(uri) = &*net_connection.0.uri.borrow() {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, uri).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(net_connection) = NetConnection::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let command = args.get(0).unwrap_or(&Value::Undefined);
+    if let Value::Null | Value::Undefined = command {
+        // A connection to `null` is used to play local or progressively downloaded videos.
+        net_connection.0.uri.replace(Some("null".to_string()));
+        net_connection.0.gateway_url.replace(None);
+        net_connection.0.is_connected.set(true);
+        on_status(activation, this, "NetConnection.Connect.Success", "status")?;
+        return Ok(true.into());
+    }
+
+    let url = command
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    net_connection.0.uri.replace(Some(url.clone()));
+    net_connection.0.is_connected.set(false);
+    if url.starts_with("http://") || url.starts_with("https://") {
+        // Connecting to a Flash Remoting gateway only remembers its URL,
+        // requests are made by each call.
+        net_connection.0.gateway_url.replace(Some(url));
+        return Ok(true.into());
+    }
+
+    net_connection.0.gateway_url.replace(None);
+    avm1_stub!(
+        activation,
+        "NetConnection",
+        "connect",
+        "with non-null, non-http command"
+    );
+    Ok(false.into())
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(net_connection) = NetConnection::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let was_connected = net_connection.is_connected();
+    net_connection.0.uri.replace(None);
+    net_connection.0.gateway_url.replace(None);
+    net_connection.0.is_connected.set(false);
+    if was_connected {
+        on_status(activation, this, "NetConnection.Connect.Closed", "status")?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn add_header<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(net_connection) = NetConnection::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let must_understand = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+    let param = args.get(2).copied().unwrap_or(Value::Undefined);
+
+    let value = match param {
+        Value::Null | Value::Undefined => None,
+        param => serialize_value(activation, param)
+            .and_then(|value| write_value(value, AMFVersion::AMF0)),
+    };
+
+    let mut headers = net_connection.0.headers.borrow_mut();
+    headers.retain(|header| header.name != name);
+    let Some(value) = value else {
+        return Ok(Value::Undefined);
+    };
+    headers.push(AmfHeader {
+        name,
+        must_understand,
+        value,
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.call`
+fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(net_connection) = NetConnection::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let command = match args.get(0) {
+        Some(Value::Null | Value::Undefined) | None => return Ok(Value::Undefined),
+        Some(command) => command.coerce_to_string(activation)?,
+    };
+    let result_object = match args.get(1) {
+        Some(Value::Object(result_object)) => Some(*result_object),
+        _ => None,
+    };
+
+    let Some(gateway_url) = net_connection.0.gateway_url.borrow().clone() else {
+        avm1_stub!(
+            activation,
+            "NetConnection",
+            "call",
+            "without a Flash Remoting gateway"
+        );
+        return Ok(Value::Undefined);
+    };
+
+    let arguments = args
+        .iter()
+        .skip(2)
+        .map(|arg| serialize_value(activation, *arg).unwrap_or(AmfValue::Undefined))
+        .collect();
+    let Some(arguments) = write_arguments(arguments, AMFVersion::AMF0) else {
+        tracing::warn!("NetConnection: Unable to serialize call to {}", command);
+        return Ok(Value::Undefined);
+    };
+
+    let response_id = net_connection.0.next_response_id.get();
+    net_connection.0.next_response_id.set(response_id + 1);
+
+    let message = AmfMessage {
+        target_uri: command.to_utf8_lossy().into_owned(),
+        response_uri: format!("/{response_id}"),
+        value: arguments,
+    };
+    let packet = write_packet(
+        AMFVersion::AMF0,
+        &net_connection.0.headers.borrow(),
+        &[message],
+    );
+    let request = Request::post(gateway_url, Some((packet, AMF_CONTENT_TYPE.to_string())));
+
+    let future = activation.context.load_manager.call_net_connection(
+        activation.context.player.clone(),
+        NetConnectionCall::Avm1 {
+            connection: this,
+            result_object,
+        },
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+/// Handle the response of a gateway to a call, passing the results to the
+/// `onResult` and `onStatus` handlers of the result object.
+///
+/// `data` is `None` if the request failed.
+pub fn receive_call_response<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    result_object: Option<Object<'gc>>,
+    data: Option<&[u8]>,
+) -> Result<(), Error<'gc>> {
+    let Some(data) = data else {
+        return on_status(activation, this, "NetConnection.Call.Failed", "error");
+    };
+
+    let Some((headers, messages)) = read_packet(data) else {
+        return on_status(activation, this, "NetConnection.Call.BadVersion", "error");
+    };
+
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        for header in headers {
+            // Gateways can ask for the session to be tracked in the URL.
+            let mut gateway_url = net_connection.0.gateway_url.borrow_mut();
+            match (header.name.as_str(), &header.value) {
+                ("AppendToGatewayUrl", AmfValue::String(suffix)) => {
+                    if let Some(url) = gateway_url.as_mut() {
+                        url.push_str(suffix);
+                    }
+                }
+                ("ReplaceGatewayUrl", AmfValue::String(url)) => {
+                    *gateway_url = Some(url.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let Some(result_object) = result_object else {
+        return Ok(());
+    };
+
+    let decoder = AMF0Decoder::default();
+    for message in messages {
+        let handler = if message.target_uri.ends_with("/onResult") {
+            "onResult"
+        } else if message.target_uri.ends_with("/onStatus") {
+            "onStatus"
+        } else {
+            tracing::warn!(
+                "NetConnection: Ignoring message with unknown target {}",
+                message.target_uri
+            );
+            continue;
+        };
+
+        let value = deserialize_value(
+            activation,
+            &message.value,
+            &decoder,
+            &mut BTreeMap::default(),
+        );
+        result_object.call_method(
+            handler.into(),
+            &[value],
+            activation,
+            ExecutionReason::Special,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    net_connection_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        net_connection_proto,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    thread_local! {
+        static STATUS_CODES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    }
+
+    fn record_status<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        _this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let info = args
+            .first()
+            .expect("onStatus should receive an info object")
+            .coerce_to_object(activation);
+        let code = info.get("code", activation)?.coerce_to_string(activation)?;
+        STATUS_CODES.with(|codes| codes.borrow_mut().push(code.to_string()));
+        Ok(Value::Undefined)
+    }
+
+    fn construct<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        class_name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation
+            .context
+            .avm1
+            .global_object()
+            .get(class_name, activation)?
+            .coerce_to_object(activation);
+        Ok(class
+            .construct(activation, args)?
+            .coerce_to_object(activation))
+    }
+
+    /// Constructs a `NetConnection` recording the codes of its status events.
+    fn net_connection<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let net_connection = construct(activation, "NetConnection", &[])?;
+        let on_status = FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(record_status),
+            activation.context.avm1.prototypes().function,
+            activation.context.avm1.prototypes().function,
+        );
+        net_connection.set("onStatus", on_status.into(), activation)?;
+        Ok(net_connection)
+    }
+
+    fn call_method<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        object.call_method(name.into(), args, activation, ExecutionReason::Special)
+    }
+
+    #[test]
+    fn connect_to_null() {
+        with_avm(8, |activation, _root| {
+            let net_connection = net_connection(activation)?;
+            assert_eq!(net_connection.get("isConnected", activation)?, false.into());
+            assert_eq!(net_connection.get("uri", activation)?, Value::Undefined);
+
+            let result = call_method(activation, net_connection, "connect", &[Value::Null])?;
+            assert_eq!(result, true.into());
+            assert_eq!(net_connection.get("isConnected", activation)?, true.into());
+            let uri = net_connection.get("uri", activation)?;
+            assert_eq!(uri.coerce_to_string(activation)?.to_string(), "null");
+
+            call_method(activation, net_connection, "close", &[])?;
+            assert_eq!(net_connection.get("isConnected", activation)?, false.into());
+            assert_eq!(net_connection.get("uri", activation)?, Value::Undefined);
+
+            // Closing a closed connection doesn't report anything.
+            call_method(activation, net_connection, "close", &[])?;
+
+            let codes = STATUS_CODES.with(|codes| codes.take());
+            assert_eq!(
+                codes,
+                [
+                    "NetConnection.Connect.Success",
+                    "NetConnection.Connect.Closed"
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn connect_to_gateway() {
+        with_avm(8, |activation, _root| {
+            let net_connection = net_connection(activation)?;
+            let url =
+                AvmString::new_utf8(activation.context.gc_context, "http://example.com/gateway");
+
+            let result = call_method(activation, net_connection, "connect", &[url.into()])?;
+            assert_eq!(result, true.into());
+            assert_eq!(net_connection.get("isConnected", activation)?, false.into());
+            let uri = net_connection.get("uri", activation)?;
+            assert_eq!(
+                uri.coerce_to_string(activation)?.to_string(),
+                "http://example.com/gateway"
+            );
+
+            let codes = STATUS_CODES.with(|codes| codes.take());
+            assert!(codes.is_empty());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn net_stream_from_connection() {
+        with_avm(8, |activation, _root| {
+            let net_connection = net_connection(activation)?;
+            call_method(activation, net_connection, "connect", &[Value::Null])?;
+
+            let net_stream = construct(activation, "NetStream", &[net_connection.into()])?;
+            assert!(matches!(net_stream.native(), NativeObject::NetStream(_)));
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,456 @@
+//! `PrintJob` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
+use crate::backend::print::{PageFormat, PrintOrientation};
+use crate::context::GcContext;
+use crate::prelude::*;
+use crate::print_job::{print_page, send_print_job, PrintPageOptions};
+use crate::string::WStr;
+use gc_arena::{Collect, Gc};
+use std::cell::Cell;
+
+#[derive(Debug, Default, Collect)]
+#[collect(require_static)]
+struct PrintJobData {
+    /// The format of the pages, once the print job started.
+    format: Cell<Option<PageFormat>>,
+}
+
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct PrintJob<'gc>(Gc<'gc, PrintJobData>);
+
+impl<'gc> PrintJob<'gc> {
+    fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::PrintJob(print_job) = object.native() {
+                return Some(print_job);
+            }
+        }
+        None
+    }
+
+    fn format(&self) -> Option<PageFormat> {
+        self.0.format.get()
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "orientation" => property(get_orientation);
+    "pageHeight" => property(get_page_height);
+    "pageWidth" => property(get_page_width);
+    "paperHeight" => property(get_paper_height);
+    "paperWidth" => property(get_paper_width);
+    "addPage" => method(add_page; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+    "start" => method(start; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `PrintJob`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let print_job = PrintJob(Gc::new(
+        activation.context.gc_context,
+        PrintJobData::default(),
+    ));
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::PrintJob(print_job),
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    print_job_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        print_job_proto,
+    )
+}
+
+/// Parse the URL of a `print` function call, which is compiled to a `getURL`.
+///
+/// Returns whether to print as a bitmap, and the bounding box option.
+pub fn parse_print_url(url: &WStr) -> Option<(bool, String)> {
+    let (scheme, bounding_box) = match url.find(b':') {
+        Some(index) => (&url[..index], &url[index + 1..]),
+        None => return None,
+    };
+    let print_as_bitmap = if scheme.eq_ignore_case(WStr::from_units(b"print")) {
+        false
+    } else if scheme.eq_ignore_case(WStr::from_units(b"printasbitmap")) {
+        true
+    } else {
+        return None;
+    };
+
+    let bounding_box = bounding_box.to_utf8_lossy().to_ascii_lowercase();
+    let bounding_box = match bounding_box.strip_prefix('#') {
+        Some(bounding_box) => bounding_box.to_string(),
+        None => "bmovie".to_string(),
+    };
+    Some((print_as_bitmap, bounding_box))
+}
+
+fn page_property<'gc>(this: Object<'gc>, property: fn(&PageFormat) -> Value<'gc>) -> Value<'gc> {
+    PrintJob::cast(this.into())
+        .and_then(|print_job| print_job.format())
+        .map_or(Value::Undefined, |format| property(&format))
+}
+
+fn get_orientation<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(page_property(this, |format| match format.orientation {
+        PrintOrientation::Portrait => "portrait".into(),
+        PrintOrientation::Landscape => "landscape".into(),
+    }))
+}
+
+fn get_page_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(page_property(this, |format| format.page_height.into()))
+}
+
+fn get_page_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(page_property(this, |format| format.page_width.into()))
+}
+
+fn get_paper_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(page_property(this, |format| format.paper_height.into()))
+}
+
+fn get_paper_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(page_property(this, |format| format.paper_width.into()))
+}
+
+fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(print_job) = PrintJob::cast(this.into()) else {
+        return Ok(false.into());
+    };
+    if print_job.format().is_some() {
+        return Ok(false.into());
+    }
+
+    let format = activation.context.print.start();
+    print_job.0.format.set(format);
+    Ok(format.is_some().into())
+}
+
+/// Read a rectangle given as an object with `xMin`, `xMax`, `yMin` and `yMax` properties.
+fn print_area<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Option<Rectangle<Twips>>, Error<'gc>> {
+    let mut values = [0.0; 4];
+    for (name, value) in ["xMin", "xMax", "yMin", "yMax"]
+        .into_iter()
+        .zip(&mut values)
+    {
+        match object.get(name, activation)? {
+            Value::Undefined | Value::Null => return Ok(None),
+            v => *value = v.coerce_to_f64(activation)?,
+        }
+    }
+    let [x_min, x_max, y_min, y_max] = values;
+    Ok(Some(Rectangle {
+        x_min: Twips::from_pixels(x_min),
+        x_max: Twips::from_pixels(x_max),
+        y_min: Twips::from_pixels(y_min),
+        y_max: Twips::from_pixels(y_max),
+    }))
+}
+
+fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = PrintJob::cast(this.into()).and_then(|print_job| print_job.format()) else {
+        return Ok(false.into());
+    };
+
+    // The target is either a movie clip, or the number of a level.
+    let target = match args.get(0).copied().unwrap_or(Value::Undefined) {
+        Value::Number(level) => activation.get_level(level as i32),
+        target => {
+            let start = activation.target_clip_or_root();
+            activation.resolve_target_display_object(start, target, false)?
+        }
+    };
+    let Some(target) = target else {
+        return Ok(false.into());
+    };
+
+    // Without a valid area, the whole stage of the target is printed.
+    let print_area = match args.get(1) {
+        Some(Value::Object(object)) => print_area(activation, *object)?,
+        _ => None,
+    }
+    .unwrap_or_else(|| activation.context.swf.stage_size().clone());
+
+    let print_as_bitmap = match args.get(2) {
+        Some(Value::Object(options)) => options
+            .get("printAsBitmap", activation)?
+            .as_bool(activation.swf_version()),
+        _ => false,
+    };
+
+    let frame = match args.get(3) {
+        Some(frame) => frame.coerce_to_i32(activation)?,
+        None => 0,
+    };
+
+    let options = PrintPageOptions {
+        print_area: Some(print_area),
+        print_as_bitmap,
+        fit_to_page: false,
+        frame: (frame > 0).then_some(frame as u16),
+    };
+    Ok(print_page(&mut activation.context, &format, target, &options).into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(print_job) = PrintJob::cast(this.into()) {
+        if print_job.0.format.take().is_some() {
+            send_print_job(&mut activation.context);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::{ActivationIdentifier, ExecutionReason};
+    use crate::backend::print::MemoryPrintBackend;
+    use crate::display_object::TDisplayObject;
+    use crate::limits::ExecutionLimit;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use ruffle_render::backend::ViewportDimensions;
+    use ruffle_render_software::SoftwareRenderBackend;
+    use swf::{
+        Color, FillStyle, Header, PlaceObjectAction, PointDelta, ShapeFlag, ShapeRecord,
+        ShapeStyles, StyleChangeData, Tag,
+    };
+
+    /// Constructs a movie with an empty first frame, and a red 10x10 square on
+    /// its second frame.
+    fn two_frame_movie() -> SwfMovie {
+        let size = Twips::from_pixels(10.0);
+        let bounds = Rectangle {
+            x_min: Twips::ZERO,
+            x_max: size,
+            y_min: Twips::ZERO,
+            y_max: size,
+        };
+        let square = swf::Shape {
+            version: 1,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            flags: ShapeFlag::empty(),
+            styles: ShapeStyles {
+                fill_styles: vec![FillStyle::Color(Color::RED)],
+                line_styles: vec![],
+            },
+            shape: vec![
+                ShapeRecord::StyleChange(Box::new(StyleChangeData {
+                    move_to: None,
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                })),
+                ShapeRecord::StraightEdge {
+                    delta: PointDelta::new(size, Twips::ZERO),
+                },
+                ShapeRecord::StraightEdge {
+                    delta: PointDelta::new(Twips::ZERO, size),
+                },
+                ShapeRecord::StraightEdge {
+                    delta: PointDelta::new(-size, Twips::ZERO),
+                },
+                ShapeRecord::StraightEdge {
+                    delta: PointDelta::new(Twips::ZERO, -size),
+                },
+            ],
+        };
+        let place = swf::PlaceObject {
+            version: 2,
+            action: PlaceObjectAction::Place(1),
+            depth: 1,
+            matrix: None,
+            color_transform: None,
+            ratio: None,
+            name: None,
+            clip_depth: None,
+            class_name: None,
+            filters: None,
+            background_color: None,
+            blend_mode: None,
+            clip_actions: None,
+            has_image: false,
+            is_bitmap_cached: None,
+            is_visible: None,
+            amf_data: None,
+        };
+        let header = Header {
+            stage_size: Rectangle {
+                x_min: Twips::ZERO,
+                x_max: Twips::from_pixels(100.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(100.0),
+            },
+            num_frames: 2,
+            ..Header::default_with_swf_version(10)
+        };
+
+        let mut data = Vec::new();
+        swf::write_swf(
+            &header,
+            &[
+                Tag::DefineShape(square),
+                Tag::ShowFrame,
+                Tag::PlaceObject(Box::new(place)),
+                Tag::ShowFrame,
+            ],
+            &mut data,
+        )
+        .unwrap();
+        SwfMovie::from_data(&data, "file:///movie.swf".into(), None).unwrap()
+    }
+
+    fn call_method<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        object.call_method(name.into(), args, activation, ExecutionReason::Special)
+    }
+
+    #[test]
+    fn add_page_prints_other_frame() {
+        let print = MemoryPrintBackend::new(PageFormat::LETTER);
+        let player = PlayerBuilder::new()
+            .with_renderer(SoftwareRenderBackend::new(ViewportDimensions {
+                width: 100,
+                height: 100,
+                scale_factor: 1.0,
+            }))
+            .with_print(print.clone())
+            .with_movie(two_frame_movie())
+            .build();
+        let mut player = player.lock().unwrap();
+        player.preload(&mut ExecutionLimit::none());
+
+        player.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip().expect("Root should exist");
+            let current_frame = root.as_movie_clip().unwrap().current_frame();
+
+            let mut activation =
+                Activation::from_stub(context.reborrow(), ActivationIdentifier::root("[Test]"));
+            let print_job_class = activation
+                .context
+                .avm1
+                .global_object()
+                .get("PrintJob", &mut activation)
+                .unwrap()
+                .coerce_to_object(&mut activation);
+            let print_job = print_job_class
+                .construct(&mut activation, &[])
+                .unwrap()
+                .coerce_to_object(&mut activation);
+            let result = call_method(&mut activation, print_job, "start", &[]).unwrap();
+            assert_eq!(result, true.into());
+
+            let area = ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            for (name, value) in [("xMin", 0), ("xMax", 20), ("yMin", 0), ("yMax", 20)] {
+                area.set(name, value.into(), &mut activation).unwrap();
+            }
+            let result = call_method(
+                &mut activation,
+                print_job,
+                "addPage",
+                &[0.into(), area.into(), Value::Null, 2.into()],
+            )
+            .unwrap();
+            assert_eq!(result, true.into());
+            call_method(&mut activation, print_job, "send", &[]).unwrap();
+
+            // The root is printed from a snapshot, without being navigated.
+            assert_eq!(root.as_movie_clip().unwrap().current_frame(), current_frame);
+        });
+
+        let jobs = print.jobs();
+        assert_eq!(jobs.len(), 1);
+        let [page] = &jobs[0][..] else {
+            panic!("One page should be printed, got {}", jobs[0].len());
+        };
+        assert_eq!(page.format, PageFormat::LETTER);
+        // The printable area is rendered at 144 DPI, twice the point size of the page.
+        assert_eq!((page.width, page.height), (1152, 1512));
+        assert_eq!(page.pixels.len(), 1152 * 1512 * 3);
+
+        let pixel = |x: usize, y: usize| {
+            let offset = (y * page.width as usize + x) * 3;
+            &page.pixels[offset..offset + 3]
+        };
+        // The 10x10 square of the second frame covers 20x20 pixels of the page.
+        assert_eq!(pixel(5, 5), [255, 0, 0]);
+        assert_eq!(pixel(19, 19), [255, 0, 0]);
+        assert_eq!(pixel(30, 30), [255, 255, 255]);
+    }
+}
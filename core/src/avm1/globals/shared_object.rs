@@ -130,7 +130,7 @@ fn recursive_serialize<'gc>(
 }
 
 /// Deserialize a AmfValue to a Value
-fn deserialize_value<'gc>(
+pub(crate) fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     val: &AmfValue,
     lso: &AMF0Decoder,
@@ -174,6 +174,27 @@ fn deserialize_value<'gc>(
                 Value::Undefined
             }
         }
+        AmfValue::StrictArray(values) => {
+            let array_constructor = activation.context.avm1.prototypes().array_constructor;
+            if let Ok(Value::Object(obj)) =
+                array_constructor.construct(activation, &[values.len().into()])
+            {
+                let v: Value<'gc> = obj.into();
+
+                if let Some(reference) = lso.as_reference(val) {
+                    reference_cache.insert(reference, v);
+                }
+
+                for (i, value) in values.iter().enumerate() {
+                    let value = deserialize_value(activation, value, lso, reference_cache);
+                    obj.set_element(activation, i as i32, value).unwrap();
+                }
+
+                v
+            } else {
+                Value::Undefined
+            }
+        }
         AmfValue::Object(elements, _) => {
             // Deserialize Object
             let obj = ScriptObject::new(
@@ -235,6 +256,27 @@ fn deserialize_value<'gc>(
     }
 }
 
+/// Serialize a Value to an AmfValue, as sent to a Flash Remoting gateway.
+///
+/// Returns `None` for functions, which are skipped like in `SharedObject`s.
+pub(crate) fn serialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Option<AmfValue> {
+    let wrapper = ScriptObject::new(activation.context.gc_context, None);
+    wrapper.define_value(
+        activation.context.gc_context,
+        "value",
+        value,
+        Attribute::empty(),
+    );
+
+    let mut w = Amf0Writer::default();
+    recursive_serialize(activation, wrapper.into(), &mut w);
+    let lso = w.commit_lso("");
+    lso.body.first().map(|element| element.value().clone())
+}
+
 /// Deserializes a Lso into an object containing the properties stored
 fn deserialize_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -260,7 +302,11 @@ fn deserialize_lso<'gc>(
     Ok(obj.into())
 }
 
-fn new_lso<'gc>(activation: &mut Activation<'_, 'gc>, name: &str, data: Object<'gc>) -> Lso {
+pub(crate) fn new_lso<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &str,
+    data: Object<'gc>,
+) -> Lso {
     let mut w = Amf0Writer::default();
     recursive_serialize(activation, data, &mut w);
     w.commit_lso(
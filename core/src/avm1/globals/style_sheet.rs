@@ -0,0 +1,308 @@
+//! `TextField.StyleSheet` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::text_field::new_text_format;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{
+    Activation, ArrayObject, Error, ExecutionReason, Object, ScriptObject, TObject, Value,
+};
+use crate::backend::navigator::Request;
+use crate::context::GcContext;
+use crate::html::{parse_css, StyleSheet, TextStyle, STYLE_PROPERTIES};
+use crate::string::AvmString;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getStyle" => method(get_style; DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(set_style; DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(get_style_names; DONT_ENUM | DONT_DELETE);
+    "clear" => method(clear; DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(parse_css_method; DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+    "load" => method(load; DONT_ENUM | DONT_DELETE);
+    "onData" => method(on_data; DONT_ENUM | DONT_DELETE);
+    "onLoad" => method(on_load; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `TextField.StyleSheet`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = new_object(activation);
+    this.define_value(
+        activation.context.gc_context,
+        "_css",
+        css.into(),
+        Attribute::DONT_ENUM,
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    style_sheet_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        style_sheet_proto,
+    )
+}
+
+fn new_object<'gc>(activation: &mut Activation<'_, 'gc>) -> Object<'gc> {
+    ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    )
+    .into()
+}
+
+/// The styles of a style sheet, by their lowercase names.
+fn css_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match this.get("_css", activation)? {
+        Value::Object(css) => Ok(css),
+        _ => {
+            let css = new_object(activation);
+            this.define_value(
+                activation.context.gc_context,
+                "_css",
+                css.into(),
+                Attribute::DONT_ENUM,
+            );
+            Ok(css)
+        }
+    }
+}
+
+fn copy_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let copy = new_object(activation);
+    for key in object.get_keys(activation, false) {
+        let value = object.get(key, activation)?;
+        copy.set(key, value, activation)?;
+    }
+    Ok(copy)
+}
+
+/// Read the CSS properties of a style object.
+fn text_style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<TextStyle, Error<'gc>> {
+    let mut style = TextStyle::default();
+    for &name in STYLE_PROPERTIES {
+        match object.get(name, activation)? {
+            Value::Undefined | Value::Null => {}
+            value => {
+                let value = value.coerce_to_string(activation)?;
+                style.set_property(name, &value);
+            }
+        }
+    }
+    Ok(style)
+}
+
+/// Read the styles of a `TextField.StyleSheet` object.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+    let css = css_object(activation, object)?;
+    for name in css.get_keys(activation, false) {
+        if let Value::Object(style) = css.get(name, activation)? {
+            let style = text_style_from_object(activation, style)?;
+            style_sheet.set_style(&name, style);
+        }
+    }
+    Ok(style_sheet)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new(activation.context.gc_context, name.to_ascii_lowercase());
+    let css = css_object(activation, this)?;
+    match css.get(name, activation)? {
+        Value::Object(style) => Ok(copy_object(activation, style)?.into()),
+        _ => Ok(Value::Null),
+    }
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new(activation.context.gc_context, name.to_ascii_lowercase());
+    let css = css_object(activation, this)?;
+    match args.get(1) {
+        Some(Value::Object(style)) => {
+            let style = copy_object(activation, *style)?;
+            css.set(name, style.into(), activation)?;
+        }
+        _ => {
+            css.delete(activation, name);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = css_object(activation, this)?;
+    let names = css.get_keys(activation, false).into_iter().map(Value::from);
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        names,
+    )
+    .into())
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = new_object(activation);
+    this.set("_css", css.into(), activation)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.parseCSS`, merging the parsed rules into the
+/// existing styles.
+fn parse_css_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css_text = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let Some(rules) = parse_css(&css_text) else {
+        return Ok(false.into());
+    };
+
+    let css = css_object(activation, this)?;
+    for (selector, declarations) in rules {
+        let name = AvmString::new(activation.context.gc_context, selector.to_ascii_lowercase());
+        let style = match css.get(name, activation)? {
+            Value::Object(style) => style,
+            _ => {
+                let style = new_object(activation);
+                css.set(name, style.into(), activation)?;
+                style
+            }
+        };
+        for (property, value) in declarations {
+            let property = AvmString::new(activation.context.gc_context, property);
+            let value = AvmString::new(activation.context.gc_context, value);
+            style.set(property, value.into(), activation)?;
+        }
+    }
+    Ok(true.into())
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(Value::Object(style)) = args.get(0) else {
+        return Ok(Value::Null);
+    };
+    let style = text_style_from_object(activation, *style)?;
+    Ok(new_text_format(activation, style.format).into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(val) => val.coerce_to_string(activation)?,
+        None => return Ok(false.into()),
+    };
+
+    // The CSS is passed to `onData` once loaded, like with `LoadVars`.
+    let future = activation.context.load_manager.load_form_into_load_vars(
+        activation.context.player.clone(),
+        this,
+        Request::get(url.to_utf8_lossy().into_owned()),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Default implementation forwards to parseCSS and onLoad.
+    let success = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => false,
+        val => this
+            .call_method(
+                "parseCSS".into(),
+                &[*val],
+                activation,
+                ExecutionReason::FunctionCall,
+            )?
+            .as_bool(activation.swf_version()),
+    };
+
+    this.call_method(
+        "onLoad".into(),
+        &[success.into()],
+        activation,
+        ExecutionReason::FunctionCall,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+fn on_load<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
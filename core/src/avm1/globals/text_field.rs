@@ -78,6 +78,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "multiline" => property(tf_getter!(multiline), tf_setter!(set_multiline));
     "password" => property(tf_getter!(password), tf_setter!(set_password));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet); VERSION_7);
     "selectable" => property(tf_getter!(selectable), tf_setter!(set_selectable));
     "text" => property(tf_getter!(text), tf_setter!(set_text));
     "textColor" => property(tf_getter!(text_color), tf_setter!(set_text_color));
@@ -129,7 +130,7 @@ pub fn set_password<'gc>(
     Ok(())
 }
 
-fn new_text_format<'gc>(
+pub fn new_text_format<'gc>(
     activation: &mut Activation<'_, 'gc>,
     text_format: TextFormat,
 ) -> ScriptObject<'gc> {
@@ -383,6 +384,32 @@ pub fn set_html_text<'gc>(
     Ok(())
 }
 
+pub fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .style_sheet()
+        .and_then(|object| object.as_avm1_object())
+        .map_or(Value::Undefined, Value::from))
+}
+
+pub fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let style_sheet = match value {
+        Value::Object(object) => Some((
+            object.into(),
+            globals::style_sheet::style_sheet_from_object(activation, object)?,
+        )),
+        _ => None,
+    };
+    this.set_style_sheet(&mut activation.context, style_sheet);
+    Ok(())
+}
+
 pub fn background<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
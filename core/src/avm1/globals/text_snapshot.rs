@@ -0,0 +1,303 @@
+//! `TextSnapshot` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, Object, ScriptObject, TObject, Value};
+use crate::context::GcContext;
+use crate::display_object::DisplayObject;
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
+use swf::{Color, Point, Twips};
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "findText" => method(find_text; DONT_ENUM | DONT_DELETE);
+    "getCount" => method(get_count; DONT_ENUM | DONT_DELETE);
+    "getSelected" => method(get_selected; DONT_ENUM | DONT_DELETE);
+    "getSelectedText" => method(get_selected_text; DONT_ENUM | DONT_DELETE);
+    "getText" => method(get_text; DONT_ENUM | DONT_DELETE);
+    "getTextRunInfo" => method(get_text_run_info; DONT_ENUM | DONT_DELETE);
+    "hitTestTextNearPos" => method(hit_test_text_near_pos; DONT_ENUM | DONT_DELETE);
+    "setSelectColor" => method(set_select_color; DONT_ENUM | DONT_DELETE);
+    "setSelected" => method(set_selected; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `TextSnapshot`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(Value::Object(container)) = args.get(0) {
+        if let Some(container) = container.as_display_object() {
+            this.set_native(
+                activation.context.gc_context,
+                NativeObject::TextSnapshot(container),
+            );
+        }
+    }
+    Ok(this.into())
+}
+
+/// Create a `TextSnapshot` of the static texts inside a container.
+pub fn create<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    container: DisplayObject<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().text_snapshot),
+    );
+    object.set_native(
+        activation.context.gc_context,
+        NativeObject::TextSnapshot(container),
+    );
+    object.into()
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    text_snapshot_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        text_snapshot_proto,
+    )
+}
+
+/// Take a snapshot of the static texts of the container of a `TextSnapshot` object.
+fn snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<TextSnapshot<'gc>> {
+    if let NativeObject::TextSnapshot(container) = this.native() {
+        return Some(TextSnapshot::new(container, activation.context.library));
+    }
+    None
+}
+
+fn arg_i32<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<i32, Error<'gc>> {
+    args.get(index)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)
+}
+
+fn arg_bool<'gc>(activation: &mut Activation<'_, 'gc>, args: &[Value<'gc>], index: usize) -> bool {
+    args.get(index)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version())
+}
+
+fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let begin_index = arg_i32(activation, args, 0)?;
+    let text = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let case_sensitive = arg_bool(activation, args, 2);
+    Ok(snapshot
+        .find_text(begin_index, &text, case_sensitive)
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+fn get_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    Ok(snapshot.char_count().into())
+}
+
+fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let begin_index = arg_i32(activation, args, 0)?;
+    let end_index = arg_i32(activation, args, 1)?;
+    Ok(snapshot.is_selected(begin_index, end_index).into())
+}
+
+fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let include_line_endings = arg_bool(activation, args, 0);
+    let text = snapshot.selected_text(include_line_endings);
+    Ok(AvmString::new(activation.context.gc_context, text).into())
+}
+
+fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let begin_index = arg_i32(activation, args, 0)?;
+    let end_index = arg_i32(activation, args, 1)?;
+    let include_line_endings = arg_bool(activation, args, 2);
+    let text = snapshot.text(begin_index, end_index, include_line_endings);
+    Ok(AvmString::new(activation.context.gc_context, text).into())
+}
+
+fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let begin_index = arg_i32(activation, args, 0)?;
+    let end_index = arg_i32(activation, args, 1)?;
+    let mut runs = Vec::new();
+    for info in snapshot.text_run_info(begin_index, end_index) {
+        let object = ScriptObject::new(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        let font = AvmString::new_utf8(activation.context.gc_context, info.font);
+        let [corner0, corner1, corner2, corner3] = info.corners;
+        let properties: [(&'static str, Value<'gc>); 19] = [
+            ("indexInRun", info.index_in_run.into()),
+            ("selected", info.selected.into()),
+            ("font", font.into()),
+            ("color", info.color.to_rgb().into()),
+            ("height", info.height.into()),
+            ("matrix_a", info.matrix.a.into()),
+            ("matrix_b", info.matrix.b.into()),
+            ("matrix_c", info.matrix.c.into()),
+            ("matrix_d", info.matrix.d.into()),
+            ("matrix_tx", info.matrix.tx.to_pixels().into()),
+            ("matrix_ty", info.matrix.ty.to_pixels().into()),
+            ("corner0x", corner0.x.to_pixels().into()),
+            ("corner0y", corner0.y.to_pixels().into()),
+            ("corner1x", corner1.x.to_pixels().into()),
+            ("corner1y", corner1.y.to_pixels().into()),
+            ("corner2x", corner2.x.to_pixels().into()),
+            ("corner2y", corner2.y.to_pixels().into()),
+            ("corner3x", corner3.x.to_pixels().into()),
+            ("corner3y", corner3.y.to_pixels().into()),
+        ];
+        for (name, value) in properties {
+            object.set(name, value, activation)?;
+        }
+        runs.push(object.into());
+    }
+
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        runs,
+    )
+    .into())
+}
+
+fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let mut number = |index: usize| -> Result<f64, Error<'gc>> {
+        args.get(index)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_f64(activation)
+    };
+    let point = Point::new(
+        Twips::from_pixels(number(0)?),
+        Twips::from_pixels(number(1)?),
+    );
+    let max_distance = match args.get(2) {
+        Some(_) => Twips::from_pixels(number(2)?),
+        None => Twips::ZERO,
+    };
+    Ok(snapshot
+        .hit_test_text_near_pos(point, max_distance)
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let color = match args.get(0) {
+        Some(color) => color.coerce_to_u32(activation)?,
+        None => 0xFFFF00,
+    };
+    snapshot.set_select_color(activation.context.gc_context, Color::from_rgb(color, 255));
+    Ok(Value::Undefined)
+}
+
+fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let begin_index = arg_i32(activation, args, 0)?;
+    let end_index = arg_i32(activation, args, 1)?;
+    let selected = arg_bool(activation, args, 2);
+    snapshot.set_selected(
+        activation.context.gc_context,
+        begin_index,
+        end_index,
+        selected,
+    );
+    Ok(Value::Undefined)
+}
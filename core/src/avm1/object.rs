@@ -9,8 +9,11 @@ use crate::avm1::globals::convolution_filter::ConvolutionFilter;
 use crate::avm1::globals::date::Date;
 use crate::avm1::globals::displacement_map_filter::DisplacementMapFilter;
 use crate::avm1::globals::drop_shadow_filter::DropShadowFilter;
+use crate::avm1::globals::file_reference::FileReference;
 use crate::avm1::globals::glow_filter::GlowFilter;
 use crate::avm1::globals::gradient_filter::GradientFilter;
+use crate::avm1::globals::net_connection::NetConnection;
+use crate::avm1::globals::print_job::PrintJob;
 use crate::avm1::globals::shared_object::SharedObject;
 use crate::avm1::globals::transform::TransformObject;
 use crate::avm1::globals::xml::Xml;
@@ -56,12 +59,18 @@ pub enum NativeObject<'gc> {
     ColorTransform(GcCell<'gc, ColorTransformObject>),
     Transform(TransformObject<'gc>),
     TextFormat(Gc<'gc, RefCell<TextFormat>>),
+    NetConnection(NetConnection<'gc>),
     NetStream(NetStream<'gc>),
     BitmapData(BitmapDataWrapper<'gc>),
     Xml(Xml<'gc>),
     XmlNode(XmlNode<'gc>),
     SharedObject(GcCell<'gc, SharedObject>),
     XmlSocket(XmlSocket<'gc>),
+    FileReference(FileReference<'gc>),
+    PrintJob(PrintJob<'gc>),
+
+    /// A `TextSnapshot` of the static texts inside a display object.
+    TextSnapshot(DisplayObject<'gc>),
 }
 
 /// Represents an object that can be directly interacted with by the AVM
@@ -8,7 +8,7 @@ use crate::avm1::{Object, ObjectPtr, ScriptObject, TObject, Value};
 use crate::avm_warn;
 use crate::context::UpdateContext;
 use crate::display_object::{
-    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
 use crate::string::{AvmString, WStr};
 use crate::types::Percent;
@@ -740,17 +740,33 @@ fn set_high_quality<'gc>(
     Ok(())
 }
 
-fn focus_rect<'gc>(activation: &mut Activation<'_, 'gc>, _this: DisplayObject<'gc>) -> Value<'gc> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
-    Value::Null
+fn focus_rect<'gc>(activation: &mut Activation<'_, 'gc>, this: DisplayObject<'gc>) -> Value<'gc> {
+    // The root clip holds the global setting.
+    if DisplayObject::option_ptr_eq(Some(this), activation.context.stage.root_clip()) {
+        return activation.context.stage.stage_focus_rect().into();
+    }
+    this.as_interactive()
+        .and_then(|this| this.focus_rect())
+        .map_or(Value::Null, Value::from)
 }
 
 fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    let focus_rect = match val {
+        Value::Undefined | Value::Null => None,
+        _ => Some(val.as_bool(activation.swf_version())),
+    };
+    if DisplayObject::option_ptr_eq(Some(this), activation.context.stage.root_clip()) {
+        activation
+            .context
+            .stage
+            .set_stage_focus_rect(activation.context.gc_context, focus_rect.unwrap_or(true));
+    } else if let Some(this) = this.as_interactive() {
+        this.set_focus_rect(activation.context.gc_context, focus_rect);
+    }
     Ok(())
 }
 
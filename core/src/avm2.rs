@@ -53,6 +53,8 @@ mod scope;
 mod script;
 mod string;
 mod stubs;
+#[cfg(test)]
+pub mod test_utils;
 mod traits;
 mod value;
 pub mod vector;
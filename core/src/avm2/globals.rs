@@ -69,6 +69,7 @@ pub struct SystemClasses<'gc> {
     pub shape: ClassObject<'gc>,
     pub textfield: ClassObject<'gc>,
     pub textformat: ClassObject<'gc>,
+    pub font: ClassObject<'gc>,
     pub graphics: ClassObject<'gc>,
     pub igraphicsdata: ClassObject<'gc>,
     pub graphicsbitmapfill: ClassObject<'gc>,
@@ -97,15 +98,22 @@ pub struct SystemClasses<'gc> {
     pub date: ClassObject<'gc>,
     pub qname: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
+    pub touchevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub focusevent: ClassObject<'gc>,
+    pub sampledataevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
+    pub asyncerrorevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
+    pub matrix3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
@@ -126,6 +134,7 @@ pub struct SystemClasses<'gc> {
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
+    pub textline: ClassObject<'gc>,
     pub stage3d: ClassObject<'gc>,
     pub context3d: ClassObject<'gc>,
     pub indexbuffer3d: ClassObject<'gc>,
@@ -196,6 +205,7 @@ impl<'gc> SystemClasses<'gc> {
             shape: object,
             textfield: object,
             textformat: object,
+            font: object,
             graphics: object,
             igraphicsdata: object,
             graphicsbitmapfill: object,
@@ -222,15 +232,22 @@ impl<'gc> SystemClasses<'gc> {
             date: object,
             qname: object,
             mouseevent: object,
+            touchevent: object,
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            focusevent: object,
+            sampledataevent: object,
             errorevent: object,
+            asyncerrorevent: object,
+            dataevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
             transform: object,
             colortransform: object,
             matrix: object,
+            matrix3d: object,
+            perspectiveprojection: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
@@ -251,6 +268,7 @@ impl<'gc> SystemClasses<'gc> {
             uncaughterrorevents: object,
             statictext: object,
             textlinemetrics: object,
+            textline: object,
             stage3d: object,
             context3d: object,
             indexbuffer3d: object,
@@ -799,19 +817,26 @@ fn load_playerglobal<'gc>(
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "FocusEvent", focusevent),
+            ("flash.events", "SampleDataEvent", sampledataevent),
             ("flash.events", "ErrorEvent", errorevent),
+            ("flash.events", "AsyncErrorEvent", asyncerrorevent),
+            ("flash.events", "DataEvent", dataevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
             ("flash.events", "HTTPStatusEvent", httpstatusevent),
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
             ("flash.events", "IOErrorEvent", ioerrorevent),
             ("flash.events", "MouseEvent", mouseevent),
+            ("flash.events", "TouchEvent", touchevent),
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.events", "NetStatusEvent", netstatusevent),
             ("flash.events", "StatusEvent", statusevent),
             ("flash.events", "ContextMenuEvent", contextmenuevent),
             ("flash.geom", "Matrix", matrix),
+            ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
@@ -824,8 +849,10 @@ fn load_playerglobal<'gc>(
             ("flash.system", "ApplicationDomain", application_domain),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
+            ("flash.text", "Font", font),
             ("flash.text", "TextField", textfield),
             ("flash.text", "TextLineMetrics", textlinemetrics),
+            ("flash.text.engine", "TextLine", textline),
             ("flash.filters", "BevelFilter", bevelfilter),
             ("flash.filters", "BitmapFilter", bitmapfilter),
             ("flash.filters", "BlurFilter", blurfilter),
@@ -7,6 +7,9 @@ package flash.display {
     import flash.media.SoundTransform;
     import flash.display.DisplayObject;
     import flash.display.InteractiveObject;
+    import flash.text.TextSnapshot;
+
+    namespace ruffle = "__ruffle__";
 
     [Ruffle(NativeInstanceInit)]
     public class DisplayObjectContainer extends InteractiveObject {
@@ -21,6 +24,12 @@ package flash.display {
         public native function get tabChildren():Boolean;
         public native function set tabChildren(value:Boolean):void;
 
+        public function get textSnapshot():TextSnapshot {
+            var snapshot:TextSnapshot = new TextSnapshot();
+            snapshot.ruffle::_container = this;
+            return snapshot;
+        }
+
         public native function addChild(child:DisplayObject):DisplayObject;
         public native function addChildAt(child:DisplayObject, index:int):DisplayObject;
         public native function contains(child:DisplayObject):Boolean;
@@ -1,3 +1,4 @@
 //! `flash.geom` namespace
 
+pub mod perspective_projection;
 pub mod transform;
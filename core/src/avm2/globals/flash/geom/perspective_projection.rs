@@ -0,0 +1,21 @@
+//! `flash.geom.PerspectiveProjection` native methods
+
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `PerspectiveProjection.stageWidth`, which projections are relative to.
+pub fn stage_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.stage.stage_size().0.into())
+}
+
+/// Implements `PerspectiveProjection.stageHeight`, which projections are relative to.
+pub fn stage_height<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.stage.stage_size().1.into())
+}
@@ -1,9 +1,13 @@
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
-use crate::display_object::TDisplayObject;
-use crate::prelude::{DisplayObject, Matrix, Twips};
+use crate::display_object::{PerspectiveProjection, TDisplayObject};
+use crate::prelude::{DisplayObject, Matrix, Point, Twips};
+use gc_arena::Mutation;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
@@ -64,7 +68,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    // Objects with a 3D transform only have a `matrix3D`.
+    if dobj.base().transform_3d().is_some() {
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -75,6 +84,7 @@ pub fn set_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
+    dobj.set_matrix_3d(activation.context.gc_context, None);
     dobj.set_matrix(activation.context.gc_context, matrix);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
@@ -84,6 +94,100 @@ pub fn set_matrix<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    match dobj.matrix_3d(activation.context.gc_context) {
+        Some(matrix) => matrix_3d_to_object(matrix, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_matrix_3d(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_matrix_3d(activation.context.gc_context, matrix);
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    // Root objects always have a projection, defaulting to the one of the stage.
+    let projection = dobj.perspective_projection().or_else(|| {
+        dobj.is_root()
+            .then(|| PerspectiveProjection::for_stage(activation.context.stage))
+    });
+    match projection {
+        Some(projection) => perspective_projection_to_object(projection, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_perspective_projection(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
+pub fn get_relative_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let relative_to = args
+        .get_object(activation, 0, "relativeTo")?
+        .as_display_object();
+    let dobj = get_display_object(this, activation)?;
+    let gc_context = activation.context.gc_context;
+    let matrix = relative_to.and_then(|relative_to| {
+        Some(
+            concatenated_matrix_3d(relative_to, gc_context).inverse()?
+                * concatenated_matrix_3d(dobj, gc_context),
+        )
+    });
+    match matrix {
+        Some(matrix) => matrix_3d_to_object(matrix, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+/// The 3D matrix from the local space of a display object to the stage,
+/// without any perspective projection.
+fn concatenated_matrix_3d<'gc>(dobj: DisplayObject<'gc>, gc_context: &Mutation<'gc>) -> Matrix3D {
+    let mut matrix = Matrix3D::IDENTITY;
+    let mut node = Some(dobj);
+    while let Some(obj) = node {
+        let local_matrix = obj
+            .matrix_3d(gc_context)
+            .unwrap_or_else(|| Matrix3D::from(*obj.base().matrix()));
+        matrix = local_matrix * matrix;
+        node = obj.parent();
+    }
+    matrix
+}
+
 pub fn get_concatenated_matrix<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -217,6 +321,87 @@ pub fn matrix_to_object<'gc>(
     Ok(object.into())
 }
 
+pub fn matrix_3d_to_object<'gc>(
+    matrix: Matrix3D,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let number = activation.avm2().classes().number;
+    let raw_data = VectorStorage::from_values(
+        matrix.raw_data.iter().map(|v| (*v).into()).collect(),
+        false,
+        Some(number),
+    );
+    let raw_data = VectorObject::from_vector(raw_data, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .matrix3d
+        .construct(activation, &[raw_data.into()])?;
+    Ok(object.into())
+}
+
+pub fn object_to_matrix_3d<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Matrix3D, Error<'gc>> {
+    let raw_data = object
+        .get_public_property("rawData", activation)?
+        .coerce_to_object(activation)?;
+    let values: Vec<Value<'gc>> = raw_data
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+
+    let mut matrix = Matrix3D::IDENTITY;
+    for (i, value) in values.into_iter().take(16).enumerate() {
+        matrix.raw_data[i] = value.coerce_to_number(activation)?;
+    }
+    Ok(matrix)
+}
+
+fn perspective_projection_to_object<'gc>(
+    projection: PerspectiveProjection,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    object.set_public_property("fieldOfView", projection.field_of_view.into(), activation)?;
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[
+            projection.center.x.to_pixels().into(),
+            projection.center.y.to_pixels().into(),
+        ],
+    )?;
+    object.set_public_property("projectionCenter", center.into(), activation)?;
+    Ok(object.into())
+}
+
+fn object_to_perspective_projection<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<PerspectiveProjection, Error<'gc>> {
+    let field_of_view = object
+        .get_public_property("fieldOfView", activation)?
+        .coerce_to_number(activation)?;
+    let center = object
+        .get_public_property("projectionCenter", activation)?
+        .coerce_to_object(activation)?;
+    let x = center
+        .get_public_property("x", activation)?
+        .coerce_to_number(activation)?;
+    let y = center
+        .get_public_property("y", activation)?
+        .coerce_to_number(activation)?;
+    Ok(PerspectiveProjection {
+        field_of_view,
+        center: Point::new(Twips::from_pixels(x), Twips::from_pixels(y)),
+    })
+}
+
 pub fn object_to_matrix<'gc>(
     object: Object<'gc>,
     activation: &mut Activation<'_, 'gc>,
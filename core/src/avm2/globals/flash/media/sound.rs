@@ -191,30 +191,59 @@ pub fn play<'gc>(
     Ok(Value::Null)
 }
 
-/// `Sound.extract`
+/// Implements `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
-
-    let bytearray = args
-        .get(0)
-        .unwrap_or(&Value::Undefined)
-        .coerce_to_object(activation)?;
-    let length = args
-        .get(1)
-        .unwrap_or(&Value::Number(0.0))
-        .coerce_to_number(activation)?;
-
-    if let Some(mut bytearray) = bytearray.as_bytearray_mut(activation.context.gc_context) {
-        bytearray
-            .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
-            .map_err(|e| e.to_avm(activation))?;
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(0.into());
+    };
+
+    let target = args.get_object(activation, 0, "target")?;
+    let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
+
+    // Without a start position, extraction continues from where the last call stopped.
+    let start_sample_frame = if start_position < 0.0 {
+        sound_object.extract_position()
+    } else {
+        start_position as u32
+    };
+
+    let Some(sound) = sound_object.sound_handle() else {
+        return Ok(0.into());
+    };
+    let samples = match activation.context.audio.extract_sound(
+        sound,
+        start_sample_frame,
+        length.max(0.0) as usize,
+    ) {
+        Ok(samples) => samples,
+        Err(e) => {
+            tracing::error!("Sound.extract: Failed to decode sound: {e}");
+            Vec::new()
+        }
+    };
+
+    if let Some(mut bytearray) = target.as_bytearray_mut(activation.context.gc_context) {
+        for &[left, right] in &samples {
+            bytearray
+                .write_float(left)
+                .map_err(|e| e.to_avm(activation))?;
+            bytearray
+                .write_float(right)
+                .map_err(|e| e.to_avm(activation))?;
+        }
     }
 
-    Ok(Value::Undefined)
+    sound_object.set_extract_position(
+        activation.context.gc_context,
+        start_sample_frame + samples.len() as u32,
+    );
+
+    Ok(samples.len().into())
 }
 
 /// `Sound.close`
@@ -275,12 +304,64 @@ pub fn load_compressed_data_from_byte_array<'gc>(
     Ok(Value::Undefined)
 }
 
-/// `Sound.loadPCMFromByteArray`
+/// Implements `Sound.loadPCMFromByteArray`
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(Value::Undefined);
+    };
+    if sound_object.sound_handle().is_some() {
+        avm2_stub_method!(
+            activation,
+            "flash.media.Sound",
+            "loadPCMFromByteArray",
+            "with an already loaded sound"
+        );
+        return Ok(Value::Undefined);
+    }
+
+    let bytes = args.get_object(activation, 0, "bytes")?;
+    let num_samples = args.get_u32(activation, 1)?;
+    let format = args.get_string(activation, 2)?;
+    let is_stereo = args.get_bool(3);
+    let sample_rate = args.get_u32(activation, 4)?.clamp(1, u16::MAX.into()) as u16;
+
+    let is_float = &*format == b"float";
+    let num_values = (num_samples as usize).saturating_mul(if is_stereo { 2 } else { 1 });
+
+    // Convert the samples to 16-bit little-endian PCM, which the audio backends can play.
+    let mut data = Vec::new();
+    if let Some(bytearray) = bytes.as_bytearray() {
+        data.reserve(num_values.min(bytearray.bytes_available()) * 2);
+        for _ in 0..num_values {
+            let sample = if is_float {
+                let sample = bytearray.read_float().map_err(|e| e.to_avm(activation))?;
+                (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16
+            } else {
+                bytearray.read_short().map_err(|e| e.to_avm(activation))?
+            };
+            data.extend_from_slice(&sample.to_le_bytes());
+        }
+    }
+
+    let swf_sound = swf::Sound {
+        id: 0,
+        format: swf::SoundFormat {
+            compression: swf::AudioCompression::Uncompressed,
+            sample_rate,
+            is_stereo,
+            is_16_bit: true,
+        },
+        num_samples,
+        data: &data,
+    };
+    match activation.context.audio.register_sound(&swf_sound) {
+        Ok(sound) => sound_object.set_sound(&mut activation.context, sound)?,
+        Err(e) => tracing::error!("Sound.loadPCMFromByteArray: Failed to register sound: {e}"),
+    }
+
     Ok(Value::Undefined)
 }
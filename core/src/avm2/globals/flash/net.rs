@@ -3,6 +3,7 @@
 use crate::avm2::object::TObject;
 use crate::avm2::{Activation, Error, Object, Value};
 
+pub mod file_reference;
 pub mod local_connection;
 pub mod net_connection;
 pub mod net_stream;
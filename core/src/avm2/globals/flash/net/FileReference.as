@@ -4,88 +4,79 @@ package flash.net
     import flash.utils.ByteArray;
     import __ruffle__.stub_method;
 
+    namespace ruffle = "__ruffle__";
+
     public class FileReference extends EventDispatcher
     {
-        private var _creationDate: Date;
-        private var _creator: String;
-        private var _data: ByteArray;
-        private var _extension: String;
-        private var _modificationDate: Date;
-        private var _name: String;
+        ruffle var _creationDate: Date;
+        ruffle var _creator: String;
+        ruffle var _data: ByteArray;
+        ruffle var _extension: String;
+        ruffle var _modificationDate: Date;
+        ruffle var _name: String;
         private static var _permissionStatus: String;
-        private var _size: Number;
-        private var _type: String;
+        ruffle var _size: Number;
+        ruffle var _type: String;
+
+        // The contents of the picked file, which are only exposed by `data` once loaded.
+        ruffle var _fileData: ByteArray;
 
         public function FileReference() {
-            
+
         }
 
         public function get creationDate(): Date {
-            return this._creationDate;
-        }   
+            return this.ruffle::_creationDate;
+        }
 
         public function get creator(): String {
-            retunr this._creator;
-        }   
+            return this.ruffle::_creator;
+        }
 
         public function get data(): ByteArray {
-            return this._data;
-        }   
+            return this.ruffle::_data;
+        }
 
         public function get extension(): String {
-            return this._extension;
-        }   
+            return this.ruffle::_extension;
+        }
 
         public function get modificationDate(): Date {
-            return this._modificationDate;
-        }   
+            return this.ruffle::_modificationDate;
+        }
 
         public function get name(): String {
-            retunr this._name;
-        }   
+            return this.ruffle::_name;
+        }
 
         public static function get permissionStatus(): String {
             return FileReference._permissionStatus;
-        }   
+        }
 
         public function get size(): Number {
-            return this._size;
-        }   
+            return this.ruffle::_size;
+        }
 
         public function get type(): String {
-            return this._type;
-        }   
+            return this.ruffle::_type;
+        }
 
-        public function browse(typeFilter:Array = null):Boolean {
-            return false;
-        }   
+        public native function browse(typeFilter:Array = null):Boolean;
 
-        public function cancel():void {
-            stub_method("flash.net.FileReference", "cancel");
-        }   
+        public native function cancel():void;
 
-        public function download(request:URLRequest, defaultFileName:String = null):void {
-            stub_method("flash.net.FileReference", "download");
-        }   
+        public native function download(request:URLRequest, defaultFileName:String = null):void;
 
-        public function load():void {
-            stub_method("flash.net.FileReference", "load");
-        }   
+        public native function load():void;
 
         public function requestPermission():void {
             stub_method("flash.net.FileReference", "requestPermission");
-        }   
+        }
 
-        public function save(data:*, defaultFileName:String = null):void {
-            stub_method("flash.net.FileReference", "save");
-        }   
+        public native function save(data:*, defaultFileName:String = null):void;
 
-        public function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void {
-            stub_method("flash.net.FileReference", "upload");
-        }   
+        public native function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void;
 
-        public function uploadUnencoded(request:URLRequest):void {
-            stub_method("flash.net.FileReference", "uploadUnencoded");
-        }
+        public native function uploadUnencoded(request:URLRequest):void;
     }
-}
\ No newline at end of file
+}
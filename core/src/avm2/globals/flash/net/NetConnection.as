@@ -1,7 +1,8 @@
 package flash.net {
     import flash.events.EventDispatcher;
     import flash.errors.IOError;
-    import __ruffle__.stub_method;
+
+    namespace ruffle = "__ruffle__";
 
     public class NetConnection extends EventDispatcher {
 
@@ -9,19 +10,38 @@ package flash.net {
 
         public var objectEncoding:uint = NetConnection.defaultObjectEncoding;
 
+        // The URL of the Flash Remoting gateway, if connected to one.
+        ruffle var _gatewayUrl:String = null;
+
+        // The headers sent with every call, as objects with `name`, `mustUnderstand` and `param` properties.
+        ruffle var _headers:Array = [];
+
+        // The identifier of the next call, used to match it with its response.
+        ruffle var _nextResponseId:uint = 1;
 
         public native function connect(command:String, ... arguments):void;
 
         public function addHeader(operation:String, mustUnderstand:Boolean = false, param:Object = null):void {
-            stub_method("flash.net.NetConnection", "addHeader");
+            var headers:Array = this.ruffle::_headers;
+            for (var i:int = 0; i < headers.length; i++) {
+                if (headers[i].name == operation) {
+                    headers.splice(i, 1);
+                    break;
+                }
+            }
+            if (param != null) {
+                headers.push({name: operation, mustUnderstand: mustUnderstand, param: param});
+            }
         }
 
-        public function call(command:String, responder:Responder, ... arguments):void {
-            stub_method("flash.net.NetConnection", "call");
-        }
-        
+        public native function call(command:String, responder:Responder, ... arguments):void;
+
         public function close():void {
-            stub_method("flash.net.NetConnection", "close");
+            this.ruffle::_gatewayUrl = null;
+        }
+
+        public function get uri():String {
+            return this.ruffle::_gatewayUrl;
         }
     }
 }
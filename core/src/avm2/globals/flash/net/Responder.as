@@ -1,9 +1,13 @@
 package flash.net {
-    import __ruffle__.stub_constructor;
-    
+    namespace ruffle = "__ruffle__";
+
     public class Responder {
+        ruffle var _result: Function;
+        ruffle var _status: Function;
+
         public function Responder(result:Function, status:Function = null) {
-            stub_constructor("flash.net.Responder");
+            this.ruffle::_result = result;
+            this.ruffle::_status = status;
         }
     }
 }
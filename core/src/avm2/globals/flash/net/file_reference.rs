@@ -0,0 +1,444 @@
+//! `flash.net.FileReference` native function definitions
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{argument_error, make_error_2037};
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Avm2, Error, EventObject, Multiname, Namespace, Object, Value};
+use crate::backend::file_dialog::{FileFilter, SelectedFile};
+use crate::backend::navigator::Request;
+use crate::file_reference::{
+    multipart_body, FileReferenceEvent, FileReferenceKind, FileSaveSource,
+};
+use crate::string::AvmString;
+use chrono::{DateTime, Utc};
+use url::form_urlencoded;
+
+fn ruffle_name<'gc>(activation: &mut Activation<'_, 'gc>, name: &'static str) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        name,
+    )
+}
+
+/// The picked file, if any.
+fn selected_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<SelectedFile>, Error<'gc>> {
+    let name = ruffle_name(activation, "_name");
+    let name = this.get_property(&name, activation)?;
+    let file_data = ruffle_name(activation, "_fileData");
+    let file_data = this.get_property(&file_data, activation)?;
+
+    let Some(data) = file_data.as_object().and_then(|data| {
+        data.as_bytearray()
+            .map(|bytearray| bytearray.bytes().to_vec())
+    }) else {
+        return Ok(None);
+    };
+
+    let name = name.coerce_to_string(activation)?;
+    Ok(Some(SelectedFile::new(name.to_utf8_lossy(), data)))
+}
+
+fn date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    date: Option<DateTime<Utc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(date) = date else {
+        return Ok(Value::Null);
+    };
+
+    let millis = date.timestamp_millis() as f64;
+    let date = activation
+        .avm2()
+        .classes()
+        .date
+        .construct(activation, &[millis.into()])?;
+    Ok(date.into())
+}
+
+/// Expose the properties of a picked file.
+pub fn set_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    file: SelectedFile,
+) -> Result<(), Error<'gc>> {
+    let extension = file
+        .extension()
+        .map(|extension| AvmString::new_utf8(activation.context.gc_context, extension).into())
+        .unwrap_or(Value::Null);
+    let file_type = file
+        .extension()
+        .map(|extension| {
+            AvmString::new_utf8(activation.context.gc_context, format!(".{extension}")).into()
+        })
+        .unwrap_or(Value::Null);
+    let creation_date = date(activation, file.creation_date)?;
+    let modification_date = date(activation, file.modification_date)?;
+    let name = AvmString::new_utf8(activation.context.gc_context, &file.name);
+    let size = file.data.len();
+    let file_data =
+        ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(file.data))?;
+
+    let properties = [
+        ("_name", name.into()),
+        ("_size", size.into()),
+        ("_type", file_type),
+        ("_extension", extension),
+        ("_creator", Value::Null),
+        ("_creationDate", creation_date),
+        ("_modificationDate", modification_date),
+        ("_fileData", file_data.into()),
+        // A new file needs to be loaded again.
+        ("_data", Value::Null),
+    ];
+    for (name, value) in properties {
+        let name = ruffle_name(activation, name);
+        this.set_property(&name, value, activation)?;
+    }
+
+    Ok(())
+}
+
+/// Expose the contents of the picked file with the `data` property.
+pub fn set_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let file_data = ruffle_name(activation, "_fileData");
+    let file_data = this.get_property(&file_data, activation)?;
+    let data = ruffle_name(activation, "_data");
+    this.set_property(&data, file_data, activation)?;
+    Ok(())
+}
+
+/// Dispatch the event matching the progress of an operation.
+pub fn dispatch<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    event: FileReferenceEvent<'_>,
+) -> Result<(), Error<'gc>> {
+    let event = match event {
+        FileReferenceEvent::Select => {
+            EventObject::bare_default_event(&mut activation.context, "select")
+        }
+        FileReferenceEvent::Cancel => {
+            EventObject::bare_default_event(&mut activation.context, "cancel")
+        }
+        FileReferenceEvent::Open => {
+            EventObject::bare_default_event(&mut activation.context, "open")
+        }
+        FileReferenceEvent::Complete => {
+            EventObject::bare_default_event(&mut activation.context, "complete")
+        }
+        FileReferenceEvent::Progress {
+            bytes_loaded,
+            bytes_total,
+        } => activation.avm2().classes().progressevent.construct(
+            activation,
+            &[
+                "progress".into(),
+                false.into(),
+                false.into(),
+                bytes_loaded.into(),
+                bytes_total.into(),
+            ],
+        )?,
+        FileReferenceEvent::HttpStatus(status) => {
+            activation.avm2().classes().httpstatusevent.construct(
+                activation,
+                &[
+                    "httpStatus".into(),
+                    false.into(),
+                    false.into(),
+                    status.into(),
+                    false.into(),
+                ],
+            )?
+        }
+        FileReferenceEvent::IoError => activation.avm2().classes().ioerrorevent.construct(
+            activation,
+            &[
+                "ioError".into(),
+                false.into(),
+                false.into(),
+                "Error #2038: File I/O Error.".into(),
+                2038.into(),
+            ],
+        )?,
+        FileReferenceEvent::UploadCompleteData(data) => {
+            let data = AvmString::new_utf8_bytes(activation.context.gc_context, data);
+            activation.avm2().classes().dataevent.construct(
+                activation,
+                &[
+                    "uploadCompleteData".into(),
+                    false.into(),
+                    false.into(),
+                    data.into(),
+                ],
+            )?
+        }
+    };
+
+    Avm2::dispatch_event(&mut activation.context, event, this);
+    Ok(())
+}
+
+/// Implements `FileReference.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut filters = Vec::new();
+    if let Some(type_filter) = args.try_get_object(activation, 0) {
+        let type_filter: Vec<_> = type_filter
+            .as_array_storage()
+            .map(|storage| storage.iter().collect())
+            .unwrap_or_default();
+        for filter in type_filter {
+            let Some(filter) = filter.and_then(|filter| filter.as_object()) else {
+                continue;
+            };
+            // `description` is the full description, including the extensions.
+            let description = filter
+                .get_public_property("description", activation)?
+                .coerce_to_string(activation)?;
+            let extension = filter
+                .get_public_property("extension", activation)?
+                .coerce_to_string(activation)?;
+            let mac_type = match filter.get_public_property("macType", activation)? {
+                Value::Null | Value::Undefined => None,
+                mac_type => Some(mac_type.coerce_to_string(activation)?),
+            };
+            filters.push(FileFilter::new(
+                &description.to_utf8_lossy(),
+                &extension.to_utf8_lossy(),
+                mac_type
+                    .map(|mac_type| mac_type.to_utf8_lossy().into_owned())
+                    .as_deref(),
+            ));
+        }
+    }
+
+    let dialog = activation.context.file_dialog.browse(&filters);
+    let future = activation.context.load_manager.browse_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm2(this),
+        dialog,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+/// Implements `FileReference.cancel`
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .load_manager
+        .cancel_file_reference(FileReferenceKind::Avm2(this));
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.load`
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file) = selected_file(activation, this)? else {
+        return Err(make_error_2037(activation));
+    };
+
+    let future = activation.context.load_manager.load_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm2(this),
+        file.data.len(),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.save`
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let data = match args.get_value(0) {
+        Value::Null | Value::Undefined => {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2007: Parameter data must be non-null.",
+                2007,
+            )?));
+        }
+        Value::Object(object) if object.as_bytearray().is_some() => {
+            // Note that this does *not* respect or modify the position.
+            object.as_bytearray().unwrap().bytes().to_vec()
+        }
+        Value::Object(object)
+            if object.as_xml_object().is_some() || object.as_xml_list_object().is_some() =>
+        {
+            let xml_string = object.call_public_property("toXMLString", &[], activation)?;
+            xml_string
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .as_bytes()
+                .to_vec()
+        }
+        data => data
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .as_bytes()
+            .to_vec(),
+    };
+
+    let default_file_name = match args.get_value(1) {
+        Value::Null | Value::Undefined => String::new(),
+        name => name
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned(),
+    };
+
+    let dialog = activation.context.file_dialog.save(&default_file_name);
+    let future = activation.context.load_manager.save_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm2(this),
+        dialog,
+        FileSaveSource::Data(data),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.download`
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+
+    let default_file_name = match args.get_value(1) {
+        Value::Null | Value::Undefined => {
+            // Suggest the name of the downloaded file.
+            let url = request.url();
+            let path = url.split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default().to_string()
+        }
+        name => name
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned(),
+    };
+
+    let dialog = activation.context.file_dialog.save(&default_file_name);
+    let future = activation.context.load_manager.save_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm2(this),
+        dialog,
+        FileSaveSource::Download(request),
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.upload`
+pub fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let field_name = args.get_string(activation, 1)?;
+
+    let Some(file) = selected_file(activation, this)? else {
+        return Err(make_error_2037(activation));
+    };
+
+    // The variables of the request are sent as fields of the form.
+    let url = url_request
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?;
+    let variables = match url_request.get_public_property("data", activation)? {
+        Value::Null | Value::Undefined => Vec::new(),
+        data => {
+            let data = data
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned();
+            form_urlencoded::parse(data.as_bytes())
+                .into_owned()
+                .collect()
+        }
+    };
+    let headers = request_from_url_request(activation, url_request)?
+        .headers()
+        .clone();
+
+    let body = multipart_body(&variables, &field_name.to_utf8_lossy(), &file);
+    let mut request = Request::post(url.to_utf8_lossy().into_owned(), Some(body));
+    request.set_headers(headers);
+
+    let future = activation.context.load_manager.upload_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm2(this),
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.uploadUnencoded`
+pub fn upload_unencoded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+
+    let Some(file) = selected_file(activation, this)? else {
+        return Err(make_error_2037(activation));
+    };
+
+    // The file is sent as is, in place of the data of the request.
+    let url = url_request
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?;
+    let content_type = url_request
+        .get_public_property("contentType", activation)?
+        .coerce_to_string(activation)?;
+    let headers = request_from_url_request(activation, url_request)?
+        .headers()
+        .clone();
+
+    let mut request = Request::post(
+        url.to_utf8_lossy().into_owned(),
+        Some((file.data, content_type.to_utf8_lossy().into_owned())),
+    );
+    request.set_headers(headers);
+
+    let future = activation.context.load_manager.upload_file(
+        activation.context.player.clone(),
+        FileReferenceKind::Avm2(this),
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
@@ -1,10 +1,23 @@
-use crate::avm2::error::type_error;
+use crate::avm2::amf::{deserialize_value, serialize_value};
+use crate::avm2::error::{argument_error, type_error};
 use crate::avm2::object::TObject;
 use crate::avm2::parameters::ParametersExt;
-use crate::avm2::{Activation, Avm2, Error, Object, Value};
+use crate::avm2::{Activation, Avm2, Error, Multiname, Namespace, Object, Value};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::local_connection::{LocalConnectionKind, LocalConnections, MAX_MESSAGE_SIZE};
 use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
 
-use crate::avm2_stub_method;
+fn allowed_domains_name<'gc>(activation: &mut Activation<'_, 'gc>) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        "_allowedDomains",
+    )
+}
+
+fn movie_domain(activation: &mut Activation<'_, '_>) -> Option<String> {
+    LocalConnections::get_domain(activation.context.swf.url())
+}
 
 /// Implements `domain` getter
 pub fn get_domain<'gc>(
@@ -12,23 +25,66 @@ pub fn get_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = activation.context.swf;
-
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(activation) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+/// Implements `LocalConnection.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter connectionName must be non-null.",
+            2007,
+        )?));
+    }
+
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let domain = movie_domain(activation).unwrap_or_else(|| "localhost".to_string());
+    let context = &mut activation.context;
+    if !context.local_connections.connect(
+        context.local_connection,
+        LocalConnectionKind::Avm2(this),
+        &domain,
+        &name,
+    ) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2082: Connect failed because the object is already connected.",
+            2082,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let context = &mut activation.context;
+    if !context
+        .local_connections
+        .close(context.local_connection, LocalConnectionKind::Avm2(this))
+    {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2083: Close failed because the object is not connected.",
+            2083,
+        )?));
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `LocalConnection.send`
@@ -53,21 +109,154 @@ pub fn send<'gc>(
         )?));
     }
 
-    avm2_stub_method!(activation, "flash.net.LocalConnection", "send");
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let method_name = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+
+    // Arguments are encoded with AMF0, so that AVM1 movies can receive them too.
+    let mut elements = Vec::new();
+    for (index, arg) in args.iter().skip(2).enumerate() {
+        let value =
+            serialize_value(activation, *arg, AMFVersion::AMF0).unwrap_or(AmfValue::Undefined);
+        elements.push(Element::new(index.to_string(), value));
+    }
+    let mut lso = Lso::new(elements, "", AMFVersion::AMF0);
+    let arguments = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    if arguments.len() > MAX_MESSAGE_SIZE {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2084: The AMF encoding of the arguments cannot exceed 40K.",
+            2084,
+        )?));
+    }
+
+    let message = LocalConnectionMessage {
+        sender_domain: movie_domain(activation).unwrap_or_else(|| "localhost".to_string()),
+        method_name,
+        arguments,
+    };
+    let context = &mut activation.context;
+    context.local_connections.send(
+        context.local_connection,
+        LocalConnectionKind::Avm2(this),
+        &name,
+        message,
+    );
 
-    let event = activation.avm2().classes().statusevent.construct(
-        activation,
-        &[
-            "status".into(),
-            false.into(),
-            false.into(),
-            Value::Null,
-            "error".into(),
-        ],
-    )?;
+    Ok(Value::Undefined)
+}
 
-    // FIXME: Adding the event listener after calling `send` works in FP.
-    Avm2::dispatch_event(&mut activation.context, event, this);
+/// Implements `LocalConnection.allowDomain`
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = allowed_domains_name(activation);
+    let allowed_domains = this.get_property(&name, activation)?;
+    let Some(allowed_domains) = allowed_domains.as_object() else {
+        return Ok(Value::Undefined);
+    };
+
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        if let Some(mut storage) =
+            allowed_domains.as_array_storage_mut(activation.context.gc_context)
+        {
+            storage.push(domain.into());
+        }
+    }
 
     Ok(Value::Undefined)
 }
+
+/// Implements `LocalConnection.allowInsecureDomain`
+pub fn allow_insecure_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Ruffle doesn't distinguish secure and insecure senders.
+    allow_domain(activation, this, args)
+}
+
+/// Deliver a message to a connected `LocalConnection`, calling the method on its `client`.
+pub fn receive_message<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    domain: &str,
+    message: &LocalConnectionMessage,
+) -> Result<(), Error<'gc>> {
+    if message.sender_domain != domain {
+        // Other domains must be allowed with `allowDomain`.
+        let name = allowed_domains_name(activation);
+        let allowed_domains = this.get_property(&name, activation)?;
+        let is_allowed = allowed_domains
+            .as_object()
+            .and_then(|allowed_domains| {
+                let allowed_domains = allowed_domains.as_array_storage()?;
+                let is_allowed = allowed_domains.iter().any(|allowed_domain| {
+                    let Some(Value::String(allowed_domain)) = allowed_domain else {
+                        return false;
+                    };
+                    let allowed_domain = allowed_domain.to_utf8_lossy();
+                    allowed_domain == "*"
+                        || allowed_domain.eq_ignore_ascii_case(&message.sender_domain)
+                });
+                Some(is_allowed)
+            })
+            .unwrap_or(false);
+
+        if !is_allowed {
+            tracing::warn!(
+                "LocalConnection: Ignoring message from disallowed domain {}",
+                message.sender_domain
+            );
+            return Ok(());
+        }
+    }
+
+    let Ok(lso) = flash_lso::read::Reader::default().parse(&message.arguments) else {
+        tracing::warn!("LocalConnection: Unable to parse message arguments");
+        return Ok(());
+    };
+
+    let mut arguments = Vec::new();
+    for element in &lso.body {
+        let Ok(index) = element.name.parse::<usize>() else {
+            continue;
+        };
+        if arguments.len() <= index {
+            arguments.resize(index + 1, Value::Undefined);
+        }
+        arguments[index] = deserialize_value(activation, element.value())?;
+    }
+
+    let method_name = AvmString::new_utf8(activation.context.gc_context, &message.method_name);
+    let result = match this.get_public_property("client", activation)?.as_object() {
+        Some(client) => client.call_public_property(method_name, &arguments, activation),
+        None => Err("LocalConnection client is not an object".into()),
+    };
+
+    match result {
+        Err(Error::AvmError(error)) => {
+            // Errors thrown by the callback are reported on the `LocalConnection` itself.
+            let text = format!(
+                "Error #2095: flash.net.LocalConnection was unable to invoke callback {}.",
+                message.method_name
+            );
+            let event = activation.avm2().classes().asyncerrorevent.construct(
+                activation,
+                &[
+                    "asyncError".into(),
+                    false.into(),
+                    false.into(),
+                    AvmString::new_utf8(activation.context.gc_context, text).into(),
+                    error,
+                ],
+            )?;
+            Avm2::dispatch_event(&mut activation.context, event, this);
+            Ok(())
+        }
+        result => result.map(|_| ()),
+    }
+}
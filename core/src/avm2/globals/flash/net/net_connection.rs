@@ -1,11 +1,27 @@
-use crate::{
-    avm2::{Activation, Avm2, Error, EventObject, Object, Value},
-    avm2_stub_method,
+use crate::avm2::amf::{deserialize_value, serialize_value};
+use crate::avm2::error::type_error;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Avm2, Error, EventObject, Multiname, Namespace, Object, Value};
+use crate::avm2_stub_method;
+use crate::backend::navigator::Request;
+use crate::net_connection::{
+    read_packet, write_arguments, write_packet, write_value, AmfHeader, AmfMessage,
+    NetConnectionCall, AMF_CONTENT_TYPE,
 };
+use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+
+fn ruffle_name<'gc>(activation: &mut Activation<'_, 'gc>, name: &'static str) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        name,
+    )
+}
 
 pub fn connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    this: Object<'gc>,
+    mut this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Value::Null = args[0] {
@@ -20,11 +36,207 @@ pub fn connect<'gc>(
         Avm2::dispatch_event(&mut activation.context, event, this);
         return Ok(Value::Undefined);
     }
+
+    let command = args.get_string(activation, 0)?;
+    let url = command.to_utf8_lossy();
+    if url.starts_with("http://") || url.starts_with("https://") {
+        // Connecting to a Flash Remoting gateway only remembers its URL,
+        // requests are made by each call.
+        let gateway_url = ruffle_name(activation, "_gatewayUrl");
+        this.set_property(&gateway_url, command.into(), activation)?;
+        return Ok(Value::Undefined);
+    }
+
     avm2_stub_method!(
         activation,
         "flash.net.NetConnection",
         "connect",
-        "with non-null command"
+        "with non-null, non-http command"
+    );
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.call`
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter command must be non-null.",
+            2007,
+        )?));
+    }
+
+    let command = args.get_string(activation, 0)?;
+    let responder = args.try_get_object(activation, 1);
+
+    let gateway_url = ruffle_name(activation, "_gatewayUrl");
+    let gateway_url = this.get_property(&gateway_url, activation)?;
+    let Value::String(gateway_url) = gateway_url else {
+        avm2_stub_method!(
+            activation,
+            "flash.net.NetConnection",
+            "call",
+            "without a Flash Remoting gateway"
+        );
+        return Ok(Value::Undefined);
+    };
+
+    let amf_version = if this
+        .get_public_property("objectEncoding", activation)?
+        .coerce_to_u32(activation)?
+        == 0
+    {
+        AMFVersion::AMF0
+    } else {
+        AMFVersion::AMF3
+    };
+
+    let mut headers = Vec::new();
+    let header_objects = ruffle_name(activation, "_headers");
+    let header_objects = this.get_property(&header_objects, activation)?;
+    let header_objects: Vec<_> = header_objects
+        .as_object()
+        .and_then(|headers| Some(headers.as_array_storage()?.iter().flatten().collect()))
+        .unwrap_or_default();
+    for header in header_objects {
+        let Some(header) = header.as_object() else {
+            continue;
+        };
+        let name = header
+            .get_public_property("name", activation)?
+            .coerce_to_string(activation)?;
+        let must_understand = header
+            .get_public_property("mustUnderstand", activation)?
+            .coerce_to_boolean();
+        let param = header.get_public_property("param", activation)?;
+        let value = serialize_value(activation, param, amf_version)
+            .and_then(|value| write_value(value, amf_version))
+            .ok_or("Unable to serialize NetConnection header")?;
+        headers.push(AmfHeader {
+            name: name.to_utf8_lossy().into_owned(),
+            must_understand,
+            value,
+        });
+    }
+
+    let arguments = args
+        .iter()
+        .skip(2)
+        .map(|arg| serialize_value(activation, *arg, amf_version).unwrap_or(AmfValue::Undefined))
+        .collect();
+    let arguments =
+        write_arguments(arguments, amf_version).ok_or("Unable to serialize NetConnection call")?;
+
+    let next_response_id = ruffle_name(activation, "_nextResponseId");
+    let response_id = this
+        .get_property(&next_response_id, activation)?
+        .coerce_to_u32(activation)?;
+    this.set_property(&next_response_id, (response_id + 1).into(), activation)?;
+
+    let message = AmfMessage {
+        target_uri: command.to_utf8_lossy().into_owned(),
+        response_uri: format!("/{response_id}"),
+        value: arguments,
+    };
+    let packet = write_packet(amf_version, &headers, &[message]);
+    let request = Request::post(
+        gateway_url.to_utf8_lossy().into_owned(),
+        Some((packet, AMF_CONTENT_TYPE.to_string())),
     );
+
+    let future = activation.context.load_manager.call_net_connection(
+        activation.context.player.clone(),
+        NetConnectionCall::Avm2 {
+            connection: this,
+            responder,
+        },
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+
     Ok(Value::Undefined)
 }
+
+/// Handle the response of a gateway to a call, passing the results to the `Responder`.
+///
+/// `data` is `None` if the request failed.
+pub fn receive_call_response<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    responder: Option<Object<'gc>>,
+    data: Option<&[u8]>,
+) -> Result<(), Error<'gc>> {
+    let Some(data) = data else {
+        let event = EventObject::net_status_event(
+            activation,
+            "netStatus",
+            &[("code", "NetConnection.Call.Failed"), ("level", "error")],
+        );
+        Avm2::dispatch_event(&mut activation.context, event, this);
+        return Ok(());
+    };
+
+    let Some((headers, messages)) = read_packet(data) else {
+        let event = EventObject::net_status_event(
+            activation,
+            "netStatus",
+            &[
+                ("code", "NetConnection.Call.BadVersion"),
+                ("level", "error"),
+            ],
+        );
+        Avm2::dispatch_event(&mut activation.context, event, this);
+        return Ok(());
+    };
+
+    for header in headers {
+        // Gateways can ask for the session to be tracked in the URL.
+        let gateway_url = ruffle_name(activation, "_gatewayUrl");
+        match (header.name.as_str(), &header.value) {
+            ("AppendToGatewayUrl", AmfValue::String(suffix)) => {
+                // There is nothing to append to once the connection is closed.
+                if let Value::String(url) = this.get_property(&gateway_url, activation)? {
+                    let url = format!("{}{}", url.to_utf8_lossy(), suffix);
+                    let url = AvmString::new_utf8(activation.context.gc_context, url);
+                    this.set_property(&gateway_url, url.into(), activation)?;
+                }
+            }
+            ("ReplaceGatewayUrl", AmfValue::String(url)) => {
+                let url = AvmString::new_utf8(activation.context.gc_context, url);
+                this.set_property(&gateway_url, url.into(), activation)?;
+            }
+            _ => {}
+        }
+    }
+
+    let Some(responder) = responder else {
+        return Ok(());
+    };
+
+    for message in messages {
+        let handler = if message.target_uri.ends_with("/onResult") {
+            "_result"
+        } else if message.target_uri.ends_with("/onStatus") {
+            "_status"
+        } else {
+            tracing::warn!(
+                "NetConnection: Ignoring message with unknown target {}",
+                message.target_uri
+            );
+            continue;
+        };
+
+        let handler = ruffle_name(activation, handler);
+        let handler = responder.get_property(&handler, activation)?;
+        if let Some(handler) = handler.as_object() {
+            let value = deserialize_value(activation, &message.value)?;
+            handler.call(Value::Null, &[value], activation)?;
+        }
+    }
+
+    Ok(())
+}
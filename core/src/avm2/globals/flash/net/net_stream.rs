@@ -71,6 +71,20 @@ pub fn resume<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let offset = args.get_f64(activation, 0)?;
+
+        ns.seek(&mut activation.context, offset * 1000.0);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn toggle_pause<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,3 @@
+//! `flash.printing` namespace
+
+pub mod print_job;
@@ -1,6 +1,55 @@
 package flash.printing {
+    import flash.display.Sprite;
     import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+
+    namespace ruffle = "__ruffle__";
 
     public class PrintJob extends EventDispatcher {
+        // Set by `start` once the user accepted the print dialog.
+        ruffle var _started: Boolean = false;
+        ruffle var _orientation: String = PrintJobOrientation.PORTRAIT;
+        ruffle var _pageWidth: int = 0;
+        ruffle var _pageHeight: int = 0;
+        ruffle var _paperWidth: int = 0;
+        ruffle var _paperHeight: int = 0;
+
+        public function PrintJob() {
+        }
+
+        public static native function get isSupported(): Boolean;
+
+        public function get orientation(): String {
+            return this.ruffle::_orientation;
+        }
+
+        public function get pageWidth(): int {
+            return this.ruffle::_pageWidth;
+        }
+
+        public function get pageHeight(): int {
+            return this.ruffle::_pageHeight;
+        }
+
+        public function get paperWidth(): int {
+            return this.ruffle::_paperWidth;
+        }
+
+        public function get paperHeight(): int {
+            return this.ruffle::_paperHeight;
+        }
+
+        public native function start(): Boolean;
+
+        public function addPage(sprite: Sprite, printArea: Rectangle = null, options: PrintJobOptions = null, frameNum: int = 0): void {
+            var printAsBitmap: Boolean = options != null && options.printAsBitmap;
+            if (!this.ruffle::_started || !this.printSprite(sprite, printArea, printAsBitmap, frameNum)) {
+                throw new Error("Error #2057: The page could not be added to the print job.", 2057);
+            }
+        }
+
+        private native function printSprite(sprite: Sprite, printArea: Rectangle, printAsBitmap: Boolean, frameNum: int): Boolean;
+
+        public native function send(): void;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,107 @@
+//! `flash.printing.PrintJob` native function definitions
+
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Multiname, Namespace, Object, Value};
+use crate::backend::print::PrintOrientation;
+use crate::print_job::{print_page, send_print_job, PrintPageOptions};
+
+fn ruffle_name<'gc>(activation: &mut Activation<'_, 'gc>, name: &'static str) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        name,
+    )
+}
+
+/// Implements `PrintJob.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.print.is_supported().into())
+}
+
+/// Implements `PrintJob.start`
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only one print job can be active at a time.
+    let started = ruffle_name(activation, "_started");
+    if this.get_property(&started, activation)?.coerce_to_boolean()
+        || activation.context.print.page_format().is_some()
+    {
+        return Ok(false.into());
+    }
+
+    let Some(format) = activation.context.print.start() else {
+        return Ok(false.into());
+    };
+
+    let orientation = match format.orientation {
+        PrintOrientation::Portrait => "portrait",
+        PrintOrientation::Landscape => "landscape",
+    };
+    let properties: [(&'static str, Value<'gc>); 6] = [
+        ("_started", true.into()),
+        ("_orientation", orientation.into()),
+        ("_pageWidth", format.page_width.into()),
+        ("_pageHeight", format.page_height.into()),
+        ("_paperWidth", format.paper_width.into()),
+        ("_paperHeight", format.paper_height.into()),
+    ];
+    for (name, value) in properties {
+        let name = ruffle_name(activation, name);
+        this.set_property(&name, value, activation)?;
+    }
+    Ok(true.into())
+}
+
+/// Implements the native part of `PrintJob.addPage`
+pub fn print_sprite<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(sprite) = args
+        .try_get_object(activation, 0)
+        .and_then(|sprite| sprite.as_display_object())
+    else {
+        return Ok(false.into());
+    };
+    let print_area = match args.try_get_object(activation, 1) {
+        Some(print_area) => Some(object_to_rectangle(activation, print_area)?),
+        None => None,
+    };
+    let print_as_bitmap = args.get_bool(2);
+    let frame = args.get_i32(activation, 3)?;
+
+    let Some(format) = activation.context.print.page_format() else {
+        return Ok(false.into());
+    };
+
+    let options = PrintPageOptions {
+        print_area,
+        print_as_bitmap,
+        fit_to_page: false,
+        frame: (frame > 0).then_some(frame as u16),
+    };
+    Ok(print_page(&mut activation.context, &format, sprite, &options).into())
+}
+
+/// Implements `PrintJob.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let started = ruffle_name(activation, "_started");
+    if this.get_property(&started, activation)?.coerce_to_boolean() {
+        send_print_job(&mut activation.context);
+        this.set_property(&started, false.into(), activation)?;
+    }
+    Ok(Value::Undefined)
+}
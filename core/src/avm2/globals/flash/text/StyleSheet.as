@@ -1,37 +1,73 @@
 package flash.text {
-    
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    
-    public dynamic class StyleSheet {
+    import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
+    public dynamic class StyleSheet extends EventDispatcher {
+        // The styles by their lowercase names, as copies of the objects given to `setStyle`.
+        ruffle var _css:Object = {};
+
         public function StyleSheet() {}
-        
+
         public function get styleNames():Array {
-            stub_getter("flash.text.StyleSheet", "styleNames");
-            return [];
+            var names:Array = [];
+            for (var name:String in this.ruffle::_css) {
+                names.push(name);
+            }
+            return names;
         }
-        
+
         public function clear():void {
-            stub_method("flash.text.StyleSheet", "clear");
+            this.ruffle::_css = {};
         }
-        
+
         public function getStyle(styleName:String):Object {
-            stub_method("flash.text.StyleSheet", "getStyle");
-            return null;
+            return copy(this.ruffle::_css[styleName.toLowerCase()]);
         }
-        
-        public function parseCSS(CSSText:String):void {
-            stub_method("flash.text.StyleSheet", "parseCSS");
-        }
-        
+
         public function setStyle(styleName:String, styleObject:Object):void {
-            stub_method("flash.text.StyleSheet", "setStyle");
+            var name:String = styleName.toLowerCase();
+            if (styleObject == null) {
+                delete this.ruffle::_css[name];
+            } else {
+                this.ruffle::_css[name] = copy(styleObject);
+            }
+        }
+
+        public function parseCSS(CSSText:String):void {
+            // Pairs of selectors and their declarations, or null if the CSS is malformed.
+            var rules:Array = this.innerParseCSS(CSSText);
+            if (rules == null) {
+                return;
+            }
+
+            for each (var rule:Array in rules) {
+                var name:String = String(rule[0]).toLowerCase();
+                var style:Object = this.ruffle::_css[name];
+                if (style == null) {
+                    style = {};
+                    this.ruffle::_css[name] = style;
+                }
+                var declarations:Object = rule[1];
+                for (var property:String in declarations) {
+                    style[property] = declarations[property];
+                }
+            }
         }
-        
-        public function transform(formatObject:Object):TextFormat {
-            stub_method("flash.text.StyleSheet", "transform");
-            return null;
+
+        public native function transform(formatObject:Object):TextFormat;
+
+        private native function innerParseCSS(css:String):Array;
+
+        private static function copy(object:Object):Object {
+            if (object == null) {
+                return null;
+            }
+            var result:Object = {};
+            for (var property:String in object) {
+                result[property] = object[property];
+            }
+            return result;
         }
     }
 }
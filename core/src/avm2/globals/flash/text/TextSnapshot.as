@@ -0,0 +1,21 @@
+package flash.text {
+    import flash.display.DisplayObjectContainer;
+
+    namespace ruffle = "__ruffle__";
+
+    public class TextSnapshot {
+        // The container whose static texts are exposed by this snapshot.
+        ruffle var _container:DisplayObjectContainer;
+
+        public native function get charCount():int;
+
+        public native function findText(beginIndex:int, textToFind:String, caseSensitive:Boolean):int;
+        public native function getSelected(beginIndex:int, endIndex:int):Boolean;
+        public native function getSelectedText(includeLineEndings:Boolean = false):String;
+        public native function getText(beginIndex:int, endIndex:int, includeLineEndings:Boolean = false):String;
+        public native function getTextRunInfo(beginIndex:int, endIndex:int):Array;
+        public native function hitTestTextNearPos(x:Number, y:Number, maxDistance:Number = 0):Number;
+        public native function setSelectColor(hexColor:uint = 0xFFFF00):void;
+        public native function setSelected(beginIndex:int, endIndex:int, select:Boolean):void;
+    }
+}
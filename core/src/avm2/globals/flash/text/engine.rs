@@ -1,4 +1,5 @@
 //! `flash.text.engine` namespace
 
+pub mod text_block;
 pub mod text_justifier;
 pub mod text_line;
@@ -1,22 +1,70 @@
 package flash.text.engine {
     import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
     public class ContentElement {
         public static const GRAPHIC_ELEMENT:uint = 65007;
         public var userData;
-        
+
+        ruffle var _text:String = null;
+        ruffle var _textBlock:TextBlock = null;
+        ruffle var _textBlockBeginIndex:int = 0;
+        ruffle var _groupElement:GroupElement = null;
+
         private var _elementFormat:ElementFormat;
-        
+        private var _eventMirror:EventDispatcher;
+        private var _textRotation:String;
+
         public function ContentElement(elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
             // FIXME: `new ContentElement()` throws an error in Flash; see TextJustifier
             this._elementFormat = elementFormat;
+            this._eventMirror = eventMirror;
+            this._textRotation = textRotation;
         }
-        
+
+        public function get text():String {
+            return this.ruffle::_text;
+        }
+
+        public function get rawText():String {
+            return this.ruffle::_text;
+        }
+
         public function get elementFormat():ElementFormat {
             return this._elementFormat;
         }
-        
+
         public function set elementFormat(value:ElementFormat):void {
             this._elementFormat = value;
         }
+
+        public function get eventMirror():EventDispatcher {
+            return this._eventMirror;
+        }
+
+        public function set eventMirror(value:EventDispatcher):void {
+            this._eventMirror = value;
+        }
+
+        public function get textRotation():String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value:String):void {
+            this._textRotation = value;
+        }
+
+        public function get textBlock():TextBlock {
+            return this.ruffle::_textBlock;
+        }
+
+        public function get textBlockBeginIndex():int {
+            return this.ruffle::_textBlockBeginIndex;
+        }
+
+        public function get groupElement():GroupElement {
+            return this.ruffle::_groupElement;
+        }
     }
 }
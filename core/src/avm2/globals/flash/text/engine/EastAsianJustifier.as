@@ -1,7 +1,24 @@
 package flash.text.engine {
     public final class EastAsianJustifier extends TextJustifier {
+        private var _justificationStyle:String;
+
         public function EastAsianJustifier(locale:String = "ja", lineJustification:String = "allButLast", justificationStyle:String = "pushInKinsoku") {
             super(locale, lineJustification);
+            this.locale = locale;
+            this.lineJustification = lineJustification;
+            this._justificationStyle = justificationStyle;
+        }
+
+        public function get justificationStyle():String {
+            return this._justificationStyle;
+        }
+
+        public function set justificationStyle(value:String):void {
+            this._justificationStyle = value;
+        }
+
+        override public function clone():TextJustifier {
+            return new EastAsianJustifier(this.locale, this.lineJustification, this._justificationStyle);
         }
     }
 }
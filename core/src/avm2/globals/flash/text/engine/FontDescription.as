@@ -1,6 +1,109 @@
 package flash.text.engine {
+    import flash.errors.IllegalOperationError;
+
     public final class FontDescription {
+        private var _fontName:String;
+        private var _fontWeight:String;
+        private var _fontPosture:String;
+        private var _fontLookup:String;
+        private var _renderingMode:String;
+        private var _cffHinting:String;
+        private var _locked:Boolean = false;
+
         public function FontDescription(fontName:String = "_serif", fontWeight:String = "normal", fontPosture:String = "normal",
-                                        fontLookup:String = "device", renderingMode:String = "cff", cffHinting:String = "horizontalStem") {}
+                                        fontLookup:String = "device", renderingMode:String = "cff", cffHinting:String = "horizontalStem") {
+            this.fontName = fontName;
+            this.fontWeight = fontWeight;
+            this.fontPosture = fontPosture;
+            this.fontLookup = fontLookup;
+            this.renderingMode = renderingMode;
+            this.cffHinting = cffHinting;
+        }
+
+        private function checkLocked():void {
+            if (this._locked) {
+                throw new IllegalOperationError("Error #2185: The FontDescription object is locked and cannot be modified.", 2185);
+            }
+        }
+
+        private static function checkNonNull(value:String, name:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+            }
+        }
+
+        public function get fontName():String {
+            return this._fontName;
+        }
+
+        public function set fontName(value:String):void {
+            this.checkLocked();
+            checkNonNull(value, "fontName");
+            this._fontName = value;
+        }
+
+        public function get fontWeight():String {
+            return this._fontWeight;
+        }
+
+        public function set fontWeight(value:String):void {
+            this.checkLocked();
+            checkNonNull(value, "fontWeight");
+            this._fontWeight = value;
+        }
+
+        public function get fontPosture():String {
+            return this._fontPosture;
+        }
+
+        public function set fontPosture(value:String):void {
+            this.checkLocked();
+            checkNonNull(value, "fontPosture");
+            this._fontPosture = value;
+        }
+
+        public function get fontLookup():String {
+            return this._fontLookup;
+        }
+
+        public function set fontLookup(value:String):void {
+            this.checkLocked();
+            checkNonNull(value, "fontLookup");
+            this._fontLookup = value;
+        }
+
+        public function get renderingMode():String {
+            return this._renderingMode;
+        }
+
+        public function set renderingMode(value:String):void {
+            this.checkLocked();
+            checkNonNull(value, "renderingMode");
+            this._renderingMode = value;
+        }
+
+        public function get cffHinting():String {
+            return this._cffHinting;
+        }
+
+        public function set cffHinting(value:String):void {
+            this.checkLocked();
+            checkNonNull(value, "cffHinting");
+            this._cffHinting = value;
+        }
+
+        public function get locked():Boolean {
+            return this._locked;
+        }
+
+        public function set locked(value:Boolean):void {
+            this.checkLocked();
+            this._locked = value;
+        }
+
+        public function clone():FontDescription {
+            return new FontDescription(this._fontName, this._fontWeight, this._fontPosture,
+                                       this._fontLookup, this._renderingMode, this._cffHinting);
+        }
     }
 }
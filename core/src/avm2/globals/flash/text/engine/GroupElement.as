@@ -0,0 +1,59 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    namespace ruffle = "__ruffle__";
+
+    public final class GroupElement extends ContentElement {
+        private var _elements:Vector.<ContentElement> = new Vector.<ContentElement>();
+
+        public function GroupElement(elements:Vector.<ContentElement> = null, elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
+            super(elementFormat, eventMirror, textRotation);
+            if (elements) {
+                this.setElements(elements);
+            }
+        }
+
+        public function get elementCount():int {
+            return this._elements.length;
+        }
+
+        override public function get text():String {
+            var text:String = "";
+            for each (var element:ContentElement in this._elements) {
+                var elementText:String = element.text;
+                if (elementText != null) {
+                    text += elementText;
+                }
+            }
+            return text;
+        }
+
+        override public function get rawText():String {
+            return this.text;
+        }
+
+        public function getElementAt(index:int):ContentElement {
+            if (index < 0 || index >= this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._elements[index];
+        }
+
+        public function getElementIndex(element:ContentElement):int {
+            return this._elements.indexOf(element);
+        }
+
+        public function setElements(value:Vector.<ContentElement>):void {
+            for each (var oldElement:ContentElement in this._elements) {
+                oldElement.ruffle::_groupElement = null;
+                oldElement.ruffle::_textBlock = null;
+            }
+
+            this._elements = value != null ? value.concat() : new Vector.<ContentElement>();
+            for each (var element:ContentElement in this._elements) {
+                element.ruffle::_groupElement = this;
+                element.ruffle::_textBlock = this.ruffle::_textBlock;
+            }
+        }
+    }
+}
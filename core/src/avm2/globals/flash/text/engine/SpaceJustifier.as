@@ -4,6 +4,8 @@ package flash.text.engine {
 
         public function SpaceJustifier(locale:String = "en", lineJustification:String = "unjustified", letterSpacing:Boolean = false) {
             super(locale, lineJustification);
+            this.locale = locale;
+            this.lineJustification = lineJustification;
             this._letterSpacing = letterSpacing;
         }
         
@@ -14,5 +16,9 @@ package flash.text.engine {
         public function set letterSpacing(value:Boolean):void {
             this._letterSpacing = value;
         }
+
+        override public function clone():TextJustifier {
+            return new SpaceJustifier(this.locale, this.lineJustification, this._letterSpacing);
+        }
     }
 }
@@ -1,6 +1,14 @@
 package flash.text.engine {
+    namespace ruffle = "__ruffle__";
+
     public final class TextBlock {
         public var userData;
+
+        // Set by `layOutLine`.
+        ruffle var _textLineCreationResult:String = null;
+
+        private var _firstLine:TextLine = null;
+        private var _lastLine:TextLine = null;
         
         private var _applyNonLinearFontScaling:Boolean;
         private var _baselineFontDescription:FontDescription = null;
@@ -34,7 +42,7 @@ package flash.text.engine {
             if (textJustifier) {
                 this.textJustifier = textJustifier;
             } else {
-                // This should creaate a new TextJustifier with locale "en", but we don't actually support creating TextJustifiers yet.
+                this.textJustifier = new SpaceJustifier("en");
             }
             
             this.lineRotation = lineRotation;
@@ -124,7 +132,260 @@ package flash.text.engine {
         }
         
         public function set content(value:ContentElement):void {
+            if (this._content) {
+                setTextBlock(this._content, null);
+            }
             this._content = value;
+            if (value) {
+                setTextBlock(value, this);
+            }
+            this.releaseLines(this._firstLine, this._lastLine);
+        }
+
+        public function get firstLine():TextLine {
+            return this._firstLine;
+        }
+
+        public function get lastLine():TextLine {
+            return this._lastLine;
+        }
+
+        public function get firstInvalidLine():TextLine {
+            for (var line:TextLine = this._firstLine; line != null; line = line.nextLine) {
+                if (line.validity != TextLineValidity.VALID) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function get textLineCreationResult():String {
+            return this.ruffle::_textLineCreationResult;
+        }
+
+        private static function setTextBlock(element:ContentElement, textBlock:TextBlock):void {
+            element.ruffle::_textBlock = textBlock;
+            var group:GroupElement = element as GroupElement;
+            if (group) {
+                for (var i:int = 0; i < group.elementCount; i++) {
+                    setTextBlock(group.getElementAt(i), textBlock);
+                }
+            }
+        }
+
+        // Flatten the content into its text elements and their formats,
+        // recording where each element begins in the text block.
+        private static function collectElements(element:ContentElement, parentFormat:ElementFormat, beginIndex:int,
+                                                elements:Array, formats:Array):int {
+            element.ruffle::_textBlockBeginIndex = beginIndex;
+            var format:ElementFormat = element.elementFormat != null ? element.elementFormat : parentFormat;
+
+            var group:GroupElement = element as GroupElement;
+            if (group) {
+                for (var i:int = 0; i < group.elementCount; i++) {
+                    beginIndex = collectElements(group.getElementAt(i), format, beginIndex, elements, formats);
+                }
+                return beginIndex;
+            }
+
+            elements.push(element);
+            formats.push(format != null ? format : new ElementFormat());
+            var text:String = element.rawText;
+            return beginIndex + (text != null ? text.length : 0);
+        }
+
+        public function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            return this.layOutNextLine(previousLine, width, lineOffset, fitSomething, null);
+        }
+
+        public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            if (textLine == null) {
+                throw new TypeError("Error #2007: Parameter textLine must be non-null.", 2007);
+            }
+            if (textLine == previousLine) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (textLine.textBlock != null) {
+                textLine.textBlock.releaseLines(textLine, textLine);
+            }
+            return this.layOutNextLine(previousLine, width, lineOffset, fitSomething, textLine);
+        }
+
+        private function layOutNextLine(previousLine:TextLine, width:Number, lineOffset:Number, fitSomething:Boolean,
+                                        reuseLine:TextLine):TextLine {
+            if (isNaN(width) || width < 0 || width > TextLine.MAX_LINE_WIDTH) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (previousLine != null && previousLine.textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            if (previousLine == null) {
+                this.releaseLines(this._firstLine, this._lastLine);
+            } else if (previousLine.nextLine != null) {
+                this.releaseLines(previousLine.nextLine, this._lastLine);
+            }
+
+            var elements:Array = [];
+            var formats:Array = [];
+            var textLength:int = 0;
+            if (this._content) {
+                textLength = collectElements(this._content, null, 0, elements, formats);
+            }
+
+            var beginIndex:int = previousLine != null ? previousLine.textBlockBeginIndex + previousLine.rawTextLength : 0;
+            if (beginIndex >= textLength) {
+                this.ruffle::_textLineCreationResult = TextLineCreationResult.COMPLETE;
+                return null;
+            }
+
+            var tabStops:Array = [];
+            if (this._tabStops) {
+                for each (var tabStop:TabStop in this._tabStops) {
+                    tabStops.push(tabStop);
+                }
+            }
+
+            var lineJustification:String = "unjustified";
+            var letterSpacing:Boolean = false;
+            if (this._textJustifier) {
+                lineJustification = this._textJustifier.lineJustification;
+                if (this._textJustifier is SpaceJustifier) {
+                    letterSpacing = (this._textJustifier as SpaceJustifier).letterSpacing;
+                } else if (this._textJustifier is EastAsianJustifier) {
+                    letterSpacing = true;
+                }
+            }
+
+            var line:TextLine = this.layOutLine(elements, formats, tabStops, lineJustification, letterSpacing,
+                                                beginIndex, width, lineOffset, fitSomething, reuseLine);
+            if (line == null) {
+                return null;
+            }
+
+            line.ruffle::_validity = TextLineValidity.VALID;
+            line.ruffle::_previousLine = previousLine;
+            line.ruffle::_nextLine = null;
+            if (previousLine != null) {
+                previousLine.ruffle::_nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
+            return line;
+        }
+
+        private native function layOutLine(elements:Array, formats:Array, tabStops:Array, lineJustification:String,
+                                           letterSpacing:Boolean, beginIndex:int, width:Number, lineOffset:Number,
+                                           fitSomething:Boolean, reuseLine:TextLine):TextLine;
+
+        public function releaseLines(firstLine:TextLine, lastLine:TextLine):void {
+            if (firstLine == null || lastLine == null || firstLine.textBlock != this || lastLine.textBlock != this) {
+                return;
+            }
+
+            var before:TextLine = firstLine.previousLine;
+            var line:TextLine = firstLine;
+            while (line != null) {
+                var next:TextLine = line.nextLine;
+                line.ruffle::_textBlock = null;
+                line.ruffle::_previousLine = null;
+                line.ruffle::_nextLine = null;
+                line.ruffle::_validity = TextLineValidity.INVALID;
+                if (line == lastLine) {
+                    line = next;
+                    break;
+                }
+                line = next;
+            }
+
+            if (before != null) {
+                before.ruffle::_nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            if (line != null) {
+                line.ruffle::_previousLine = before;
+            } else {
+                this._lastLine = before;
+            }
+        }
+
+        public function releaseLineCreationData():void {
+            // We don't keep any line creation data around.
+        }
+
+        private function contentText():String {
+            if (this._content == null) {
+                return "";
+            }
+            var text:String = this._content.rawText;
+            return text != null ? text : "";
+        }
+
+        private static function isWordCharacter(c:String):Boolean {
+            return c != " " && c != "\t" && c != "\n" && c != "\r" && c != "\u3000" && c != "\u2028" && c != "\u2029";
+        }
+
+        public function findNextWordBoundary(afterCharIndex:int):int {
+            var text:String = this.contentText();
+            if (afterCharIndex < 0 || afterCharIndex >= text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var index:int = afterCharIndex;
+            var inWord:Boolean = isWordCharacter(text.charAt(index));
+            while (index < text.length && isWordCharacter(text.charAt(index)) == inWord) {
+                index++;
+            }
+            return index;
+        }
+
+        public function findPreviousWordBoundary(beforeCharIndex:int):int {
+            var text:String = this.contentText();
+            if (beforeCharIndex < 0 || beforeCharIndex > text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            if (beforeCharIndex == 0) {
+                return 0;
+            }
+            var index:int = beforeCharIndex - 1;
+            var inWord:Boolean = isWordCharacter(text.charAt(index));
+            while (index > 0 && isWordCharacter(text.charAt(index - 1)) == inWord) {
+                index--;
+            }
+            return index;
+        }
+
+        public function findNextAtomBoundary(afterCharIndex:int):int {
+            var text:String = this.contentText();
+            if (afterCharIndex < 0 || afterCharIndex >= text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var code:Number = text.charCodeAt(afterCharIndex);
+            if (code >= 0xD800 && code <= 0xDBFF && afterCharIndex + 1 < text.length) {
+                return afterCharIndex + 2;
+            }
+            return afterCharIndex + 1;
+        }
+
+        public function findPreviousAtomBoundary(beforeCharIndex:int):int {
+            var text:String = this.contentText();
+            if (beforeCharIndex <= 0 || beforeCharIndex > text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var code:Number = text.charCodeAt(beforeCharIndex - 1);
+            if (code >= 0xDC00 && code <= 0xDFFF && beforeCharIndex >= 2) {
+                return beforeCharIndex - 2;
+            }
+            return beforeCharIndex - 1;
+        }
+
+        public function dump():String {
+            var result:String = "<block>";
+            for (var line:TextLine = this._firstLine; line != null; line = line.nextLine) {
+                result += line.dump();
+            }
+            return result + "</block>";
         }
     }
 }
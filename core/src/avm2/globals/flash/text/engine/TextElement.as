@@ -1,16 +1,28 @@
 package flash.text.engine {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_setter;
-   
+
+    namespace ruffle = "__ruffle__";
+
     public final class TextElement extends ContentElement {
         public function TextElement(text:String = null, elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
             super(elementFormat, eventMirror, textRotation);
+            this.ruffle::_text = text;
         }
-        
+
         // Contrary to the documentation, TextElement does not implement a getter here. It inherits the getter from ContentElement.
         public function set text(value:String):void {
-            stub_setter("flash.text.engine.TextElement", "text");
+            this.ruffle::_text = value;
+        }
+
+        public function replaceText(beginIndex:int, endIndex:int, newText:String):void {
+            var text:String = this.ruffle::_text != null ? this.ruffle::_text : "";
+            if (beginIndex < 0 || endIndex < beginIndex || endIndex > text.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            if (newText == null) {
+                newText = "";
+            }
+            this.ruffle::_text = text.substring(0, beginIndex) + newText + text.substring(endIndex);
         }
     }
 }
-
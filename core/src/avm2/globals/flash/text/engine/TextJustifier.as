@@ -2,10 +2,47 @@ package flash.text.engine {
 
     [Ruffle(NativeInstanceInit)]
     public class TextJustifier {
+        private var _locale:String;
+        private var _lineJustification:String;
+
+        // Subclasses are initialized natively, and set `locale` and
+        // `lineJustification` themselves.
         public function TextJustifier(locale:String, lineJustification:String) {
             throw new ArgumentError("Error #2012: TextJustifier$ class cannot be instantiated.", 2012);
         }
-        
+
+        public static function getJustifierForLocale(locale:String):TextJustifier {
+            if (locale != null) {
+                var language:String = locale.substr(0, 2).toLowerCase();
+                if (language == "ja" || language == "zh") {
+                    return new EastAsianJustifier(locale);
+                }
+            }
+            return new SpaceJustifier(locale);
+        }
+
+        public function get locale():String {
+            return this._locale;
+        }
+
+        public function set locale(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter locale must be non-null.", 2007);
+            }
+            this._locale = value;
+        }
+
+        public function get lineJustification():String {
+            return this._lineJustification;
+        }
+
+        public function set lineJustification(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter lineJustification must be non-null.", 2007);
+            }
+            this._lineJustification = value;
+        }
+
         public function clone():TextJustifier {
             return null;
         }
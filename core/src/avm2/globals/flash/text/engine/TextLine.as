@@ -1,14 +1,218 @@
 package flash.text.engine {
+    import flash.display.DisplayObject;
     import flash.display.DisplayObjectContainer;
-    
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+
+    namespace ruffle = "__ruffle__";
+
     [Ruffle(NativeInstanceInit)]
     public final class TextLine extends DisplayObjectContainer {
         public static const MAX_LINE_WIDTH:int = 1000000;
         
         public var userData;
+
+        // These are set by `TextBlock.layOutLine`.
+        ruffle var _textBlock:TextBlock = null;
+        ruffle var _textBlockBeginIndex:int = 0;
+        ruffle var _rawTextLength:int = 0;
+        ruffle var _ascent:Number = 0;
+        ruffle var _descent:Number = 0;
+        ruffle var _textWidth:Number = 0;
+        ruffle var _unjustifiedTextWidth:Number = 0;
+        ruffle var _specifiedWidth:Number = 0;
+        ruffle var _atomBounds:Array = [];
+        ruffle var _atomBeginIndices:Array = [];
+        ruffle var _atomEndIndices:Array = [];
+
+        ruffle var _nextLine:TextLine = null;
+        ruffle var _previousLine:TextLine = null;
+        ruffle var _validity:String = "valid";
         
         public function TextLine() {
             throw new ArgumentError("Error #2012: TextLine$ class cannot be instantiated.", 2012);
         }
+
+        public function get ascent():Number {
+            return this.ruffle::_ascent;
+        }
+
+        public function get descent():Number {
+            return this.ruffle::_descent;
+        }
+
+        public function get textHeight():Number {
+            return this.ruffle::_ascent + this.ruffle::_descent;
+        }
+
+        public function get textWidth():Number {
+            return this.ruffle::_textWidth;
+        }
+
+        public function get unjustifiedTextWidth():Number {
+            return this.ruffle::_unjustifiedTextWidth;
+        }
+
+        public function get specifiedWidth():Number {
+            return this.ruffle::_specifiedWidth;
+        }
+
+        public function get totalAscent():Number {
+            return this.ruffle::_ascent;
+        }
+
+        public function get totalDescent():Number {
+            return this.ruffle::_descent;
+        }
+
+        public function get totalHeight():Number {
+            return this.ruffle::_ascent + this.ruffle::_descent;
+        }
+
+        public function get rawTextLength():int {
+            return this.ruffle::_rawTextLength;
+        }
+
+        public function get textBlock():TextBlock {
+            return this.ruffle::_textBlock;
+        }
+
+        public function get textBlockBeginIndex():int {
+            return this.ruffle::_textBlockBeginIndex;
+        }
+
+        public function get atomCount():int {
+            return this.ruffle::_atomBounds.length;
+        }
+
+        public function get nextLine():TextLine {
+            return this.ruffle::_nextLine;
+        }
+
+        public function get previousLine():TextLine {
+            return this.ruffle::_previousLine;
+        }
+
+        public function get validity():String {
+            return this.ruffle::_validity;
+        }
+
+        public function set validity(value:String):void {
+            if (this.ruffle::_validity == TextLineValidity.STATIC || value == TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this.ruffle::_validity = value;
+        }
+
+        public function get hasGraphicElement():Boolean {
+            return false;
+        }
+
+        private function checkAtomIndex(atomIndex:int):void {
+            if (atomIndex < 0 || atomIndex >= this.ruffle::_atomBounds.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+        }
+
+        public function getAtomBounds(atomIndex:int):Rectangle {
+            this.checkAtomIndex(atomIndex);
+            return (this.ruffle::_atomBounds[atomIndex] as Rectangle).clone();
+        }
+
+        public function getAtomCenter(atomIndex:int):Number {
+            this.checkAtomIndex(atomIndex);
+            var bounds:Rectangle = this.ruffle::_atomBounds[atomIndex];
+            return bounds.x + bounds.width / 2;
+        }
+
+        public function getAtomIndexAtCharIndex(charIndex:int):int {
+            var count:int = this.ruffle::_atomBounds.length;
+            for (var i:int = 0; i < count; i++) {
+                if (charIndex >= this.ruffle::_atomBeginIndices[i] && charIndex < this.ruffle::_atomEndIndices[i]) {
+                    return i;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
+            var point:Point = this.globalToLocal(new Point(stageX, stageY));
+            var count:int = this.ruffle::_atomBounds.length;
+            for (var i:int = 0; i < count; i++) {
+                if ((this.ruffle::_atomBounds[i] as Rectangle).containsPoint(point)) {
+                    return i;
+                }
+            }
+            return -1;
+        }
+
+        public function getAtomTextBlockBeginIndex(atomIndex:int):int {
+            this.checkAtomIndex(atomIndex);
+            return this.ruffle::_atomBeginIndices[atomIndex];
+        }
+
+        public function getAtomTextBlockEndIndex(atomIndex:int):int {
+            this.checkAtomIndex(atomIndex);
+            return this.ruffle::_atomEndIndices[atomIndex];
+        }
+
+        public function getAtomBidiLevel(atomIndex:int):int {
+            this.checkAtomIndex(atomIndex);
+            return 0;
+        }
+
+        public function getAtomTextRotation(atomIndex:int):String {
+            this.checkAtomIndex(atomIndex);
+            return TextRotation.ROTATE_0;
+        }
+
+        public function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean {
+            this.checkAtomIndex(atomIndex);
+            var beginIndex:int = this.ruffle::_atomBeginIndices[atomIndex];
+            if (beginIndex == 0 || this.ruffle::_textBlock == null || this.ruffle::_textBlock.content == null) {
+                return true;
+            }
+            var text:String = this.ruffle::_textBlock.content.rawText;
+            var before:String = text.charAt(beginIndex - 1);
+            var after:String = text.charAt(beginIndex);
+            return (before == " ") != (after == " ");
+        }
+
+        public function getAtomGraphic(atomIndex:int):DisplayObject {
+            this.checkAtomIndex(atomIndex);
+            return null;
+        }
+
+        public function getBaselinePosition(baseline:String):Number {
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this.ruffle::_ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this.ruffle::_descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this.ruffle::_descent - this.ruffle::_ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+        }
+
+        public function flushAtomData():void {
+            // Atom data is always kept.
+        }
+
+        public function dump():String {
+            var result:String = "<line>";
+            var count:int = this.ruffle::_atomBounds.length;
+            for (var i:int = 0; i < count; i++) {
+                var bounds:Rectangle = this.ruffle::_atomBounds[i];
+                result += "<atom begin=\"" + this.ruffle::_atomBeginIndices[i] + "\" end=\"" + this.ruffle::_atomEndIndices[i]
+                        + "\" x=\"" + bounds.x + "\" width=\"" + bounds.width + "\"/>";
+            }
+            return result + "</line>";
+        }
     }
 }
@@ -0,0 +1,293 @@
+//! `flash.text.engine.TextBlock` native function definitions
+
+use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Multiname, Namespace, Object, Value};
+use crate::avm2_stub_method;
+use crate::display_object::{MovieClip, TDisplayObject};
+use crate::font::{Font, FontType};
+use crate::string::{AvmString, WStr};
+use crate::text_engine::{
+    draw_line, lay_out_line as lay_out_text_line, Justifier, LineJustification, RunFormat,
+    TabAlignment, TabStop, TextRun,
+};
+use swf::Color;
+
+fn ruffle_name<'gc>(activation: &mut Activation<'_, 'gc>, name: &'static str) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        name,
+    )
+}
+
+/// The values of an array, with holes as `undefined`.
+fn array_values<'gc>(array: Object<'gc>) -> Vec<Value<'gc>> {
+    array
+        .as_array_storage()
+        .map(|storage| {
+            storage
+                .iter()
+                .map(|value| value.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Find the font described by a `FontDescription` for rendering `text`.
+fn font_for_description<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    font_description: Object<'gc>,
+    text: &WStr,
+) -> Result<Option<Font<'gc>>, Error<'gc>> {
+    let name = font_description
+        .get_public_property("fontName", activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let weight = font_description
+        .get_public_property("fontWeight", activation)?
+        .coerce_to_string(activation)?;
+    let posture = font_description
+        .get_public_property("fontPosture", activation)?
+        .coerce_to_string(activation)?;
+    let lookup = font_description
+        .get_public_property("fontLookup", activation)?
+        .coerce_to_string(activation)?;
+    let is_bold = &weight == b"bold";
+    let is_italic = &posture == b"italic";
+
+    let library = &activation.context.library;
+    if &lookup == b"embeddedCFF" {
+        let embedded_font = library.known_movies().into_iter().find_map(|movie| {
+            library
+                .library_for_movie(movie)?
+                .get_font_by_name(&name, is_bold, is_italic)
+                .filter(|font| font.font_type() == FontType::EmbeddedCFF && font.has_glyphs())
+        });
+        if embedded_font.is_some() {
+            return Ok(embedded_font);
+        }
+    }
+
+    Ok(library.get_device_font(&name, is_bold, is_italic, text))
+}
+
+/// Read the formatting of an `ElementFormat` used by a run of text.
+fn run_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element_format: Object<'gc>,
+    text: &WStr,
+) -> Result<Option<RunFormat<'gc>>, Error<'gc>> {
+    let font_description = element_format
+        .get_public_property("fontDescription", activation)?
+        .as_object();
+    let font = match font_description {
+        Some(font_description) => font_for_description(activation, font_description, text)?,
+        None => activation.context.library.device_font(),
+    };
+    let Some(font) = font else {
+        return Ok(None);
+    };
+
+    let mut number = |name: &'static str| -> Result<f64, Error<'gc>> {
+        element_format
+            .get_public_property(name, activation)?
+            .coerce_to_number(activation)
+    };
+    let size = number("fontSize")?;
+    let alpha = number("alpha")?;
+    let baseline_shift = number("baselineShift")?;
+    let tracking_left = number("trackingLeft")?;
+    let tracking_right = number("trackingRight")?;
+    let color = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let kerning = element_format
+        .get_public_property("kerning", activation)?
+        .coerce_to_string(activation)?;
+
+    Ok(Some(RunFormat {
+        font,
+        size,
+        color: Color::from_rgb(color, (alpha.clamp(0.0, 1.0) * 255.0) as u8),
+        baseline_shift,
+        tracking_left,
+        tracking_right,
+        kerning: &kerning != b"off",
+    }))
+}
+
+fn tab_stops<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    tab_stops: Object<'gc>,
+) -> Result<Vec<TabStop>, Error<'gc>> {
+    let mut result = Vec::new();
+    for tab_stop in array_values(tab_stops) {
+        let Some(tab_stop) = tab_stop.as_object() else {
+            continue;
+        };
+        let alignment = tab_stop
+            .get_public_property("alignment", activation)?
+            .coerce_to_string(activation)?;
+        let position = tab_stop
+            .get_public_property("position", activation)?
+            .coerce_to_number(activation)?;
+        let decimal_alignment_token = tab_stop
+            .get_public_property("decimalAlignmentToken", activation)?
+            .coerce_to_string(activation)?;
+        result.push(TabStop {
+            alignment: TabAlignment::from_name(&alignment).unwrap_or(TabAlignment::Start),
+            position,
+            decimal_alignment_token: decimal_alignment_token.as_wstr().into(),
+        });
+    }
+    result.sort_by(|a, b| a.position.total_cmp(&b.position));
+    Ok(result)
+}
+
+/// Implements `TextBlock.layOutLine`, breaking the next line out of the
+/// given text elements and their formats.
+///
+/// The line is drawn into `reuseLine` if given, or into a new `TextLine`.
+pub fn lay_out_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let elements = args.get_object(activation, 0, "elements")?;
+    let formats = args.get_object(activation, 1, "formats")?;
+    let tab_stops = args.get_object(activation, 2, "tabStops")?;
+    let tab_stops = self::tab_stops(activation, tab_stops)?;
+    let line_justification = args.get_string(activation, 3)?;
+    let justifier = Justifier {
+        line_justification: LineJustification::from_name(&line_justification)
+            .unwrap_or(LineJustification::Unjustified),
+        letter_spacing: args.get_bool(4),
+    };
+    let begin_index = args.get_i32(activation, 5)?.max(0) as usize;
+    let width = args.get_f64(activation, 6)?;
+    let line_offset = args.get_f64(activation, 7)?;
+    let fit_something = args.get_bool(8);
+    let reuse_line = args.try_get_object(activation, 9);
+
+    if !matches!(
+        this.get_public_property("baselineFontDescription", activation)?,
+        Value::Null | Value::Undefined
+    ) {
+        avm2_stub_method!(
+            activation,
+            "flash.text.engine.TextBlock",
+            "createTextLine",
+            "with baselineFontDescription"
+        );
+    }
+
+    let mut runs = Vec::new();
+    let formats = array_values(formats);
+    for (element, format) in array_values(elements).into_iter().zip(formats) {
+        let (Some(element), Some(format)) = (element.as_object(), format.as_object()) else {
+            continue;
+        };
+        let text = match element.get_public_property("text", activation)? {
+            Value::Null | Value::Undefined => AvmString::default(),
+            text => text.coerce_to_string(activation)?,
+        };
+        let Some(format) = run_format(activation, format, &text)? else {
+            tracing::warn!("TextBlock.createTextLine: No font available for text");
+            return Ok(Value::Null);
+        };
+        runs.push(TextRun {
+            text: text.as_wstr().into(),
+            format,
+        });
+    }
+
+    let creation_result = ruffle_name(activation, "_textLineCreationResult");
+    let Some(layout) = lay_out_text_line(
+        &runs,
+        begin_index,
+        width,
+        line_offset,
+        justifier,
+        &tab_stops,
+        fit_something,
+    ) else {
+        this.set_property(&creation_result, "insufficientWidth".into(), activation)?;
+        return Ok(Value::Null);
+    };
+    let result = if layout.is_emergency {
+        "emergency"
+    } else {
+        "success"
+    };
+    this.set_property(&creation_result, result.into(), activation)?;
+
+    let reused_clip = reuse_line
+        .and_then(|line| line.as_display_object())
+        .and_then(|line| line.as_movie_clip());
+    let (mut line, movie_clip) = if let (Some(line), Some(movie_clip)) = (reuse_line, reused_clip) {
+        movie_clip.drawing(activation.context.gc_context).clear();
+        (line, movie_clip)
+    } else {
+        let class = activation.avm2().classes().textline;
+        let movie_clip = MovieClip::new(
+            activation.context.swf.clone(),
+            activation.context.gc_context,
+        );
+        let line = initialize_for_allocator(activation, movie_clip.into(), class)?;
+        class.call_native_init(line, &[], activation)?;
+        (line, movie_clip)
+    };
+    draw_line(
+        &mut movie_clip.drawing(activation.context.gc_context),
+        &runs,
+        &layout,
+    );
+
+    let mut atom_bounds = Vec::with_capacity(layout.atoms.len());
+    let mut atom_begin_indices = Vec::with_capacity(layout.atoms.len());
+    let mut atom_end_indices = Vec::with_capacity(layout.atoms.len());
+    for atom in &layout.atoms {
+        let bounds = activation.avm2().classes().rectangle.construct(
+            activation,
+            &[
+                atom.x.into(),
+                (-layout.ascent).into(),
+                atom.width.into(),
+                (layout.ascent + layout.descent).into(),
+            ],
+        )?;
+        atom_bounds.push(bounds.into());
+        atom_begin_indices.push(atom.begin_index.into());
+        atom_end_indices.push(atom.end_index.into());
+    }
+    let atom_bounds = ArrayObject::from_storage(activation, ArrayStorage::from_args(&atom_bounds))?;
+    let atom_begin_indices =
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&atom_begin_indices))?;
+    let atom_end_indices =
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&atom_end_indices))?;
+
+    let properties: [(&'static str, Value<'gc>); 11] = [
+        ("_textBlock", this.into()),
+        ("_textBlockBeginIndex", layout.begin_index.into()),
+        ("_rawTextLength", layout.raw_text_length.into()),
+        ("_ascent", layout.ascent.into()),
+        ("_descent", layout.descent.into()),
+        ("_textWidth", layout.text_width.into()),
+        (
+            "_unjustifiedTextWidth",
+            layout.unjustified_text_width.into(),
+        ),
+        ("_specifiedWidth", width.into()),
+        ("_atomBounds", atom_bounds.into()),
+        ("_atomBeginIndices", atom_begin_indices.into()),
+        ("_atomEndIndices", atom_end_indices.into()),
+    ];
+    for (name, value) in properties {
+        let name = ruffle_name(activation, name);
+        line.set_property(&name, value, activation)?;
+    }
+
+    Ok(line.into())
+}
@@ -1,39 +1,26 @@
 //! `flash.text.Font` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::error::argument_error;
+use crate::avm2::object::{FontObject, Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
-use crate::avm2_stub_method;
 use crate::character::Character;
 use crate::string::AvmString;
 
+pub use crate::avm2::object::font_allocator;
+
 /// Implements `Font.fontName`
 pub fn get_font_name<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.instance_of().and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
-        if let Some(Character::Font(font)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            return Ok(AvmString::new_utf8(
-                activation.context.gc_context,
-                font.descriptor().class(),
-            )
-            .into());
-        }
+    if let Some(font) = this.as_font() {
+        return Ok(
+            AvmString::new_utf8(activation.context.gc_context, font.descriptor().class()).into(),
+        );
     }
 
     Ok(Value::Undefined)
@@ -41,30 +28,17 @@ pub fn get_font_name<'gc>(
 
 /// Implements `Font.fontStyle`
 pub fn get_font_style<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.instance_of().and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
-        if let Some(Character::Font(font)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            return match (font.descriptor().bold(), font.descriptor().italic()) {
-                (false, false) => Ok("regular".into()),
-                (false, true) => Ok("italic".into()),
-                (true, false) => Ok("bold".into()),
-                (true, true) => Ok("boldItalic".into()),
-            };
-        }
+    if let Some(font) = this.as_font() {
+        return match (font.descriptor().bold(), font.descriptor().italic()) {
+            (false, false) => Ok("regular".into()),
+            (false, true) => Ok("italic".into()),
+            (true, false) => Ok("bold".into()),
+            (true, true) => Ok("boldItalic".into()),
+        };
     }
 
     Ok(Value::Undefined)
@@ -72,26 +46,12 @@ pub fn get_font_style<'gc>(
 
 /// Implements `Font.fontType`
 pub fn get_font_type<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.instance_of().and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
-        if let Some(Character::Font(_)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            //TODO: How do we distinguish between CFF and non-CFF embedded fonts?
-            return Ok("embedded".into());
-        }
+    if let Some(font) = this.as_font() {
+        return Ok(font.font_type().as_str().into());
     }
 
     Ok(Value::Undefined)
@@ -103,44 +63,260 @@ pub fn has_glyphs<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some((movie, character_id)) = this.instance_of().and_then(|this| {
-        activation
-            .context
-            .library
-            .avm2_class_registry()
-            .class_symbol(this)
-    }) {
+    if let Some(font) = this.as_font() {
         let my_str = args.get_string(activation, 0)?;
-
-        if let Some(Character::Font(font)) = activation
-            .context
-            .library
-            .library_for_movie_mut(movie)
-            .character_by_id(character_id)
-        {
-            return Ok(font.has_glyphs_for_str(&my_str).into());
-        }
+        return Ok(font.has_glyphs_for_str(&my_str).into());
     }
 
     Ok(Value::Undefined)
 }
 
-/// `Font.enumerateFonts`
+/// Implements `Font.enumerateFonts`
 pub fn enumerate_fonts<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "enumerateFonts");
-    Ok(ArrayObject::from_storage(activation, ArrayStorage::new(0))?.into())
+    let enumerate_device_fonts = args.get_bool(0);
+
+    // The fonts embedded in the calling movie come first, then those registered by any movie.
+    let library = &activation.context.library;
+    let mut fonts: Vec<_> = match activation.caller_domain() {
+        Some(domain) => library
+            .libraries_for_avm2_domain(domain)
+            .flat_map(|library| library.embedded_fonts())
+            .collect(),
+        None => library
+            .library_for_movie(activation.context.swf.clone())
+            .map(|library| library.embedded_fonts())
+            .unwrap_or_default(),
+    };
+    for font in library.registered_fonts() {
+        if !fonts.iter().any(|f| f.descriptor() == font.descriptor()) {
+            fonts.push(font);
+        }
+    }
+    if enumerate_device_fonts {
+        fonts.extend(library.device_fonts());
+    }
+
+    let mut storage = ArrayStorage::new(0);
+    for font in fonts {
+        storage.push(FontObject::for_font(activation, font)?.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
 }
 
-/// `Font.registerFont`
+/// Implements `Font.registerFont`
 pub fn register_font<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "registerFont");
+    let font_class = args.get_object(activation, 0, "font")?;
+
+    let font = font_class
+        .as_class_object()
+        .and_then(|class| {
+            activation
+                .context
+                .library
+                .avm2_class_registry()
+                .class_symbol(class)
+        })
+        .and_then(|(movie, id)| {
+            match activation
+                .context
+                .library
+                .library_for_movie_mut(movie)
+                .character_by_id(id)
+            {
+                Some(Character::Font(font)) => Some(*font),
+                _ => None,
+            }
+        });
+
+    let Some(font) = font else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #1508: The value specified for argument font is invalid.",
+            1508,
+        )?));
+    };
+    activation.context.library.register_font(font);
+
     Ok(Value::Undefined)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::Domain;
+    use crate::context::UpdateContext;
+    use crate::font::Font;
+    use crate::html::{FormatSpans, LayoutBox, TextFormat};
+    use crate::player::PlayerBuilder;
+    use crate::string::WString;
+    use crate::tag_utils::SwfMovie;
+    use std::sync::Arc;
+    use swf::{FileAttributes, Header, SwfStr, Tag, Twips};
+
+    const NOTO_SANS: &[u8] = include_bytes!("../../../../../assets/noto-sans-definefont3.bin");
+
+    /// Constructs an AS3 movie without any tags but its `FileAttributes`.
+    fn as3_movie() -> Arc<SwfMovie> {
+        let mut data = Vec::new();
+        swf::write_swf(
+            &Header::default_with_swf_version(10),
+            &[Tag::FileAttributes(FileAttributes::IS_ACTION_SCRIPT_3)],
+            &mut data,
+        )
+        .unwrap();
+        Arc::new(SwfMovie::from_data(&data, "file:///movie.swf".into(), None).unwrap())
+    }
+
+    /// Embeds a copy of the device font under the given name as character 1 of `movie`.
+    fn embed_font<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        movie: Arc<SwfMovie>,
+        name: &str,
+    ) -> Font<'gc> {
+        let mut reader = swf::read::Reader::new(NOTO_SANS, 8);
+        let tag = swf::Font {
+            name: SwfStr::from_utf8_str(name),
+            ..reader.read_define_font_2(3).unwrap()
+        };
+        let font = Font::from_swf_tag(context.gc_context, context.renderer, tag, reader.encoding());
+        context
+            .library
+            .library_for_movie_mut(movie)
+            .register_character(1, Character::Font(font));
+        font
+    }
+
+    /// Returns the name of the font a text field of `movie` lays out the given font name with.
+    fn layout_font_name(
+        context: &mut UpdateContext<'_, '_>,
+        movie: Arc<SwfMovie>,
+        name: &str,
+    ) -> String {
+        let text = WString::from_utf8("Hello");
+        let format = TextFormat {
+            font: Some(WString::from_utf8(name)),
+            size: Some(12.0),
+            ..Default::default()
+        };
+        let spans = FormatSpans::from_text(text.clone(), format);
+        let (boxes, _) = LayoutBox::lower_from_text_spans(
+            &spans,
+            context,
+            movie,
+            Twips::from_pixels(100.0),
+            false,
+            false,
+        );
+        boxes
+            .iter()
+            .find_map(|layout_box| layout_box.as_renderable_text(&text))
+            .map(|(_, _, font, _, _)| font.descriptor().class().to_string())
+            .expect("Text should be laid out")
+    }
+
+    fn font_names(fonts: Value<'_>) -> Vec<String> {
+        let fonts = fonts.as_object().unwrap();
+        let storage = fonts.as_array_storage().unwrap();
+        let names = storage
+            .iter()
+            .map(|font| {
+                let font = font.and_then(|font| font.as_object()).unwrap();
+                font.as_font().unwrap().descriptor().class().to_string()
+            })
+            .collect();
+        names
+    }
+
+    #[test]
+    fn registered_font_is_visible_to_other_movies() {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let (movie, other_movie, avm1_movie) =
+                    (as3_movie(), as3_movie(), Arc::new(SwfMovie::empty(10)));
+                embed_font(context, movie.clone(), "Registered Font");
+                assert_eq!(
+                    layout_font_name(context, other_movie.clone(), "Registered Font"),
+                    "Noto Sans"
+                );
+
+                let mut activation = Activation::from_nothing(context.reborrow());
+                let font_class = activation.avm2().classes().font;
+                activation
+                    .context
+                    .library
+                    .avm2_class_registry_mut()
+                    .set_class_symbol(font_class, movie, 1);
+                register_font(&mut activation, font_class.into(), &[font_class.into()]).unwrap();
+
+                // Only AVM2 movies can use fonts registered by other movies.
+                assert_eq!(
+                    layout_font_name(&mut activation.context, other_movie, "Registered Font"),
+                    "Registered Font"
+                );
+                assert_eq!(
+                    layout_font_name(&mut activation.context, avm1_movie, "Registered Font"),
+                    "Noto Sans"
+                );
+            });
+    }
+
+    #[test]
+    fn enumerate_embedded_and_registered_fonts() {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let (movie, other_movie) = (as3_movie(), as3_movie());
+                embed_font(context, movie.clone(), "Embedded Font");
+                embed_font(context, other_movie.clone(), "Registered Font");
+
+                let mut activation = Activation::from_nothing(context.reborrow());
+                let stage_domain = activation.avm2().stage_domain();
+                let mut domains = Vec::new();
+                for movie in [&movie, &other_movie] {
+                    let domain = Domain::movie_domain(&mut activation, stage_domain);
+                    activation
+                        .context
+                        .library
+                        .library_for_movie_mut(movie.clone())
+                        .set_avm2_domain(domain);
+                    domains.push(domain);
+                }
+
+                let font_class = activation.avm2().classes().font;
+                activation
+                    .context
+                    .library
+                    .avm2_class_registry_mut()
+                    .set_class_symbol(font_class, other_movie, 1);
+                register_font(&mut activation, font_class.into(), &[font_class.into()]).unwrap();
+
+                // The embedded fonts of the calling movie come before the registered ones,
+                // which are only listed once.
+                for (domain, expected) in domains.into_iter().zip([
+                    &["Embedded Font", "Registered Font"][..],
+                    &["Registered Font"],
+                ]) {
+                    let mut activation =
+                        Activation::from_domain(activation.context.reborrow(), domain);
+                    let fonts =
+                        enumerate_fonts(&mut activation, font_class.into(), &[false.into()])
+                            .unwrap();
+                    assert_eq!(font_names(fonts), expected);
+                }
+            });
+    }
+}
@@ -1,6 +1,9 @@
 use crate::avm2::error::argument_error;
+use crate::avm2::object::TObject;
 use crate::avm2::{Activation, ClassObject, Error, Object, Value};
-use crate::avm2_stub_getter;
+use crate::display_object::DisplayObject;
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
 
 pub fn static_text_allocator<'gc>(
     _class: ClassObject<'gc>,
@@ -16,9 +19,14 @@ pub fn static_text_allocator<'gc>(
 /// Implements `StaticText.text`
 pub fn get_text<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.StaticText", "text");
+    if let Some(DisplayObject::Text(text)) = this.as_display_object() {
+        let snapshot = TextSnapshot::of_text(text, activation.context.library);
+        let text = snapshot.text(0, snapshot.char_count() as i32, true);
+        return Ok(AvmString::new(activation.context.gc_context, text).into());
+    }
+
     Ok("".into())
 }
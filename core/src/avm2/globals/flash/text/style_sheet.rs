@@ -0,0 +1,103 @@
+//! `flash.text.StyleSheet` native function definitions
+
+use crate::avm2::object::{ArrayObject, TObject, TextFormatObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Object, Value};
+use crate::html::{parse_css, StyleSheet, TextStyle, STYLE_PROPERTIES};
+use crate::string::AvmString;
+
+/// Read the CSS properties of a style object.
+fn text_style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<TextStyle, Error<'gc>> {
+    let mut style = TextStyle::default();
+    for &name in STYLE_PROPERTIES {
+        match object.get_public_property(name, activation)? {
+            Value::Undefined | Value::Null => {}
+            value => {
+                let value = value.coerce_to_string(activation)?;
+                style.set_property(name, &value);
+            }
+        }
+    }
+    Ok(style)
+}
+
+/// Read the styles of a `StyleSheet` object.
+pub fn style_sheet_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let mut style_sheet = StyleSheet::new();
+    let names = object.get_public_property("styleNames", activation)?;
+    let Some(names) = names.as_object() else {
+        return Ok(style_sheet);
+    };
+
+    let names: Vec<_> = names
+        .as_array_storage()
+        .map(|storage| storage.iter().flatten().collect())
+        .unwrap_or_default();
+    for name in names {
+        let name = name.coerce_to_string(activation)?;
+        if let Some(style) = object
+            .call_public_property("getStyle", &[name.into()], activation)?
+            .as_object()
+        {
+            let style = text_style_from_object(activation, style)?;
+            style_sheet.set_style(&name, style);
+        }
+    }
+    Ok(style_sheet)
+}
+
+/// Implements `StyleSheet.transform`
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format_object) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+
+    let style = text_style_from_object(activation, format_object)?;
+    Ok(TextFormatObject::from_text_format(activation, style.format)?.into())
+}
+
+/// Implements `StyleSheet.innerParseCSS`, returning the rules of some CSS
+/// as pairs of selectors and declaration objects.
+pub fn inner_parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args.get_string(activation, 0)?;
+    let Some(rules) = parse_css(&css) else {
+        return Ok(Value::Null);
+    };
+
+    let mut result = Vec::with_capacity(rules.len());
+    for (selector, declarations) in rules {
+        let mut declarations_object = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        for (name, value) in declarations {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = AvmString::new(activation.context.gc_context, value);
+            declarations_object.set_public_property(name, value.into(), activation)?;
+        }
+
+        let selector = AvmString::new(activation.context.gc_context, selector);
+        let rule = ArrayObject::from_storage(
+            activation,
+            ArrayStorage::from_args(&[selector.into(), declarations_object.into()]),
+        )?;
+        result.push(rule.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&result))?.into())
+}
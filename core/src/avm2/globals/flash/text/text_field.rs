@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2008;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::text::style_sheet::style_sheet_from_object;
 use crate::avm2::object::{ClassObject, Object, TObject, TextFormatObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -392,6 +393,25 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn apply_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.try_get_object(activation, 0) {
+            Some(object) => Some((object.into(), style_sheet_from_object(activation, object)?)),
+            None => None,
+        };
+        this.set_style_sheet(&mut activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_length<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
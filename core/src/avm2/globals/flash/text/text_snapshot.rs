@@ -0,0 +1,213 @@
+//! `flash.text.TextSnapshot` native function definitions
+
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayStorage, Error, Multiname, Namespace, Object, Value};
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
+use swf::{Color, Point, Twips};
+
+fn ruffle_name<'gc>(activation: &mut Activation<'_, 'gc>, name: &'static str) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        name,
+    )
+}
+
+/// Take a snapshot of the static texts of the container of a `TextSnapshot`.
+fn snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<TextSnapshot<'gc>>, Error<'gc>> {
+    let container = ruffle_name(activation, "_container");
+    let container = this.get_property(&container, activation)?;
+    let Some(container) = container.as_object().and_then(|c| c.as_display_object()) else {
+        return Ok(None);
+    };
+    Ok(Some(TextSnapshot::new(
+        container,
+        activation.context.library,
+    )))
+}
+
+/// Implements `TextSnapshot.charCount`
+pub fn get_char_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok(0.into());
+    };
+    Ok(snapshot.char_count().into())
+}
+
+/// Implements `TextSnapshot.findText`
+pub fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok((-1).into());
+    };
+
+    let begin_index = args.get_i32(activation, 0)?;
+    let text = args.get_string(activation, 1)?;
+    let case_sensitive = args.get_bool(2);
+    Ok(snapshot
+        .find_text(begin_index, &text, case_sensitive)
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+/// Implements `TextSnapshot.getSelected`
+pub fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok(false.into());
+    };
+
+    let begin_index = args.get_i32(activation, 0)?;
+    let end_index = args.get_i32(activation, 1)?;
+    Ok(snapshot.is_selected(begin_index, end_index).into())
+}
+
+/// Implements `TextSnapshot.getSelectedText`
+pub fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok("".into());
+    };
+
+    let text = snapshot.selected_text(args.get_bool(0));
+    Ok(AvmString::new(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getText`
+pub fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok("".into());
+    };
+
+    let begin_index = args.get_i32(activation, 0)?;
+    let end_index = args.get_i32(activation, 1)?;
+    let text = snapshot.text(begin_index, end_index, args.get_bool(2));
+    Ok(AvmString::new(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getTextRunInfo`
+pub fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let runs = match snapshot(activation, this)? {
+        Some(snapshot) => {
+            let begin_index = args.get_i32(activation, 0)?;
+            let end_index = args.get_i32(activation, 1)?;
+            snapshot.text_run_info(begin_index, end_index)
+        }
+        None => Vec::new(),
+    };
+
+    let mut result = Vec::with_capacity(runs.len());
+    for info in runs {
+        let mut object = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        let font = AvmString::new_utf8(activation.context.gc_context, info.font);
+        let [corner0, corner1, corner2, corner3] = info.corners;
+        let properties: [(&'static str, Value<'gc>); 19] = [
+            ("indexInRun", info.index_in_run.into()),
+            ("selected", info.selected.into()),
+            ("font", font.into()),
+            ("color", info.color.to_rgb().into()),
+            ("height", info.height.into()),
+            ("matrix_a", info.matrix.a.into()),
+            ("matrix_b", info.matrix.b.into()),
+            ("matrix_c", info.matrix.c.into()),
+            ("matrix_d", info.matrix.d.into()),
+            ("matrix_tx", info.matrix.tx.to_pixels().into()),
+            ("matrix_ty", info.matrix.ty.to_pixels().into()),
+            ("corner0x", corner0.x.to_pixels().into()),
+            ("corner0y", corner0.y.to_pixels().into()),
+            ("corner1x", corner1.x.to_pixels().into()),
+            ("corner1y", corner1.y.to_pixels().into()),
+            ("corner2x", corner2.x.to_pixels().into()),
+            ("corner2y", corner2.y.to_pixels().into()),
+            ("corner3x", corner3.x.to_pixels().into()),
+            ("corner3y", corner3.y.to_pixels().into()),
+        ];
+        for (name, value) in properties {
+            object.set_public_property(name, value, activation)?;
+        }
+        result.push(object.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&result))?.into())
+}
+
+/// Implements `TextSnapshot.hitTestTextNearPos`
+pub fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok((-1).into());
+    };
+
+    let x = args.get_f64(activation, 0)?;
+    let y = args.get_f64(activation, 1)?;
+    let max_distance = args.get_f64(activation, 2)?;
+    let point = Point::new(Twips::from_pixels(x), Twips::from_pixels(y));
+    Ok(snapshot
+        .hit_test_text_near_pos(point, Twips::from_pixels(max_distance))
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+/// Implements `TextSnapshot.setSelectColor`
+pub fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(snapshot) = snapshot(activation, this)? {
+        let color = args.get_u32(activation, 0)?;
+        snapshot.set_select_color(activation.context.gc_context, Color::from_rgb(color, 255));
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.setSelected`
+pub fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(snapshot) = snapshot(activation, this)? {
+        let begin_index = args.get_i32(activation, 0)?;
+        let end_index = args.get_i32(activation, 1)?;
+        snapshot.set_selected(
+            activation.context.gc_context,
+            begin_index,
+            end_index,
+            args.get_bool(2),
+        );
+    }
+    Ok(Value::Undefined)
+}
@@ -1,6 +1,9 @@
 package flash.ui {
     public final class Multitouch {
-        // FIXME - implement a setter and gett
-        public static var inputMode:String = MultitouchInputMode.GESTURE;
+        public static native function get inputMode():String;
+        public static native function set inputMode(value:String):void;
+
+        public static native function get maxTouchPoints():int;
+        public static native function get supportsTouchEvents():Boolean;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,57 @@
+//! `flash.ui.Multitouch` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+/// Implements `inputMode`'s getter
+pub fn get_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input_mode = AvmString::new_utf8(
+        activation.context.gc_context,
+        activation.context.stage.multitouch_input_mode().to_string(),
+    );
+    Ok(input_mode.into())
+}
+
+/// Implements `inputMode`'s setter
+pub fn set_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Ok(input_mode) = args.get_string(activation, 0)?.parse() {
+        activation
+            .context
+            .stage
+            .set_multitouch_input_mode(activation.context.gc_context, input_mode);
+    } else {
+        return Err(make_error_2008(activation, "inputMode"));
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `maxTouchPoints`'s getter
+pub fn get_max_touch_points<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.max_touch_points().into())
+}
+
+/// Implements `supportsTouchEvents`'s getter
+pub fn get_supports_touch_events<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((activation.context.ui.max_touch_points() > 0).into())
+}
@@ -337,6 +337,7 @@ include "flash/text/TextFormatDisplay.as"
 include "flash/text/TextInteractionMode.as"
 include "flash/text/TextLineMetrics.as"
 include "flash/text/TextRenderer.as"
+include "flash/text/TextSnapshot.as"
 
 include "flash/text/engine/BreakOpportunity.as"
 include "flash/text/engine/CFFHinting.as"
@@ -348,6 +349,7 @@ include "flash/text/engine/FontDescription.as"
 include "flash/text/engine/FontLookup.as"
 include "flash/text/engine/FontPosture.as"
 include "flash/text/engine/FontWeight.as"
+include "flash/text/engine/GroupElement.as"
 include "flash/text/engine/JustificationStyle.as"
 include "flash/text/engine/Kerning.as"
 include "flash/text/engine/LigatureLevel.as"
@@ -1,9 +1,10 @@
 //! Object representation for events
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::events::Event;
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::object::{ByteArrayObject, ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
@@ -14,6 +15,7 @@ use crate::string::AvmString;
 use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
+use swf::{Point, Twips};
 
 /// A class instance allocator that allocates Event objects.
 pub fn event_allocator<'gc>(
@@ -145,6 +147,138 @@ impl<'gc> EventObject<'gc> {
             .unwrap() // we don't expect to break here
     }
 
+    /// Create a `TouchEvent` for a touch point at the given position, in stage coordinates.
+    #[allow(clippy::too_many_arguments)]
+    pub fn touch_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        target: DisplayObject<'gc>,
+        touch_point_id: i32,
+        is_primary_touch_point: bool,
+        position: Point<Twips>,
+        related_object: Option<InteractiveObject<'gc>>,
+        bubbles: bool,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let local = target.mouse_to_local(position);
+
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let touch_event_cls = activation.avm2().classes().touchevent;
+        touch_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    bubbles.into(),
+                    // cancellable
+                    false.into(),
+                    // touchPointID
+                    touch_point_id.into(),
+                    // isPrimaryTouchPoint
+                    is_primary_touch_point.into(),
+                    // localX
+                    local.x.to_pixels().into(),
+                    // localY
+                    local.y.to_pixels().into(),
+                    // sizeX
+                    f64::NAN.into(),
+                    // sizeY
+                    f64::NAN.into(),
+                    // pressure
+                    f64::NAN.into(),
+                    // relatedObject
+                    related_object
+                        .map(|o| o.as_displayobject().object2())
+                        .unwrap_or(Value::Null),
+                    // ctrlKey
+                    activation
+                        .context
+                        .input
+                        .is_key_down(KeyCode::Control)
+                        .into(),
+                    // altKey
+                    activation.context.input.is_key_down(KeyCode::Alt).into(),
+                    // shiftKey
+                    activation.context.input.is_key_down(KeyCode::Shift).into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    pub fn focus_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        cancelable: bool,
+        related_object: Option<InteractiveObject<'gc>>,
+        key_code: u32,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let shift_key = activation.context.input.is_key_down(KeyCode::Shift);
+
+        let focus_event_cls = activation.avm2().classes().focusevent;
+        focus_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    true.into(),
+                    // cancelable
+                    cancelable.into(),
+                    // relatedObject
+                    related_object
+                        .map(|o| o.as_displayobject().object2())
+                        .unwrap_or(Value::Null),
+                    // shiftKey
+                    shift_key.into(),
+                    // keyCode
+                    key_code.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
+    /// Create a `SampleDataEvent` with an empty `data` `ByteArray`, for a handler to
+    /// write the next samples of a dynamic sound to.
+    pub fn sample_data_event<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        position: u32,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let event_type: AvmString<'gc> = event_type.into();
+
+        let data = ByteArrayObject::from_storage(activation, ByteArrayStorage::new()).unwrap(); // we don't expect to break here
+
+        let sample_data_event_cls = activation.avm2().classes().sampledataevent;
+        sample_data_event_cls
+            .construct(
+                activation,
+                &[
+                    event_type.into(),
+                    // bubbles
+                    false.into(),
+                    // cancelable
+                    false.into(),
+                    // position
+                    position.into(),
+                    // data
+                    data.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
+
     pub fn text_event<S>(
         activation: &mut Activation<'_, 'gc>,
         event_type: S,
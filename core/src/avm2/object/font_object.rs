@@ -0,0 +1,121 @@
+//! Object representation for fonts
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::character::Character;
+use crate::font::Font;
+use core::fmt;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Ref, RefMut};
+
+/// A class instance allocator that allocates Font objects.
+///
+/// Instances of a font class associated with an embedded font by a `SymbolClass`
+/// tag represent that font.
+pub fn font_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let font = activation
+        .context
+        .library
+        .avm2_class_registry()
+        .class_symbol(class)
+        .and_then(|(movie, id)| {
+            match activation
+                .context
+                .library
+                .library_for_movie_mut(movie)
+                .character_by_id(id)
+            {
+                Some(Character::Font(font)) => Some(*font),
+                _ => None,
+            }
+        });
+
+    Ok(FontObject(Gc::new(
+        activation.gc(),
+        FontObjectData {
+            base: RefLock::new(ScriptObjectData::new(class)),
+            font,
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct FontObject<'gc>(pub Gc<'gc, FontObjectData<'gc>>);
+
+#[derive(Clone, Collect, Copy, Debug)]
+#[collect(no_drop)]
+pub struct FontObjectWeak<'gc>(pub GcWeak<'gc, FontObjectData<'gc>>);
+
+impl fmt::Debug for FontObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontObject")
+            .field("ptr", &Gc::as_ptr(self.0))
+            .finish()
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct FontObjectData<'gc> {
+    /// Base script object
+    base: RefLock<ScriptObjectData<'gc>>,
+
+    /// The font this object represents, if any.
+    font: Option<Font<'gc>>,
+}
+
+impl<'gc> FontObject<'gc> {
+    /// Create a `Font` instance representing the given font, as returned by
+    /// `Font.enumerateFonts`.
+    pub fn for_font(
+        activation: &mut Activation<'_, 'gc>,
+        font: Font<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().font;
+
+        let mut this: Object<'gc> = Self(Gc::new(
+            activation.gc(),
+            FontObjectData {
+                base: RefLock::new(ScriptObjectData::new(class)),
+                font: Some(font),
+            },
+        ))
+        .into();
+        this.install_instance_slots(activation.gc());
+
+        Ok(this)
+    }
+}
+
+impl<'gc> TObject<'gc> for FontObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), FontObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    /// Unwrap this object as a font.
+    fn as_font(&self) -> Option<Font<'gc>> {
+        self.0.font
+    }
+}
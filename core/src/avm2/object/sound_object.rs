@@ -5,7 +5,7 @@ use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::backend::audio::SoundHandle;
+use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
 use core::fmt;
@@ -29,6 +29,7 @@ pub fn sound_allocator<'gc>(
             sound_data: SoundData::NotLoaded {
                 queued_plays: Vec::new(),
             },
+            extract_position: 0,
         },
     ))
     .into())
@@ -58,6 +59,10 @@ pub struct SoundObjectData<'gc> {
 
     /// The sound this object holds.
     sound_data: SoundData<'gc>,
+
+    /// The position that `Sound.extract` continues from when no start position is given,
+    /// in 44.1KHz sample frames.
+    extract_position: u32,
 }
 
 #[derive(Collect)]
@@ -92,12 +97,35 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    pub fn extract_position(self) -> u32 {
+        self.0.read().extract_position
+    }
+
+    pub fn set_extract_position(self, mc: &Mutation<'gc>, extract_position: u32) {
+        self.0.write(mc).extract_position = extract_position;
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
+    ///
+    /// A sound without any loaded audio that has `sampleData` listeners starts playing
+    /// immediately, with samples generated by the listeners.
     pub fn play(
         self,
         queued: QueuedPlay<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<bool, Error<'gc>> {
+        if self.sound_handle().is_none() {
+            let has_sample_data_listener = Object::from(self)
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean();
+            if has_sample_data_listener {
+                if let Some(instance) = activation.context.start_dynamic_sound(self.into()) {
+                    attach_sound_channel(queued, instance, activation);
+                }
+                return Ok(true);
+            }
+        }
+
         let mut this = self.0.write(activation.context.gc_context);
         match &mut this.sound_data {
             SoundData::NotLoaded { queued_plays } => {
@@ -152,23 +180,32 @@ fn play_queued<'gc>(
         .context
         .start_sound(sound, &queued.sound_info, None, None)
     {
-        if let Some(sound_transform) = queued.sound_transform {
-            activation
-                .context
-                .set_local_sound_transform(instance, sound_transform);
-        }
-
-        queued
-            .sound_channel
-            .as_sound_channel()
-            .unwrap()
-            .set_sound_instance(activation, instance);
+        attach_sound_channel(queued, instance, activation);
+    }
+    Ok(true)
+}
 
+/// Attach the `SoundChannel` of a queued play to the sound instance that was started for it.
+fn attach_sound_channel<'gc>(
+    queued: QueuedPlay<'gc>,
+    instance: SoundInstanceHandle,
+    activation: &mut Activation<'_, 'gc>,
+) {
+    if let Some(sound_transform) = queued.sound_transform {
         activation
             .context
-            .attach_avm2_sound_channel(instance, queued.sound_channel);
+            .set_local_sound_transform(instance, sound_transform);
     }
-    Ok(true)
+
+    queued
+        .sound_channel
+        .as_sound_channel()
+        .unwrap()
+        .set_sound_instance(activation, instance);
+
+    activation
+        .context
+        .attach_avm2_sound_channel(instance, queued.sound_channel);
 }
 
 impl<'gc> TObject<'gc> for SoundObject<'gc> {
@@ -0,0 +1,15 @@
+use crate::avm2::activation::Activation;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::object::FunctionObject;
+use crate::avm2::scope::ScopeChain;
+
+/// Construct a function from a native method, in the scope of the stage's domain.
+pub fn native_function<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: NativeMethodImpl,
+    name: &'static str,
+) -> FunctionObject<'gc> {
+    let method = Method::from_builtin(method, name, activation.context.gc_context);
+    let scope = ScopeChain::new(activation.context.avm2.stage_domain());
+    FunctionObject::from_method(activation, method, scope, None, None)
+}
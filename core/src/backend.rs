@@ -1,5 +1,8 @@
 pub mod audio;
+pub mod file_dialog;
+pub mod local_connection;
 pub mod log;
 pub mod navigator;
+pub mod print;
 pub mod storage;
 pub mod ui;
@@ -1,6 +1,9 @@
 use crate::{
     avm1::SoundObject,
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{
+        Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+        SoundChannelObject, TObject as _,
+    },
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
@@ -131,6 +134,35 @@ pub trait AudioBackend: Downcast {
         stream_info: &SoundStreamInfo,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a dynamic sound, whose 44.1KHz stereo samples are generated by
+    /// ActionScript and supplied with `append_dynamic_sound_samples` while it's playing.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError>;
+
+    /// Appends 44.1KHz stereo samples to a playing dynamic sound.
+    /// If `is_complete` is set, the sound ends once all of its samples have been played.
+    fn append_dynamic_sound_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_complete: bool,
+    );
+
+    /// Get the number of sample frames that were appended to a dynamic sound, but haven't
+    /// been played yet.
+    /// Returns `None` if the sound is not/no longer playing.
+    fn get_dynamic_sound_queued_samples(&self, instance: SoundInstanceHandle) -> Option<usize>;
+
+    /// Decodes part of a sound into 44.1KHz stereo sample frames, as used by `Sound.extract`.
+    ///
+    /// `start_sample_frame` is in 44.1KHz sample frames. Fewer than `num_sample_frames` frames
+    /// are returned if the end of the sound is reached.
+    fn extract_sound(
+        &self,
+        sound: SoundHandle,
+        start_sample_frame: u32,
+        num_sample_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -290,6 +322,31 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::from_raw_parts(0, 0))
     }
 
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+    }
+
+    fn append_dynamic_sound_samples(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _samples: &[[f32; 2]],
+        _is_complete: bool,
+    ) {
+    }
+
+    fn get_dynamic_sound_queued_samples(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
+    fn extract_sound(
+        &self,
+        _sound: SoundHandle,
+        _start_sample_frame: u32,
+        _num_sample_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        Ok(Vec::new())
+    }
+
     fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
 
     fn stop_all_sounds(&mut self) {}
@@ -376,6 +433,21 @@ impl<'gc> AudioManager<'gc> {
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
     pub const STREAM_DEFAULT_SYNC_THRESHOLD: f64 = 0.2;
 
+    /// The minimum number of sample frames that a `sampleData` handler must supply to keep
+    /// a dynamic sound playing. If it supplies fewer, the sound ends after playing them.
+    pub const DYNAMIC_SOUND_MIN_SAMPLES: usize = 2048;
+
+    /// The maximum number of sample frames taken from a single `sampleData` event, which is
+    /// also the number of sample frames that dynamic sounds keep buffered.
+    pub const DYNAMIC_SOUND_MAX_SAMPLES: usize = 8192;
+
+    /// The interval in milliseconds at which the buffers of dynamic sounds are refilled,
+    /// independently of the frame rate.
+    ///
+    /// `DYNAMIC_SOUND_MAX_SAMPLES` lasts about 186ms, so this keeps the buffers from running
+    /// out between two refills.
+    pub const DYNAMIC_SOUND_REFILL_INTERVAL: f64 = 50.0;
+
     pub fn new() -> Self {
         Self {
             sounds: Vec::with_capacity(Self::MAX_SOUNDS),
@@ -437,6 +509,8 @@ impl<'gc> AudioManager<'gc> {
             Avm2::dispatch_event(context, event, target.into());
         }
 
+        Self::request_dynamic_sound_samples(context);
+
         // Update sound transforms, if dirty.
         context.audio_manager.update_sound_transforms(context.audio);
     }
@@ -459,6 +533,36 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Starts a dynamic sound, whose samples are requested from the `sampleData`
+    /// handlers of the given AVM2 `Sound` object as the sound plays.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        sound_object: Avm2Object<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound().ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                sample_data_source: Some(sound_object),
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -468,6 +572,97 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Returns whether any dynamic sounds are playing, which need to be refilled with
+    /// `request_dynamic_sound_samples` at least every `DYNAMIC_SOUND_REFILL_INTERVAL`.
+    pub fn has_dynamic_sounds(&self) -> bool {
+        self.sounds
+            .iter()
+            .any(|sound| sound.sample_data_source.is_some())
+    }
+
+    /// Dispatch `sampleData` events to the `Sound` objects of dynamic sounds that are running
+    /// low on samples, and pass the samples written by their handlers to the audio backend.
+    ///
+    /// This is called every frame, and by the player between frames while dynamic sounds are
+    /// playing.
+    pub fn request_dynamic_sound_samples(context: &mut UpdateContext<'_, 'gc>) {
+        let dynamic_sounds: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| Some((sound.instance, sound.sample_data_source?)))
+            .collect();
+
+        for (instance, sound_object) in dynamic_sounds {
+            while let Some(queued_samples) =
+                context.audio.get_dynamic_sound_queued_samples(instance)
+            {
+                if queued_samples >= Self::DYNAMIC_SOUND_MAX_SAMPLES {
+                    break;
+                }
+                // The handlers may have stopped the sound.
+                let Some(sound) = context
+                    .audio_manager
+                    .sounds
+                    .iter()
+                    .find(|sound| sound.instance == instance)
+                else {
+                    break;
+                };
+                let position = sound.sample_data_position;
+
+                let samples = Self::dispatch_sample_data(context, sound_object, position);
+                let is_complete = samples.len() < Self::DYNAMIC_SOUND_MIN_SAMPLES;
+                if let Some(sound) = context
+                    .audio_manager
+                    .sounds
+                    .iter_mut()
+                    .find(|sound| sound.instance == instance)
+                {
+                    sound.sample_data_position = position + samples.len() as u32;
+                }
+                context
+                    .audio
+                    .append_dynamic_sound_samples(instance, &samples, is_complete);
+                if is_complete {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Dispatch a `sampleData` event to a `Sound` object, and return the 32-bit float
+    /// stereo samples that its handlers wrote to the event's `data`.
+    fn dispatch_sample_data(
+        context: &mut UpdateContext<'_, 'gc>,
+        sound_object: Avm2Object<'gc>,
+        position: u32,
+    ) -> Vec<[f32; 2]> {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let event = Avm2EventObject::sample_data_event(&mut activation, "sampleData", position);
+        Avm2::dispatch_event(&mut activation.context, event, sound_object);
+
+        let data = match event.get_public_property("data", &mut activation) {
+            Ok(data) => data.as_object(),
+            Err(e) => {
+                tracing::error!("Error reading SampleDataEvent.data: {e:?}");
+                None
+            }
+        };
+        let Some(data) = data.as_ref().and_then(|data| data.as_bytearray()) else {
+            return Vec::new();
+        };
+
+        let num_samples = (data.len() / 8).min(Self::DYNAMIC_SOUND_MAX_SAMPLES);
+        (0..num_samples)
+            .map(|i| {
+                let left = data.read_float_at(i * 8).unwrap_or_default();
+                let right = data.read_float_at(i * 8 + 4).unwrap_or_default();
+                [left, right]
+            })
+            .collect()
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -556,6 +751,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -582,6 +779,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -765,6 +964,13 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The AVM2 `Sound` object whose `sampleData` handlers generate the samples of this
+    /// sound, if this is a dynamic sound.
+    sample_data_source: Option<Avm2Object<'gc>>,
+
+    /// The number of sample frames supplied by the `sampleData` handlers so far.
+    sample_data_position: u32,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
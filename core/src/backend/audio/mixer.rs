@@ -4,6 +4,7 @@ use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
 use crate::tag_utils::SwfSlice;
 use generational_arena::Arena;
+use std::collections::VecDeque;
 use std::io::Cursor;
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
@@ -87,6 +88,9 @@ trait Stream: dasp::signal::Signal<Frame = [i16; 2]> + Send + Sync {
     fn source_sample_rate(&self) -> u16;
 }
 
+/// The sample rate of dynamic sounds and of the audio returned by `AudioMixer::extract_sound`.
+const DYNAMIC_SOUND_SAMPLE_RATE: u16 = 44100;
+
 /// A stream that wraps a `Decoder`.
 struct DecoderStream<D> {
     decoder: D,
@@ -159,6 +163,20 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// The stream used by the last call to `AudioMixer::extract_sound`.
+    ///
+    /// Consecutive extractions continue from this stream, instead of decoding the sound
+    /// from the start again.
+    extractor: Mutex<Option<SoundExtractor>>,
+}
+
+/// A stream of a sound at 44.1KHz, as extracted with `AudioMixer::extract_sound`.
+struct SoundExtractor {
+    stream: Box<dyn Stream>,
+
+    /// The position of the next sample frame of `stream`, in 44.1KHz sample frames.
+    position: u32,
 }
 
 /// An actively playing instance of a sound.
@@ -195,6 +213,9 @@ struct SoundInstance {
     /// (respectively) of this sound over the buffer currently being
     /// mixed. Used to compute `peak`, and is reset after every time.
     range: ([f32; 2], [f32; 2]),
+
+    /// The buffer that samples are appended to, for dynamic sounds.
+    dynamic_buffer: Option<Arc<Mutex<DynamicSoundBuffer>>>,
 }
 
 impl SoundInstance {
@@ -208,6 +229,7 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([std::f32::INFINITY; 2], [std::f32::NEG_INFINITY; 2]),
+            dynamic_buffer: None,
         }
     }
 
@@ -223,6 +245,15 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([std::f32::INFINITY; 2], [std::f32::NEG_INFINITY; 2]),
+            dynamic_buffer: None,
+        }
+    }
+
+    /// Creates a new `SoundInstance` from a `Stream`, for dynamic sounds.
+    fn new_dynamic(stream: Box<dyn Stream>, buffer: Arc<Mutex<DynamicSoundBuffer>>) -> Self {
+        SoundInstance {
+            dynamic_buffer: Some(buffer),
+            ..Self::new_stream(stream)
         }
     }
 
@@ -332,7 +363,12 @@ impl AudioMixer {
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::resample(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn resample(mut stream: impl Stream, output_sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
@@ -342,7 +378,7 @@ impl AudioMixer {
             stream,
             interpolator,
             sample_rate,
-            self.output_sample_rate.into(),
+            output_sample_rate.into(),
         ))
     }
 
@@ -521,6 +557,7 @@ impl AudioMixer {
             data: Arc::from(data),
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            extractor: Mutex::new(None),
         };
         Ok(self.sounds.insert(sound))
     }
@@ -541,6 +578,7 @@ impl AudioMixer {
             data,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            extractor: Mutex::new(None),
         };
         Ok(self.sounds.insert(sound))
     }
@@ -623,6 +661,144 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a dynamic sound, which plays 44.1KHz stereo samples supplied by calling
+    /// `AudioMixer::append_dynamic_sound_samples` while it's playing.
+    pub fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        let buffer = Arc::new(Mutex::new(DynamicSoundBuffer::default()));
+        let stream = DynamicSoundStream::new(Arc::clone(&buffer));
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_dynamic(stream, buffer));
+        Ok(handle)
+    }
+
+    /// Appends 44.1KHz stereo samples to a playing dynamic sound.
+    ///
+    /// If `is_complete` is set, the sound ends once all of its samples have been played.
+    pub fn append_dynamic_sound_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_complete: bool,
+    ) {
+        use dasp::Sample;
+
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        if let Some(buffer) = sound_instances
+            .get(instance)
+            .and_then(|instance| instance.dynamic_buffer.as_ref())
+        {
+            let mut buffer = buffer.lock().expect("Cannot be called reentrant");
+            buffer.samples.extend(
+                samples
+                    .iter()
+                    .map(|&[left, right]| [left.to_sample(), right.to_sample()]),
+            );
+            buffer.is_complete |= is_complete;
+        }
+    }
+
+    /// Returns the number of sample frames that were appended to a playing dynamic sound,
+    /// but haven't been played yet.
+    ///
+    /// This doesn't include the block of samples that the sound is currently playing.
+    /// Returns `None` if the sound is no longer playing.
+    pub fn get_dynamic_sound_queued_samples(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        let sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let buffer = sound_instances.get(instance)?.dynamic_buffer.as_ref()?;
+        let num_samples = buffer
+            .lock()
+            .expect("Cannot be called reentrant")
+            .samples
+            .len();
+        Some(num_samples)
+    }
+
+    /// Decodes part of a registered sound into 44.1KHz stereo sample frames.
+    ///
+    /// `start_sample_frame` is in 44.1KHz sample frames, regardless of the sample rate of the
+    /// sound. Fewer than `num_sample_frames` frames are returned if the end of the sound is
+    /// reached, and none if the sound is not registered.
+    pub fn extract_sound(
+        &self,
+        sound: SoundHandle,
+        start_sample_frame: u32,
+        num_sample_frames: usize,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        use dasp::{signal::Signal, Sample};
+
+        let Some(sound) = self.sounds.get(sound) else {
+            return Ok(Vec::new());
+        };
+
+        // Clamp the number of sample frames to the end of the sound.
+        let total_sample_frames = (f64::from(sound.num_sample_frames)
+            * f64::from(DYNAMIC_SOUND_SAMPLE_RATE)
+            / f64::from(sound.format.sample_rate)) as u32;
+        let num_sample_frames =
+            num_sample_frames.min(total_sample_frames.saturating_sub(start_sample_frame) as usize);
+        if num_sample_frames == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Continue from the last extraction if possible, as seeking means decoding the sound
+        // from the start.
+        let mut cached_extractor = sound.extractor.lock().expect("Cannot be called reentrant");
+        let mut extractor = match cached_extractor.take() {
+            Some(extractor) if extractor.position == start_sample_frame => extractor,
+            _ => SoundExtractor {
+                stream: Self::make_extract_stream(sound, start_sample_frame)?,
+                position: start_sample_frame,
+            },
+        };
+
+        let mut samples = Vec::with_capacity(num_sample_frames);
+        while samples.len() < num_sample_frames && !extractor.stream.is_exhausted() {
+            let [left, right] = extractor.stream.next();
+            samples.push([left.to_sample(), right.to_sample()]);
+        }
+        extractor.position += samples.len() as u32;
+        *cached_extractor = Some(extractor);
+        Ok(samples)
+    }
+
+    /// Creates a `Stream` that decodes a sound at 44.1KHz, starting at the given position in
+    /// 44.1KHz sample frames.
+    fn make_extract_stream(
+        sound: &Sound,
+        start_sample_frame: u32,
+    ) -> Result<Box<dyn Stream>, DecodeError> {
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let settings = swf::SoundInfo {
+            event: swf::SoundEvent::Start,
+            in_sample: Some(start_sample_frame),
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        };
+        let stream = EventSoundStream::new_with_settings(
+            decoder,
+            &settings,
+            sound.num_sample_frames,
+            sound.skip_sample_frames,
+        );
+        Ok(Box::new(Self::resample(
+            stream,
+            DYNAMIC_SOUND_SAMPLE_RATE.into(),
+        )))
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -882,6 +1058,88 @@ impl Stream for EventSoundStream {
     }
 }
 
+/// The samples of a dynamic sound that were appended, but haven't been played yet.
+///
+/// This is shared between the `DynamicSoundStream` playing the sound and its `SoundInstance`,
+/// so that more samples can be appended while the sound is playing.
+#[derive(Default)]
+struct DynamicSoundBuffer {
+    samples: VecDeque<[i16; 2]>,
+
+    /// Whether the last samples of the sound have been appended.
+    is_complete: bool,
+}
+
+/// A stream for a dynamic sound, whose 44.1KHz stereo samples are generated by ActionScript.
+///
+/// In AVM2, the samples come from the `sampleData` event handlers of a `Sound` without any
+/// loaded audio, which are called as the buffer runs low. The stream plays silence if it runs
+/// out of samples, and only ends once the sound is complete.
+struct DynamicSoundStream {
+    buffer: Arc<Mutex<DynamicSoundBuffer>>,
+
+    /// The samples taken from `buffer` that haven't been played yet.
+    ///
+    /// Samples are taken in blocks of `DynamicSoundStream::BLOCK_SIZE`, so that `buffer` isn't
+    /// locked for every sample.
+    block: VecDeque<[i16; 2]>,
+
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl DynamicSoundStream {
+    /// The maximum number of sample frames taken from the shared buffer at once.
+    const BLOCK_SIZE: usize = 256;
+
+    fn new(buffer: Arc<Mutex<DynamicSoundBuffer>>) -> Self {
+        Self {
+            buffer,
+            block: VecDeque::with_capacity(Self::BLOCK_SIZE),
+            position: 0,
+            is_exhausted: false,
+        }
+    }
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Self::Frame {
+        if self.block.is_empty() {
+            let mut buffer = self.buffer.lock().expect("Cannot be called reentrant");
+            let num_samples = buffer.samples.len().min(Self::BLOCK_SIZE);
+            self.block.extend(buffer.samples.drain(..num_samples));
+            self.is_exhausted = self.block.is_empty() && buffer.is_complete;
+        }
+
+        if let Some(frame) = self.block.pop_front() {
+            self.position += 1;
+            frame
+        } else {
+            [0, 0]
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        DYNAMIC_SOUND_SAMPLE_RATE
+    }
+}
+
 /// A stream that converts a source stream to a different sample rate.
 struct ConverterStream<S, I>(dasp::signal::interpolate::Converter<S, I>)
 where
@@ -1116,6 +1374,38 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.start_substream(stream_data, stream_info)
         }
 
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_sound_samples(
+            &mut self,
+            instance: SoundInstanceHandle,
+            samples: &[[f32; 2]],
+            is_complete: bool,
+        ) {
+            self.$mixer
+                .append_dynamic_sound_samples(instance, samples, is_complete)
+        }
+
+        #[inline]
+        fn get_dynamic_sound_queued_samples(&self, instance: SoundInstanceHandle) -> Option<usize> {
+            self.$mixer.get_dynamic_sound_queued_samples(instance)
+        }
+
+        #[inline]
+        fn extract_sound(
+            &self,
+            sound: SoundHandle,
+            start_sample_frame: u32,
+            num_sample_frames: usize,
+        ) -> Result<Vec<[f32; 2]>, DecodeError> {
+            self.$mixer
+                .extract_sound(sound, start_sample_frame, num_sample_frames)
+        }
+
         #[inline]
         fn stop_sound(&mut self, sound: SoundInstanceHandle) {
             self.$mixer.stop_sound(sound)
@@ -1175,3 +1465,91 @@ macro_rules! impl_audio_mixer_backend {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Registers a 44.1KHz 16-bit stereo PCM sound with the given number of sample frames.
+    fn register_pcm_sound(mixer: &mut AudioMixer, num_samples: u32) -> SoundHandle {
+        let data: Vec<u8> = (0..num_samples as i16)
+            .flat_map(|i| [i * 100, -i * 100])
+            .flat_map(i16::to_le_bytes)
+            .collect();
+        mixer
+            .register_sound(&swf::Sound {
+                id: 1,
+                format: swf::SoundFormat {
+                    compression: AudioCompression::Uncompressed,
+                    sample_rate: 44100,
+                    is_stereo: true,
+                    is_16_bit: true,
+                },
+                num_samples,
+                data: &data,
+            })
+            .expect("PCM sound should register")
+    }
+
+    #[test]
+    fn dynamic_sound_plays_until_complete() {
+        let mut mixer = AudioMixer::new(2, 44100);
+        let instance = mixer.start_dynamic_sound().unwrap();
+        let mut output = [0i16; 2 * 1024];
+
+        mixer.append_dynamic_sound_samples(instance, &[[0.5, -0.5]; 512], false);
+        mixer.mix(&mut output);
+        assert!(output.iter().any(|&sample| sample != 0));
+        assert_eq!(mixer.get_dynamic_sound_queued_samples(instance), Some(0));
+
+        // Without more samples, the sound keeps playing silence.
+        mixer.mix(&mut output);
+        assert!(output.iter().all(|&sample| sample == 0));
+        assert_eq!(mixer.get_dynamic_sound_queued_samples(instance), Some(0));
+
+        // Once the sound is complete, it ends after its last samples.
+        mixer.append_dynamic_sound_samples(instance, &[[0.5, -0.5]; 512], true);
+        mixer.mix(&mut output);
+        assert_eq!(mixer.get_dynamic_sound_queued_samples(instance), None);
+    }
+
+    #[test]
+    fn dynamic_sound_queues_appended_samples() {
+        let mut mixer = AudioMixer::new(2, 44100);
+        let instance = mixer.start_dynamic_sound().unwrap();
+        mixer.append_dynamic_sound_samples(instance, &[[0.0, 0.0]; 4096], false);
+        assert_eq!(mixer.get_dynamic_sound_queued_samples(instance), Some(4096));
+
+        let mut output = [0i16; 2 * 1024];
+        mixer.mix(&mut output);
+        let queued_samples = mixer.get_dynamic_sound_queued_samples(instance).unwrap();
+        assert!(queued_samples < 4096 - 1024 + DynamicSoundStream::BLOCK_SIZE);
+        assert!(queued_samples >= 4096 - 1024 - DynamicSoundStream::BLOCK_SIZE);
+    }
+
+    #[test]
+    fn extract_sound_clamps_to_end() {
+        let mut mixer = AudioMixer::new(2, 44100);
+        let sound = register_pcm_sound(&mut mixer, 100);
+
+        assert_eq!(mixer.extract_sound(sound, 0, 1000).unwrap().len(), 100);
+        assert_eq!(mixer.extract_sound(sound, 90, 50).unwrap().len(), 10);
+        assert!(mixer.extract_sound(sound, 100, 10).unwrap().is_empty());
+        assert!(mixer.extract_sound(sound, 1000, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn extract_sound_continues_from_last_position() {
+        let mut mixer = AudioMixer::new(2, 44100);
+        let sound = register_pcm_sound(&mut mixer, 100);
+        let all_samples = mixer.extract_sound(sound, 0, 100).unwrap();
+
+        let mut samples = mixer.extract_sound(sound, 0, 40).unwrap();
+        samples.extend(mixer.extract_sound(sound, 40, 60).unwrap());
+        assert_eq!(samples, all_samples);
+
+        // Seeking backwards starts a new stream.
+        let samples = mixer.extract_sound(sound, 20, 30).unwrap();
+        assert_eq!(samples, all_samples[20..50]);
+    }
+}
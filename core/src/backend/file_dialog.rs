@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// A future resolving to the choice made in a dialog, or `None` if it was cancelled.
+pub type DialogFuture<T> = Pin<Box<dyn Future<Output = Option<T>> + 'static>>;
+
+/// Restricts the files which can be picked in a dialog, as given by `FileFilter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFilter {
+    pub description: String,
+
+    /// The patterns of the allowed files, such as `*.jpg`.
+    pub extensions: Vec<String>,
+
+    /// The allowed Macintosh file types, if any.
+    pub mac_type: Option<String>,
+}
+
+impl FileFilter {
+    /// Build a filter from the semicolon-separated lists used by `FileFilter`.
+    pub fn new(description: &str, extension: &str, mac_type: Option<&str>) -> Self {
+        Self {
+            description: description.to_string(),
+            extensions: extension
+                .split(';')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_string)
+                .collect(),
+            mac_type: mac_type.map(str::to_string),
+        }
+    }
+}
+
+/// A file picked by the user to be opened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedFile {
+    /// The name of the file, without its directory.
+    pub name: String,
+
+    pub creation_date: Option<DateTime<Utc>>,
+
+    pub modification_date: Option<DateTime<Utc>>,
+
+    /// The contents of the file.
+    pub data: Vec<u8>,
+}
+
+impl SelectedFile {
+    pub fn new(name: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            name: name.into(),
+            creation_date: None,
+            modification_date: None,
+            data,
+        }
+    }
+
+    /// The extension of the file, without the leading dot.
+    pub fn extension(&self) -> Option<&str> {
+        self.name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension)
+            .filter(|extension| !extension.is_empty())
+    }
+}
+
+/// A location picked by the user to save a file to.
+pub trait SaveTarget {
+    /// The name of the file, without its directory.
+    fn file_name(&self) -> String;
+
+    /// Replace the contents of the file.
+    fn write(&mut self, data: &[u8]) -> io::Result<()>;
+}
+
+/// The dialogs used by `FileReference` to open and save files.
+pub trait FileDialogBackend {
+    /// Ask the user for a file to open, matching one of the given filters.
+    ///
+    /// An empty list of filters allows any file.
+    fn browse(&mut self, filters: &[FileFilter]) -> DialogFuture<SelectedFile>;
+
+    /// Ask the user where to save a file, suggesting the given name.
+    fn save(&mut self, default_file_name: &str) -> DialogFuture<Box<dyn SaveTarget>>;
+}
+
+#[derive(Default)]
+struct MemoryFileDialogState {
+    browse_responses: VecDeque<Option<SelectedFile>>,
+    save_responses: VecDeque<Option<String>>,
+    saved_files: Vec<(String, Vec<u8>)>,
+}
+
+/// A `FileDialogBackend` answering dialogs with scripted responses.
+///
+/// Dialogs without a queued response are cancelled. Clones of this backend
+/// share the same state, so a clone can be kept to script the dialogs and
+/// to inspect the saved files.
+#[derive(Clone, Default)]
+pub struct MemoryFileDialogBackend {
+    state: Arc<Mutex<MemoryFileDialogState>>,
+}
+
+impl MemoryFileDialogBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue the file picked by the next open dialog, `None` cancelling it.
+    pub fn push_browse_response(&self, file: Option<SelectedFile>) {
+        let mut state = self.state.lock().unwrap();
+        state.browse_responses.push_back(file);
+    }
+
+    /// Queue the file name picked by the next save dialog, `None` cancelling it.
+    pub fn push_save_response(&self, file_name: Option<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.save_responses.push_back(file_name);
+    }
+
+    /// The files written so far, as their names and contents.
+    pub fn saved_files(&self) -> Vec<(String, Vec<u8>)> {
+        let state = self.state.lock().unwrap();
+        state.saved_files.clone()
+    }
+}
+
+impl FileDialogBackend for MemoryFileDialogBackend {
+    fn browse(&mut self, _filters: &[FileFilter]) -> DialogFuture<SelectedFile> {
+        let mut state = self.state.lock().unwrap();
+        let file = state.browse_responses.pop_front().flatten();
+        Box::pin(std::future::ready(file))
+    }
+
+    fn save(&mut self, _default_file_name: &str) -> DialogFuture<Box<dyn SaveTarget>> {
+        let mut state = self.state.lock().unwrap();
+        let file_name = state.save_responses.pop_front().flatten();
+        let target = file_name.map(|file_name| -> Box<dyn SaveTarget> {
+            Box::new(MemorySaveTarget {
+                file_name,
+                state: self.state.clone(),
+            })
+        });
+        Box::pin(std::future::ready(target))
+    }
+}
+
+struct MemorySaveTarget {
+    file_name: String,
+    state: Arc<Mutex<MemoryFileDialogState>>,
+}
+
+impl SaveTarget for MemorySaveTarget {
+    fn file_name(&self) -> String {
+        self.file_name.clone()
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .saved_files
+            .push((self.file_name.clone(), data.to_vec()));
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A call sent through a `LocalConnection`, waiting to be delivered to its listener.
+#[derive(Debug, Clone)]
+pub struct LocalConnectionMessage {
+    /// The domain of the movie that sent this message.
+    pub sender_domain: String,
+
+    /// The name of the method to call on the listener.
+    pub method_name: String,
+
+    /// The arguments of the call, serialized as an AMF0 LSO.
+    pub arguments: Vec<u8>,
+}
+
+/// A registry of `LocalConnection` listeners.
+///
+/// Connection names are already fully qualified and normalized by the player.
+/// Implementations may share their registry between several players, allowing
+/// those movies to talk to each other.
+pub trait LocalConnectionBackend {
+    /// Start listening on the given connection name.
+    ///
+    /// Returns `false` if another listener is already using this name.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Stop listening on the given connection name, dropping any undelivered messages.
+    fn close(&mut self, name: &str);
+
+    /// Queue a message for the listener of the given connection name.
+    ///
+    /// Returns `false` if nothing is listening on this name.
+    fn send(&mut self, name: &str, message: LocalConnectionMessage) -> bool;
+
+    /// Take the messages queued for the given connection name.
+    fn receive(&mut self, name: &str) -> Vec<LocalConnectionMessage>;
+}
+
+/// A `LocalConnectionBackend` that keeps its listeners in memory.
+///
+/// Clones of this backend share the same registry, so giving a clone to
+/// each player lets them communicate.
+#[derive(Clone, Default)]
+pub struct MemoryLocalConnectionBackend {
+    connections: Arc<Mutex<HashMap<String, Vec<LocalConnectionMessage>>>>,
+}
+
+impl MemoryLocalConnectionBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LocalConnectionBackend for MemoryLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        let mut connections = self.connections.lock().unwrap();
+        if connections.contains_key(name) {
+            return false;
+        }
+        connections.insert(name.to_owned(), Vec::new());
+        true
+    }
+
+    fn close(&mut self, name: &str) {
+        let mut connections = self.connections.lock().unwrap();
+        connections.remove(name);
+    }
+
+    fn send(&mut self, name: &str, message: LocalConnectionMessage) -> bool {
+        let mut connections = self.connections.lock().unwrap();
+        if let Some(messages) = connections.get_mut(name) {
+            messages.push(message);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn receive(&mut self, name: &str) -> Vec<LocalConnectionMessage> {
+        let mut connections = self.connections.lock().unwrap();
+        connections
+            .get_mut(name)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
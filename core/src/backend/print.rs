@@ -0,0 +1,370 @@
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::future::Future;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// A future resolving once the pages of a print job are printed.
+pub type PrintFuture = Pin<Box<dyn Future<Output = ()> + 'static>>;
+
+/// The orientation of the printed pages.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrintOrientation {
+    Portrait,
+    Landscape,
+}
+
+/// The format of the pages of a print job, as chosen in the print dialog.
+///
+/// All sizes are in points, which are 1/72 of an inch. Like in Flash Player,
+/// a pixel of the printed content is one point large.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageFormat {
+    /// The size of the paper, in the direction of the orientation.
+    pub paper_width: u32,
+    pub paper_height: u32,
+
+    /// The size of the printable area, centered on the paper.
+    pub page_width: u32,
+    pub page_height: u32,
+
+    pub orientation: PrintOrientation,
+
+    /// The resolution vector content is rendered at, in dots per inch.
+    ///
+    /// Content printed as a bitmap is always rendered at screen resolution.
+    pub dpi: u32,
+}
+
+impl PageFormat {
+    /// US Letter paper with quarter-inch margins.
+    pub const LETTER: Self = Self {
+        paper_width: 612,
+        paper_height: 792,
+        page_width: 576,
+        page_height: 756,
+        orientation: PrintOrientation::Portrait,
+        dpi: 144,
+    };
+
+    /// Rotate the paper to the given orientation.
+    pub fn with_orientation(self, orientation: PrintOrientation) -> Self {
+        if orientation == self.orientation {
+            return self;
+        }
+        Self {
+            paper_width: self.paper_height,
+            paper_height: self.paper_width,
+            page_width: self.page_height,
+            page_height: self.page_width,
+            orientation,
+            dpi: self.dpi,
+        }
+    }
+}
+
+impl Default for PageFormat {
+    fn default() -> Self {
+        Self::LETTER
+    }
+}
+
+/// A page rendered by a print job.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintedPage {
+    /// The format of the page this was printed on.
+    pub format: PageFormat,
+
+    /// The size of the image, in pixels.
+    ///
+    /// The image covers the printable area of the page.
+    pub width: u32,
+    pub height: u32,
+
+    /// The opaque RGB pixels of the page.
+    pub pixels: Vec<u8>,
+}
+
+/// The printer used by `PrintJob`.
+///
+/// Only one print job is active at a time: it starts with `start`, and ends
+/// with `send` or with the next `start`.
+pub trait PrintBackend {
+    /// Whether printing is available at all.
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    /// Ask the user to start a print job.
+    ///
+    /// Returns the format of the pages, or `None` if the user cancelled.
+    fn start(&mut self) -> Option<PageFormat>;
+
+    /// The format of the pages of the active print job, if any.
+    fn page_format(&self) -> Option<PageFormat>;
+
+    /// Add a rendered page to the active print job.
+    fn add_page(&mut self, page: PrintedPage);
+
+    /// Print the pages of the active print job.
+    ///
+    /// Printing may ask the user where to print the pages, so it happens in
+    /// the returned future, which the player spawns.
+    fn send(&mut self) -> PrintFuture;
+}
+
+/// A `PrintBackend` for players which can't print.
+#[derive(Default)]
+pub struct NullPrintBackend;
+
+impl NullPrintBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PrintBackend for NullPrintBackend {
+    fn is_supported(&self) -> bool {
+        false
+    }
+
+    fn start(&mut self) -> Option<PageFormat> {
+        None
+    }
+
+    fn page_format(&self) -> Option<PageFormat> {
+        None
+    }
+
+    fn add_page(&mut self, _page: PrintedPage) {}
+
+    fn send(&mut self) -> PrintFuture {
+        Box::pin(std::future::ready(()))
+    }
+}
+
+#[derive(Default)]
+struct MemoryPrintState {
+    page_format: PageFormat,
+    cancel_next: bool,
+    current_job: Option<(PageFormat, Vec<PrintedPage>)>,
+    jobs: Vec<Vec<PrintedPage>>,
+}
+
+/// A `PrintBackend` collecting the printed pages in memory.
+///
+/// Clones of this backend share the same state, so a clone can be kept to
+/// inspect the printed jobs.
+#[derive(Clone, Default)]
+pub struct MemoryPrintBackend {
+    state: Arc<Mutex<MemoryPrintState>>,
+}
+
+impl MemoryPrintBackend {
+    pub fn new(page_format: PageFormat) -> Self {
+        let state = MemoryPrintState {
+            page_format,
+            ..Default::default()
+        };
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Cancel the print dialog of the next print job.
+    pub fn cancel_next_job(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.cancel_next = true;
+    }
+
+    /// The pages of the print jobs sent so far.
+    pub fn jobs(&self) -> Vec<Vec<PrintedPage>> {
+        let state = self.state.lock().unwrap();
+        state.jobs.clone()
+    }
+}
+
+impl PrintBackend for MemoryPrintBackend {
+    fn start(&mut self) -> Option<PageFormat> {
+        let mut state = self.state.lock().unwrap();
+        if std::mem::take(&mut state.cancel_next) {
+            state.current_job = None;
+            return None;
+        }
+        state.current_job = Some((state.page_format, Vec::new()));
+        Some(state.page_format)
+    }
+
+    fn page_format(&self) -> Option<PageFormat> {
+        let state = self.state.lock().unwrap();
+        state.current_job.as_ref().map(|(format, _)| *format)
+    }
+
+    fn add_page(&mut self, page: PrintedPage) {
+        let mut state = self.state.lock().unwrap();
+        if let Some((_, job)) = &mut state.current_job {
+            job.push(page);
+        }
+    }
+
+    fn send(&mut self) -> PrintFuture {
+        let mut state = self.state.lock().unwrap();
+        if let Some((_, job)) = state.current_job.take() {
+            state.jobs.push(job);
+        }
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// Write printed pages as a PDF document, one image per page.
+pub fn pages_to_pdf(pages: &[PrintedPage]) -> Vec<u8> {
+    let mut pdf = PdfWriter::default();
+    pdf.bytes
+        .extend_from_slice(b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n");
+
+    // Objects 1 and 2 are the catalog and the page tree, then each page
+    // takes three objects: the page, its contents and its image.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 3 + i * 3).collect();
+
+    pdf.begin_object(1);
+    pdf.bytes
+        .extend_from_slice(b"<< /Type /Catalog /Pages 2 0 R >>\n");
+    pdf.end_object();
+
+    pdf.begin_object(2);
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+    let _ = writeln!(
+        pdf.bytes,
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        pages.len()
+    );
+    pdf.end_object();
+
+    for (page, page_id) in pages.iter().zip(page_ids) {
+        let contents_id = page_id + 1;
+        let image_id = page_id + 2;
+        let format = &page.format;
+
+        pdf.begin_object(page_id);
+        let _ = writeln!(
+            pdf.bytes,
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /XObject << /Page {image_id} 0 R >> >> \
+             /Contents {contents_id} 0 R >>",
+            format.paper_width, format.paper_height
+        );
+        pdf.end_object();
+
+        // Center the printable area on the paper.
+        let x = (format.paper_width.saturating_sub(format.page_width)) as f64 / 2.0;
+        let y = (format.paper_height.saturating_sub(format.page_height)) as f64 / 2.0;
+        let contents = format!(
+            "q {} 0 0 {} {x} {y} cm /Page Do Q",
+            format.page_width, format.page_height
+        );
+        pdf.begin_object(contents_id);
+        pdf.write_stream("", contents.as_bytes());
+        pdf.end_object();
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let _ = encoder.write_all(&page.pixels);
+        let pixels = encoder.finish().unwrap_or_default();
+        pdf.begin_object(image_id);
+        pdf.write_stream(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode",
+                page.width, page.height
+            ),
+            &pixels,
+        );
+        pdf.end_object();
+    }
+
+    pdf.finish()
+}
+
+#[derive(Default)]
+struct PdfWriter {
+    bytes: Vec<u8>,
+
+    /// The offsets of the objects, by their number minus one.
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn begin_object(&mut self, id: usize) {
+        if self.offsets.len() < id {
+            self.offsets.resize(id, 0);
+        }
+        self.offsets[id - 1] = self.bytes.len();
+        let _ = writeln!(self.bytes, "{id} 0 obj");
+    }
+
+    fn end_object(&mut self) {
+        self.bytes.extend_from_slice(b"endobj\n");
+    }
+
+    fn write_stream(&mut self, dictionary: &str, data: &[u8]) {
+        let _ = writeln!(
+            self.bytes,
+            "<< {dictionary} /Length {} >>\nstream",
+            data.len()
+        );
+        self.bytes.extend_from_slice(data);
+        self.bytes.extend_from_slice(b"\nendstream\n");
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let xref_offset = self.bytes.len();
+        let _ = writeln!(
+            self.bytes,
+            "xref\n0 {}\n0000000000 65535 f ",
+            self.offsets.len() + 1
+        );
+        for offset in &self.offsets {
+            let _ = writeln!(self.bytes, "{offset:010} 00000 n ");
+        }
+        let _ = writeln!(
+            self.bytes,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF",
+            self.offsets.len() + 1
+        );
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_has_one_page_per_printed_page() {
+        let page = PrintedPage {
+            format: PageFormat::LETTER,
+            width: 2,
+            height: 2,
+            pixels: vec![255; 12],
+        };
+        let pdf = pages_to_pdf(&[page.clone(), page]);
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("/Kids [3 0 R 6 0 R] /Count 2"));
+        assert!(pdf.contains("/MediaBox [0 0 612 792]"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+    }
+
+    #[test]
+    fn landscape_swaps_dimensions() {
+        let format = PageFormat::LETTER.with_orientation(PrintOrientation::Landscape);
+        assert_eq!(format.paper_width, 792);
+        assert_eq!(format.page_height, 576);
+        assert_eq!(
+            format.with_orientation(PrintOrientation::Portrait),
+            PageFormat::LETTER
+        );
+    }
+}
@@ -39,6 +39,10 @@ pub trait UiBackend: Downcast {
     fn language(&self) -> &LanguageIdentifier;
 
     fn display_unsupported_video(&self, url: Url);
+
+    /// The number of points a touch screen can track at once, or 0 if there's
+    /// no touch screen.
+    fn max_touch_points(&self) -> u32;
 }
 impl_downcast!(UiBackend);
 
@@ -180,6 +184,10 @@ impl UiBackend for NullUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &US_ENGLISH
     }
+
+    fn max_touch_points(&self) -> u32 {
+        0
+    }
 }
 
 impl Default for NullUiBackend {
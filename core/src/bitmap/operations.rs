@@ -1470,6 +1470,7 @@ pub fn draw<'gc>(
         is_offscreen: true,
         use_bitmap_cache: false,
         stage: context.stage,
+        scaling_grid: None,
     };
 
     // Make the screen opacity match the opacity of this bitmap
@@ -6,8 +6,11 @@ use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    file_dialog::FileDialogBackend,
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
+    print::PrintBackend,
     storage::StorageBackend,
     ui::{InputManager, UiBackend},
 };
@@ -18,6 +21,7 @@ use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
 use crate::player::Player;
 use crate::prelude::*;
 use crate::socket::Sockets;
@@ -26,12 +30,14 @@ use crate::string::AvmStringInterner;
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
+use crate::touch_tracker::TouchTracker;
 use core::fmt;
 use gc_arena::{Collect, Mutation};
 use instant::Instant;
 use rand::rngs::SmallRng;
 use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
 use ruffle_render::commands::CommandList;
+use ruffle_render::scaling_grid::ScalingGrid;
 use ruffle_render::transform::TransformStack;
 use ruffle_video::backend::VideoBackend;
 use std::collections::{HashMap, VecDeque};
@@ -119,6 +125,15 @@ pub struct UpdateContext<'a, 'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The local connection backend, used to deliver `LocalConnection` messages.
+    pub local_connection: &'a mut dyn LocalConnectionBackend,
+
+    /// The file dialog backend, used by `FileReference` to open and save files.
+    pub file_dialog: &'a mut dyn FileDialogBackend,
+
+    /// The print backend, used by `PrintJob` to print pages.
+    pub print: &'a mut dyn PrintBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -202,6 +217,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// A tracker for the current keyboard focused element
     pub focus_tracker: FocusTracker<'gc>,
 
+    /// A tracker for the points of contact currently on the touch screen.
+    pub touch_tracker: TouchTracker<'gc>,
+
     /// How many times getTimer() was called so far. Used to detect busy-loops.
     pub times_get_time_called: u32,
 
@@ -227,6 +245,9 @@ pub struct UpdateContext<'a, 'gc> {
 
     pub sockets: &'a mut Sockets<'gc>,
 
+    /// The `LocalConnection` objects listening for messages, and messages awaiting a status.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 }
@@ -268,6 +289,14 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(
+        &mut self,
+        sound_object: Avm2Object<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager
+            .start_dynamic_sound(self.audio, sound_object)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -363,6 +392,9 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             ui: self.ui,
             video: self.video,
             storage: self.storage,
+            local_connection: self.local_connection,
+            file_dialog: self.file_dialog,
+            print: self.print,
             rng: self.rng,
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
@@ -386,6 +418,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             update_start: self.update_start,
             max_execution_duration: self.max_execution_duration,
             focus_tracker: self.focus_tracker,
+            touch_tracker: self.touch_tracker,
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
             frame_rate: self.frame_rate,
@@ -394,6 +427,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
             sockets: self.sockets,
+            local_connections: self.local_connections,
             dynamic_root: self.dynamic_root,
         }
     }
@@ -517,6 +551,9 @@ pub struct RenderContext<'a, 'gc> {
 
     /// The current player's stage (including all loaded levels)
     pub stage: Stage<'gc>,
+
+    /// The 9-slice scaling of the container whose children are being rendered, if any.
+    pub scaling_grid: Option<ScalingGrid>,
 }
 
 impl<'a, 'gc> RenderContext<'a, 'gc> {
@@ -46,7 +46,9 @@ pub use avm2_button::Avm2Button;
 pub use bitmap::Bitmap;
 pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
-pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
+pub use interactive::{
+    lowest_common_ancestor, Avm2MousePick, InteractiveObject, TInteractiveObject,
+};
 pub use loader_display::LoaderDisplay;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
@@ -55,8 +57,12 @@ use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
-pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
-pub use text::Text;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::scaling_grid::ScalingGrid;
+pub use stage::{
+    MultitouchInputMode, Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode,
+};
+pub use text::{Text, TextGlyph};
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
@@ -182,6 +188,67 @@ impl BitmapCache {
     }
 }
 
+/// The 3D properties of a display object, which it only has once any of `z`,
+/// `rotationX`, `rotationY`, `scaleZ` or `transform.matrix3D` has been set.
+///
+/// The X and Y position and scale, and the rotation around the Z axis, are
+/// still kept in the 2D matrix of the display object.
+#[derive(Clone, Debug)]
+pub struct Transform3D {
+    pub z: f64,
+    pub rotation_x: Degrees,
+    pub rotation_y: Degrees,
+    pub scale_z: f64,
+
+    /// The bitmap that this object is drawn to before being projected onto its parent.
+    cache: BitmapCache,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from(0.0),
+            rotation_y: Degrees::from(0.0),
+            scale_z: 1.0,
+            cache: Default::default(),
+        }
+    }
+}
+
+/// The perspective projection of the 3D descendants of a display object,
+/// as set by `transform.perspectiveProjection`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The angle of the field of view, in degrees.
+    pub field_of_view: f64,
+
+    /// The point that the projection converges on, in the local space of the
+    /// display object.
+    pub center: Point<Twips>,
+}
+
+impl PerspectiveProjection {
+    /// The projection used when no display object has one: centered on the stage,
+    /// with a field of view of 55 degrees.
+    pub fn for_stage(stage: Stage<'_>) -> Self {
+        let (width, height) = stage.stage_size();
+        Self {
+            field_of_view: 55.0,
+            center: Point::new(
+                Twips::from_pixels(width as f64 / 2.0),
+                Twips::from_pixels(height as f64 / 2.0),
+            ),
+        }
+    }
+
+    /// The distance in pixels between the viewer and the `z = 0` plane.
+    /// The field of view always spans the width of the stage.
+    pub fn focal_length(&self, stage_width: f64) -> f64 {
+        stage_width / 2.0 / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+}
+
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 pub struct DisplayObjectBase<'gc> {
@@ -252,10 +319,23 @@ pub struct DisplayObjectBase<'gc> {
     #[collect(require_static)]
     next_scroll_rect: Rectangle<Twips>,
 
+    /// The `scale9Grid` of this display object, which slices the shapes within it
+    /// into nine regions when it is scaled.
+    #[collect(require_static)]
+    scaling_grid: Option<Rectangle<Twips>>,
+
     /// If this Display Object should cacheAsBitmap - and if so, the cache itself.
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The 3D properties of this display object, if it has any.
+    #[collect(require_static)]
+    transform_3d: Option<Box<Transform3D>>,
+
+    /// The perspective projection of the 3D descendants of this display object, if it has one.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -282,7 +362,10 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             flags: DisplayObjectFlags::VISIBLE,
             scroll_rect: None,
             next_scroll_rect: Default::default(),
+            scaling_grid: None,
             cache: None,
+            transform_3d: None,
+            perspective_projection: None,
         }
     }
 }
@@ -488,6 +571,78 @@ impl<'gc> DisplayObjectBase<'gc> {
         changed
     }
 
+    pub fn transform_3d(&self) -> Option<&Transform3D> {
+        self.transform_3d.as_deref()
+    }
+
+    /// The 3D properties of this display object, giving it some if it has none.
+    fn transform_3d_mut(&mut self) -> &mut Transform3D {
+        self.set_transformed_by_script(true);
+        self.transform_3d.get_or_insert_with(Default::default)
+    }
+
+    /// The full 3D matrix of this display object, with translations in pixels,
+    /// or `None` if it has no 3D properties.
+    ///
+    /// This is `Matrix3D::recompose` of its properties, except that the
+    /// 2D matrix is used as is, rather than its scale and rotation, so that
+    /// any skew or flip is kept.
+    fn matrix_3d(&mut self) -> Option<Matrix3D> {
+        self.transform_3d.as_ref()?;
+        self.cache_scale_rotation();
+        let transform_3d = self.transform_3d.as_ref()?;
+        let matrix = self.transform.matrix;
+        let rotation = self.rotation.into_radians();
+        Some(
+            Matrix3D::translate(matrix.tx.to_pixels(), matrix.ty.to_pixels(), transform_3d.z)
+                * Matrix3D::rotate_z(rotation)
+                * Matrix3D::rotate_y(transform_3d.rotation_y.into_radians())
+                * Matrix3D::rotate_x(transform_3d.rotation_x.into_radians())
+                * Matrix3D::rotate_z(-rotation)
+                * Matrix3D::from(Matrix {
+                    tx: Twips::ZERO,
+                    ty: Twips::ZERO,
+                    ..matrix
+                })
+                * Matrix3D::scale(1.0, 1.0, transform_3d.scale_z),
+        )
+    }
+
+    /// Sets the full 3D matrix of this display object, or removes its 3D
+    /// properties if `None`. Matrices with a projection are ignored.
+    fn set_matrix_3d(&mut self, matrix: Option<Matrix3D>) {
+        let Some(matrix) = matrix else {
+            self.transform_3d = None;
+            return;
+        };
+        let Some((translation, rotation, scale)) = matrix.decompose() else {
+            return;
+        };
+
+        let (sin, cos) = rotation[2].sin_cos();
+        self.set_matrix(Matrix {
+            a: (scale[0] * cos) as f32,
+            b: (scale[0] * sin) as f32,
+            c: (scale[1] * -sin) as f32,
+            d: (scale[1] * cos) as f32,
+            tx: Twips::from_pixels(translation[0]),
+            ty: Twips::from_pixels(translation[1]),
+        });
+        let transform_3d = self.transform_3d_mut();
+        transform_3d.z = translation[2];
+        transform_3d.rotation_x = Degrees::from_radians(rotation[0]);
+        transform_3d.rotation_y = Degrees::from_radians(rotation[1]);
+        transform_3d.scale_z = scale[2];
+    }
+
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.perspective_projection
+    }
+
+    fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) {
+        self.perspective_projection = projection;
+    }
+
     fn name(&self) -> Option<AvmString<'gc>> {
         self.name
     }
@@ -688,6 +843,9 @@ impl<'gc> DisplayObjectBase<'gc> {
         if let Some(cache) = &mut self.cache {
             cache.make_dirty();
         }
+        if let Some(transform_3d) = &mut self.transform_3d {
+            transform_3d.cache.make_dirty();
+        }
         self.flags.insert(DisplayObjectFlags::CACHE_INVALIDATED);
         true
     }
@@ -761,6 +919,10 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+
+    let parent_matrix = context.transform_stack.transform().matrix;
+    let projected_matrix = this.projected_matrix(context.gc_context, context.stage);
+
     context.transform_stack.push(this.base().transform());
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
@@ -769,7 +931,12 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         None
     };
 
-    let cache_info = if context.use_bitmap_cache && this.is_bitmap_cached() {
+    let projected = projected_matrix
+        .is_some_and(|matrix| render_projected(this, context, parent_matrix, matrix));
+
+    let cache_info = if projected {
+        None
+    } else if context.use_bitmap_cache && this.is_bitmap_cached() {
         let mut cache_info: Option<DrawCacheInfo> = None;
         let base_transform = context.transform_stack.transform();
         let bounds: Rectangle<Twips> = this.render_bounds_with_transform(
@@ -883,6 +1050,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                 is_offscreen: true,
                 use_bitmap_cache: true,
                 stage: context.stage,
+                scaling_grid: context.scaling_grid,
             };
             this.render_self(&mut offscreen_context);
             offscreen_context.cache_draws.push(BitmapCacheEntry {
@@ -909,7 +1077,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                 PixelSnapping::Always, // cacheAsBitmap forces pixel snapping
             )
         });
-    } else {
+    } else if !projected {
         if let Some(background) = this.opaque_background() {
             // This is intended for use with cacheAsBitmap, but can be set for non-cached objects too
             // It wants the entire bounding box to be cleared before any draws happen
@@ -949,6 +1117,115 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// Draws a display object with a 3D transform to an offscreen bitmap, then draws
+/// that bitmap onto the render target through its projection.
+///
+/// The object is drawn offscreen at the scale it would have when flat, so that
+/// it isn't blurry when facing the viewer. Any filters are ignored.
+///
+/// Returns `false` if nothing was drawn, in which case the object should be drawn flat.
+fn render_projected<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    parent_matrix: Matrix,
+    projected_matrix: Matrix3D,
+) -> bool {
+    let matrix = context.transform_stack.transform().matrix;
+    let scale = f32::max(
+        f32::hypot(matrix.a, matrix.b),
+        f32::hypot(matrix.c, matrix.d),
+    );
+    if scale.is_nan() || scale <= 0.0 {
+        return false;
+    }
+    let scale_matrix = Matrix::scale(scale, scale);
+    let bounds: Rectangle<Twips> =
+        this.render_bounds_with_transform(&scale_matrix, false, &context.stage.view_matrix());
+    let width = bounds.width().to_pixels().ceil().max(0.0);
+    let height = bounds.height().to_pixels().ceil().max(0.0);
+    if width > u16::MAX as f64 || height > u16::MAX as f64 {
+        return false;
+    }
+    let (width, height) = (width as u16, height as u16);
+    let swf_version = this.swf_version();
+
+    let (handle, dirty) = {
+        let mut base = this.base_mut(context.gc_context);
+        let Some(transform_3d) = base.transform_3d.as_mut() else {
+            return false;
+        };
+        let cache = &mut transform_3d.cache;
+        let dirty = cache.is_dirty(&scale_matrix, width, height);
+        if dirty {
+            cache.update(
+                context.renderer,
+                scale_matrix,
+                width,
+                height,
+                width,
+                height,
+                Point::new(0, 0),
+                swf_version,
+            );
+        }
+        let Some(handle) = cache.handle() else {
+            return false;
+        };
+        (handle, dirty)
+    };
+
+    if dirty {
+        let mut transform_stack = TransformStack::new();
+        transform_stack.push(&Transform {
+            color_transform: Default::default(),
+            matrix: Matrix {
+                tx: -bounds.x_min,
+                ty: -bounds.y_min,
+                ..scale_matrix
+            },
+        });
+        let mut offscreen_context = RenderContext {
+            renderer: context.renderer,
+            commands: CommandList::new(),
+            cache_draws: context.cache_draws,
+            gc_context: context.gc_context,
+            library: context.library,
+            transform_stack: &mut transform_stack,
+            is_offscreen: true,
+            use_bitmap_cache: true,
+            stage: context.stage,
+            scaling_grid: context.scaling_grid,
+        };
+        this.render_self(&mut offscreen_context);
+        offscreen_context.cache_draws.push(BitmapCacheEntry {
+            handle: handle.clone(),
+            commands: offscreen_context.commands,
+            clear: this.opaque_background().unwrap_or_default(),
+            filters: vec![],
+        });
+    }
+
+    // The projection maps from the local space of this object to the stage, so go
+    // back to the space of the parent before applying the parent's render matrix.
+    let stage_to_parent = this
+        .parent()
+        .map(|parent| parent.global_to_local_matrix().unwrap_or_default())
+        .unwrap_or_default();
+    let scale = f64::from(scale);
+    let matrix = Matrix3D::from(parent_matrix)
+        * Matrix3D::from(stage_to_parent)
+        * projected_matrix
+        * Matrix3D::scale(1.0 / scale, 1.0 / scale, 1.0)
+        * Matrix3D::translate(bounds.x_min.to_pixels(), bounds.y_min.to_pixels(), 0.0);
+    let color_transform = context.transform_stack.transform().color_transform;
+    apply_standard_mask_and_scroll(this, context, |context| {
+        context
+            .commands
+            .render_bitmap_projected(handle, matrix, color_transform, true)
+    });
+    true
+}
+
 /// This applies the **standard** method of `mask` and `scrollRect`.
 ///
 /// It uses the stencil buffer so that any pixel drawn in the mask will allow the inner contents to show.
@@ -1343,6 +1620,165 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the `z` ActionScript property.
+    fn z(&self) -> f64 {
+        self.base().transform_3d().map_or(0.0, |t| t.z)
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space,
+    /// giving it a 3D transform.
+    /// Set by the `z` ActionScript property.
+    fn set_z(&self, gc_context: &Mutation<'gc>, z: f64) {
+        self.base_mut(gc_context).transform_3d_mut().z = z;
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The rotation in degrees of this display object around the X axis.
+    /// Returned by the `rotationX` ActionScript property.
+    fn rotation_x(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(Degrees::from(0.0), |t| t.rotation_x)
+    }
+
+    /// Sets the rotation in degrees of this display object around the X axis,
+    /// giving it a 3D transform.
+    /// Set by the `rotationX` ActionScript property.
+    fn set_rotation_x(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        self.base_mut(gc_context).transform_3d_mut().rotation_x = degrees;
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The rotation in degrees of this display object around the Y axis.
+    /// Returned by the `rotationY` ActionScript property.
+    fn rotation_y(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(Degrees::from(0.0), |t| t.rotation_y)
+    }
+
+    /// Sets the rotation in degrees of this display object around the Y axis,
+    /// giving it a 3D transform.
+    /// Set by the `rotationY` ActionScript property.
+    fn set_rotation_y(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        self.base_mut(gc_context).transform_3d_mut().rotation_y = degrees;
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The Z axis scale of this display object, as a unit proportion.
+    /// Returned by the `scaleZ` ActionScript property.
+    fn scale_z(&self) -> f64 {
+        self.base().transform_3d().map_or(1.0, |t| t.scale_z)
+    }
+
+    /// Sets the Z axis scale of this display object, giving it a 3D transform.
+    /// Set by the `scaleZ` ActionScript property.
+    fn set_scale_z(&self, gc_context: &Mutation<'gc>, value: f64) {
+        self.base_mut(gc_context).transform_3d_mut().scale_z = value;
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The 3D matrix of this display object in local space, or `None` if it
+    /// only has a 2D transform.
+    /// Returned by the `transform.matrix3D` ActionScript property.
+    fn matrix_3d(&self, gc_context: &Mutation<'gc>) -> Option<Matrix3D> {
+        self.base().transform_3d()?;
+        let matrix = self.base_mut(gc_context).matrix_3d();
+        self.set_scale_rotation_cached(gc_context);
+        matrix
+    }
+
+    /// Sets the 3D matrix of this display object in local space, or goes back
+    /// to a 2D transform if `None`.
+    /// Set by the `transform.matrix3D` ActionScript property.
+    fn set_matrix_3d(&self, gc_context: &Mutation<'gc>, matrix: Option<Matrix3D>) {
+        self.base_mut(gc_context).set_matrix_3d(matrix);
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// The perspective projection of the 3D descendants of this display object, if it has one.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection()
+    }
+
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        projection: Option<PerspectiveProjection>,
+    ) {
+        self.base_mut(gc_context)
+            .set_perspective_projection(projection);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The matrix which projects this display object from its local space onto the stage,
+    /// with translations in pixels, or `None` if it has no 3D transform.
+    ///
+    /// The projection is that of the closest ancestor which has one, or the
+    /// default projection of the stage. 3D objects within another 3D object are
+    /// flattened into its projection, so they have no projected matrix of their own.
+    fn projected_matrix(&self, gc_context: &Mutation<'gc>, stage: Stage<'gc>) -> Option<Matrix3D> {
+        self.base().transform_3d()?;
+        if std::iter::successors(self.parent(), |o| o.parent())
+            .any(|ancestor| ancestor.base().transform_3d().is_some())
+        {
+            return None;
+        }
+        let matrix = self.matrix_3d(gc_context)?;
+        let parent_matrix = self
+            .parent()
+            .map(|parent| parent.local_to_global_matrix())
+            .unwrap_or_default();
+        let (projection, projection_matrix) = std::iter::successors(self.parent(), |o| o.parent())
+            .find_map(|ancestor| {
+                Some((
+                    ancestor.perspective_projection()?,
+                    ancestor.local_to_global_matrix(),
+                ))
+            })
+            .unwrap_or_else(|| (PerspectiveProjection::for_stage(stage), Matrix::IDENTITY));
+
+        let center = projection_matrix * projection.center;
+        let focal_length = projection.focal_length(stage.stage_size().0 as f64);
+        Some(
+            Matrix3D::perspective(focal_length, center.x.to_pixels(), center.y.to_pixels())
+                * Matrix3D::from(parent_matrix)
+                * matrix,
+        )
+    }
+
+    /// Maps a stage position on this display object, as projected by its 3D
+    /// transform, to the stage position it would have if the object was flat.
+    /// This allows 3D objects to be hit-tested with their 2D matrices.
+    ///
+    /// Points are returned unchanged if this object has no 3D transform, and
+    /// `None` is returned if the point can't be on this object.
+    fn unproject_point(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        point: Point<Twips>,
+    ) -> Option<Point<Twips>> {
+        let Some(matrix) = self.projected_matrix(context.gc_context, context.stage) else {
+            return Some(point);
+        };
+        let (x, y) = matrix.unproject_point(point.x.to_pixels(), point.y.to_pixels())?;
+        Some(
+            self.local_to_global_matrix_without_own_scroll_rect()
+                * Point::new(Twips::from_pixels(x), Twips::from_pixels(y)),
+        )
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -1614,6 +2050,63 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `scale9Grid` of this display object, in local space.
+    fn scaling_grid(&self) -> Option<Rectangle<Twips>> {
+        self.base().scaling_grid.clone()
+    }
+
+    fn set_scaling_grid(&self, gc_context: &Mutation<'gc>, rectangle: Option<Rectangle<Twips>>) {
+        self.base_mut(gc_context).scaling_grid = rectangle;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The 9-slice scaling currently applied to the shapes within this display object.
+    ///
+    /// This is `None` if it has no `scale9Grid`, or if it is rotated or skewed, in
+    /// which case it is scaled normally.
+    fn active_scaling_grid(&self) -> Option<ScalingGrid> {
+        let grid = self.scaling_grid()?;
+        let matrix = *self.base().matrix();
+        if matrix.b != 0.0 || matrix.c != 0.0 {
+            return None;
+        }
+        ScalingGrid::new(
+            &self.bounds_with_transform(&Matrix::IDENTITY),
+            &grid,
+            matrix.a.into(),
+            matrix.d.into(),
+        )
+    }
+
+    /// The 9-slice scaling applied to the shapes directly within this display object.
+    ///
+    /// The sprites holding the states of an AVM2 button have no `scale9Grid` of
+    /// their own, and are sliced by the `scale9Grid` of the button instead.
+    fn children_scaling_grid(&self) -> Option<ScalingGrid> {
+        if self.scaling_grid().is_none() && self.as_movie_clip().is_some() {
+            if let Some(button) = self.parent().and_then(|parent| parent.as_avm2_button()) {
+                return button.active_scaling_grid();
+            }
+        }
+        self.active_scaling_grid()
+    }
+
+    /// Maps a stage position on this display object, as drawn through the
+    /// `scale9Grid` of its parent, to the stage position it would have without it.
+    /// This allows shapes to be hit-tested as they were defined.
+    fn unslice_point(&self, point: Point<Twips>) -> Point<Twips> {
+        let Some(parent) = self.parent() else {
+            return point;
+        };
+        let Some(grid) = parent.children_scaling_grid() else {
+            return point;
+        };
+        let Some(global_to_local) = parent.global_to_local_matrix() else {
+            return point;
+        };
+        parent.local_to_global_matrix() * grid.unmap_point(global_to_local * point)
+    }
+
     /// Whether this object has been removed. Only applies to AVM1.
     fn avm1_removed(&self) -> bool {
         self.base().avm1_removed()
@@ -2154,10 +2647,13 @@ pub trait TDisplayObject<'gc>:
     /// Tests if a given stage position point intersects within this object, considering the art.
     fn hit_test_shape(
         &self,
-        _context: &mut UpdateContext<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc>,
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        let Some(point) = self.unproject_point(context, point) else {
+            return false;
+        };
         // Default to using bounding box.
         (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
             && self.hit_test_bounds(point)
@@ -360,6 +360,9 @@ impl<'gc> TDisplayObject<'gc> for Avm1Button<'gc> {
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        let Some(point) = self.unproject_point(context, point) else {
+            return false;
+        };
         for child in self.iter_render_list() {
             if child.hit_test_shape(context, point, options) {
                 return true;
@@ -439,6 +442,10 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
+
     fn filter_clip_event(
         self,
         _context: &mut UpdateContext<'_, 'gc>,
@@ -574,6 +581,7 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
     ) -> Option<InteractiveObject<'gc>> {
         // The button is hovered if the mouse is over any child nodes.
         if self.visible() && self.mouse_enabled() {
+            let point = self.unproject_point(context, point)?;
             for child in self.iter_render_list().rev() {
                 let result = child
                     .as_interactive()
@@ -606,7 +606,12 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
         let current_state = self.get_state_child(state.into());
 
         if let Some(state) = current_state {
+            // A state with a single shape is rendered through the 9-slice scaling
+            // of the button, like the children of a container.
+            let parent_scaling_grid =
+                std::mem::replace(&mut context.scaling_grid, self.children_scaling_grid());
             state.render(context);
+            context.scaling_grid = parent_scaling_grid;
         }
     }
 
@@ -673,6 +678,9 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        let Some(point) = self.unproject_point(context, point) else {
+            return false;
+        };
         if !options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible() {
             let state = self.0.read().state;
             if let Some(child) = self.get_state_child(state.into()) {
@@ -750,6 +758,10 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
+
     fn filter_clip_event(
         self,
         _context: &mut UpdateContext<'_, 'gc>,
@@ -822,11 +834,14 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
     fn mouse_pick_avm2(
         &self,
         context: &mut UpdateContext<'_, 'gc>,
-        mut point: Point<Twips>,
+        point: Point<Twips>,
         require_button_mode: bool,
     ) -> Avm2MousePick<'gc> {
         // The button is hovered if the mouse is over any child nodes.
         if self.visible() && self.mouse_enabled() {
+            let Some(mut point) = self.unproject_point(context, point) else {
+                return Avm2MousePick::Miss;
+            };
             let state = self.0.read().state;
             let state_child = self.get_state_child(state.into());
 
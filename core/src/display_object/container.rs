@@ -457,6 +457,11 @@ pub trait TDisplayObjectContainer<'gc>:
 
     /// Renders the children of this container in render list order.
     fn render_children(self, context: &mut RenderContext<'_, 'gc>) {
+        // Shapes within this container are rendered through its 9-slice scaling.
+        let this: DisplayObject<'_> = self.into();
+        let parent_scaling_grid =
+            std::mem::replace(&mut context.scaling_grid, this.children_scaling_grid());
+
         let mut clip_depth = 0;
         let mut clip_depth_stack: Vec<(Depth, DisplayObject<'_>)> = vec![];
         for child in self.iter_render_list() {
@@ -496,6 +501,8 @@ pub trait TDisplayObjectContainer<'gc>:
             clip_child.render(context);
             context.commands.pop_mask();
         }
+
+        context.scaling_grid = parent_scaling_grid;
     }
 
     #[cfg(not(feature = "avm_debug"))]
@@ -576,6 +583,9 @@ pub struct ChildContainer<'gc> {
     has_pending_removals: bool,
 
     mouse_children: bool,
+
+    /// Whether the children of this container are part of the tab order.
+    tab_children: bool,
 }
 
 impl<'gc> Default for ChildContainer<'gc> {
@@ -591,6 +601,7 @@ impl<'gc> ChildContainer<'gc> {
             depth_list: BTreeMap::new(),
             has_pending_removals: false,
             mouse_children: true,
+            tab_children: true,
         }
     }
 
@@ -840,6 +851,14 @@ impl<'gc> ChildContainer<'gc> {
         self.mouse_children = mouse_children;
     }
 
+    pub fn tab_children(&self) -> bool {
+        self.tab_children
+    }
+
+    pub fn set_tab_children(&mut self, tab_children: bool) {
+        self.tab_children = tab_children;
+    }
+
     /// Insert a child at a given render list position.
     ///
     /// If the child is already a child of another container, you must remove
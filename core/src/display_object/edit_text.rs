@@ -22,7 +22,8 @@ use crate::drawing::Drawing;
 use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
 use crate::html::{
-    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, TextFormat,
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, StyleSheet,
+    TextFormat,
 };
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
@@ -158,6 +159,27 @@ pub struct EditTextData<'gc> {
     /// Flags indicating the text field's settings.
     #[collect(require_static)]
     flags: EditTextFlag,
+
+    /// The CSS stylesheet applied to the HTML text, if any.
+    style_sheet: Option<EditTextStyleSheet<'gc>>,
+}
+
+/// A CSS stylesheet set on a text field by `TextField.styleSheet`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EditTextStyleSheet<'gc> {
+    /// The `StyleSheet` object the styles were read from.
+    object: AvmObject<'gc>,
+
+    #[collect(require_static)]
+    style_sheet: StyleSheet,
+
+    /// The HTML source of the text, which is returned by `htmlText` as is.
+    #[collect(require_static)]
+    html: WString,
+
+    /// The start of the link under the mouse, which `a:hover` applies to.
+    hovered_link: Option<usize>,
 }
 
 impl<'gc> EditTextData<'gc> {
@@ -322,6 +344,7 @@ impl<'gc> EditText<'gc> {
                 line_data,
                 scroll: 1,
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
+                style_sheet: None,
             },
         ));
 
@@ -382,6 +405,10 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn html_text(self) -> WString {
+        if let Some(style_sheet) = &self.0.read().style_sheet {
+            return style_sheet.html.clone();
+        }
+
         if self.is_html() {
             self.0.read().text_spans.to_html()
         } else {
@@ -393,6 +420,14 @@ impl<'gc> EditText<'gc> {
     pub fn set_html_text(self, text: &WStr, context: &mut UpdateContext<'_, 'gc>) {
         if self.is_html() {
             let mut write = self.0.write(context.gc_context);
+            if let Some(style_sheet) = &mut write.style_sheet {
+                style_sheet.html = text.into();
+                style_sheet.hovered_link = None;
+                drop(write);
+                self.lower_styled_html(context);
+                return;
+            }
+
             let default_format = write.text_spans.default_format().clone();
             write.text_spans = FormatSpans::from_html(
                 text,
@@ -407,6 +442,113 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// The `StyleSheet` object applied to this text field, if any.
+    pub fn style_sheet(self) -> Option<AvmObject<'gc>> {
+        self.0
+            .read()
+            .style_sheet
+            .as_ref()
+            .map(|style_sheet| style_sheet.object)
+    }
+
+    /// Apply the styles of a `StyleSheet` object to the HTML text, or remove
+    /// the current ones.
+    ///
+    /// The styles are copied, so later changes to the object only apply once
+    /// it is set again.
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        style_sheet: Option<(AvmObject<'gc>, StyleSheet)>,
+    ) {
+        let html = self.html_text();
+        self.0.write(context.gc_context).style_sheet =
+            style_sheet.map(|(object, style_sheet)| EditTextStyleSheet {
+                object,
+                style_sheet,
+                html: html.clone(),
+                hovered_link: None,
+            });
+
+        if self.is_html() {
+            self.set_html_text(&html, context);
+        }
+    }
+
+    /// Lower the HTML source of a text field with a stylesheet.
+    fn lower_styled_html(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        let Some(style_sheet) = &write.style_sheet else {
+            return;
+        };
+        let default_format = write.text_spans.default_format().clone();
+        let text_spans = FormatSpans::lower_from_css(
+            &style_sheet.html,
+            default_format,
+            &style_sheet.style_sheet,
+            style_sheet.hovered_link,
+            write.flags.contains(EditTextFlag::MULTILINE),
+        );
+        write.text_spans = text_spans;
+        drop(write);
+
+        self.relayout(context);
+    }
+
+    /// Whether the stylesheet of this text field has an `a:hover` style.
+    fn has_hover_style(self) -> bool {
+        self.0
+            .read()
+            .style_sheet
+            .as_ref()
+            .is_some_and(|style_sheet| style_sheet.style_sheet.has_hover_style())
+    }
+
+    /// Restyle the link under the mouse, if the stylesheet has an `a:hover`
+    /// style.
+    fn update_hovered_link(self, context: &mut UpdateContext<'_, 'gc>, is_hovered: bool) {
+        let hovered_link = if is_hovered {
+            let mouse_position = *context.mouse_position;
+            self.unproject_point(context, mouse_position)
+                .and_then(|position| self.screen_position_to_index(position))
+                .and_then(|position| self.link_start_at(position))
+        } else {
+            None
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        let Some(style_sheet) = &mut write.style_sheet else {
+            return;
+        };
+        if !style_sheet.style_sheet.has_hover_style() || style_sheet.hovered_link == hovered_link {
+            return;
+        }
+        style_sheet.hovered_link = hovered_link;
+        drop(write);
+
+        self.lower_styled_html(context);
+    }
+
+    /// The position of the start of the link covering a text position.
+    fn link_start_at(self, position: usize) -> Option<usize> {
+        let text = self.0.read();
+        let (span_index, offset) = text.text_spans.resolve_position_as_span(position)?;
+        let url = &text.text_spans.span(span_index)?.url;
+        if url.is_empty() {
+            return None;
+        }
+
+        let mut start = position - offset;
+        for index in (0..span_index).rev() {
+            let span = text.text_spans.span(index)?;
+            if &span.url != url {
+                break;
+            }
+            start -= span.span_length;
+        }
+        Some(start)
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -443,7 +585,9 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn is_editable(self) -> bool {
-        !self.0.read().flags.contains(EditTextFlag::READ_ONLY)
+        // Text fields with a stylesheet can't be edited.
+        let read = self.0.read();
+        !read.flags.contains(EditTextFlag::READ_ONLY) && read.style_sheet.is_none()
     }
 
     pub fn was_static(self) -> bool {
@@ -2013,23 +2157,42 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.is_editable()
+    }
+
     fn filter_clip_event(
         self,
         _context: &mut UpdateContext<'_, 'gc>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if event != ClipEvent::Press {
-            return ClipEventResult::NotHandled;
+        match event {
+            ClipEvent::Press => ClipEventResult::Handled,
+            // Mouse movement only matters for restyling hovered links.
+            ClipEvent::MouseMoveInside | ClipEvent::RollOut { .. } if self.has_hover_style() => {
+                ClipEventResult::Handled
+            }
+            _ => ClipEventResult::NotHandled,
         }
-
-        ClipEventResult::Handled
     }
 
     fn event_dispatch(
         self,
         context: &mut UpdateContext<'_, 'gc>,
-        _event: ClipEvent<'gc>,
+        event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        match event {
+            ClipEvent::MouseMoveInside => {
+                self.update_hovered_link(context, true);
+                return ClipEventResult::Handled;
+            }
+            ClipEvent::RollOut { .. } => {
+                self.update_hovered_link(context, false);
+                return ClipEventResult::Handled;
+            }
+            _ => {}
+        }
+
         if self.is_editable() || self.is_selectable() {
             let tracker = context.focus_tracker;
             tracker.set(Some(self.into()), context);
@@ -2038,7 +2201,11 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         // We can't hold self as any link may end up modifying this object, so pull the info out
         let mut link_to_open = None;
 
-        if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
+        let mouse_position = *context.mouse_position;
+        if let Some(position) = self
+            .unproject_point(context, mouse_position)
+            .and_then(|position| self.screen_position_to_index(position))
+        {
             self.0.write(context.gc_context).selection =
                 Some(TextSelection::for_position(position));
 
@@ -2076,10 +2243,11 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         _require_button_mode: bool,
     ) -> Option<InteractiveObject<'gc>> {
         // The text is hovered if the mouse is over any child nodes.
+        let point = self.unproject_point(context, point)?;
         if self.visible()
             && self.mouse_enabled()
             && (self.is_selectable() || self.is_link_at(point))
-            && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+            && self.hit_test_bounds(point)
         {
             Some((*self).into())
         } else {
@@ -2094,7 +2262,10 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         _require_button_mode: bool,
     ) -> Avm2MousePick<'gc> {
         // The text is hovered if the mouse is over any child nodes.
-        if self.visible() && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
+        let Some(point) = self.unproject_point(context, point) else {
+            return Avm2MousePick::Miss;
+        };
+        if self.visible() && self.hit_test_bounds(point) {
             // Note - for mouse-enabled selectable text, we consider this to be a hit (which
             // will cause us to show the proper cursor on mouse over).
             // However, in `Interactive::event_dispatch_to_avm2`, we will prevent mouse events
@@ -2112,7 +2283,11 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 
     fn mouse_cursor(self, context: &mut UpdateContext<'_, 'gc>) -> MouseCursor {
-        if self.is_link_at(*context.mouse_position) {
+        let mouse_position = *context.mouse_position;
+        if self
+            .unproject_point(context, mouse_position)
+            .is_some_and(|point| self.is_link_at(point))
+        {
             MouseCursor::Hand
         } else if self.is_selectable() {
             MouseCursor::IBeam
@@ -13,6 +13,9 @@ use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::scaling_grid::ScalingGrid;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefMut};
 use std::sync::Arc;
 
@@ -36,6 +39,10 @@ pub struct GraphicData<'gc> {
     avm2_object: Option<Avm2Object<'gc>>,
     #[collect(require_static)]
     drawing: Option<Drawing>,
+    /// The shape registered for the last 9-slice scaling of the parent this was
+    /// rendered with, along with the scaling and the matrix of this object.
+    #[collect(require_static)]
+    sliced_render_handle: Option<(ScalingGrid, Matrix, ShapeHandle)>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -67,6 +74,7 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: None,
+                sliced_render_handle: None,
             },
         ))
     }
@@ -100,6 +108,7 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: Some(drawing),
+                sliced_render_handle: None,
             },
         ))
     }
@@ -109,6 +118,64 @@ impl<'gc> Graphic<'gc> {
             w.drawing.get_or_insert_with(Drawing::new)
         })
     }
+
+    /// Renders this shape through the 9-slice scaling of its parent.
+    /// Returns `false` if it must be rendered normally instead.
+    fn render_sliced(&self, context: &mut RenderContext<'_, 'gc>, grid: &ScalingGrid) -> bool {
+        let matrix = *self.base().matrix();
+        let Some(inverse) = matrix.inverse() else {
+            return false;
+        };
+
+        // The sliced shape is in the space of the parent.
+        context.transform_stack.push(&Transform {
+            matrix: inverse,
+            color_transform: Default::default(),
+        });
+        let has_drawing = if let Some(drawing) = &self.0.read().drawing {
+            drawing.render_sliced(context, grid, &matrix);
+            true
+        } else {
+            false
+        };
+        if !has_drawing {
+            let cached_handle = match &self.0.read().sliced_render_handle {
+                Some((cached_grid, cached_matrix, handle))
+                    if cached_grid == grid && *cached_matrix == matrix =>
+                {
+                    Some(handle.clone())
+                }
+                _ => None,
+            };
+            let handle = cached_handle.or_else(|| {
+                let static_data = self.0.read().static_data;
+                let handle = context
+                    .library
+                    .library_for_movie(static_data.movie.clone())
+                    .map(|library| {
+                        let mut shape = DistilledShape::from(&static_data.shape);
+                        grid.map_shape(&mut shape, &matrix);
+                        context.renderer.register_shape(
+                            shape,
+                            &MovieLibrarySource {
+                                library,
+                                gc_context: context.gc_context,
+                            },
+                        )
+                    })?;
+                self.0.write(context.gc_context).sliced_render_handle =
+                    Some((*grid, matrix, handle.clone()));
+                Some(handle)
+            });
+            if let Some(handle) = handle {
+                context
+                    .commands
+                    .render_shape(handle, context.transform_stack.transform());
+            }
+        }
+        context.transform_stack.pop();
+        true
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
@@ -181,7 +248,13 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         // Noop
     }
 
-    fn render_self(&self, context: &mut RenderContext) {
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
+        if let Some(grid) = context.scaling_grid {
+            if self.render_sliced(context, &grid) {
+                return;
+            }
+        }
+
         if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
             // Off-screen; culled
             return;
@@ -198,10 +271,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
     fn hit_test_shape(
         &self,
-        _context: &mut UpdateContext<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc>,
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        let Some(point) = self.unproject_point(context, point) else {
+            return false;
+        };
+        let point = self.unslice_point(point);
         // Transform point to local coordinates and test.
         if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
             && self.world_bounds().contains(point)
@@ -1,5 +1,8 @@
 //! Interactive object enumtrait
 
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, TObject as Avm1TObject, Value as Avm1Value,
+};
 use crate::avm2::activation::Activation as Avm2Activation;
 use crate::avm2::{Avm2, EventObject as Avm2EventObject, Value as Avm2Value};
 use crate::backend::ui::MouseCursor;
@@ -28,7 +31,7 @@ use swf::{Point, Twips};
 /// `to`.
 ///
 /// If no such common ancestor exists, this returns `None`.
-fn lowest_common_ancestor<'gc>(
+pub fn lowest_common_ancestor<'gc>(
     from: DisplayObject<'gc>,
     to: DisplayObject<'gc>,
 ) -> Option<DisplayObject<'gc>> {
@@ -62,6 +65,30 @@ fn lowest_common_ancestor<'gc>(
     hca
 }
 
+/// Look up a property of the AVM1 object of a display object.
+///
+/// This is used for properties like `tabEnabled`, which AVM1 leaves as plain
+/// properties for scripts to set, but which are still read by the player.
+/// Returns `None` if the property is undefined.
+fn get_avm1_property<'gc, T>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    name: &'static str,
+    convert: impl FnOnce(&mut Avm1Activation<'_, 'gc>, Avm1Value<'gc>) -> Option<T>,
+) -> Option<T> {
+    let Avm1Value::Object(object) = object.object() else {
+        return None;
+    };
+    let mut activation = Avm1Activation::from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[Tab Order]"),
+    );
+    match object.get(name, &mut activation) {
+        Ok(Avm1Value::Undefined) | Err(_) => None,
+        Ok(value) => convert(&mut activation, value),
+    }
+}
+
 bitflags! {
     /// Boolean state flags used by `InteractiveObject`.
     #[derive(Clone, Copy)]
@@ -89,6 +116,19 @@ pub struct InteractiveObjectBase<'gc> {
     /// display object.
     #[collect(require_static)]
     last_click: Option<Instant>,
+
+    /// The `tabEnabled` property of this object, if it was set by AVM2 code.
+    #[collect(require_static)]
+    tab_enabled: Option<bool>,
+
+    /// The `tabIndex` property of this object, if it was set by AVM2 code.
+    #[collect(require_static)]
+    tab_index: Option<i32>,
+
+    /// Whether a focus rectangle is drawn around this object when it has
+    /// keyboard focus, or `None` to follow the `stageFocusRect` of the stage.
+    #[collect(require_static)]
+    focus_rect: Option<bool>,
 }
 
 impl<'gc> Default for InteractiveObjectBase<'gc> {
@@ -98,6 +138,9 @@ impl<'gc> Default for InteractiveObjectBase<'gc> {
             flags: InteractiveObjectFlags::MOUSE_ENABLED,
             context_menu: Avm2Value::Null,
             last_click: None,
+            tab_enabled: None,
+            tab_index: None,
+            focus_rect: None,
         }
     }
 }
@@ -159,6 +202,68 @@ pub trait TInteractiveObject<'gc>:
         self.raw_interactive_mut(mc).context_menu = value;
     }
 
+    /// Whether this object is part of the tab order when `tabEnabled` is unset.
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        false
+    }
+
+    /// Whether this object can be focused with the Tab key.
+    fn tab_enabled(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let this = self.as_displayobject();
+        let tab_enabled = if this.movie().is_action_script_3() {
+            self.raw_interactive().tab_enabled
+        } else {
+            get_avm1_property(context, this, "tabEnabled", |activation, value| {
+                Some(value.as_bool(activation.swf_version()))
+            })
+        };
+        tab_enabled.unwrap_or_else(|| self.tab_enabled_default(context))
+    }
+
+    /// Set the `tabEnabled` property of this object, or `None` to use the default.
+    fn set_tab_enabled(self, mc: &Mutation<'gc>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).tab_enabled = value;
+    }
+
+    /// The explicit position of this object in the tab order, if it has one.
+    fn tab_index(self, context: &mut UpdateContext<'_, 'gc>) -> Option<i32> {
+        let this = self.as_displayobject();
+        if this.movie().is_action_script_3() {
+            self.raw_interactive().tab_index
+        } else {
+            get_avm1_property(context, this, "tabIndex", |activation, value| {
+                value.coerce_to_i32(activation).ok()
+            })
+        }
+    }
+
+    fn set_tab_index(self, mc: &Mutation<'gc>, value: Option<i32>) {
+        self.raw_interactive_mut(mc).tab_index = value;
+    }
+
+    /// Whether the children of this object are part of the tab order.
+    fn tab_children(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let this = self.as_displayobject();
+        if this.movie().is_action_script_3() {
+            this.as_container()
+                .is_some_and(|container| container.raw_container().tab_children())
+        } else {
+            this.as_container().is_some()
+                && get_avm1_property(context, this, "tabChildren", |activation, value| {
+                    Some(value.as_bool(activation.swf_version()))
+                })
+                .unwrap_or(true)
+        }
+    }
+
+    fn focus_rect(self) -> Option<bool> {
+        self.raw_interactive().focus_rect
+    }
+
+    fn set_focus_rect(self, mc: &Mutation<'gc>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).focus_rect = value;
+    }
+
     /// Filter the incoming clip event.
     ///
     /// If this returns `Handled`, then the rest of the event handling
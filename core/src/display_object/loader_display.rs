@@ -182,6 +182,7 @@ impl<'gc> TInteractiveObject<'gc> for LoaderDisplay<'gc> {
         point: Point<Twips>,
         require_button_mode: bool,
     ) -> Option<InteractiveObject<'gc>> {
+        let point = self.unproject_point(context, point)?;
         for child in self.iter_render_list().rev() {
             if let Some(int) = child.as_interactive() {
                 if let Some(result) = int.mouse_pick_avm1(context, point, require_button_mode) {
@@ -199,6 +200,9 @@ impl<'gc> TInteractiveObject<'gc> for LoaderDisplay<'gc> {
         point: Point<Twips>,
         require_button_mode: bool,
     ) -> Avm2MousePick<'gc> {
+        let Some(point) = self.unproject_point(context, point) else {
+            return Avm2MousePick::Miss;
+        };
         // We have at most one child
         if let Some(child) = self.iter_render_list().next() {
             if let Some(int) = child.as_interactive() {
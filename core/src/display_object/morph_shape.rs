@@ -155,10 +155,13 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
 
     fn hit_test_shape(
         &self,
-        _context: &mut UpdateContext<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc>,
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        let Some(point) = self.unproject_point(context, point) else {
+            return false;
+        };
         if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
             && self.world_bounds().contains(point)
         {
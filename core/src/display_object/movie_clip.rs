@@ -46,7 +46,7 @@ use std::cmp::max;
 use std::collections::HashMap;
 use std::sync::Arc;
 use swf::extensions::ReadSwfExt;
-use swf::{ClipEventFlag, FontFlag, FrameLabelData, SwfStr};
+use swf::{ClipEventFlag, FrameLabelData};
 
 use super::interactive::Avm2MousePick;
 
@@ -600,6 +600,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_morph_shape(context, reader, 2),
+                TagCode::DefineScalingGrid => self
+                    .0
+                    .write(context.gc_context)
+                    .define_scaling_grid(context, reader),
                 TagCode::DefineShape => self
                     .0
                     .write(context.gc_context)
@@ -2000,6 +2004,97 @@ impl<'gc> MovieClip<'gc> {
         self.assert_expected_tag_end(context, hit_target_frame);
     }
 
+    /// Builds a copy of this clip showing the given frame, to render another
+    /// frame of the clip without navigating it, such as when printing.
+    ///
+    /// The copy isn't on the display list and runs no scripts: only the
+    /// objects placed by the timeline are reproduced, and child clips show
+    /// their first frame.
+    pub fn snapshot_frame(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        frame: FrameNumber,
+    ) -> MovieClip<'gc> {
+        let snapshot = MovieClip::new(self.movie(), context.gc_context);
+        snapshot.0.write(context.gc_context).static_data = self.0.read().static_data;
+        snapshot.place_timeline_frame(context, frame);
+        snapshot
+    }
+
+    /// Places the objects of the timeline of this empty clip as they are on
+    /// the given frame, without running any scripts.
+    fn place_timeline_frame(self, context: &mut UpdateContext<'_, 'gc>, frame: FrameNumber) {
+        let mc = self.0.read();
+        let data = mc.static_data.swf.clone();
+        let frame = frame.min(max(mc.frames_loaded(), 0) as FrameNumber);
+        drop(mc);
+
+        // Aggregate the deltas of each frame, like a goto does.
+        let mut place_commands: Vec<GotoPlaceObject<'_>> = vec![];
+        let mut current_frame = 0;
+        let mut index = 0;
+        let mut reader = data.read_from(0);
+        while current_frame < frame && !reader.get_ref().is_empty() {
+            current_frame += 1;
+            let _ = tag_utils::decode_tags(&mut reader, |reader, tag_code, _tag_len| {
+                use swf::TagCode;
+                let place_object = match tag_code {
+                    TagCode::PlaceObject => reader.read_place_object()?,
+                    TagCode::PlaceObject2 => reader.read_place_object_2_or_3(2)?,
+                    TagCode::PlaceObject3 => reader.read_place_object_2_or_3(3)?,
+                    TagCode::PlaceObject4 => reader.read_place_object_2_or_3(4)?,
+                    TagCode::RemoveObject => {
+                        let depth = Depth::from(reader.read_remove_object_1()?.depth);
+                        place_commands.retain(|params| params.depth() != depth);
+                        return Ok(ControlFlow::Continue);
+                    }
+                    TagCode::RemoveObject2 => {
+                        let depth = Depth::from(reader.read_remove_object_2()?.depth);
+                        place_commands.retain(|params| params.depth() != depth);
+                        return Ok(ControlFlow::Continue);
+                    }
+                    TagCode::ShowFrame => return Ok(ControlFlow::Exit),
+                    _ => return Ok(ControlFlow::Continue),
+                };
+
+                index += 1;
+                let mut place =
+                    GotoPlaceObject::new(current_frame, place_object, false, index, 0, 0);
+                match place_commands
+                    .iter_mut()
+                    .find(|params| params.depth() == place.depth())
+                {
+                    Some(params) => params.merge(&mut place),
+                    None => place_commands.push(place),
+                }
+                Ok(ControlFlow::Continue)
+            });
+        }
+
+        place_commands.sort_by_key(|params| params.index);
+        let movie = self.movie();
+        for params in &place_commands {
+            let id = match params.place_object.action {
+                swf::PlaceObjectAction::Place(id) | swf::PlaceObjectAction::Replace(id) => id,
+                swf::PlaceObjectAction::Modify => continue,
+            };
+            let library = context.library.library_for_movie_mut(movie.clone());
+            let Ok(child) = library.instantiate_by_id(id, context.gc_context) else {
+                continue;
+            };
+            self.replace_at_depth(context, child, params.depth());
+            child.set_instantiated_by_timeline(context.gc_context, true);
+            child.apply_place_object(context, &params.place_object);
+            if let Some(clip_depth) = params.place_object.clip_depth {
+                child.set_clip_depth(context.gc_context, clip_depth.into());
+            }
+            if let Some(clip) = child.as_movie_clip() {
+                clip.place_timeline_frame(context, 1);
+            }
+        }
+        self.0.write(context.gc_context).current_frame = frame;
+    }
+
     fn construct_as_avm1_object(
         self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -2351,6 +2446,16 @@ impl<'gc> MovieClip<'gc> {
         RefMut::map(self.0.write(gc_context), |s| &mut s.drawing)
     }
 
+    /// Hit-tests the drawing of this clip against a point in local space,
+    /// as the drawing is rendered through the `scale9Grid` of this clip.
+    fn hit_test_drawing(&self, point: Point<Twips>, local_matrix: &Matrix) -> bool {
+        let point = match self.active_scaling_grid() {
+            Some(grid) => grid.unmap_point(point),
+            None => point,
+        };
+        self.0.read().drawing.hit_test(point, local_matrix)
+    }
+
     pub fn is_button_mode(&self, context: &mut UpdateContext<'_, 'gc>) -> bool {
         if self.forced_button_mode()
             || self
@@ -2754,7 +2859,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        self.0.read().drawing.render(context);
+        if let Some(grid) = self.active_scaling_grid() {
+            self.0
+                .read()
+                .drawing
+                .render_sliced(context, &grid, &Matrix::IDENTITY);
+        } else {
+            self.0.read().drawing.render(context);
+        }
         self.render_children(context);
     }
 
@@ -2779,12 +2891,18 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             return false;
         }
 
+        // Masks aren't part of our projection, so they're tested with the original point.
+        let stage_point = point;
+        let Some(point) = self.unproject_point(context, point) else {
+            return false;
+        };
+
         if self.world_bounds().contains(point) {
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return false;
             };
             if let Some(masker) = self.masker() {
-                if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                if !masker.hit_test_shape(context, stage_point, HitTestOptions::SKIP_INVISIBLE) {
                     return false;
                 }
             }
@@ -2810,7 +2928,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             }
 
             let point = local_matrix * point;
-            if self.0.read().drawing.hit_test(point, &local_matrix) {
+            if self.hit_test_drawing(point, &local_matrix) {
                 return true;
             }
         }
@@ -2973,6 +3091,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.is_button_mode(context)
+    }
+
     fn filter_clip_event(
         self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -3087,6 +3209,11 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 }
             }
 
+            let stage_point = point;
+            let Some(point) = self.unproject_point(context, point) else {
+                return None;
+            };
+
             // In AVM2, mouse_enabled should only impact the ability to select the current clip
             // but it should still be possible to select any children where child.mouse_enabled() is
             // true.
@@ -3100,7 +3227,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 if is_button_mode {
                     let mut options = HitTestOptions::SKIP_INVISIBLE;
                     options.set(HitTestOptions::SKIP_MASK, self.maskee().is_none());
-                    if self.hit_test_shape(context, point, options) {
+                    if self.hit_test_shape(context, stage_point, options) {
                         return Some(this);
                     }
                 }
@@ -3147,7 +3274,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.mouse_enabled() && check_non_interactive {
                 let point = local_matrix * point;
-                if self.0.read().drawing.hit_test(point, &local_matrix) {
+                if self.hit_test_drawing(point, &local_matrix) {
                     return Some(this);
                 }
             }
@@ -3174,6 +3301,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 }
             }
 
+            let Some(point) = self.unproject_point(context, point) else {
+                return Avm2MousePick::Miss;
+            };
+
             if self.maskee().is_some() {
                 // If we're masking another object, we can't be hit.
                 return Avm2MousePick::Miss;
@@ -3273,7 +3404,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
             if self.world_bounds().contains(point) {
                 let point = local_matrix * point;
 
-                if self.0.read().drawing.hit_test(point, &local_matrix) {
+                if self.hit_test_drawing(point, &local_matrix) {
                     return if self.mouse_enabled() {
                         Avm2MousePick::Hit((*self).into())
                     } else {
@@ -3698,6 +3829,39 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'a>,
+    ) -> Result<(), Error> {
+        let id = reader.read_u16()?;
+        let rect = reader.read_rectangle()?;
+        // Instances are copies of the library character, so they all get the grid.
+        match context
+            .library
+            .library_for_movie_mut(self.movie())
+            .character_by_id(id)
+        {
+            Some(Character::MovieClip(clip)) => {
+                clip.set_scaling_grid(context.gc_context, Some(rect));
+            }
+            Some(Character::Avm1Button(button)) => {
+                button.set_scaling_grid(context.gc_context, Some(rect));
+            }
+            Some(Character::Avm2Button(button)) => {
+                button.set_scaling_grid(context.gc_context, Some(rect));
+            }
+            Some(_) => {
+                tracing::warn!("DefineScalingGrid: Tried to apply on non-sprite ID {}", id);
+            }
+            None => {
+                tracing::warn!("DefineScalingGrid: Character ID {} doesn't exist", id);
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn define_button_sound(
         &mut self,
@@ -3838,23 +4002,26 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         reader: &mut SwfStream<'a>,
     ) -> Result<(), Error> {
-        tracing::warn!("DefineFont4 tag (TLF text) is not implemented");
         let font = reader.read_define_font_4()?;
         let font_id = font.id;
-        let font_object = Font::from_swf_tag(
+        let font_object = match Font::from_font4_tag(
             context.gc_context,
             context.renderer,
-            swf::Font {
-                version: 4,
-                id: font.id,
-                name: SwfStr::from_bytes(b"<Unknown Font4>"),
-                language: swf::Language::Unknown,
-                layout: None,
-                glyphs: Vec::new(),
-                flags: FontFlag::empty(),
-            },
+            font.clone(),
             reader.encoding(),
-        );
+        ) {
+            Ok(font_object) => font_object,
+            Err(e) => {
+                tracing::warn!("Unable to parse DefineFont4 font {}: {}", font_id, e);
+                // Keep the font as a placeholder without glyphs, so that it can still be referenced.
+                Font::from_font4_tag_without_glyphs(
+                    context.gc_context,
+                    context.renderer,
+                    font,
+                    reader.encoding(),
+                )
+            }
+        };
         context
             .library
             .library_for_movie_mut(self.movie())
@@ -129,6 +129,10 @@ pub struct StageData<'gc> {
     /// Whether to show default context menu items
     show_menu: bool,
 
+    /// How touch screen input is interpreted.
+    #[collect(require_static)]
+    multitouch_input_mode: MultitouchInputMode,
+
     /// The AVM2 view of this stage object.
     avm2_object: Avm2Object<'gc>,
 
@@ -178,6 +182,7 @@ impl<'gc> Stage<'gc> {
                 view_bounds: Default::default(),
                 window_mode: Default::default(),
                 show_menu: true,
+                multitouch_input_mode: Default::default(),
                 stage_focus_rect: true,
                 avm2_object: Avm2ScriptObject::custom_object(gc_context, None, None),
                 loader_info: Avm2ScriptObject::custom_object(gc_context, None, None),
@@ -296,6 +301,18 @@ impl<'gc> Stage<'gc> {
         this.stage_focus_rect = fr
     }
 
+    /// Get how touch screen input is interpreted.
+    /// Used by AVM2 `Multitouch.inputMode`.
+    pub fn multitouch_input_mode(self) -> MultitouchInputMode {
+        self.0.read().multitouch_input_mode
+    }
+
+    /// Set how touch screen input is interpreted.
+    /// Used by AVM2 `Multitouch.inputMode`.
+    pub fn set_multitouch_input_mode(self, gc_context: &Mutation<'gc>, mode: MultitouchInputMode) {
+        self.0.write(gc_context).multitouch_input_mode = mode;
+    }
+
     /// Get the size of the stage.
     /// Used by AVM1 `stage.width`/`height` and AVM2 `Stage.stageWidth`/`stageHeight` properties.
     /// If `scale_mode` is `StageScaleMode::NO_SCALE`, this returns the size of the viewport.
@@ -1033,6 +1050,49 @@ impl FromWStr for StageDisplayState {
     }
 }
 
+/// How touch screen input is interpreted.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultitouchInputMode {
+    /// Gesture events are dispatched, and simple touches are interpreted as mouse events.
+    /// Ruffle doesn't recognize any gestures yet.
+    #[default]
+    Gesture,
+
+    /// All touches are interpreted as mouse events.
+    None,
+
+    /// Touch events are dispatched for each touch point.
+    TouchPoint,
+}
+
+impl Display for MultitouchInputMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match string values returned by AS.
+        let s = match *self {
+            MultitouchInputMode::Gesture => "gesture",
+            MultitouchInputMode::None => "none",
+            MultitouchInputMode::TouchPoint => "touchPoint",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromWStr for MultitouchInputMode {
+    type Err = ParseEnumError;
+
+    fn from_wstr(s: &WStr) -> Result<Self, Self::Err> {
+        if s == WStr::from_units(b"gesture") {
+            Ok(MultitouchInputMode::Gesture)
+        } else if s == WStr::from_units(b"none") {
+            Ok(MultitouchInputMode::None)
+        } else if s == WStr::from_units(b"touchPoint") {
+            Ok(MultitouchInputMode::TouchPoint)
+        } else {
+            Err(ParseEnumError)
+        }
+    }
+}
+
 bitflags! {
     /// The alignment of the stage.
     /// This controls the position of the movie after scaling to fill the viewport.
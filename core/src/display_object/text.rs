@@ -3,7 +3,8 @@ use crate::avm2::{
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
-use crate::font::TextRenderSettings;
+use crate::font::{Font, Glyph, TextRenderSettings};
+use crate::library::Library;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -34,6 +35,62 @@ pub struct TextData<'gc> {
     #[collect(require_static)]
     render_settings: TextRenderSettings,
     avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Whether each glyph is highlighted by `TextSnapshot.setSelected`.
+    #[collect(require_static)]
+    selected_glyphs: Vec<bool>,
+
+    /// The color highlighting the selected glyphs.
+    #[collect(require_static)]
+    select_color: Color,
+}
+
+/// A glyph of static text, as exposed to scripts by `TextSnapshot`.
+#[derive(Clone, Debug)]
+pub struct TextGlyph<'gc> {
+    /// The index of the glyph in the text.
+    pub index: usize,
+
+    /// The character drawn by the glyph.
+    pub character: char,
+
+    pub font: Font<'gc>,
+    pub color: Color,
+    pub height: Twips,
+
+    /// The position of the glyph on the baseline, in the coordinate space of the text records.
+    pub x: Twips,
+    pub y: Twips,
+
+    pub advance: Twips,
+
+    /// Whether the glyph is the first of a line.
+    pub is_line_start: bool,
+}
+
+impl<'gc> TextGlyph<'gc> {
+    /// The distances from the baseline to the top and to the bottom of the glyph.
+    pub fn vertical_extent(&self) -> (Twips, Twips) {
+        let ascent = self.font.get_baseline_for_height(self.height);
+        let descent = self.font.get_descent_for_height(self.height);
+        if ascent == Twips::ZERO && descent == Twips::ZERO {
+            // Fonts without layout information don't know their ascent.
+            (self.height, Twips::ZERO)
+        } else {
+            (ascent, descent)
+        }
+    }
+
+    /// The bounds of the glyph, in the coordinate space of the text records.
+    pub fn bounds(&self) -> Rectangle<Twips> {
+        let (ascent, descent) = self.vertical_extent();
+        Rectangle {
+            x_min: self.x,
+            x_max: self.x + self.advance,
+            y_min: self.y - ascent,
+            y_max: self.y + descent,
+        }
+    }
 }
 
 impl<'gc> Text<'gc> {
@@ -58,6 +115,8 @@ impl<'gc> Text<'gc> {
                 ),
                 render_settings: Default::default(),
                 avm2_object: None,
+                selected_glyphs: Vec::new(),
+                select_color: Color::from_rgb(0xFFFF00, 255),
             },
         ))
     }
@@ -66,6 +125,99 @@ impl<'gc> Text<'gc> {
         self.0.write(gc_context).render_settings = settings;
         self.invalidate_cached_bitmap(gc_context);
     }
+
+    /// The transform of the text records, relative to this object.
+    pub fn text_transform(self) -> Matrix {
+        self.0.read().static_data.text_transform
+    }
+
+    /// Visit each glyph drawn by this text, along with its outline.
+    fn for_each_glyph(self, library: &Library<'gc>, mut f: impl FnMut(TextGlyph<'gc>, &Glyph)) {
+        let tf = self.0.read();
+        let Some(library) = library.library_for_movie(tf.static_data.swf.clone()) else {
+            return;
+        };
+
+        let mut color = Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+        let mut font_id = 0;
+        let mut height = Twips::ZERO;
+        let mut x = Twips::ZERO;
+        let mut y = Twips::ZERO;
+        let mut index = 0;
+        let mut is_line_start = true;
+        for block in &tf.static_data.text_blocks {
+            if let Some(x_offset) = block.x_offset {
+                x = x_offset;
+            }
+            if let Some(y_offset) = block.y_offset {
+                is_line_start |= y_offset != y;
+                y = y_offset;
+            }
+            color = block.color.unwrap_or(color);
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+            if let Some(font) = library.get_font(font_id) {
+                for c in &block.glyphs {
+                    if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        let text_glyph = TextGlyph {
+                            index,
+                            character: glyph.character(),
+                            font,
+                            color,
+                            height,
+                            x,
+                            y,
+                            advance: Twips::new(c.advance),
+                            is_line_start,
+                        };
+                        f(text_glyph, glyph);
+                        index += 1;
+                        is_line_start = false;
+                        x += Twips::new(c.advance);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The glyphs drawn by this text, in order.
+    pub fn glyphs(self, library: &Library<'gc>) -> Vec<TextGlyph<'gc>> {
+        let mut glyphs = Vec::new();
+        self.for_each_glyph(library, |glyph, _| glyphs.push(glyph));
+        glyphs
+    }
+
+    pub fn is_glyph_selected(self, index: usize) -> bool {
+        self.0
+            .read()
+            .selected_glyphs
+            .get(index)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    pub fn set_glyph_selected(self, gc_context: &Mutation<'gc>, index: usize, selected: bool) {
+        let mut write = self.0.write(gc_context);
+        if write.selected_glyphs.len() <= index {
+            if !selected {
+                return;
+            }
+            write.selected_glyphs.resize(index + 1, false);
+        }
+        write.selected_glyphs[index] = selected;
+        drop(write);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    pub fn set_select_color(self, gc_context: &Mutation<'gc>, color: Color) {
+        self.0.write(gc_context).select_color = color;
+        self.invalidate_cached_bitmap(gc_context);
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Text<'gc> {
@@ -111,54 +263,48 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        let tf = self.0.read();
+        let (text_transform, select_color) = {
+            let tf = self.0.read();
+            (tf.static_data.text_transform, tf.select_color)
+        };
         context.transform_stack.push(&Transform {
-            matrix: tf.static_data.text_transform,
+            matrix: text_transform,
             ..Default::default()
         });
 
-        let mut color = swf::Color {
-            r: 0,
-            g: 0,
-            b: 0,
-            a: 0,
-        };
-        let mut font_id = 0;
-        let mut height = Twips::ZERO;
-        let mut transform: Transform = Default::default();
-        for block in &tf.static_data.text_blocks {
-            if let Some(x) = block.x_offset {
-                transform.matrix.tx = x;
-            }
-            if let Some(y) = block.y_offset {
-                transform.matrix.ty = y;
+        self.for_each_glyph(context.library, |text_glyph, glyph| {
+            if self.is_glyph_selected(text_glyph.index) {
+                let bounds = text_glyph.bounds();
+                let highlight = context.transform_stack.transform().matrix
+                    * Matrix::create_box(
+                        bounds.width().to_pixels() as f32,
+                        bounds.height().to_pixels() as f32,
+                        0.0,
+                        bounds.x_min,
+                        bounds.y_min,
+                    );
+                context.commands.draw_rect(select_color, highlight);
             }
-            color = block.color.unwrap_or(color);
-            font_id = block.font_id.unwrap_or(font_id);
-            height = block.height.unwrap_or(height);
-            if let Some(font) = context
-                .library
-                .library_for_movie(self.movie())
-                .unwrap()
-                .get_font(font_id)
-            {
-                let scale = (height.get() as f32) / font.scale();
-                transform.matrix.a = scale;
-                transform.matrix.d = scale;
-                transform.color_transform.set_mult_color(&color);
-                for c in &block.glyphs {
-                    if let Some(glyph) = font.get_glyph(c.index as usize) {
-                        context.transform_stack.push(&transform);
-                        let glyph_shape_handle = glyph.shape_handle(context.renderer);
-                        context
-                            .commands
-                            .render_shape(glyph_shape_handle, context.transform_stack.transform());
-                        context.transform_stack.pop();
-                        transform.matrix.tx += Twips::new(c.advance);
-                    }
-                }
-            }
-        }
+
+            let scale = (text_glyph.height.get() as f32) / text_glyph.font.scale();
+            let mut transform = Transform {
+                matrix: Matrix {
+                    a: scale,
+                    d: scale,
+                    tx: text_glyph.x,
+                    ty: text_glyph.y,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            transform.color_transform.set_mult_color(&text_glyph.color);
+            context.transform_stack.push(&transform);
+            let glyph_shape_handle = glyph.shape_handle(context.renderer);
+            context
+                .commands
+                .render_shape(glyph_shape_handle, context.transform_stack.transform());
+            context.transform_stack.pop();
+        });
         context.transform_stack.pop();
     }
 
@@ -169,9 +315,12 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
     fn hit_test_shape(
         &self,
         context: &mut UpdateContext<'_, 'gc>,
-        mut point: Point<Twips>,
+        point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        let Some(mut point) = self.unproject_point(context, point) else {
+            return false;
+        };
         if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
             && self.world_bounds().contains(point)
         {
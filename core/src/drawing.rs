@@ -2,6 +2,8 @@ use crate::context::RenderContext;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::scaling_grid::ScalingGrid;
 use ruffle_render::shape_utils::{
     cubic_curve_bounds, quadratic_curve_bounds, DistilledShape, DrawCommand, DrawPath, FillRule,
 };
@@ -11,6 +13,9 @@ use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 #[derive(Clone, Debug)]
 pub struct Drawing {
     render_handle: RefCell<Option<ShapeHandle>>,
+    /// The shape registered for the last 9-slice scaling this drawing was rendered with,
+    /// along with the scaling and the matrix to the sliced object.
+    sliced_render_handle: RefCell<Option<(ScalingGrid, Matrix, ShapeHandle)>>,
     shape_bounds: Rectangle<Twips>,
     edge_bounds: Rectangle<Twips>,
     dirty: Cell<bool>,
@@ -34,6 +39,7 @@ impl Drawing {
     pub fn new() -> Self {
         Self {
             render_handle: RefCell::new(None),
+            sliced_render_handle: RefCell::new(None),
             shape_bounds: Default::default(),
             edge_bounds: Default::default(),
             dirty: Cell::new(false),
@@ -51,6 +57,7 @@ impl Drawing {
     pub fn from_swf_shape(shape: &swf::Shape) -> Self {
         let mut this = Self {
             render_handle: RefCell::new(None),
+            sliced_render_handle: RefCell::new(None),
             shape_bounds: shape.shape_bounds.clone(),
             edge_bounds: shape.edge_bounds.clone(),
             dirty: Cell::new(true),
@@ -107,6 +114,7 @@ impl Drawing {
     pub fn copy_from(&mut self, other: &Drawing) {
         *self = Drawing {
             render_handle: RefCell::new(None),
+            sliced_render_handle: RefCell::new(None),
             dirty: Cell::new(true),
             shape_bounds: other.shape_bounds.clone(),
             edge_bounds: other.edge_bounds.clone(),
@@ -239,77 +247,89 @@ impl Drawing {
         id
     }
 
-    pub fn render(&self, context: &mut RenderContext) {
-        if self.dirty.get() {
-            self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: FillRule::EvenOdd,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
-                }
-            }
+    fn distilled_shape(&self) -> DistilledShape<'_> {
+        let mut paths = Vec::with_capacity(self.paths.len());
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: FillRule::EvenOdd,
-                })
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
+                        winding_rule: FillRule::EvenOdd,
+                    });
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
+                }
             }
+        }
 
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: FillRule::EvenOdd,
+            })
+        }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
 
-            let shape = DistilledShape {
-                paths,
-                shape_bounds: self.shape_bounds.clone(),
-                edge_bounds: self.edge_bounds.clone(),
-                id: 0,
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
             };
-            self.render_handle
-                .replace(Some(context.renderer.register_shape(shape, self)));
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        DistilledShape {
+            paths,
+            shape_bounds: self.shape_bounds.clone(),
+            edge_bounds: self.edge_bounds.clone(),
+            id: 0,
+        }
+    }
+
+    /// Registers the shape of this drawing again if it changed since it was last rendered.
+    fn update_render_handle(&self, context: &mut RenderContext) {
+        if self.dirty.get() {
+            self.dirty.set(false);
+            self.sliced_render_handle.replace(None);
+            self.render_handle.replace(Some(
+                context
+                    .renderer
+                    .register_shape(self.distilled_shape(), self),
+            ));
         }
+    }
+
+    pub fn render(&self, context: &mut RenderContext) {
+        self.update_render_handle(context);
 
         if let Some(handle) = self.render_handle.borrow().to_owned() {
             context
@@ -318,6 +338,35 @@ impl Drawing {
         }
     }
 
+    /// Renders this drawing through the 9-slice scaling of the object it is in,
+    /// `matrix` being the transform from this drawing to that object.
+    /// The current transform must be the one of that object.
+    pub fn render_sliced(&self, context: &mut RenderContext, grid: &ScalingGrid, matrix: &Matrix) {
+        self.update_render_handle(context);
+        if self.render_handle.borrow().is_none() {
+            return;
+        }
+
+        let mut sliced_render_handle = self.sliced_render_handle.borrow_mut();
+        let handle = match &*sliced_render_handle {
+            Some((cached_grid, cached_matrix, handle))
+                if cached_grid == grid && cached_matrix == matrix =>
+            {
+                handle.clone()
+            }
+            _ => {
+                let mut shape = self.distilled_shape();
+                grid.map_shape(&mut shape, matrix);
+                let handle = context.renderer.register_shape(shape, self);
+                *sliced_render_handle = Some((*grid, *matrix, handle.clone()));
+                handle
+            }
+        };
+        context
+            .commands
+            .render_shape(handle, context.transform_stack.transform());
+    }
+
     pub fn self_bounds(&self) -> &Rectangle<Twips> {
         &self.shape_bounds
     }
@@ -36,6 +36,25 @@ pub enum PlayerEvent {
     TextControl {
         code: TextControlCode,
     },
+
+    /// A new point of contact was made with a touch screen.
+    ///
+    /// `id` identifies the touch point until it ends, and may be reused afterwards.
+    TouchBegin {
+        id: u64,
+        x: f64,
+        y: f64,
+    },
+    TouchMove {
+        id: u64,
+        x: f64,
+        y: f64,
+    },
+    TouchEnd {
+        id: u64,
+        x: f64,
+        y: f64,
+    },
 }
 
 /// The distance scrolled by the mouse wheel.
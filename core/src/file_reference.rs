@@ -0,0 +1,342 @@
+//! Shared logic of the AVM1 and AVM2 `FileReference` classes.
+
+use crate::avm1::globals::file_reference as avm1_file_reference;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, Object as Avm1Object,
+    TObject as Avm1TObject,
+};
+use crate::avm2::globals::flash::net::file_reference as avm2_file_reference;
+use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object};
+use crate::backend::file_dialog::SelectedFile;
+use crate::backend::navigator::Request;
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+
+/// The boundary between the parts of an upload.
+///
+/// This matches the boundary used by Flash Player.
+const MULTIPART_BOUNDARY: &str = "----------Ij5ae0ae0KM7GI3KM7ei4cH2ei4gL6";
+
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub enum FileReferenceKind<'gc> {
+    Avm2(Avm2Object<'gc>),
+    Avm1(Avm1Object<'gc>),
+}
+
+/// Where the contents of a file saved by a `FileReference` come from.
+pub enum FileSaveSource {
+    /// The data given to `FileReference.save`.
+    Data(Vec<u8>),
+
+    /// The file fetched by `FileReference.download`.
+    Download(Request),
+}
+
+/// The progress of a `FileReference` operation, reported to scripts.
+#[derive(Debug, Clone, Copy)]
+pub enum FileReferenceEvent<'a> {
+    /// A file was picked in a dialog.
+    Select,
+
+    /// The dialog was dismissed.
+    Cancel,
+
+    /// The transfer of the file started.
+    Open,
+
+    Progress {
+        bytes_loaded: usize,
+        bytes_total: usize,
+    },
+
+    /// The transfer of the file succeeded.
+    Complete,
+
+    /// The server replied to an upload or download with this status.
+    HttpStatus(u16),
+
+    /// The transfer of the file failed.
+    IoError,
+
+    /// The server replied to an upload with this body.
+    UploadCompleteData(&'a [u8]),
+}
+
+impl<'gc> FileReferenceKind<'gc> {
+    pub fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Avm2(a), Self::Avm2(b)) => Avm2Object::ptr_eq(a, b),
+            (Self::Avm1(a), Self::Avm1(b)) => a.as_ptr() == b.as_ptr(),
+            _ => false,
+        }
+    }
+
+    /// Remember the file picked in a dialog, exposing its properties to scripts.
+    pub fn set_file(self, context: &mut UpdateContext<'_, 'gc>, file: SelectedFile) {
+        match self {
+            FileReferenceKind::Avm2(target) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                if let Err(e) = avm2_file_reference::set_file(&mut activation, target, file) {
+                    tracing::error!("Unable to set the file of a FileReference: {:?}", e);
+                }
+            }
+            FileReferenceKind::Avm1(target) => {
+                if let Some(file_reference) =
+                    avm1_file_reference::FileReference::cast(target.into())
+                {
+                    file_reference.set_file(file);
+                }
+            }
+        }
+    }
+
+    /// Expose the contents of the picked file with the `data` property, after a `load`.
+    ///
+    /// This is only supported by AVM2.
+    pub fn set_loaded(self, context: &mut UpdateContext<'_, 'gc>) {
+        if let FileReferenceKind::Avm2(target) = self {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Err(e) = avm2_file_reference::set_loaded(&mut activation, target) {
+                tracing::error!("Unable to load the file of a FileReference: {:?}", e);
+            }
+        }
+    }
+
+    /// Report progress to the scripts listening to this object.
+    pub fn dispatch(self, context: &mut UpdateContext<'_, 'gc>, event: FileReferenceEvent<'_>) {
+        match self {
+            FileReferenceKind::Avm2(target) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                if let Err(e) = avm2_file_reference::dispatch(&mut activation, target, event) {
+                    tracing::error!("Unhandled error dispatching FileReference event: {:?}", e);
+                }
+            }
+            FileReferenceKind::Avm1(target) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    ActivationIdentifier::root("[FileReference]"),
+                );
+                avm1_file_reference::broadcast(&mut activation, target, event);
+            }
+        }
+    }
+}
+
+/// Build the body of an upload, as a `multipart/form-data` form.
+///
+/// Returns the body and its content type.
+pub fn multipart_body(
+    variables: &[(String, String)],
+    field_name: &str,
+    file: &SelectedFile,
+) -> (Vec<u8>, String) {
+    fn write_field(body: &mut Vec<u8>, name: &str, value: &str) {
+        body.extend(format!("--{MULTIPART_BOUNDARY}\r\n").as_bytes());
+        body.extend(format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes());
+        body.extend(value.as_bytes());
+        body.extend(b"\r\n");
+    }
+
+    let mut body = Vec::new();
+    write_field(&mut body, "Filename", &file.name);
+    for (name, value) in variables {
+        write_field(&mut body, name, value);
+    }
+
+    body.extend(format!("--{MULTIPART_BOUNDARY}\r\n").as_bytes());
+    body.extend(
+        format!(
+            "Content-Disposition: form-data; name=\"{field_name}\"; filename=\"{}\"\r\n",
+            file.name
+        )
+        .as_bytes(),
+    );
+    body.extend(b"Content-Type: application/octet-stream\r\n\r\n");
+    body.extend(&file.data);
+    body.extend(b"\r\n");
+
+    write_field(&mut body, "Upload", "Submit Query");
+    body.extend(format!("--{MULTIPART_BOUNDARY}--\r\n").as_bytes());
+
+    let content_type = format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}");
+    (body, content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::native_function;
+    use crate::avm2::{Error as Avm2Error, TObject as _, Value as Avm2Value};
+    use crate::backend::file_dialog::MemoryFileDialogBackend;
+    use crate::backend::navigator::{
+        ErrorResponse, NavigationMethod, NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture,
+        SuccessResponse,
+    };
+    use crate::loader::Error;
+    use crate::player::PlayerBuilder;
+    use crate::socket::{ConnectionState, SocketAction, SocketHandle};
+    use crate::tag_utils::SwfMovie;
+    use async_channel::Receiver;
+    use indexmap::IndexMap;
+    use std::cell::RefCell;
+    use std::sync::mpsc::Sender;
+    use std::time::Duration;
+    use url::{ParseError, Url};
+
+    /// A navigator answering every request with a 404.
+    struct NotFoundNavigator(NullSpawner);
+
+    impl NavigatorBackend for NotFoundNavigator {
+        fn navigate_to_url(
+            &self,
+            _url: &str,
+            _target: &str,
+            _vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+        ) {
+        }
+
+        fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+            let response = ErrorResponse {
+                url: request.url().to_string(),
+                error: Error::HttpNotOk("Not Found".to_string(), 404, false),
+            };
+            Box::pin(async move { Err(response) })
+        }
+
+        fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+            Url::parse(url)
+        }
+
+        fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+            self.0.spawn_local(future);
+        }
+
+        fn pre_process_url(&self, url: Url) -> Url {
+            url
+        }
+
+        fn connect_socket(
+            &mut self,
+            _host: String,
+            _port: u16,
+            _timeout: Duration,
+            handle: SocketHandle,
+            _receiver: Receiver<Vec<u8>>,
+            sender: Sender<SocketAction>,
+        ) {
+            sender
+                .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+        }
+    }
+
+    thread_local! {
+        static EVENTS: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    }
+
+    fn record_event<'gc>(
+        _activation: &mut Avm2Activation<'_, 'gc>,
+        _this: Avm2Object<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        let event = args
+            .first()
+            .and_then(|event| event.as_object())
+            .expect("Listener should receive an event");
+        let event_type = event.as_event().unwrap().event_type().to_string();
+        EVENTS.with(|events| events.borrow_mut().push(event_type));
+        Ok(Avm2Value::Undefined)
+    }
+
+    fn construct<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        class_name: &'static str,
+        args: &[Avm2Value<'gc>],
+    ) -> Avm2Object<'gc> {
+        let class = activation
+            .avm2()
+            .stage_domain()
+            .get_defined_value_handling_vector(activation, class_name.into())
+            .unwrap();
+        class
+            .as_object()
+            .expect("Class should be an object")
+            .construct(activation, args)
+            .unwrap()
+    }
+
+    #[test]
+    fn download_http_error() {
+        let mut executor = NullExecutor::new();
+        let file_dialog = MemoryFileDialogBackend::new();
+        file_dialog.push_save_response(Some("level.txt".to_string()));
+        let player = PlayerBuilder::new()
+            .with_navigator(NotFoundNavigator(executor.spawner()))
+            .with_file_dialog(file_dialog.clone())
+            .with_movie(SwfMovie::empty(10))
+            .build();
+
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let file_reference = construct(&mut activation, "flash.net.FileReference", &[]);
+                let listener = native_function(&mut activation, record_event, "listener");
+                for event_type in ["select", "open", "httpStatus", "ioError", "complete"] {
+                    file_reference
+                        .call_public_property(
+                            "addEventListener",
+                            &[event_type.into(), listener.into()],
+                            &mut activation,
+                        )
+                        .unwrap();
+                }
+
+                let request = construct(
+                    &mut activation,
+                    "flash.net.URLRequest",
+                    &["http://example.com/level.txt".into()],
+                );
+                file_reference
+                    .call_public_property("download", &[request.into()], &mut activation)
+                    .unwrap();
+            });
+        executor.run();
+
+        let events = EVENTS.with(|events| events.take());
+        assert_eq!(events, ["select", "open", "httpStatus", "ioError"]);
+        assert!(file_dialog.saved_files().is_empty());
+    }
+
+    #[test]
+    fn multipart_upload() {
+        let file = SelectedFile::new("level.txt", b"hello".to_vec());
+        let variables = vec![("id".to_string(), "5".to_string())];
+        let (body, content_type) = multipart_body(&variables, "Filedata", &file);
+
+        assert_eq!(
+            content_type,
+            format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}")
+        );
+        let expected = format!(
+            "--{b}\r\n\
+             Content-Disposition: form-data; name=\"Filename\"\r\n\r\n\
+             level.txt\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"id\"\r\n\r\n\
+             5\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"Filedata\"; filename=\"level.txt\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n\
+             hello\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"Upload\"\r\n\r\n\
+             Submit Query\r\n\
+             --{b}--\r\n",
+            b = MULTIPART_BOUNDARY
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+    }
+}
@@ -1,23 +1,52 @@
 use crate::avm1::Avm1;
 use crate::avm1::Value;
-use crate::context::UpdateContext;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Value as Avm2Value,
+};
+use crate::context::{RenderContext, UpdateContext};
 pub use crate::display_object::{
     DisplayObject, TDisplayObject, TDisplayObjectContainer, TextSelection,
 };
-use gc_arena::lock::GcLock;
-use gc_arena::{Collect, Mutation};
+use crate::display_object::{InteractiveObject, TInteractiveObject};
+use crate::events::{ClipEvent, KeyCode};
+use gc_arena::{Collect, GcCell, Mutation};
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use swf::{Color, Rectangle, Twips};
+
+/// The color of the rectangle drawn around an object focused with the keyboard.
+const HIGHLIGHT_COLOR: Color = Color::from_rgb(0xFFFF00, 255);
+
+/// The thickness of the rectangle drawn around an object focused with the keyboard, in pixels.
+const HIGHLIGHT_THICKNESS: f32 = 2.0;
 
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
-pub struct FocusTracker<'gc>(GcLock<'gc, Option<DisplayObject<'gc>>>);
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct FocusTrackerData<'gc> {
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether the focus was moved with the keyboard, in which case it's highlighted
+    /// until the focus changes or the mouse is pressed.
+    highlight: bool,
+}
 
 impl<'gc> FocusTracker<'gc> {
     pub fn new(mc: &Mutation<'gc>) -> Self {
-        Self(GcLock::new(mc, None.into()))
+        Self(GcCell::new(
+            mc,
+            FocusTrackerData {
+                focus: None,
+                highlight: false,
+            },
+        ))
     }
 
     pub fn get(&self) -> Option<DisplayObject<'gc>> {
-        self.0.get()
+        self.0.read().focus
     }
 
     pub fn set(
@@ -25,11 +54,14 @@ impl<'gc> FocusTracker<'gc> {
         focused_element: Option<DisplayObject<'gc>>,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        let old = self.0.get();
+        let old = self.0.read().focus;
 
         // Check if the focused element changed.
         if old.map(|o| o.as_ptr()) != focused_element.map(|o| o.as_ptr()) {
-            self.0.set(context.gc(), focused_element);
+            let mut write = self.0.write(context.gc());
+            write.focus = focused_element;
+            write.highlight = false;
+            drop(write);
 
             if let Some(old) = old {
                 old.on_focus_changed(context.gc(), false);
@@ -66,4 +98,445 @@ impl<'gc> FocusTracker<'gc> {
             }
         }
     }
+
+    /// Whether the focus was moved with the keyboard and is highlighted.
+    pub fn is_highlighted(&self) -> bool {
+        self.0.read().highlight
+    }
+
+    /// Stop highlighting the focused object, for example when the mouse is pressed.
+    pub fn reset_highlight(&self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc());
+        if write.highlight {
+            write.highlight = false;
+            *context.needs_render = true;
+        }
+    }
+
+    /// Move the focus to the next object in the tab order, or to the previous one
+    /// if `reverse` is set, as done by pressing Tab or Shift+Tab.
+    ///
+    /// In AVM2, a cancelable `keyFocusChange` event is dispatched first.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc>, reverse: bool) {
+        let tab_order = Self::tab_order(context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let current = self.get();
+        let position = current.and_then(|current| {
+            tab_order
+                .iter()
+                .position(|o| DisplayObject::ptr_eq(o.as_displayobject(), current))
+        });
+        let len = tab_order.len();
+        let next = match (position, reverse) {
+            (Some(i), false) => tab_order[(i + 1) % len],
+            (Some(i), true) => tab_order[(i + len - 1) % len],
+            (None, false) => tab_order[0],
+            (None, true) => tab_order[len - 1],
+        };
+
+        if !Self::dispatch_key_focus_change(context, current, next) {
+            return;
+        }
+
+        self.set(Some(next.as_displayobject()), context);
+        self.0.write(context.gc()).highlight = true;
+        *context.needs_render = true;
+    }
+
+    /// Click the focused object if it's a button, as done by pressing Enter or Space
+    /// after focusing it with the keyboard.
+    pub fn click_focused(&self, context: &mut UpdateContext<'_, 'gc>) {
+        if !self.is_highlighted() {
+            return;
+        }
+        let Some(focus) = self.get() else {
+            return;
+        };
+        let Some(interactive) = focus.as_interactive() else {
+            return;
+        };
+        let is_button = focus.as_avm1_button().is_some()
+            || focus.as_avm2_button().is_some()
+            || focus
+                .as_movie_clip()
+                .is_some_and(|mc| mc.is_button_mode(context));
+        if !is_button {
+            return;
+        }
+
+        for event in [
+            ClipEvent::Press,
+            ClipEvent::MouseUpInside,
+            ClipEvent::Release,
+        ] {
+            if focus.avm1_removed() {
+                break;
+            }
+            interactive.handle_clip_event(context, event);
+            if context.is_action_script_3() {
+                interactive.event_dispatch_to_avm2(context, event);
+            }
+        }
+        *context.needs_render = true;
+    }
+
+    /// Draw a rectangle around the focused object if it was focused with the keyboard.
+    pub fn render_highlight(&self, context: &mut RenderContext<'_, 'gc>) {
+        if !self.is_highlighted() {
+            return;
+        }
+        let Some(focus) = self.get() else {
+            return;
+        };
+        let Some(interactive) = focus.as_interactive() else {
+            return;
+        };
+        // Text fields show their cursor instead.
+        if focus.as_edit_text().is_some() {
+            return;
+        }
+        if !interactive
+            .focus_rect()
+            .unwrap_or_else(|| context.stage.stage_focus_rect())
+        {
+            return;
+        }
+
+        let world_matrix = context.stage.view_matrix() * *context.stage.base().matrix();
+        let bounds = world_matrix * focus.world_bounds();
+        draw_highlight_rect(context, bounds);
+    }
+
+    /// The objects that can be focused with the Tab key, in the order they are visited.
+    ///
+    /// Objects with an explicit `tabIndex` come first, ordered by it. They are
+    /// followed by the other objects, ordered by their position on the stage, from
+    /// left to right and from top to bottom.
+    fn tab_order(context: &mut UpdateContext<'_, 'gc>) -> Vec<InteractiveObject<'gc>> {
+        let mut tab_order = Vec::new();
+        let stage: InteractiveObject<'gc> = context.stage.into();
+        Self::fill_tab_order(&mut tab_order, stage, context);
+
+        let (mut indexed, mut unindexed): (Vec<_>, Vec<_>) = tab_order
+            .into_iter()
+            .partition(|(_, tab_index)| tab_index.is_some());
+        // The sort is stable, so objects with the same `tabIndex` stay in render order.
+        indexed.sort_by_key(|(_, tab_index)| *tab_index);
+        unindexed.sort_by_key(|(object, _)| {
+            let bounds = object.as_displayobject().world_bounds();
+            (bounds.y_min, bounds.x_min)
+        });
+
+        indexed
+            .into_iter()
+            .chain(unindexed)
+            .map(|(object, _)| object)
+            .collect()
+    }
+
+    fn fill_tab_order(
+        tab_order: &mut Vec<(InteractiveObject<'gc>, Option<i32>)>,
+        parent: InteractiveObject<'gc>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        if !parent.tab_children(context) {
+            return;
+        }
+        let Some(container) = parent.as_displayobject().as_container() else {
+            return;
+        };
+
+        for child in container.iter_render_list() {
+            if !child.visible() || child.avm1_removed() {
+                continue;
+            }
+            let Some(child) = child.as_interactive() else {
+                continue;
+            };
+            if child.tab_enabled(context) {
+                let tab_index = child.tab_index(context);
+                tab_order.push((child, tab_index));
+            }
+            Self::fill_tab_order(tab_order, child, context);
+        }
+    }
+
+    /// Dispatch `keyFocusChange` on the focused object, or on the stage if nothing
+    /// is focused. Returns `false` if the event was canceled.
+    fn dispatch_key_focus_change(
+        context: &mut UpdateContext<'_, 'gc>,
+        current: Option<DisplayObject<'gc>>,
+        next: InteractiveObject<'gc>,
+    ) -> bool {
+        let target = current.unwrap_or_else(|| context.stage.into());
+        let Avm2Value::Object(target) = target.object2() else {
+            return true;
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let event = Avm2EventObject::focus_event(
+            &mut activation,
+            "keyFocusChange",
+            true,
+            Some(next),
+            KeyCode::Tab as u32,
+        );
+        Avm2::dispatch_event(&mut activation.context, event, target);
+
+        !event.as_event().is_some_and(|event| event.is_cancelled())
+    }
+}
+
+fn draw_highlight_rect(context: &mut RenderContext<'_, '_>, bounds: Rectangle<Twips>) {
+    let width = bounds.width().to_pixels() as f32;
+    let height = bounds.height().to_pixels() as f32;
+    let thickness = HIGHLIGHT_THICKNESS;
+    let thickness_twips = Twips::from_pixels(thickness as f64);
+
+    // Top
+    context.commands.draw_rect(
+        HIGHLIGHT_COLOR,
+        Matrix::create_box(
+            width + 2.0 * thickness,
+            thickness,
+            0.0,
+            bounds.x_min - thickness_twips,
+            bounds.y_min - thickness_twips,
+        ),
+    );
+    // Bottom
+    context.commands.draw_rect(
+        HIGHLIGHT_COLOR,
+        Matrix::create_box(
+            width + 2.0 * thickness,
+            thickness,
+            0.0,
+            bounds.x_min - thickness_twips,
+            bounds.y_max,
+        ),
+    );
+    // Left
+    context.commands.draw_rect(
+        HIGHLIGHT_COLOR,
+        Matrix::create_box(
+            thickness,
+            height,
+            0.0,
+            bounds.x_min - thickness_twips,
+            bounds.y_min,
+        ),
+    );
+    // Right
+    context.commands.draw_rect(
+        HIGHLIGHT_COLOR,
+        Matrix::create_box(thickness, height, 0.0, bounds.x_max, bounds.y_min),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier, TObject as _};
+    use crate::avm2::test_utils::native_function;
+    use crate::avm2::{Error as Avm2Error, Object as Avm2Object, TObject as _};
+    use crate::display_object::{Instantiator, MovieClip};
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use ruffle_render::shape_utils::DrawCommand;
+    use swf::Point;
+
+    /// Runs a test with clips of 10x10 pixels placed at the given positions on the root of
+    /// an AVM1 movie.
+    fn with_clips<F>(positions: &[(f64, f64)], test: F)
+    where
+        F: for<'gc> FnOnce(&mut UpdateContext<'_, 'gc>, Vec<MovieClip<'gc>>),
+    {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| {
+            let root = context
+                .stage
+                .root_clip()
+                .and_then(|root| root.as_movie_clip())
+                .expect("Root should exist for freshly made movie");
+            let clips = positions
+                .iter()
+                .zip(1..)
+                .map(|(&(x, y), depth)| add_clip(context, root, depth, x, y))
+                .collect();
+            test(context, clips);
+        })
+    }
+
+    fn add_clip<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        parent: MovieClip<'gc>,
+        depth: i32,
+        x: f64,
+        y: f64,
+    ) -> MovieClip<'gc> {
+        let clip = MovieClip::new(parent.movie(), context.gc_context);
+        clip.set_x(context.gc_context, Twips::from_pixels(x));
+        clip.set_y(context.gc_context, Twips::from_pixels(y));
+        clip.drawing(context.gc_context)
+            .draw_command(DrawCommand::LineTo(Point::from_pixels(10.0, 10.0)));
+        parent.replace_at_depth(context, clip.into(), depth);
+        clip.post_instantiation(context, None, Instantiator::Avm1, false);
+        clip
+    }
+
+    fn set_property<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        clip: MovieClip<'gc>,
+        name: &'static str,
+        value: Value<'gc>,
+    ) {
+        let Value::Object(object) = clip.object() else {
+            panic!("Clip should have an AVM1 object");
+        };
+        let mut activation =
+            Avm1Activation::from_stub(context.reborrow(), ActivationIdentifier::root("[Test]"));
+        object.set(name, value, &mut activation).unwrap();
+    }
+
+    fn assert_tab_order<'gc>(context: &mut UpdateContext<'_, 'gc>, expected: &[MovieClip<'gc>]) {
+        let tab_order: Vec<_> = FocusTracker::tab_order(context)
+            .into_iter()
+            .map(|object| object.as_displayobject().as_ptr())
+            .collect();
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|&clip| DisplayObject::from(clip).as_ptr())
+            .collect();
+        assert_eq!(tab_order, expected);
+    }
+
+    fn assert_focus<'gc>(focus_tracker: FocusTracker<'gc>, expected: MovieClip<'gc>) {
+        let focus = focus_tracker.get().expect("An object should be focused");
+        assert!(DisplayObject::ptr_eq(focus, expected.into()));
+    }
+
+    fn prevent_default<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        _this: Avm2Object<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        let event = args
+            .first()
+            .and_then(|event| event.as_object())
+            .expect("Listener should receive an event");
+        if let Some(mut event) = event.as_event_mut(activation.context.gc_context) {
+            event.cancel();
+        }
+        Ok(Avm2Value::Undefined)
+    }
+
+    #[test]
+    fn tab_order_by_position() {
+        with_clips(
+            &[(50.0, 0.0), (0.0, 0.0), (0.0, 50.0), (100.0, 100.0)],
+            |context, clips| {
+                for &clip in &clips[..3] {
+                    set_property(context, clip, "tabEnabled", true.into());
+                }
+                assert_tab_order(context, &[clips[1], clips[0], clips[2]]);
+            },
+        );
+    }
+
+    #[test]
+    fn tab_order_by_tab_index() {
+        with_clips(
+            &[(0.0, 0.0), (50.0, 0.0), (100.0, 0.0), (150.0, 0.0)],
+            |context, clips| {
+                for &clip in &clips {
+                    set_property(context, clip, "tabEnabled", true.into());
+                }
+                set_property(context, clips[0], "tabIndex", 2.into());
+                set_property(context, clips[1], "tabIndex", 1.into());
+                set_property(context, clips[3], "tabIndex", 2.into());
+
+                // Objects without a `tabIndex` come last, and objects with the same
+                // `tabIndex` stay in render order.
+                assert_tab_order(context, &[clips[1], clips[0], clips[3], clips[2]]);
+            },
+        );
+    }
+
+    #[test]
+    fn tab_order_without_tab_index_by_position() {
+        with_clips(
+            &[(100.0, 0.0), (0.0, 50.0), (0.0, 0.0), (200.0, 0.0)],
+            |context, clips| {
+                for &clip in &clips {
+                    set_property(context, clip, "tabEnabled", true.into());
+                }
+                set_property(context, clips[3], "tabIndex", 1.into());
+
+                assert_tab_order(context, &[clips[3], clips[2], clips[0], clips[1]]);
+            },
+        );
+    }
+
+    #[test]
+    fn tab_order_without_tab_children() {
+        with_clips(&[(0.0, 0.0), (50.0, 0.0)], |context, clips| {
+            let child = add_clip(context, clips[0], 1, 0.0, 50.0);
+            set_property(context, clips[1], "tabEnabled", true.into());
+            set_property(context, child, "tabEnabled", true.into());
+            assert_tab_order(context, &[clips[1], child]);
+
+            set_property(context, clips[0], "tabChildren", false.into());
+            assert_tab_order(context, &[clips[1]]);
+        });
+    }
+
+    #[test]
+    fn cycle_follows_tab_order() {
+        with_clips(&[(0.0, 0.0), (50.0, 0.0)], |context, clips| {
+            for &clip in &clips {
+                set_property(context, clip, "tabEnabled", true.into());
+            }
+            let focus_tracker = context.focus_tracker;
+
+            focus_tracker.cycle(context, false);
+            assert_focus(focus_tracker, clips[0]);
+            assert!(focus_tracker.is_highlighted());
+            focus_tracker.cycle(context, false);
+            assert_focus(focus_tracker, clips[1]);
+            focus_tracker.cycle(context, false);
+            assert_focus(focus_tracker, clips[0]);
+            focus_tracker.cycle(context, true);
+            assert_focus(focus_tracker, clips[1]);
+        });
+    }
+
+    #[test]
+    fn prevented_key_focus_change_keeps_focus() {
+        with_clips(&[(0.0, 0.0)], |context, clips| {
+            set_property(context, clips[0], "tabEnabled", true.into());
+            let Avm2Value::Object(stage) = context.stage.object2() else {
+                panic!("Stage should have an AVM2 object");
+            };
+            {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let listener = native_function(&mut activation, prevent_default, "preventDefault");
+                stage
+                    .call_public_property(
+                        "addEventListener",
+                        &["keyFocusChange".into(), listener.into()],
+                        &mut activation,
+                    )
+                    .unwrap();
+            }
+
+            let focus_tracker = context.focus_tracker;
+            focus_tracker.cycle(context, false);
+            assert!(focus_tracker.get().is_none());
+            assert!(!focus_tracker.is_highlighted());
+        });
+    }
 }
@@ -7,6 +7,8 @@ use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefCell};
 use std::cmp::max;
+use thiserror::Error;
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
 
 pub use swf::TextGridFit;
 
@@ -83,6 +85,9 @@ struct FontData {
     /// Maps from a pair of unicode code points to horizontal offset value.
     kerning_pairs: fnv::FnvHashMap<(u16, u16), Twips>,
 
+    /// The kerning table of the font file this font was loaded from, if any.
+    file_kerning: Option<FontFileKerning>,
+
     /// The distance from the top of each glyph to the baseline of the font, in
     /// EM-square coordinates.
     ascent: u16,
@@ -98,6 +103,9 @@ struct FontData {
     /// The identity of the font.
     #[collect(require_static)]
     descriptor: FontDescriptor,
+
+    /// Where the glyphs of the font come from.
+    font_type: FontType,
 }
 
 impl<'gc> Font<'gc> {
@@ -106,6 +114,24 @@ impl<'gc> Font<'gc> {
         renderer: &mut dyn RenderBackend,
         tag: swf::Font,
         encoding: &'static swf::Encoding,
+    ) -> Font<'gc> {
+        Self::from_swf_tag_with_type(
+            gc_context,
+            renderer,
+            tag,
+            encoding,
+            FontType::Embedded,
+            None,
+        )
+    }
+
+    fn from_swf_tag_with_type(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        tag: swf::Font,
+        encoding: &'static swf::Encoding,
+        font_type: FontType,
+        file_kerning: Option<FontFileKerning>,
     ) -> Font<'gc> {
         let mut code_point_to_glyph = fnv::FnvHashMap::default();
 
@@ -159,14 +185,108 @@ impl<'gc> Font<'gc> {
                 /// (SWF19 p.164)
                 scale: if tag.version >= 3 { 20480.0 } else { 1024.0 },
                 kerning_pairs,
+                file_kerning,
                 ascent,
                 descent,
                 leading,
                 descriptor,
+                font_type,
             },
         ))
     }
 
+    /// Load a font from a TrueType or OpenType font file, for use as a device
+    /// font.
+    ///
+    /// `index` selects the font within a font collection, and should be 0 for
+    /// other font files.
+    pub fn from_font_file(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        data: &[u8],
+        index: u32,
+    ) -> Result<Font<'gc>, FontFileError> {
+        let face = Face::parse(data, index)?;
+        let name = font_family_name(&face).ok_or(FontFileError::MissingName)?;
+        let (tag, kerning) = swf_font_from_face(&face, &name);
+        Ok(Self::from_swf_tag_with_type(
+            gc_context,
+            renderer,
+            tag,
+            swf::UTF_8,
+            FontType::Device,
+            kerning,
+        ))
+    }
+
+    /// Load a font from a DefineFont4 tag, which embeds an OpenType font with
+    /// CFF outlines for use by the Flash Text Engine.
+    ///
+    /// A tag without embedded data only references a device font by name, and
+    /// produces a font without glyphs.
+    pub fn from_font4_tag(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        tag: swf::Font4,
+        encoding: &'static swf::Encoding,
+    ) -> Result<Font<'gc>, FontFileError> {
+        let Some(data) = tag.data else {
+            return Ok(Self::from_font4_tag_without_glyphs(
+                gc_context, renderer, tag, encoding,
+            ));
+        };
+        let name = tag.name.to_str_lossy(encoding);
+        let (font, kerning) = swf_font_from_face(&Face::parse(data, 0)?, &name);
+        Ok(Self::from_font4_parts(
+            gc_context, renderer, &tag, font, kerning,
+        ))
+    }
+
+    /// Create a font without glyphs from a DefineFont4 tag, ignoring any
+    /// embedded data.
+    ///
+    /// This is used as a placeholder when the embedded data can't be parsed,
+    /// so that the font can still be referenced.
+    pub fn from_font4_tag_without_glyphs(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        tag: swf::Font4,
+        encoding: &'static swf::Encoding,
+    ) -> Font<'gc> {
+        let name = tag.name.to_str_lossy(encoding);
+        let font = swf::Font {
+            version: 3,
+            id: 0,
+            name: swf::SwfStr::from_utf8_str(&name),
+            language: swf::Language::Unknown,
+            layout: None,
+            glyphs: Vec::new(),
+            flags: swf::FontFlag::empty(),
+        };
+        Self::from_font4_parts(gc_context, renderer, &tag, font, None)
+    }
+
+    fn from_font4_parts(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        tag: &swf::Font4,
+        mut font: swf::Font,
+        kerning: Option<FontFileKerning>,
+    ) -> Font<'gc> {
+        // The style given by the tag takes precedence over that of the font data.
+        font.id = tag.id;
+        font.flags.set(swf::FontFlag::IS_BOLD, tag.is_bold);
+        font.flags.set(swf::FontFlag::IS_ITALIC, tag.is_italic);
+        Self::from_swf_tag_with_type(
+            gc_context,
+            renderer,
+            font,
+            swf::UTF_8,
+            FontType::EmbeddedCFF,
+            kerning,
+        )
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
@@ -210,10 +330,12 @@ impl<'gc> Font<'gc> {
         // TODO: Properly handle UTF-16/out-of-bounds code points.
         let left_code_point = left as u16;
         let right_code_point = right as u16;
+        let pair = (left_code_point, right_code_point);
         self.0
             .kerning_pairs
-            .get(&(left_code_point, right_code_point))
-            .cloned()
+            .get(&pair)
+            .copied()
+            .or_else(|| self.0.file_kerning.as_ref()?.get(pair))
             .unwrap_or_default()
     }
 
@@ -240,7 +362,7 @@ impl<'gc> Font<'gc> {
 
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
-        !self.0.kerning_pairs.is_empty()
+        !self.0.kerning_pairs.is_empty() || self.0.file_kerning.is_some()
     }
 
     pub fn scale(&self) -> f32 {
@@ -416,6 +538,10 @@ impl<'gc> Font<'gc> {
     pub fn descriptor(&self) -> &FontDescriptor {
         &self.0.descriptor
     }
+
+    pub fn font_type(&self) -> FontType {
+        self.0.font_type
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -442,6 +568,21 @@ impl Glyph {
         Ref::map(self.shape.borrow(), |s| s.as_ref().unwrap())
     }
 
+    /// The character drawn by this glyph.
+    pub fn character(&self) -> char {
+        char::from_u32(self.swf_glyph.code.into()).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    /// The horizontal advance of this glyph, in EM-square coordinates.
+    pub fn advance(&self) -> Twips {
+        Twips::new(self.swf_glyph.advance.into())
+    }
+
+    /// The outline of this glyph, in EM-square coordinates.
+    pub fn shape_records(&self) -> &[swf::ShapeRecord] {
+        &self.swf_glyph.shape_records
+    }
+
     pub fn shape_handle(&self, renderer: &mut dyn RenderBackend) -> ShapeHandle {
         self.shape_handle
             .borrow_mut()
@@ -504,6 +645,311 @@ impl FontDescriptor {
     }
 }
 
+/// Where the glyphs of a font come from, as given by `flash.text.FontType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum FontType {
+    /// A font embedded by a DefineFont1-3 tag.
+    Embedded,
+
+    /// A font embedded by a DefineFont4 tag, for use by the Flash Text Engine.
+    EmbeddedCFF,
+
+    /// A font loaded from a font file provided by the player.
+    Device,
+}
+
+impl FontType {
+    /// The name of this font type in `flash.text.FontType`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Embedded => "embedded",
+            Self::EmbeddedCFF => "embeddedCFF",
+            Self::Device => "device",
+        }
+    }
+}
+
+/// The generic device fonts which a movie can ask for by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefaultFont {
+    /// `_sans`, a sans-serif font such as Arial.
+    Sans,
+
+    /// `_serif`, a serif font such as Times New Roman.
+    Serif,
+
+    /// `_typewriter`, a monospace font such as Courier New.
+    Typewriter,
+}
+
+impl DefaultFont {
+    /// Get the default font referenced by a font name, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "_sans" => Some(Self::Sans),
+            "_serif" => Some(Self::Serif),
+            "_typewriter" => Some(Self::Typewriter),
+            _ => None,
+        }
+    }
+}
+
+/// An error encountered while loading a font file.
+#[derive(Error, Debug)]
+pub enum FontFileError {
+    #[error("Invalid font file: {0}")]
+    InvalidFile(#[from] ttf_parser::FaceParsingError),
+
+    #[error("Font file has no family name")]
+    MissingName,
+}
+
+/// The EM square of DefineFont3 glyphs, which are stored at 20x the scale of
+/// DefineFont1/2 glyphs.
+const DEFINE_FONT_3_EM_SQUARE: f32 = 20480.0;
+
+/// Read the family name of a font file, such as `Arial`.
+fn font_family_name(face: &Face<'_>) -> Option<String> {
+    face.names()
+        .into_iter()
+        .filter(|name| name.name_id == ttf_parser::name_id::FAMILY && name.is_unicode())
+        .find_map(|name| name.to_string())
+}
+
+/// The kerning table of a font file.
+///
+/// A font file can kern many more pairs than are worth storing, so the table
+/// is kept as is and looked up when a pair is kerned.
+#[derive(Debug, Clone)]
+struct FontFileKerning {
+    /// The raw `kern` table.
+    data: Box<[u8]>,
+
+    /// A map from a code point to its glyph in the font file.
+    glyph_ids: fnv::FnvHashMap<u16, GlyphId>,
+
+    /// The scaling from font units to twips.
+    scale: f32,
+}
+
+impl FontFileKerning {
+    fn from_face(
+        face: &Face<'_>,
+        glyph_ids: fnv::FnvHashMap<u16, GlyphId>,
+        scale: f32,
+    ) -> Option<Self> {
+        let data = face
+            .raw_face()
+            .table(ttf_parser::Tag::from_bytes(b"kern"))?;
+        Some(Self {
+            data: data.into(),
+            glyph_ids,
+            scale,
+        })
+    }
+
+    /// Returns the kerning between a pair of code points, if any.
+    fn get(&self, (left, right): (u16, u16)) -> Option<Twips> {
+        let left = *self.glyph_ids.get(&left)?;
+        let right = *self.glyph_ids.get(&right)?;
+        let table = ttf_parser::kern::Table::parse(&self.data)?;
+        let adjustment = table
+            .subtables
+            .into_iter()
+            .filter(|subtable| subtable.horizontal && !subtable.variable)
+            .find_map(|subtable| subtable.glyphs_kerning(left, right))?;
+        Some(Twips::new(
+            (f32::from(adjustment) * self.scale).round() as i32
+        ))
+    }
+}
+
+/// Convert the glyphs of a font file into a DefineFont3 tag, along with its
+/// kerning table.
+///
+/// Only code points in the Basic Multilingual Plane can be stored in a
+/// DefineFont3 tag; the rest of the font is ignored.
+fn swf_font_from_face<'a>(
+    face: &Face<'_>,
+    name: &'a str,
+) -> (swf::Font<'a>, Option<FontFileKerning>) {
+    let scale = DEFINE_FONT_3_EM_SQUARE / f32::from(face.units_per_em());
+    let to_twips = |value: f32| Twips::new((value * scale).round() as i32);
+
+    let mut code_points = Vec::new();
+    if let Some(cmap) = face.tables().cmap {
+        for subtable in cmap.subtables {
+            if subtable.is_unicode() {
+                subtable.codepoints(|code_point| code_points.push(code_point));
+            }
+        }
+    }
+    code_points.sort_unstable();
+    code_points.dedup();
+
+    let mut glyphs = Vec::with_capacity(code_points.len());
+    let mut glyph_ids = fnv::FnvHashMap::default();
+    for c in code_points.into_iter().filter_map(char::from_u32) {
+        let Ok(code) = u16::try_from(u32::from(c)) else {
+            continue;
+        };
+        let Some(glyph_id) = face.glyph_index(c) else {
+            continue;
+        };
+
+        let mut builder = ShapeRecordBuilder::new(scale);
+        let bounds = face
+            .outline_glyph(glyph_id, &mut builder)
+            .map(|rect| swf::Rectangle {
+                x_min: to_twips(rect.x_min.into()),
+                x_max: to_twips(rect.x_max.into()),
+                // SWF glyphs point downwards, unlike those of font files.
+                y_min: to_twips(-f32::from(rect.y_max)),
+                y_max: to_twips(-f32::from(rect.y_min)),
+            });
+        let advance = face.glyph_hor_advance(glyph_id).unwrap_or_default();
+
+        glyphs.push(swf::Glyph {
+            shape_records: builder.records,
+            code,
+            advance: to_twips(advance.into())
+                .get()
+                .clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+            bounds,
+        });
+        glyph_ids.insert(code, glyph_id);
+    }
+
+    let to_em = |value: i16| to_twips(value.into()).get();
+    let layout = swf::FontLayout {
+        ascent: to_em(face.ascender()).clamp(0, u16::MAX.into()) as u16,
+        descent: to_em(face.descender().saturating_neg()).clamp(0, u16::MAX.into()) as u16,
+        leading: to_em(face.line_gap()).clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+        kerning: Vec::new(),
+    };
+
+    let mut flags = swf::FontFlag::HAS_LAYOUT | swf::FontFlag::HAS_WIDE_CODES;
+    flags.set(swf::FontFlag::IS_BOLD, face.is_bold());
+    flags.set(swf::FontFlag::IS_ITALIC, face.is_italic());
+
+    let font = swf::Font {
+        version: 3,
+        id: 0,
+        name: swf::SwfStr::from_utf8_str(name),
+        language: swf::Language::Unknown,
+        layout: Some(layout),
+        glyphs,
+        flags,
+    };
+    (font, FontFileKerning::from_face(face, glyph_ids, scale))
+}
+
+/// Builds the shape records of a glyph from its outline in a font file.
+struct ShapeRecordBuilder {
+    /// The scale from font units to twips.
+    scale: f32,
+
+    records: Vec<swf::ShapeRecord>,
+
+    /// The start of the current contour, in font units.
+    start: (f32, f32),
+
+    /// The current position of the pen, in font units.
+    current: (f32, f32),
+
+    /// The current position of the pen, in twips.
+    position: swf::Point<Twips>,
+}
+
+impl ShapeRecordBuilder {
+    fn new(scale: f32) -> Self {
+        Self {
+            scale,
+            records: Vec::new(),
+            start: (0.0, 0.0),
+            current: (0.0, 0.0),
+            position: swf::Point::ZERO,
+        }
+    }
+
+    fn to_point(&self, x: f32, y: f32) -> swf::Point<Twips> {
+        // SWF glyphs point downwards, unlike those of font files.
+        swf::Point::new(
+            Twips::new((x * self.scale).round() as i32),
+            Twips::new((-y * self.scale).round() as i32),
+        )
+    }
+}
+
+impl OutlineBuilder for ShapeRecordBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = self.to_point(x, y);
+        self.records.push(swf::ShapeRecord::StyleChange(Box::new(
+            swf::StyleChangeData {
+                move_to: Some(point),
+                fill_style_0: None,
+                fill_style_1: Some(1),
+                line_style: None,
+                new_styles: None,
+            },
+        )));
+        self.start = (x, y);
+        self.current = (x, y);
+        self.position = point;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.to_point(x, y);
+        self.records.push(swf::ShapeRecord::StraightEdge {
+            delta: point - self.position,
+        });
+        self.current = (x, y);
+        self.position = point;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.to_point(x1, y1);
+        let anchor = self.to_point(x, y);
+        self.records.push(swf::ShapeRecord::CurvedEdge {
+            control_delta: control - self.position,
+            anchor_delta: anchor - control,
+        });
+        self.current = (x, y);
+        self.position = anchor;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // SWF shapes have no cubic curves, so split the curve in half and
+        // approximate each half with a quadratic curve.
+        let (x0, y0) = self.current;
+        let mid = |a: (f32, f32), b: (f32, f32)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+        let a = mid((x0, y0), (x1, y1));
+        let b = mid((x1, y1), (x2, y2));
+        let c = mid((x2, y2), (x, y));
+        let ab = mid(a, b);
+        let bc = mid(b, c);
+        let m = mid(ab, bc);
+
+        let control = |start: (f32, f32), c1: (f32, f32), c2: (f32, f32), end: (f32, f32)| {
+            (
+                (3.0 * (c1.0 + c2.0) - (start.0 + end.0)) / 4.0,
+                (3.0 * (c1.1 + c2.1) - (start.1 + end.1)) / 4.0,
+            )
+        };
+        let first = control((x0, y0), a, ab, m);
+        let second = control(m, bc, c, (x, y));
+        self.quad_to(first.0, first.1, m.0, m.1);
+        self.quad_to(second.0, second.1, x, y);
+    }
+
+    fn close(&mut self) {
+        if self.current != self.start {
+            self.line_to(self.start.0, self.start.1);
+        }
+    }
+}
+
 /// The text rendering engine that a text field should use.
 /// This is controlled by the "Anti-alias" setting in the Flash IDE.
 /// Using "Anti-alias for readibility" switches to the "Advanced" text
@@ -686,16 +1132,17 @@ impl Default for TextRenderSettings {
 
 #[cfg(test)]
 mod tests {
-    use crate::font::{EvalParameters, Font};
+    use crate::font::{EvalParameters, Font, FontType, ShapeRecordBuilder};
     use crate::player::Player;
     use crate::string::WStr;
     use gc_arena::{rootless_arena, Mutation};
-    use ruffle_render::backend::{null::NullRenderer, ViewportDimensions};
-    use swf::Twips;
+    use ruffle_render::backend::{null::NullRenderer, RenderBackend, ViewportDimensions};
+    use swf::{PointDelta, ShapeRecord, SwfStr, Twips};
+    use ttf_parser::OutlineBuilder;
 
-    fn with_device_font<F>(callback: F)
+    fn with_renderer<F>(callback: F)
     where
-        F: for<'gc> FnOnce(&Mutation<'gc>, Font<'gc>),
+        F: for<'gc> FnOnce(&Mutation<'gc>, &mut dyn RenderBackend),
     {
         rootless_arena(|mc| {
             let mut renderer = NullRenderer::new(ViewportDimensions {
@@ -703,12 +1150,88 @@ mod tests {
                 height: 0,
                 scale_factor: 1.0,
             });
-            let device_font = Player::load_device_font(mc, &mut renderer);
+
+            callback(mc, &mut renderer);
+        })
+    }
+
+    fn with_device_font<F>(callback: F)
+    where
+        F: for<'gc> FnOnce(&Mutation<'gc>, Font<'gc>),
+    {
+        with_renderer(|mc, renderer| {
+            let device_font = Player::load_device_font(mc, renderer);
 
             callback(mc, device_font);
         })
     }
 
+    fn words(values: &[u16]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    /// Builds a minimal OpenType font with CFF outlines, which maps `A` to a
+    /// 400x700 rectangle in a 1000 unit EM square, and kerns `AA` by -50 units.
+    fn cff_font_file() -> Vec<u8> {
+        let mut cff = vec![
+            1, 0, 4, 1, // Header
+            0, 1, 1, 1, 5, b'T', b'e', b's', b't', // Name INDEX
+            0, 1, 1, 1, 7, 29, 0, 0, 0, 28, 17, // Top DICT INDEX, with the CharStrings offset
+            0, 0, // String INDEX
+            0, 0, // Global Subr INDEX
+            0, 2, 1, 1, 2, 16, // CharStrings INDEX
+            14, // .notdef: endchar
+        ];
+        // 100 0 rmoveto 400 0 0 700 -400 0 rlineto endchar
+        cff.extend([
+            239, 139, 21, 248, 36, 139, 139, 249, 80, 252, 36, 139, 5, 14,
+        ]);
+        // A single Windows Unicode BMP subtable in format 4, mapping `A` to
+        // glyph 1 with an ID delta of -0x40.
+        let cmap = words(&[
+            0, 1, 3, 1, 0, 12, 4, 32, 0, 4, 4, 1, 0, 0x41, 0xFFFF, 0, 0x41, 0xFFFF, 0xFFC0, 1, 0, 0,
+        ]);
+        let head = words(&[
+            1, 0, 0, 0, 0, 0, 0x5F0F, 0x3CF5, 0, 1000, 0, 0, 0, 0, 0, 0, 0, 0, 100, 0, 500, 700, 0,
+            8, 2, 0, 0,
+        ]);
+        // An ascender of 800 and a descender of -200.
+        let hhea = words(&[
+            1, 0, 800, 0xFF38, 0, 600, 0, 0, 500, 1, 0, 0, 0, 0, 0, 0, 0, 2,
+        ]);
+        let hmtx = words(&[500, 0, 600, 100]);
+        // A single horizontal subtable in format 0, with one pair of glyph 1.
+        let kern = words(&[0, 1, 0, 20, 1, 1, 6, 0, 0, 1, 1, 0xFFCE]);
+        let maxp = words(&[0, 0x5000, 2]);
+
+        let tables = [
+            (b"CFF ", cff),
+            (b"cmap", cmap),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"kern", kern),
+            (b"maxp", maxp),
+        ];
+        let mut file = words(&[0x4F54, 0x544F, tables.len() as u16, 64, 2, 32]);
+        let mut offset = file.len() + 16 * tables.len();
+        for (tag, data) in &tables {
+            file.extend(*tag);
+            file.extend(0u32.to_be_bytes());
+            file.extend((offset as u32).to_be_bytes());
+            file.extend((data.len() as u32).to_be_bytes());
+            offset += data.len().next_multiple_of(4);
+        }
+        for (_, data) in &tables {
+            file.extend(data);
+            file.resize(file.len().next_multiple_of(4), 0);
+        }
+        file
+    }
+
     #[test]
     fn wrap_line_no_breakpoint() {
         with_device_font(|_mc, df| {
@@ -866,4 +1389,106 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn font_file_outline_is_flipped_and_closed() {
+        let mut builder = ShapeRecordBuilder::new(20.0);
+        builder.move_to(0.0, 0.0);
+        builder.line_to(10.0, 0.0);
+        builder.line_to(10.0, 10.0);
+        builder.close();
+
+        let edges: Vec<_> = builder.records[1..]
+            .iter()
+            .map(|record| match record {
+                ShapeRecord::StraightEdge { delta } => *delta,
+                _ => panic!("Expected a straight edge, got {record:?}"),
+            })
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                PointDelta::new(Twips::new(200), Twips::ZERO),
+                PointDelta::new(Twips::ZERO, Twips::new(-200)),
+                PointDelta::new(Twips::new(-200), Twips::new(200)),
+            ]
+        );
+    }
+
+    #[test]
+    fn font4_tag_with_cff_outlines() {
+        let data = cff_font_file();
+        with_renderer(|mc, renderer| {
+            let tag = swf::Font4 {
+                id: 1,
+                is_italic: false,
+                is_bold: true,
+                name: SwfStr::from_utf8_str("Test"),
+                data: Some(&data),
+            };
+            let font = Font::from_font4_tag(mc, renderer, tag, swf::UTF_8).unwrap();
+            assert_eq!(font.font_type(), FontType::EmbeddedCFF);
+            assert_eq!(font.descriptor().class(), "Test");
+            assert!(font.descriptor().bold());
+
+            // 1000 font units are scaled to the 20480 unit EM square of DefineFont3.
+            let glyph = font.get_glyph_for_char('A').expect("A should have a glyph");
+            assert_eq!(glyph.advance(), Twips::new(12288));
+            let edges: Vec<_> = glyph
+                .shape_records()
+                .iter()
+                .filter_map(|record| match record {
+                    ShapeRecord::StraightEdge { delta } => Some(*delta),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(
+                edges,
+                vec![
+                    PointDelta::new(Twips::new(8192), Twips::ZERO),
+                    PointDelta::new(Twips::ZERO, Twips::new(-14336)),
+                    PointDelta::new(Twips::new(-8192), Twips::ZERO),
+                    PointDelta::new(Twips::ZERO, Twips::new(14336)),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn font_file_kerning() {
+        let data = cff_font_file();
+        with_renderer(|mc, renderer| {
+            let tag = swf::Font4 {
+                id: 1,
+                is_italic: false,
+                is_bold: false,
+                name: SwfStr::from_utf8_str("Test"),
+                data: Some(&data),
+            };
+            let font = Font::from_font4_tag(mc, renderer, tag, swf::UTF_8).unwrap();
+            assert!(font.has_kerning_info());
+            assert_eq!(font.get_kerning_offset('A', 'A'), Twips::new(-1024));
+            assert_eq!(font.get_kerning_offset('A', 'B'), Twips::ZERO);
+        });
+    }
+
+    #[test]
+    fn font4_placeholder_is_embedded_cff() {
+        with_renderer(|mc, renderer| {
+            let tag = swf::Font4 {
+                id: 1,
+                is_italic: true,
+                is_bold: false,
+                name: SwfStr::from_utf8_str("Test"),
+                data: Some(&b"not a font"[..]),
+            };
+            assert!(Font::from_font4_tag(mc, renderer, tag.clone(), swf::UTF_8).is_err());
+
+            let font = Font::from_font4_tag_without_glyphs(mc, renderer, tag, swf::UTF_8);
+            assert_eq!(font.font_type(), FontType::EmbeddedCFF);
+            assert_eq!(font.descriptor().class(), "Test");
+            assert!(font.descriptor().italic());
+            assert!(!font.has_glyphs());
+        });
+    }
 }
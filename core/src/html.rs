@@ -3,12 +3,16 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod style_sheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
 pub use layout::{LayoutBox, LayoutContent, LayoutMetrics};
+pub use style_sheet::{
+    parse_css, CssDeclarations, StyleSheet, TextDisplay, TextStyle, STYLE_PROPERTIES,
+};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -459,17 +459,30 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc>,
         span: &TextSpan,
+        text: &WStr,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
         let library = context.library.library_for_movie_mut(self.movie.clone());
+        let font_name = span.font.to_utf8_lossy();
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+        // If this text field is set to use device fonts, fallback to the registered device fonts,
+        // or to our embedded Noto Sans if none of them can render the text.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
         if let Some(font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+            .get_font_by_name(&font_name, span.bold, span.italic)
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| context.library.device_font())
+            .or_else(|| {
+                // AVM2 movies can also use fonts registered by any movie.
+                context
+                    .library
+                    .get_registered_font_by_name(&font_name, span.bold, span.italic)
+                    .filter(|_| !is_device_font && self.movie.is_action_script_3())
+            })
+            .or_else(|| {
+                context
+                    .library
+                    .get_device_font(&font_name, span.bold, span.italic, text)
+            })
         {
             self.font = Some(font);
             return self.font;
@@ -528,11 +541,24 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         is_device_font: bool,
     ) {
         let library = context.library.library_for_movie_mut(self.movie.clone());
+        let font_name = span.font.to_utf8_lossy();
+        let bullet = WStr::from_units(&[0x2022u16]);
 
         if let Some(bullet_font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+            .get_font_by_name(&font_name, span.bold, span.italic)
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| context.library.device_font())
+            .or_else(|| {
+                // AVM2 movies can also use fonts registered by any movie.
+                context
+                    .library
+                    .get_registered_font_by_name(&font_name, span.bold, span.italic)
+                    .filter(|_| !is_device_font && self.movie.is_action_script_3())
+            })
+            .or_else(|| {
+                context
+                    .library
+                    .get_device_font(&font_name, span.bold, span.italic, bullet)
+            })
             .or(self.font)
         {
             let mut bullet_cursor = self.cursor;
@@ -543,7 +569,6 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             );
 
             let params = EvalParameters::from_span(span);
-            let bullet = WStr::from_units(&[0x2022u16]);
             let text_size = Size::from(bullet_font.measure(bullet, params, false));
             let text_bounds = BoxBounds::from_position_and_size(bullet_cursor, text_size);
             let mut new_bullet = LayoutBox::from_bullet(bullet_font, span);
@@ -763,7 +788,9 @@ impl<'gc> LayoutBox<'gc> {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
-            if let Some(font) = layout_context.resolve_font(context, span, is_device_font) {
+            if let Some(font) =
+                layout_context.resolve_font(context, span, span_text, is_device_font)
+            {
                 layout_context.newspan(span);
 
                 let params = EvalParameters::from_span(span);
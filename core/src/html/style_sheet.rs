@@ -0,0 +1,264 @@
+//! CSS style sheets applied to HTML text
+
+use crate::html::TextFormat;
+use crate::string::{WStr, WString};
+use std::collections::HashMap;
+
+/// The CSS properties understood by `StyleSheet`, in their camel case form.
+pub const STYLE_PROPERTIES: &[&str] = &[
+    "color",
+    "display",
+    "fontFamily",
+    "fontSize",
+    "fontStyle",
+    "fontWeight",
+    "kerning",
+    "leading",
+    "letterSpacing",
+    "marginLeft",
+    "marginRight",
+    "textAlign",
+    "textDecoration",
+    "textIndent",
+];
+
+/// The declarations of a CSS rule, as property names and values.
+///
+/// Property names are converted to the camel case used by `StyleSheet`
+/// objects, so `font-family` becomes `fontFamily`.
+pub type CssDeclarations = Vec<(WString, WString)>;
+
+/// Convert a CSS property name such as `font-family` to camel case.
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Remove the `/* ... */` comments from some CSS.
+fn strip_comments(css: &str) -> Option<String> {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        let end = rest[start + 2..].find("*/")?;
+        rest = &rest[start + 2 + end + 2..];
+    }
+    result.push_str(rest);
+    Some(result)
+}
+
+/// Parse some CSS into its rules, as selector names and declarations.
+///
+/// Each selector of a group such as `h1, h2 { ... }` gets its own rule.
+/// `None` is returned if the CSS is malformed.
+pub fn parse_css(css: &WStr) -> Option<Vec<(WString, CssDeclarations)>> {
+    let css = strip_comments(&css.to_utf8_lossy())?;
+    let mut rules = Vec::new();
+    let mut rest = css.as_str();
+    loop {
+        let Some(open) = rest.find('{') else {
+            // Only whitespace may follow the last rule.
+            return rest.trim().is_empty().then_some(rules);
+        };
+        let close = open + rest[open..].find('}')?;
+        let selectors = &rest[..open];
+        let body = &rest[open + 1..close];
+        if body.contains('{') {
+            return None;
+        }
+
+        let mut declarations = CssDeclarations::new();
+        for declaration in body.split(';') {
+            if declaration.trim().is_empty() {
+                continue;
+            }
+            let (name, value) = declaration.split_once(':')?;
+            let name = camel_case(name.trim());
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .or_else(|| {
+                    value
+                        .strip_prefix('\'')
+                        .and_then(|value| value.strip_suffix('\''))
+                })
+                .unwrap_or(value);
+            declarations.push((WString::from_utf8(&name), WString::from_utf8(value)));
+        }
+
+        for selector in selectors.split(',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+            rules.push((WString::from_utf8(selector), declarations.clone()));
+        }
+
+        rest = &rest[close + 1..];
+    }
+}
+
+/// How an element is laid out, as given by the `display` property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextDisplay {
+    /// The element is followed by a line break.
+    Block,
+
+    /// The element flows with the surrounding text.
+    Inline,
+
+    /// The element and its contents are hidden.
+    None,
+}
+
+/// The formatting applied to the elements matching a selector.
+#[derive(Clone, Debug, Default)]
+pub struct TextStyle {
+    pub format: TextFormat,
+    pub display: Option<TextDisplay>,
+}
+
+/// Parse a CSS length such as `12px`, ignoring its unit.
+fn parse_length(value: &str) -> Option<f64> {
+    let end = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '-' && c != '+')
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+/// Parse a CSS color of the form `#RRGGBB`.
+fn parse_color(value: &str) -> Option<swf::Color> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(swf::Color::from_rgb(rgb, 0))
+}
+
+impl TextStyle {
+    /// Build a style from its CSS declarations.
+    pub fn from_declarations(declarations: &CssDeclarations) -> Self {
+        let mut style = Self::default();
+        for (name, value) in declarations {
+            style.set_property(&name.to_utf8_lossy(), value);
+        }
+        style
+    }
+
+    /// Apply a single CSS property, given by its camel case name.
+    ///
+    /// Unknown properties and invalid values are ignored, like Flash does.
+    pub fn set_property(&mut self, name: &str, value: &WStr) {
+        let value = value.to_utf8_lossy();
+        let value = value.trim();
+        let format = &mut self.format;
+        match name {
+            "color" => {
+                if let Some(color) = parse_color(value) {
+                    format.color = Some(color);
+                }
+            }
+            "display" => {
+                self.display = match value {
+                    "block" => Some(TextDisplay::Block),
+                    "inline" => Some(TextDisplay::Inline),
+                    "none" => Some(TextDisplay::None),
+                    _ => self.display,
+                };
+            }
+            "fontFamily" => {
+                let font = match value {
+                    "sans-serif" => "_sans",
+                    "serif" => "_serif",
+                    "mono" | "monospace" => "_typewriter",
+                    font => font,
+                };
+                format.font = Some(WString::from_utf8(font));
+            }
+            "fontSize" => {
+                if let Some(size) = parse_length(value) {
+                    format.size = Some(size);
+                }
+            }
+            "fontStyle" => match value {
+                "italic" => format.italic = Some(true),
+                "normal" => format.italic = Some(false),
+                _ => {}
+            },
+            "fontWeight" => match value {
+                "bold" => format.bold = Some(true),
+                "normal" => format.bold = Some(false),
+                _ => {}
+            },
+            "kerning" => match value {
+                "true" => format.kerning = Some(true),
+                "false" => format.kerning = Some(false),
+                _ => {}
+            },
+            "leading" => format.leading = parse_length(value).or(format.leading),
+            "letterSpacing" => {
+                format.letter_spacing = parse_length(value).or(format.letter_spacing)
+            }
+            "marginLeft" => format.left_margin = parse_length(value).or(format.left_margin),
+            "marginRight" => format.right_margin = parse_length(value).or(format.right_margin),
+            "textAlign" => {
+                format.align = match value {
+                    "left" => Some(swf::TextAlign::Left),
+                    "center" => Some(swf::TextAlign::Center),
+                    "right" => Some(swf::TextAlign::Right),
+                    "justify" => Some(swf::TextAlign::Justify),
+                    _ => format.align,
+                };
+            }
+            "textDecoration" => match value {
+                "underline" => format.underline = Some(true),
+                "none" => format.underline = Some(false),
+                _ => {}
+            },
+            "textIndent" => format.indent = parse_length(value).or(format.indent),
+            _ => {}
+        }
+    }
+}
+
+/// A set of styles, by selector.
+///
+/// Selectors are either element names such as `p`, class names such as
+/// `.title`, or `a:link`, `a:hover` and `a:active` for links. They are
+/// matched case-insensitively.
+#[derive(Clone, Debug, Default)]
+pub struct StyleSheet {
+    styles: HashMap<WString, TextStyle>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_style(&mut self, selector: &WStr, style: TextStyle) {
+        self.styles.insert(selector.to_ascii_lowercase(), style);
+    }
+
+    pub fn get_style(&self, selector: &WStr) -> Option<&TextStyle> {
+        self.styles.get(&selector.to_ascii_lowercase())
+    }
+
+    /// Whether hovering a link changes its style.
+    pub fn has_hover_style(&self) -> bool {
+        self.get_style(WStr::from_units(b"a:hover")).is_some()
+    }
+}
@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{parse_css, StyleSheet, TextStyle};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -864,3 +865,51 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn parse_css_rules() {
+    let rules = parse_css(WStr::from_units(
+        b"/* comment */ p, .Title { font-family: 'Arial'; font-size: 14px }\na:hover{color:#FF0000;}",
+    ))
+    .unwrap();
+
+    let font_family = (
+        WString::from_utf8("fontFamily"),
+        WString::from_utf8("Arial"),
+    );
+    let font_size = (WString::from_utf8("fontSize"), WString::from_utf8("14px"));
+    assert_eq!(rules.len(), 3);
+    assert_eq!(rules[0].0, WString::from_utf8("p"));
+    assert_eq!(rules[0].1, vec![font_family.clone(), font_size.clone()]);
+    assert_eq!(rules[1].0, WString::from_utf8(".Title"));
+    assert_eq!(rules[1].1, vec![font_family, font_size]);
+    assert_eq!(rules[2].0, WString::from_utf8("a:hover"));
+
+    assert!(parse_css(WStr::from_units(b"p { color: #000000;")).is_none());
+}
+
+#[test]
+fn formatspans_lower_from_css() {
+    let mut style_sheet = StyleSheet::new();
+    let title = parse_css(WStr::from_units(
+        b".title { font-weight: bold; font-size: 20; display: block }",
+    ))
+    .unwrap();
+    style_sheet.set_style(&title[0].0, TextStyle::from_declarations(&title[0].1));
+    let hidden = parse_css(WStr::from_units(b"secret { display: none }")).unwrap();
+    style_sheet.set_style(&hidden[0].0, TextStyle::from_declarations(&hidden[0].1));
+
+    let fs = FormatSpans::lower_from_css(
+        WStr::from_units(b"<span class='Title'>Hello</span><secret>hidden</secret>world"),
+        TextFormat::default(),
+        &style_sheet,
+        None,
+        true,
+    );
+
+    assert_eq!(WStr::from_units(b"Hello\nworld"), fs.text());
+    let title_span = fs.span(0).unwrap();
+    assert!(title_span.bold);
+    assert_eq!(title_span.size, 20.0);
+    assert!(!fs.last_span().unwrap().bold);
+}
@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::{StyleSheet, TextDisplay};
 use crate::string::{Integer, SwfStrExt as _, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -514,6 +515,18 @@ impl TextSpan {
     }
 }
 
+/// The state of an HTML element while it is being lowered.
+struct ElementState {
+    /// Whether a line break follows the element.
+    is_block: bool,
+
+    /// Whether the element and its contents are hidden by `display: none`.
+    is_hidden: bool,
+
+    /// Where a link starts, as its text position and first span index.
+    link: Option<(usize, usize)>,
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug)]
 pub struct FormatSpans {
@@ -567,7 +580,38 @@ impl FormatSpans {
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
     pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+        Self::lower_html(html, default_format, None, is_multiline)
+    }
+
+    /// Lower an HTML tree into text-span representation, styled by a CSS
+    /// stylesheet as well as presentational markup.
+    ///
+    /// Styles apply to elements by their name and `class` attribute. The
+    /// `a:hover` style only applies to the link containing `hovered_position`.
+    pub fn lower_from_css(
+        html: &WStr,
+        default_format: TextFormat,
+        style_sheet: &StyleSheet,
+        hovered_position: Option<usize>,
+        is_multiline: bool,
+    ) -> Self {
+        Self::lower_html(
+            html,
+            default_format,
+            Some((style_sheet, hovered_position)),
+            is_multiline,
+        )
+    }
+
+    fn lower_html(
+        html: &WStr,
+        default_format: TextFormat,
+        style: Option<(&StyleSheet, Option<usize>)>,
+        is_multiline: bool,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
+        let mut element_stack: Vec<ElementState> = Vec::new();
+        let mut hidden_depth = 0;
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
 
@@ -757,8 +801,44 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+
+                    let tag_name = e.name().into_inner().to_ascii_lowercase();
+                    let mut element = ElementState {
+                        is_block: is_multiline && matches!(&tag_name[..], b"p" | b"li"),
+                        is_hidden: false,
+                        link: None,
+                    };
+                    if let Some((style_sheet, _)) = style {
+                        let mut selectors = vec![decode_to_wstr(&tag_name)];
+                        if let Some(class) = attribute(b"class") {
+                            let mut selector = WString::from_buf(b".".to_vec());
+                            selector.push_str(&class);
+                            selectors.push(selector);
+                        }
+                        if tag_name == b"a" {
+                            selectors.push(WString::from_buf(b"a:link".to_vec()));
+                            element.link = Some((text.len(), spans.len()));
+                        }
+
+                        for selector in selectors {
+                            if let Some(style) = style_sheet.get_style(&selector) {
+                                format = style.format.clone().mix_with(format);
+                                match style.display {
+                                    Some(TextDisplay::Block) => element.is_block = is_multiline,
+                                    Some(TextDisplay::Inline) => element.is_block = false,
+                                    Some(TextDisplay::None) => element.is_hidden = true,
+                                    None => {}
+                                }
+                            }
+                        }
+                    }
+                    if element.is_hidden {
+                        hidden_depth += 1;
+                    }
+                    element_stack.push(element);
                     format_stack.push(format);
                 }
+                Ok(Event::Text(_)) if hidden_depth > 0 => {}
                 Ok(Event::Text(e)) if !e.is_empty() => {
                     let e = decode_to_wstr(&e.into_inner());
                     let e = process_html_entity(&e).unwrap_or(e);
@@ -780,18 +860,38 @@ impl FormatSpans {
                         None => continue,
                     }
 
-                    match &e.name().into_inner().to_ascii_lowercase()[..] {
-                        b"br" | b"sbr" => {
-                            // Skip pop from `format_stack`.
-                            continue;
+                    if matches!(
+                        &e.name().into_inner().to_ascii_lowercase()[..],
+                        b"br" | b"sbr"
+                    ) {
+                        // Skip pop from `format_stack`.
+                        continue;
+                    }
+
+                    if let Some(element) = element_stack.pop() {
+                        if element.is_hidden {
+                            hidden_depth -= 1;
                         }
-                        b"p" | b"li" if is_multiline => {
+                        if element.is_block && hidden_depth == 0 {
                             text.push_byte(b'\n');
                             if let Some(span) = spans.last_mut() {
                                 span.span_length += 1;
                             }
                         }
-                        _ => {}
+                        if let (Some((style_sheet, Some(hovered_position))), Some(link)) =
+                            (style, element.link)
+                        {
+                            let (link_start, first_span) = link;
+                            if (link_start..text.len()).contains(&hovered_position) {
+                                if let Some(hover) =
+                                    style_sheet.get_style(WStr::from_units(b"a:hover"))
+                                {
+                                    for span in &mut spans[first_span..] {
+                                        span.set_text_format(&hover.format);
+                                    }
+                                }
+                            }
+                        }
                     }
                     format_stack.pop();
                 }
@@ -30,6 +30,7 @@ mod drawing;
 mod ecma_conversions;
 pub(crate) mod either;
 pub mod events;
+mod file_reference;
 pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
@@ -37,15 +38,21 @@ mod html;
 mod library;
 pub mod limits;
 pub mod loader;
+mod local_connection;
 mod locale;
+mod net_connection;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+mod print_job;
 pub mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+mod text_engine;
+mod text_snapshot;
 pub mod timer;
+mod touch_tracker;
 mod types;
 mod vminterface;
 mod xml;
@@ -62,6 +69,7 @@ pub mod stub;
 pub use avm1::globals::system::SandboxType;
 pub use context_menu::ContextMenuItem;
 pub use events::PlayerEvent;
+pub use font::DefaultFont;
 pub use indexmap;
 pub use loader::LoadBehavior;
 pub use player::{Player, PlayerBuilder, StaticCallstack};
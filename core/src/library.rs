@@ -4,11 +4,12 @@ use crate::backend::audio::SoundHandle;
 use crate::character::Character;
 
 use crate::display_object::{Bitmap, Graphic, MorphShape, TDisplayObject, Text};
-use crate::font::{Font, FontDescriptor};
+use crate::font::{DefaultFont, Font, FontDescriptor, FontType};
 use crate::prelude::*;
-use crate::string::AvmString;
+use crate::string::{AvmString, WStr};
 use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Mutation};
+use indexmap::IndexMap;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::utils::remove_invalid_jpeg_data;
@@ -255,6 +256,25 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Returns the named fonts with glyphs defined by `DefineFont` tags in this
+    /// movie, in order of character ID.
+    pub fn embedded_fonts(&self) -> Vec<Font<'gc>> {
+        let mut fonts: Vec<_> = self
+            .characters
+            .iter()
+            .filter_map(|(id, character)| match character {
+                Character::Font(font)
+                    if font.has_glyphs() && !font.descriptor().class().is_empty() =>
+                {
+                    Some((*id, *font))
+                }
+                _ => None,
+            })
+            .collect();
+        fonts.sort_by_key(|(id, _)| *id);
+        fonts.into_iter().map(|(_, font)| font).collect()
+    }
+
     /// Find a font by it's name and parameters.
     pub fn get_font_by_name(
         &self,
@@ -391,6 +411,21 @@ pub struct Library<'gc> {
     /// The embedded device font.
     device_font: Option<Font<'gc>>,
 
+    /// The device fonts loaded from font files, by lowercase family name and
+    /// in order of registration.
+    device_fonts: IndexMap<String, Vec<Font<'gc>>>,
+
+    /// The device fonts to use for each default font, in order of preference.
+    default_fonts: HashMap<DefaultFont, Vec<String>>,
+
+    /// The device fonts to use when a font is unavailable or lacks glyphs, in
+    /// order of preference.
+    fallback_fonts: Vec<String>,
+
+    /// The fonts registered with `Font.registerFont`, which are available to
+    /// every movie, in order of registration.
+    registered_fonts: IndexMap<FontDescriptor, Font<'gc>>,
+
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
@@ -403,6 +438,12 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
             val.trace(cc);
         }
         self.device_font.trace(cc);
+        for fonts in self.device_fonts.values() {
+            fonts.trace(cc);
+        }
+        for font in self.registered_fonts.values() {
+            font.trace(cc);
+        }
         self.avm2_class_registry.trace(cc);
     }
 }
@@ -412,6 +453,10 @@ impl<'gc> Library<'gc> {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
+            device_fonts: IndexMap::new(),
+            default_fonts: HashMap::new(),
+            fallback_fonts: Vec::new(),
+            registered_fonts: IndexMap::new(),
             avm2_class_registry: Default::default(),
         }
     }
@@ -432,6 +477,16 @@ impl<'gc> Library<'gc> {
         self.movie_libraries.keys().collect()
     }
 
+    /// Returns the libraries of the movies whose AVM2 code runs in the given domain.
+    pub fn libraries_for_avm2_domain(
+        &self,
+        domain: Avm2Domain<'gc>,
+    ) -> impl Iterator<Item = &MovieLibrary<'gc>> + '_ {
+        self.movie_libraries
+            .values()
+            .filter(move |library| library.try_avm2_domain() == Some(domain))
+    }
+
     /// Returns the device font for use when a font is unavailable.
     pub fn device_font(&self) -> Option<Font<'gc>> {
         self.device_font
@@ -442,6 +497,133 @@ impl<'gc> Library<'gc> {
         self.device_font = Some(font);
     }
 
+    /// Registers a device font loaded from a font file.
+    pub fn register_device_font(&mut self, font: Font<'gc>) {
+        let name = font.descriptor().class().to_lowercase();
+        self.device_fonts.entry(name).or_default().push(font);
+    }
+
+    /// Returns the device fonts loaded from font files, in order of registration.
+    pub fn device_fonts(&self) -> impl Iterator<Item = Font<'gc>> + '_ {
+        self.device_fonts.values().flatten().copied()
+    }
+
+    /// Registers a font with `Font.registerFont`, making it available to every
+    /// movie.
+    pub fn register_font(&mut self, font: Font<'gc>) {
+        self.registered_fonts
+            .insert(font.descriptor().clone(), font);
+    }
+
+    /// Returns the fonts registered with `Font.registerFont`, in order of registration.
+    pub fn registered_fonts(&self) -> impl Iterator<Item = Font<'gc>> + '_ {
+        self.registered_fonts.values().copied()
+    }
+
+    /// Find a font registered with `Font.registerFont` for use by a text field,
+    /// by its name and style.
+    pub fn get_registered_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        self.registered_fonts
+            .get(&descriptor)
+            .into_iter()
+            .chain(
+                self.registered_fonts
+                    .values()
+                    .filter(|font| font.descriptor().class() == name),
+            )
+            .find(|font| font.font_type() == FontType::Embedded)
+            .copied()
+    }
+
+    /// Sets the device fonts used for a default font, in order of preference.
+    pub fn set_default_font(&mut self, font: DefaultFont, names: Vec<String>) {
+        self.default_fonts.insert(font, names);
+    }
+
+    /// Sets the device fonts used when a font is unavailable or lacks glyphs,
+    /// in order of preference.
+    pub fn set_fallback_fonts(&mut self, names: Vec<String>) {
+        self.fallback_fonts = names;
+    }
+
+    /// Returns the device font with the given name, preferring the given style.
+    fn get_device_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let fonts = self.device_fonts.get(&name.to_lowercase())?;
+        Self::best_style_match(fonts, is_bold, is_italic)
+    }
+
+    fn best_style_match(fonts: &[Font<'gc>], is_bold: bool, is_italic: bool) -> Option<Font<'gc>> {
+        fonts
+            .iter()
+            .max_by_key(|font| {
+                let descriptor = font.descriptor();
+                (
+                    descriptor.bold() == is_bold,
+                    descriptor.italic() == is_italic,
+                )
+            })
+            .copied()
+    }
+
+    /// Returns the device font to use for rendering `text` with the given font.
+    ///
+    /// The requested font is tried first, followed by the fallback fonts, any
+    /// other registered device font, and finally the embedded device font.
+    /// The first of them containing glyphs for all of `text` is picked, or the
+    /// first of them at all if none do.
+    pub fn get_device_font(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+        text: &WStr,
+    ) -> Option<Font<'gc>> {
+        let requested = match DefaultFont::from_name(name) {
+            Some(default_font) => self
+                .default_fonts
+                .get(&default_font)
+                .map(|names| names.iter().map(String::as_str).collect())
+                .unwrap_or_default(),
+            None => vec![name],
+        };
+
+        let candidates = requested
+            .into_iter()
+            .chain(self.fallback_fonts.iter().map(String::as_str))
+            .filter_map(|name| self.get_device_font_by_name(name, is_bold, is_italic))
+            .chain(
+                self.device_fonts
+                    .values()
+                    .filter_map(|fonts| Self::best_style_match(fonts, is_bold, is_italic)),
+            )
+            .chain(self.device_font);
+
+        let mut first = None;
+        for font in candidates {
+            let has_all_glyphs = text
+                .chars()
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .filter(|c| !c.is_control())
+                .all(|c| font.get_glyph_for_char(c).is_some());
+            if has_all_glyphs {
+                return Some(font);
+            }
+            first.get_or_insert(font);
+        }
+        first
+    }
+
     /// Get the AVM2 class registry.
     pub fn avm2_class_registry(&self) -> &Avm2ClassRegistry<'gc> {
         &self.avm2_class_registry
@@ -452,3 +634,170 @@ impl<'gc> Library<'gc> {
         &mut self.avm2_class_registry
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gc_arena::rootless_arena;
+    use ruffle_render::backend::{null::NullRenderer, ViewportDimensions};
+    use swf::SwfStr;
+
+    /// Creates a font with an empty glyph for each of `chars`.
+    fn font<'gc>(
+        mc: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        name: &str,
+        is_bold: bool,
+        chars: &str,
+    ) -> Font<'gc> {
+        let mut flags = swf::FontFlag::HAS_WIDE_CODES;
+        flags.set(swf::FontFlag::IS_BOLD, is_bold);
+        let tag = swf::Font {
+            version: 3,
+            id: 0,
+            name: SwfStr::from_utf8_str(name),
+            language: swf::Language::Unknown,
+            layout: None,
+            glyphs: chars
+                .chars()
+                .map(|c| swf::Glyph {
+                    shape_records: vec![],
+                    code: c as u16,
+                    advance: 0,
+                    bounds: None,
+                })
+                .collect(),
+            flags,
+        };
+        Font::from_swf_tag(mc, renderer, tag, swf::UTF_8)
+    }
+
+    /// Runs a test with a library holding device fonts with the given name, boldness and glyphs.
+    fn with_device_fonts<F>(fonts: &[(&str, bool, &str)], test: F)
+    where
+        F: for<'gc> FnOnce(&Mutation<'gc>, &mut dyn RenderBackend, &mut Library<'gc>),
+    {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new(ViewportDimensions {
+                width: 0,
+                height: 0,
+                scale_factor: 1.0,
+            });
+            let mut library = Library::empty();
+            for &(name, is_bold, chars) in fonts {
+                library.register_device_font(font(mc, &mut renderer, name, is_bold, chars));
+            }
+            test(mc, &mut renderer, &mut library);
+        })
+    }
+
+    fn assert_font(font: Option<Font<'_>>, name: &str, is_bold: bool) {
+        let font = font.expect("A device font should be found");
+        assert_eq!(font.descriptor().class(), name);
+        assert_eq!(font.descriptor().bold(), is_bold);
+    }
+
+    #[test]
+    fn device_font_by_name_and_style() {
+        let fonts = [
+            ("Alpha", false, "a"),
+            ("Alpha", true, "a"),
+            ("Beta", false, "a"),
+        ];
+        with_device_fonts(&fonts, |_mc, _renderer, library| {
+            let text = WStr::from_units(b"a");
+            assert_font(
+                library.get_device_font("alpha", true, false, text),
+                "Alpha",
+                true,
+            );
+            assert_font(
+                library.get_device_font("ALPHA", false, false, text),
+                "Alpha",
+                false,
+            );
+            // The closest style is used when the requested one is missing.
+            assert_font(
+                library.get_device_font("Beta", true, false, text),
+                "Beta",
+                false,
+            );
+        });
+    }
+
+    #[test]
+    fn default_fonts_in_order_of_preference() {
+        let fonts = [("Alpha", false, "a"), ("Beta", false, "a")];
+        with_device_fonts(&fonts, |_mc, _renderer, library| {
+            library.set_default_font(DefaultFont::Sans, vec!["Missing".into(), "Beta".into()]);
+            let text = WStr::from_units(b"a");
+            assert_font(
+                library.get_device_font("_sans", false, false, text),
+                "Beta",
+                false,
+            );
+            // Without any configured font, the first registered device font is used.
+            assert_font(
+                library.get_device_font("_serif", false, false, text),
+                "Alpha",
+                false,
+            );
+        });
+    }
+
+    #[test]
+    fn fallback_fonts_for_missing_glyphs() {
+        let fonts = [
+            ("Alpha", false, "a"),
+            ("Beta", false, "ax"),
+            ("Gamma", false, "a"),
+        ];
+        with_device_fonts(&fonts, |_mc, _renderer, library| {
+            library.set_fallback_fonts(vec!["Gamma".into(), "Beta".into()]);
+            assert_font(
+                library.get_device_font("Alpha", false, false, WStr::from_units(b"ax")),
+                "Beta",
+                false,
+            );
+            assert_font(
+                library.get_device_font("Missing", false, false, WStr::from_units(b"a")),
+                "Gamma",
+                false,
+            );
+            // The requested font is used when no font has all the glyphs.
+            assert_font(
+                library.get_device_font("Alpha", false, false, WStr::from_units(b"yz")),
+                "Alpha",
+                false,
+            );
+        });
+    }
+
+    #[test]
+    fn embedded_device_font_comes_last() {
+        with_device_fonts(&[], |mc, renderer, library| {
+            assert!(library
+                .get_device_font("Alpha", false, false, WStr::from_units(b"a"))
+                .is_none());
+            library.set_device_font(font(mc, renderer, "Noto Sans", false, "az"));
+            assert_font(
+                library.get_device_font("Alpha", false, false, WStr::from_units(b"a")),
+                "Noto Sans",
+                false,
+            );
+        });
+        with_device_fonts(&[("Alpha", false, "a")], |mc, renderer, library| {
+            library.set_device_font(font(mc, renderer, "Noto Sans", false, "az"));
+            assert_font(
+                library.get_device_font("Alpha", false, false, WStr::from_units(b"a")),
+                "Alpha",
+                false,
+            );
+            assert_font(
+                library.get_device_font("Alpha", false, false, WStr::from_units(b"z")),
+                "Noto Sans",
+                false,
+            );
+        });
+    }
+}
@@ -12,6 +12,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
     Value as Avm2Value,
 };
+use crate::backend::file_dialog::{DialogFuture, SaveTarget, SelectedFile};
 use crate::backend::navigator::{OwnedFuture, Request};
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
@@ -20,8 +21,10 @@ use crate::display_object::{
     DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
 use crate::events::ClipEvent;
+use crate::file_reference::{FileReferenceEvent, FileReferenceKind, FileSaveSource};
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
+use crate::net_connection::NetConnectionCall;
 use crate::player::Player;
 use crate::streams::NetStream;
 use crate::string::AvmString;
@@ -153,6 +156,12 @@ pub enum Error {
     #[error("Non-NetStream loader spawned as NetStream loader")]
     NotNetStreamLoader,
 
+    #[error("Non-NetConnection loader spawned as NetConnection call loader")]
+    NotNetConnectionCallLoader,
+
+    #[error("Non-FileReference loader spawned as FileReference loader")]
+    NotFileReferenceLoader,
+
     #[error("Other Loader spawned as Movie unloader")]
     NotMovieUnloader,
 
@@ -228,6 +237,8 @@ impl<'gc> LoadManager<'gc> {
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
+            | Loader::NetConnectionCall { self_handle, .. }
+            | Loader::FileReference { self_handle, .. }
             | Loader::MovieUnloader { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
@@ -439,6 +450,104 @@ impl<'gc> LoadManager<'gc> {
         loader.stream_loader(player, request)
     }
 
+    /// Kick off a Flash Remoting call through a `NetConnection`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn call_net_connection(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        call: NetConnectionCall<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::NetConnectionCall {
+            self_handle: None,
+            call,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.net_connection_call(player, request)
+    }
+
+    /// Wait for the user to pick a file to open with a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn browse_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceKind<'gc>,
+        dialog: DialogFuture<SelectedFile>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_reference_browse(player, dialog)
+    }
+
+    /// Give the contents of the file picked by a `FileReference` to its `data` property.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceKind<'gc>,
+        size: usize,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_reference_load(player, size)
+    }
+
+    /// Save a file where the user picked with a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn save_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceKind<'gc>,
+        dialog: DialogFuture<Box<dyn SaveTarget>>,
+        source: FileSaveSource,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_reference_save(player, dialog, source)
+    }
+
+    /// Upload the file picked by a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn upload_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceKind<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_reference_upload(player, request)
+    }
+
+    /// Stop the operations of a `FileReference`, without reporting their outcome.
+    pub fn cancel_file_reference(&mut self, target: FileReferenceKind<'gc>) {
+        self.0.retain(|_, loader| {
+            !matches!(loader, Loader::FileReference { target_object, .. } if target_object.ptr_eq(target))
+        });
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
@@ -603,6 +712,26 @@ pub enum Loader<'gc> {
         target_stream: NetStream<'gc>,
     },
 
+    /// Loader that is waiting for the response to a `NetConnection.call`.
+    NetConnectionCall {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `NetConnection` that made the call, and where to give the results.
+        call: NetConnectionCall<'gc>,
+    },
+
+    /// Loader that is opening, saving or transferring a file with a `FileReference`.
+    FileReference {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `FileReference` reporting the progress of the operation.
+        target_object: FileReferenceKind<'gc>,
+    },
+
     /// Loader that is unloading a MovieClip.
     MovieUnloader {
         /// The handle to refer to this loader instance.
@@ -1394,6 +1523,7 @@ impl<'gc> Loader<'gc> {
                     Ok(mut response) => {
                         stream.reset_buffer(uc);
                         stream.load_buffer(uc, &mut response.body);
+                        stream.finish_buffer(uc.gc_context);
                     }
                     Err(response) => {
                         stream.report_error(response.error);
@@ -1405,6 +1535,281 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a `NetConnection.call` request.
+    fn net_connection_call(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::NetConnectionCall { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotNetConnectionCallLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            player.lock().unwrap().update(|uc| {
+                let loader = uc.load_manager.get_loader(handle);
+                let call = match loader {
+                    Some(&Loader::NetConnectionCall { call, .. }) => call,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotNetConnectionCallLoader),
+                };
+                uc.load_manager.remove_loader(handle);
+
+                let data = match &response {
+                    Ok(response) => Some(response.body.as_slice()),
+                    Err(response) => {
+                        tracing::warn!("NetConnection call failed: {}", response.error);
+                        None
+                    }
+                };
+
+                call.receive_response(uc, data);
+                Ok(())
+            })
+        })
+    }
+
+    /// Get the target of a `FileReference` loader, which is removed if `finished` is set.
+    fn file_reference_target(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+        finished: bool,
+    ) -> Result<FileReferenceKind<'gc>, Error> {
+        let target = match uc.load_manager.get_loader(handle) {
+            Some(&Loader::FileReference { target_object, .. }) => target_object,
+            // The operation was cancelled by `FileReference.cancel`.
+            None => return Err(Error::Cancelled),
+            _ => return Err(Error::NotFileReferenceLoader),
+        };
+        if finished {
+            uc.load_manager.remove_loader(handle);
+        }
+        Ok(target)
+    }
+
+    /// Creates a future for a `FileReference.browse` call.
+    fn file_reference_browse(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: DialogFuture<SelectedFile>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let file = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Self::file_reference_target(uc, handle, true)?;
+                match file {
+                    Some(file) => {
+                        target.set_file(uc, file);
+                        target.dispatch(uc, FileReferenceEvent::Select);
+                    }
+                    None => target.dispatch(uc, FileReferenceEvent::Cancel),
+                }
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future for a `FileReference.load` call.
+    fn file_reference_load(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        size: usize,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            // The file was already read when it was picked.
+            player.lock().unwrap().update(|uc| {
+                let target = Self::file_reference_target(uc, handle, true)?;
+                target.dispatch(uc, FileReferenceEvent::Open);
+                target.dispatch(
+                    uc,
+                    FileReferenceEvent::Progress {
+                        bytes_loaded: size,
+                        bytes_total: size,
+                    },
+                );
+                target.set_loaded(uc);
+                target.dispatch(uc, FileReferenceEvent::Complete);
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future for a `FileReference.save` or `FileReference.download` call.
+    fn file_reference_save(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: DialogFuture<Box<dyn SaveTarget>>,
+        source: FileSaveSource,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let Some(mut save_target) = dialog.await else {
+                return player.lock().unwrap().update(|uc| {
+                    let target = Self::file_reference_target(uc, handle, true)?;
+                    target.dispatch(uc, FileReferenceEvent::Cancel);
+                    Ok(())
+                });
+            };
+
+            let file_name = save_target.file_name();
+            let saved_file = match &source {
+                FileSaveSource::Data(data) => SelectedFile::new(file_name, data.clone()),
+                FileSaveSource::Download(_) => SelectedFile::new(file_name, Vec::new()),
+            };
+            player.lock().unwrap().update(|uc| {
+                let target = Self::file_reference_target(uc, handle, false)?;
+                target.set_file(uc, saved_file);
+                target.dispatch(uc, FileReferenceEvent::Select);
+                target.dispatch(uc, FileReferenceEvent::Open);
+                Ok(())
+            })?;
+
+            let data = match source {
+                FileSaveSource::Data(data) => data,
+                FileSaveSource::Download(request) => {
+                    let fetch = player.lock().unwrap().navigator().fetch(request);
+                    match fetch.await {
+                        Ok(response) => response.body,
+                        Err(response) => {
+                            return player.lock().unwrap().update(|uc| {
+                                let target = Self::file_reference_target(uc, handle, true)?;
+                                if let Error::HttpNotOk(_, status, _) = response.error {
+                                    target.dispatch(uc, FileReferenceEvent::HttpStatus(status));
+                                }
+                                target.dispatch(uc, FileReferenceEvent::IoError);
+                                Ok(())
+                            });
+                        }
+                    }
+                }
+            };
+
+            let result = save_target.write(&data);
+
+            player.lock().unwrap().update(|uc| {
+                let target = Self::file_reference_target(uc, handle, true)?;
+                match result {
+                    Ok(()) => {
+                        target.dispatch(
+                            uc,
+                            FileReferenceEvent::Progress {
+                                bytes_loaded: data.len(),
+                                bytes_total: data.len(),
+                            },
+                        );
+                        target.dispatch(uc, FileReferenceEvent::Complete);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Unable to save file: {}", e);
+                        target.dispatch(uc, FileReferenceEvent::IoError);
+                    }
+                }
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future for a `FileReference.upload` call.
+    fn file_reference_upload(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileReference { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileReferenceLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let size = request.body().as_ref().map_or(0, |(body, _)| body.len());
+
+            player.lock().unwrap().update(|uc| {
+                let target = Self::file_reference_target(uc, handle, false)?;
+                target.dispatch(uc, FileReferenceEvent::Open);
+                Ok(())
+            })?;
+
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Self::file_reference_target(uc, handle, true)?;
+                match response {
+                    Ok(response) => {
+                        target.dispatch(
+                            uc,
+                            FileReferenceEvent::Progress {
+                                bytes_loaded: size,
+                                bytes_total: size,
+                            },
+                        );
+                        target.dispatch(uc, FileReferenceEvent::Complete);
+                        target.dispatch(uc, FileReferenceEvent::UploadCompleteData(&response.body));
+                    }
+                    Err(response) => {
+                        let event = match response.error {
+                            Error::HttpNotOk(_, status, _) => {
+                                FileReferenceEvent::HttpStatus(status)
+                            }
+                            _ => FileReferenceEvent::IoError,
+                        };
+                        target.dispatch(uc, event);
+                    }
+                }
+                Ok(())
+            })
+        })
+    }
+
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
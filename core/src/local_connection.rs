@@ -0,0 +1,365 @@
+use crate::avm1::globals::local_connection as avm1_local_connection;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, ExecutionReason, Object as Avm1Object,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject,
+};
+use crate::avm2::globals::flash::net::local_connection as avm2_local_connection;
+use crate::avm2::{Activation as Avm2Activation, Avm2, Object as Avm2Object, Value as Avm2Value};
+use crate::backend::local_connection::{LocalConnectionBackend, LocalConnectionMessage};
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+
+/// The largest size of the serialized arguments of a message.
+pub const MAX_MESSAGE_SIZE: usize = 40 * 1024;
+
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub enum LocalConnectionKind<'gc> {
+    Avm2(Avm2Object<'gc>),
+    Avm1(Avm1Object<'gc>),
+}
+
+impl<'gc> LocalConnectionKind<'gc> {
+    fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Avm2(a), Self::Avm2(b)) => Avm2Object::ptr_eq(a, b),
+            (Self::Avm1(a), Self::Avm1(b)) => a.as_ptr() == b.as_ptr(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct LocalConnection<'gc> {
+    target: LocalConnectionKind<'gc>,
+
+    /// The fully qualified name this connection is listening on.
+    #[collect(require_static)]
+    name: String,
+
+    /// The domain of the movie that opened this connection.
+    #[collect(require_static)]
+    domain: String,
+}
+
+/// Manages the `LocalConnection` objects of this player.
+///
+/// The connections themselves live in the `LocalConnectionBackend`, which may be
+/// shared with other players. This keeps track of which objects are listening,
+/// and of the status events owed to the objects that sent messages.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct LocalConnections<'gc> {
+    connections: Vec<LocalConnection<'gc>>,
+
+    /// Senders waiting to be told whether their message found a listener.
+    pending_statuses: Vec<(LocalConnectionKind<'gc>, bool)>,
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn empty() -> Self {
+        Self {
+            connections: Vec::new(),
+            pending_statuses: Vec::new(),
+        }
+    }
+
+    /// The domain of a movie, as used by `LocalConnection.domain` and to qualify connection names.
+    ///
+    /// Returns `None` if the URL of the movie is invalid.
+    pub fn get_domain(url: &str) -> Option<String> {
+        let url = url::Url::parse(url).ok()?;
+        if url.scheme() == "file" {
+            return Some("localhost".to_string());
+        }
+        // A URL without a domain is treated as local too.
+        Some(url.domain().unwrap_or("localhost").to_string())
+    }
+
+    /// Build the name used by the backend for a connection.
+    ///
+    /// Names starting with an underscore are global. Other names are prefixed with the
+    /// domain of the movie, unless the sender explicitly targets a domain with `domain:name`.
+    pub fn get_connection_name(domain: &str, name: &str) -> String {
+        let name = if name.starts_with('_') || name.contains(':') {
+            name.to_string()
+        } else {
+            format!("{domain}:{name}")
+        };
+        name.to_lowercase()
+    }
+
+    pub fn is_connected(&self, target: LocalConnectionKind<'gc>) -> bool {
+        self.connections
+            .iter()
+            .any(|connection| connection.target.ptr_eq(target))
+    }
+
+    /// Start listening on the given connection name.
+    ///
+    /// Returns `false` if the object is already connected, or if the name is in use.
+    pub fn connect(
+        &mut self,
+        backend: &mut dyn LocalConnectionBackend,
+        target: LocalConnectionKind<'gc>,
+        domain: &str,
+        name: &str,
+    ) -> bool {
+        if self.is_connected(target) {
+            return false;
+        }
+
+        let name = Self::get_connection_name(domain, name);
+        if !backend.connect(&name) {
+            return false;
+        }
+
+        self.connections.push(LocalConnection {
+            target,
+            name,
+            domain: domain.to_string(),
+        });
+        true
+    }
+
+    /// Stop listening for messages.
+    ///
+    /// Returns `false` if the object wasn't connected.
+    pub fn close(
+        &mut self,
+        backend: &mut dyn LocalConnectionBackend,
+        target: LocalConnectionKind<'gc>,
+    ) -> bool {
+        let Some(index) = self
+            .connections
+            .iter()
+            .position(|connection| connection.target.ptr_eq(target))
+        else {
+            return false;
+        };
+
+        let connection = self.connections.remove(index);
+        backend.close(&connection.name);
+        true
+    }
+
+    /// Stop listening on every connection, such as when the player is destroyed.
+    pub fn close_all(&mut self, backend: &mut dyn LocalConnectionBackend) {
+        for connection in self.connections.drain(..) {
+            backend.close(&connection.name);
+        }
+        self.pending_statuses.clear();
+    }
+
+    /// Send a message to the listener of a connection.
+    ///
+    /// The sender receives a status once the current script is done.
+    pub fn send(
+        &mut self,
+        backend: &mut dyn LocalConnectionBackend,
+        sender: LocalConnectionKind<'gc>,
+        name: &str,
+        message: LocalConnectionMessage,
+    ) {
+        let name = Self::get_connection_name(&message.sender_domain, name);
+        let delivered = backend.send(&name, message);
+        self.pending_statuses.push((sender, delivered));
+    }
+
+    pub fn update_local_connections(context: &mut UpdateContext<'_, 'gc>) {
+        for (target, delivered) in std::mem::take(&mut context.local_connections.pending_statuses) {
+            let level = if delivered { "status" } else { "error" };
+            match target {
+                LocalConnectionKind::Avm2(target) => {
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    let status_evt = activation
+                        .avm2()
+                        .classes()
+                        .statusevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "status".into(),
+                                false.into(),
+                                false.into(),
+                                Avm2Value::Null,
+                                level.into(),
+                            ],
+                        )
+                        .expect("StatusEvent should be constructed");
+
+                    Avm2::dispatch_event(&mut activation.context, status_evt, target);
+                }
+                LocalConnectionKind::Avm1(target) => {
+                    let mut activation = Avm1Activation::from_stub(
+                        context.reborrow(),
+                        ActivationIdentifier::root("[LocalConnection]"),
+                    );
+
+                    let info = Avm1ScriptObject::new(
+                        activation.context.gc_context,
+                        Some(activation.context.avm1.prototypes().object),
+                    );
+                    let _ = info.set("level", level.into(), &mut activation);
+                    let _ = target.call_method(
+                        "onStatus".into(),
+                        &[info.into()],
+                        &mut activation,
+                        ExecutionReason::Special,
+                    );
+                }
+            }
+        }
+
+        let mut messages = vec![];
+        for connection in &context.local_connections.connections {
+            for message in context.local_connection.receive(&connection.name) {
+                messages.push((connection.target, connection.domain.clone(), message));
+            }
+        }
+
+        for (target, domain, message) in messages {
+            // The listener might have closed its connection while handling a previous message.
+            if !context.local_connections.is_connected(target) {
+                continue;
+            }
+
+            match target {
+                LocalConnectionKind::Avm2(target) => {
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    if let Err(e) = avm2_local_connection::receive_message(
+                        &mut activation,
+                        target,
+                        &domain,
+                        &message,
+                    ) {
+                        tracing::error!(
+                            "Unhandled error delivering LocalConnection message `{}`: {:?}",
+                            message.method_name,
+                            e
+                        );
+                    }
+                }
+                LocalConnectionKind::Avm1(target) => {
+                    let mut activation = Avm1Activation::from_stub(
+                        context.reborrow(),
+                        ActivationIdentifier::root("[LocalConnection]"),
+                    );
+                    avm1_local_connection::receive_message(
+                        &mut activation,
+                        target,
+                        &domain,
+                        &message,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::local_connection::MemoryLocalConnectionBackend;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+
+    fn message() -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            sender_domain: "localhost".to_string(),
+            method_name: "ping".to_string(),
+            arguments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn connect_send_close() {
+        let mut backend = MemoryLocalConnectionBackend::new();
+        let player = PlayerBuilder::new()
+            .with_movie(SwfMovie::empty(10))
+            .with_local_connection(backend.clone())
+            .build();
+
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let listener = LocalConnectionKind::Avm1(
+                    Avm1ScriptObject::new(context.gc_context, None).into(),
+                );
+                let sender = LocalConnectionKind::Avm1(
+                    Avm1ScriptObject::new(context.gc_context, None).into(),
+                );
+                let backend_ref = &mut *context.local_connection;
+                let connections = &mut *context.local_connections;
+
+                assert!(connections.connect(backend_ref, listener, "localhost", "test"));
+                assert!(connections.is_connected(listener));
+                assert!(!connections.connect(backend_ref, listener, "localhost", "other"));
+                // Names are case insensitive.
+                assert!(!connections.connect(backend_ref, sender, "localhost", "TEST"));
+
+                connections.send(backend_ref, sender, "test", message());
+                connections.send(backend_ref, sender, "missing", message());
+                let statuses: Vec<_> = connections
+                    .pending_statuses
+                    .iter()
+                    .map(|(_, delivered)| *delivered)
+                    .collect();
+                assert_eq!(statuses, [true, false]);
+                assert_eq!(backend.receive("localhost:test").len(), 1);
+
+                assert!(connections.close(backend_ref, listener));
+                assert!(!connections.close(backend_ref, listener));
+                assert!(!connections.is_connected(listener));
+            });
+
+        assert!(!backend.send("localhost:test", message()));
+    }
+
+    #[test]
+    fn dropping_player_closes_connections() {
+        let mut backend = MemoryLocalConnectionBackend::new();
+        let player = PlayerBuilder::new()
+            .with_movie(SwfMovie::empty(10))
+            .with_local_connection(backend.clone())
+            .build();
+
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let listener = LocalConnectionKind::Avm1(
+                    Avm1ScriptObject::new(context.gc_context, None).into(),
+                );
+                assert!(context.local_connections.connect(
+                    context.local_connection,
+                    listener,
+                    "localhost",
+                    "test"
+                ));
+            });
+        assert!(backend.send("localhost:test", message()));
+
+        drop(player);
+        assert!(!backend.send("localhost:test", message()));
+        assert!(backend.connect("localhost:test"));
+    }
+
+    #[test]
+    fn connection_names() {
+        assert_eq!(
+            LocalConnections::get_connection_name("example.com", "Name"),
+            "example.com:name"
+        );
+        assert_eq!(
+            LocalConnections::get_connection_name("example.com", "_Global"),
+            "_global"
+        );
+        assert_eq!(
+            LocalConnections::get_connection_name("example.com", "other.org:name"),
+            "other.org:name"
+        );
+    }
+}
@@ -0,0 +1,626 @@
+//! Flash Remoting: the AMF packets exchanged with a gateway by `NetConnection.call`.
+
+use crate::avm1::globals::net_connection as avm1_net_connection;
+use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier, Object as Avm1Object};
+use crate::avm2::globals::flash::net::net_connection as avm2_net_connection;
+use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object};
+use crate::context::UpdateContext;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use gc_arena::Collect;
+
+/// The `NetConnection` that made a call, and the object its results are given to.
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub enum NetConnectionCall<'gc> {
+    /// An AVM2 `NetConnection` and its `Responder`.
+    Avm2 {
+        connection: Avm2Object<'gc>,
+        responder: Option<Avm2Object<'gc>>,
+    },
+
+    /// An AVM1 `NetConnection` and the object with `onResult` and `onStatus` handlers.
+    Avm1 {
+        connection: Avm1Object<'gc>,
+        result_object: Option<Avm1Object<'gc>>,
+    },
+}
+
+impl<'gc> NetConnectionCall<'gc> {
+    /// Handle the response of the gateway to this call.
+    ///
+    /// `data` is `None` if the request failed.
+    pub fn receive_response(self, context: &mut UpdateContext<'_, 'gc>, data: Option<&[u8]>) {
+        match self {
+            NetConnectionCall::Avm2 {
+                connection,
+                responder,
+            } => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                if let Err(e) = avm2_net_connection::receive_call_response(
+                    &mut activation,
+                    connection,
+                    responder,
+                    data,
+                ) {
+                    tracing::error!("Unhandled error in NetConnection call response: {:?}", e);
+                }
+            }
+            NetConnectionCall::Avm1 {
+                connection,
+                result_object,
+            } => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    ActivationIdentifier::root("[NetConnection]"),
+                );
+                if let Err(e) = avm1_net_connection::receive_call_response(
+                    &mut activation,
+                    connection,
+                    result_object,
+                    data,
+                ) {
+                    tracing::error!("Unhandled error in NetConnection call response: {:?}", e);
+                }
+            }
+        }
+    }
+}
+
+/// The content type of AMF packets.
+pub const AMF_CONTENT_TYPE: &str = "application/x-amf";
+
+/// The AMF0 marker switching the rest of a value to AMF3.
+const AVMPLUS_MARKER: u8 = 0x11;
+
+/// The AMF0 marker of a strict array.
+const STRICT_ARRAY_MARKER: u8 = 0x0A;
+
+/// The length of values is optional in packets, this marks it as unknown.
+const UNKNOWN_LENGTH: u32 = u32::MAX;
+
+/// A header of an AMF packet, holding context for every message of the packet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmfHeader<T> {
+    pub name: String,
+    pub must_understand: bool,
+    pub value: T,
+}
+
+/// A message of an AMF packet: a call to a service, or the response to a call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmfMessage<T> {
+    /// The method to call, or for responses the name of the call and the
+    /// handler to invoke, such as `/1/onResult`.
+    pub target_uri: String,
+
+    /// The name given to the call, which identifies its response.
+    pub response_uri: String,
+
+    pub value: T,
+}
+
+/// Encode a single value, without the LSO structure around it.
+fn write_single_value(value: AmfValue, amf_version: AMFVersion) -> Option<Vec<u8>> {
+    let mut lso = Lso::new(vec![Element::new("", value)], "", amf_version);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).ok()?;
+    // Strip out the LSO header and the element padding, as done by `ByteArray.writeObject`.
+    let element_padding = match amf_version {
+        AMFVersion::AMF0 => 8,
+        AMFVersion::AMF3 => 7,
+    };
+    Some(
+        bytes[flash_lso::write::header_length(&lso.header) + element_padding..bytes.len() - 1]
+            .to_vec(),
+    )
+}
+
+/// Encode a value for an AMF packet.
+///
+/// AMF packets are made of AMF0 values, so AMF3 values are prefixed with the AVM+ marker.
+pub fn write_value(value: AmfValue, amf_version: AMFVersion) -> Option<Vec<u8>> {
+    match amf_version {
+        AMFVersion::AMF0 => write_single_value(value, amf_version),
+        AMFVersion::AMF3 => {
+            let mut bytes = vec![AVMPLUS_MARKER];
+            bytes.extend(write_single_value(value, amf_version)?);
+            Some(bytes)
+        }
+    }
+}
+
+/// Encode the arguments of a call, which are sent as a strict array.
+pub fn write_arguments(arguments: Vec<AmfValue>, amf_version: AMFVersion) -> Option<Vec<u8>> {
+    let mut bytes = vec![STRICT_ARRAY_MARKER];
+    bytes.extend((arguments.len() as u32).to_be_bytes());
+    for argument in arguments {
+        bytes.extend(write_value(argument, amf_version)?);
+    }
+    Some(bytes)
+}
+
+fn write_string(bytes: &mut Vec<u8>, string: &str) {
+    let string = &string.as_bytes()[..string.len().min(u16::MAX.into())];
+    bytes.extend((string.len() as u16).to_be_bytes());
+    bytes.extend(string);
+}
+
+/// Build an AMF packet from encoded headers and messages.
+pub fn write_packet(
+    amf_version: AMFVersion,
+    headers: &[AmfHeader<Vec<u8>>],
+    messages: &[AmfMessage<Vec<u8>>],
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let version: u16 = match amf_version {
+        AMFVersion::AMF0 => 0,
+        AMFVersion::AMF3 => 3,
+    };
+    bytes.extend(version.to_be_bytes());
+
+    bytes.extend((headers.len() as u16).to_be_bytes());
+    for header in headers {
+        write_string(&mut bytes, &header.name);
+        bytes.push(header.must_understand.into());
+        bytes.extend(UNKNOWN_LENGTH.to_be_bytes());
+        bytes.extend(&header.value);
+    }
+
+    bytes.extend((messages.len() as u16).to_be_bytes());
+    for message in messages {
+        write_string(&mut bytes, &message.target_uri);
+        write_string(&mut bytes, &message.response_uri);
+        bytes.extend(UNKNOWN_LENGTH.to_be_bytes());
+        bytes.extend(&message.value);
+    }
+
+    bytes
+}
+
+struct PacketReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PacketReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.read_bytes(2)?.try_into().ok()?))
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.read_bytes(4)?.try_into().ok()?))
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let len = self.read_u16()?;
+        let bytes = self.read_bytes(len.into())?;
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn read_value(&mut self) -> Option<AmfValue> {
+        // The length of values is often unknown, so rely on the decoder to find their end.
+        let _length = self.read_u32()?;
+        let (rest, value) = AMF0Decoder::default()
+            .parse_single_element(self.data)
+            .ok()?;
+        self.data = rest;
+        Some(value)
+    }
+}
+
+/// A decoded AMF packet.
+pub type AmfPacket = (Vec<AmfHeader<AmfValue>>, Vec<AmfMessage<AmfValue>>);
+
+/// Decode an AMF packet returned by a gateway.
+///
+/// Returns `None` if the packet is malformed.
+pub fn read_packet(data: &[u8]) -> Option<AmfPacket> {
+    let mut reader = PacketReader { data };
+    let version = reader.read_u16()?;
+    if version != 0 && version != 3 {
+        return None;
+    }
+
+    let header_count = reader.read_u16()?;
+    let mut headers = Vec::with_capacity(header_count.into());
+    for _ in 0..header_count {
+        let name = reader.read_string()?;
+        let must_understand = reader.read_u8()? != 0;
+        let value = reader.read_value()?;
+        headers.push(AmfHeader {
+            name,
+            must_understand,
+            value,
+        });
+    }
+
+    let message_count = reader.read_u16()?;
+    let mut messages = Vec::with_capacity(message_count.into());
+    for _ in 0..message_count {
+        let target_uri = reader.read_string()?;
+        let response_uri = reader.read_string()?;
+        let value = reader.read_value()?;
+        messages.push(AmfMessage {
+            target_uri,
+            response_uri,
+            value,
+        });
+    }
+
+    Some((headers, messages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::native_function;
+    use crate::avm2::{Error as Avm2Error, TObject as _, Value as Avm2Value};
+    use crate::backend::navigator::{
+        ErrorResponse, NavigationMethod, NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture,
+        Request, SuccessResponse,
+    };
+    use crate::loader::Error;
+    use crate::player::{Player, PlayerBuilder};
+    use crate::socket::{ConnectionState, SocketAction, SocketHandle};
+    use crate::tag_utils::SwfMovie;
+    use async_channel::Receiver;
+    use gc_arena::{DynamicRoot, Rootable};
+    use indexmap::IndexMap;
+    use std::cell::RefCell;
+    use std::sync::mpsc::Sender;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use url::{ParseError, Url};
+
+    const GATEWAY_URL: &str = "http://example.com/gateway";
+
+    /// A Flash Remoting gateway which answers `service.add` with a result and
+    /// `service.fail` with a status, asking for a session to be appended to
+    /// its URL. Other services aren't found.
+    struct GatewayNavigator(NullSpawner);
+
+    impl NavigatorBackend for GatewayNavigator {
+        fn navigate_to_url(
+            &self,
+            _url: &str,
+            _target: &str,
+            _vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+        ) {
+        }
+
+        fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+            let url = request.url().to_string();
+            let (body, content_type) = request.body().clone().expect("Calls should be posted");
+            assert_eq!(content_type, AMF_CONTENT_TYPE);
+            let (_, messages) = read_packet(&body).expect("Calls should be valid packets");
+            let call = &messages[0];
+            REQUESTS.with(|requests| {
+                requests
+                    .borrow_mut()
+                    .push((url.clone(), call.target_uri.clone()))
+            });
+
+            let (handler, value) = match call.target_uri.as_str() {
+                "service.add" => ("onResult", AmfValue::Number(3.0)),
+                "service.fail" => ("onStatus", AmfValue::String("Service failed".to_string())),
+                _ => {
+                    let response = ErrorResponse {
+                        url,
+                        error: Error::HttpNotOk("Not Found".to_string(), 404, false),
+                    };
+                    return Box::pin(async move { Err(response) });
+                }
+            };
+            let headers = [AmfHeader {
+                name: "AppendToGatewayUrl".to_string(),
+                must_understand: false,
+                value: write_value(AmfValue::String(";session=1".to_string()), AMFVersion::AMF0)
+                    .unwrap(),
+            }];
+            let messages = [AmfMessage {
+                target_uri: format!("{}/{handler}", call.response_uri),
+                response_uri: "".to_string(),
+                value: write_value(value, AMFVersion::AMF0).unwrap(),
+            }];
+            let response = SuccessResponse {
+                url,
+                body: write_packet(AMFVersion::AMF0, &headers, &messages),
+                status: 200,
+                redirected: false,
+            };
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+            Url::parse(url)
+        }
+
+        fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+            self.0.spawn_local(future);
+        }
+
+        fn pre_process_url(&self, url: Url) -> Url {
+            url
+        }
+
+        fn connect_socket(
+            &mut self,
+            _host: String,
+            _port: u16,
+            _timeout: Duration,
+            handle: SocketHandle,
+            _receiver: Receiver<Vec<u8>>,
+            sender: Sender<SocketAction>,
+        ) {
+            sender
+                .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+        }
+    }
+
+    thread_local! {
+        /// The URL and service of every call received by the gateway.
+        static REQUESTS: RefCell<Vec<(String, String)>> = RefCell::new(Vec::new());
+
+        /// The results, statuses and status event codes received by the movie.
+        static EVENTS: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    }
+
+    fn record_result<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        _this: Avm2Object<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        let result = args[0].coerce_to_string(activation)?;
+        EVENTS.with(|events| events.borrow_mut().push(format!("result {result}")));
+        Ok(Avm2Value::Undefined)
+    }
+
+    fn record_status<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        _this: Avm2Object<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        let status = args[0].coerce_to_string(activation)?;
+        EVENTS.with(|events| events.borrow_mut().push(format!("status {status}")));
+        Ok(Avm2Value::Undefined)
+    }
+
+    fn record_net_status<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        _this: Avm2Object<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        let event = args
+            .first()
+            .and_then(|event| event.as_object())
+            .expect("Listener should receive an event");
+        let code = event
+            .get_public_property("info", activation)?
+            .as_object()
+            .expect("Event should have an info object")
+            .get_public_property("code", activation)?
+            .coerce_to_string(activation)?;
+        EVENTS.with(|events| events.borrow_mut().push(code.to_string()));
+        Ok(Avm2Value::Undefined)
+    }
+
+    fn construct<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        class_name: &'static str,
+        args: &[Avm2Value<'gc>],
+    ) -> Avm2Object<'gc> {
+        let class = activation
+            .avm2()
+            .stage_domain()
+            .get_defined_value_handling_vector(activation, class_name.into())
+            .unwrap();
+        class
+            .as_object()
+            .expect("Class should be an object")
+            .construct(activation, args)
+            .unwrap()
+    }
+
+    type ObjectRoot = DynamicRoot<Rootable![Avm2Object<'_>]>;
+
+    /// Connects a `NetConnection` to the gateway, returning it with a `Responder`.
+    fn connect(player: &Arc<Mutex<Player>>) -> (ObjectRoot, ObjectRoot) {
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let connection = construct(&mut activation, "flash.net.NetConnection", &[]);
+                let listener = native_function(&mut activation, record_net_status, "listener");
+                connection
+                    .call_public_property(
+                        "addEventListener",
+                        &["netStatus".into(), listener.into()],
+                        &mut activation,
+                    )
+                    .unwrap();
+                connection
+                    .call_public_property("connect", &[GATEWAY_URL.into()], &mut activation)
+                    .unwrap();
+
+                let result = native_function(&mut activation, record_result, "result");
+                let status = native_function(&mut activation, record_status, "status");
+                let responder = construct(
+                    &mut activation,
+                    "flash.net.Responder",
+                    &[result.into(), status.into()],
+                );
+
+                let dynamic_root = activation.context.dynamic_root;
+                let gc_context = activation.context.gc_context;
+                let connection: ObjectRoot = dynamic_root.stash(gc_context, connection);
+                let responder: ObjectRoot = dynamic_root.stash(gc_context, responder);
+                (connection, responder)
+            })
+    }
+
+    /// Calls a service of the gateway, and optionally closes the connection.
+    fn call(
+        player: &Arc<Mutex<Player>>,
+        connection: &ObjectRoot,
+        responder: &ObjectRoot,
+        service: &'static str,
+        close: bool,
+    ) {
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let connection = *context.dynamic_root.fetch(connection);
+                let responder = *context.dynamic_root.fetch(responder);
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                connection
+                    .call_public_property(
+                        "call",
+                        &[service.into(), responder.into(), 1.into(), 2.into()],
+                        &mut activation,
+                    )
+                    .unwrap();
+                if close {
+                    connection
+                        .call_public_property("close", &[], &mut activation)
+                        .unwrap();
+                }
+            });
+    }
+
+    fn uri(player: &Arc<Mutex<Player>>, connection: &ObjectRoot) -> Option<String> {
+        player
+            .lock()
+            .unwrap()
+            .mutate_with_update_context(|context| {
+                let connection = *context.dynamic_root.fetch(connection);
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                match connection
+                    .get_public_property("uri", &mut activation)
+                    .unwrap()
+                {
+                    Avm2Value::String(uri) => Some(uri.to_string()),
+                    _ => None,
+                }
+            })
+    }
+
+    #[test]
+    fn call_gateway() {
+        let mut executor = NullExecutor::new();
+        let player = PlayerBuilder::new()
+            .with_navigator(GatewayNavigator(executor.spawner()))
+            .with_movie(SwfMovie::empty(10))
+            .build();
+
+        let (connection, responder) = connect(&player);
+        call(&player, &connection, &responder, "service.add", false);
+        executor.run();
+        call(&player, &connection, &responder, "service.fail", false);
+        call(&player, &connection, &responder, "service.missing", false);
+        executor.run();
+
+        let session_url = format!("{GATEWAY_URL};session=1");
+        let requests = REQUESTS.with(|requests| requests.take());
+        assert_eq!(
+            requests,
+            [
+                (GATEWAY_URL.to_string(), "service.add".to_string()),
+                (session_url.clone(), "service.fail".to_string()),
+                (session_url, "service.missing".to_string()),
+            ]
+        );
+        let events = EVENTS.with(|events| events.take());
+        assert_eq!(
+            events,
+            [
+                "result 3",
+                "status Service failed",
+                "NetConnection.Call.Failed"
+            ]
+        );
+        assert_eq!(
+            uri(&player, &connection),
+            Some(format!("{GATEWAY_URL};session=1;session=1"))
+        );
+    }
+
+    #[test]
+    fn append_to_gateway_url_after_close() {
+        let mut executor = NullExecutor::new();
+        let player = PlayerBuilder::new()
+            .with_navigator(GatewayNavigator(executor.spawner()))
+            .with_movie(SwfMovie::empty(10))
+            .build();
+
+        let (connection, responder) = connect(&player);
+        call(&player, &connection, &responder, "service.add", true);
+        executor.run();
+
+        assert_eq!(uri(&player, &connection), None);
+    }
+
+    fn number(value: f64) -> Vec<u8> {
+        let mut bytes = vec![0x00];
+        bytes.extend(value.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn packet_round_trip() {
+        let headers = vec![AmfHeader {
+            name: "Credentials".to_string(),
+            must_understand: true,
+            value: number(1.0),
+        }];
+        let messages = vec![AmfMessage {
+            target_uri: "/1/onResult".to_string(),
+            response_uri: "".to_string(),
+            value: number(42.0),
+        }];
+        let bytes = write_packet(AMFVersion::AMF0, &headers, &messages);
+
+        let (headers, messages) = read_packet(&bytes).expect("Packet should be valid");
+        assert_eq!(
+            headers,
+            vec![AmfHeader {
+                name: "Credentials".to_string(),
+                must_understand: true,
+                value: AmfValue::Number(1.0),
+            }]
+        );
+        assert_eq!(
+            messages,
+            vec![AmfMessage {
+                target_uri: "/1/onResult".to_string(),
+                response_uri: "".to_string(),
+                value: AmfValue::Number(42.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn truncated_packet() {
+        let messages = vec![AmfMessage {
+            target_uri: "/1/onResult".to_string(),
+            response_uri: "".to_string(),
+            value: number(42.0),
+        }];
+        let bytes = write_packet(AMFVersion::AMF0, &[], &messages);
+        assert_eq!(read_packet(&bytes[..bytes.len() - 4]), None);
+    }
+}
@@ -8,12 +8,15 @@ use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm2::{
     object::LoaderInfoObject, object::TObject as _, Activation as Avm2Activation, Avm2, CallStack,
-    Object as Avm2Object,
+    EventObject as Avm2EventObject, Object as Avm2Object,
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    file_dialog::FileDialogBackend,
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
+    print::PrintBackend,
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
 };
@@ -26,18 +29,19 @@ use crate::context_menu::{
 };
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
-    EditText, InteractiveObject, MovieClip, Stage, StageAlign, StageDisplayState, StageScaleMode,
-    TInteractiveObject, WindowMode,
+    lowest_common_ancestor, EditText, InteractiveObject, MovieClip, MultitouchInputMode, Stage,
+    StageAlign, StageDisplayState, StageScaleMode, TInteractiveObject, WindowMode,
 };
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::FocusTracker;
-use crate::font::Font;
+use crate::font::{DefaultFont, Font};
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
+use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::prelude::*;
 use crate::socket::Sockets;
@@ -46,6 +50,7 @@ use crate::string::{AvmString, AvmStringInterner};
 use crate::stub::StubCollection;
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
+use crate::touch_tracker::{TouchPoint, TouchTracker};
 use crate::vminterface::Instantiator;
 use gc_arena::{Collect, DynamicRootSet, GcCell, Rootable};
 use instant::Instant;
@@ -155,6 +160,9 @@ struct GcRootData<'gc> {
     /// A tracker for the current keyboard focused element
     focus_tracker: FocusTracker<'gc>,
 
+    /// A tracker for the points of contact currently on the touch screen.
+    touch_tracker: TouchTracker<'gc>,
+
     /// Manager of active sound instances.
     audio_manager: AudioManager<'gc>,
 
@@ -163,6 +171,9 @@ struct GcRootData<'gc> {
 
     sockets: Sockets<'gc>,
 
+    /// The `LocalConnection` objects listening for messages.
+    local_connections: LocalConnections<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     dynamic_root: DynamicRootSet<'gc>,
 }
@@ -191,6 +202,7 @@ impl<'gc> GcRootData<'gc> {
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
         &mut Sockets<'gc>,
+        &mut LocalConnections<'gc>,
         DynamicRootSet<'gc>,
     ) {
         (
@@ -211,6 +223,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.audio_manager,
             &mut self.stream_manager,
             &mut self.sockets,
+            &mut self.local_connections,
             self.dynamic_root,
         )
     }
@@ -222,6 +235,9 @@ type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
+type LocalConnection = Box<dyn LocalConnectionBackend>;
+type FileDialog = Box<dyn FileDialogBackend>;
+type Print = Box<dyn PrintBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
@@ -248,6 +264,9 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    local_connection: LocalConnection,
+    file_dialog: FileDialog,
+    print: Print,
     log: Log,
     ui: Ui,
     video: Video,
@@ -293,6 +312,10 @@ pub struct Player {
     /// Time remaining until the next timer will fire.
     time_til_next_timer: Option<f64>,
 
+    /// Whether any dynamic sounds were playing after the last tick, whose buffers need to be
+    /// refilled between frames.
+    has_dynamic_sounds: bool,
+
     /// The instant at which the SWF was launched.
     start_time: Instant,
 
@@ -551,10 +574,12 @@ impl Player {
             });
 
             self.update_sockets();
+            self.update_local_connections();
             self.update_timers(dt);
             self.update(|context| {
                 StreamManager::tick(context, dt);
             });
+            self.update_dynamic_sounds();
             self.audio.tick();
         }
     }
@@ -578,6 +603,10 @@ impl Player {
             dt = dt.min(time_til_next_timer)
         }
 
+        if self.has_dynamic_sounds {
+            dt = dt.min(AudioManager::DYNAMIC_SOUND_REFILL_INTERVAL);
+        }
+
         dt = dt.max(0.0);
 
         std::time::Duration::from_micros(dt as u64 * 1000)
@@ -920,14 +949,27 @@ impl Player {
     ///    object is in focus and dispatch a text-control event to said object.
     /// 4. If the incoming event is text input, and neither step 3 nor step 4
     ///    resulted in an event being handled, we dispatch a text input event
-    ///    to the currently focused `EditText` (if present).
+    ///    to the currently focused `EditText` (if present). Likewise, Tab
+    ///    moves the keyboard focus, and Enter or Space click the button
+    ///    focused that way.
     /// 5. Regardless of all prior event handling, we dispatch the event
     ///    through the stage normally.
     /// 6. Then, we dispatch the event through AVM1 global listener objects.
     /// 7. The AVM1 action queue is drained.
     /// 8. Mouse state is updated. This triggers button rollovers, which are a
     ///    second wave of event processing.
+    ///
+    /// Touch events are handled separately by `handle_touch_event`, which may
+    /// in turn feed mouse events back into this function.
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        if let PlayerEvent::TouchBegin { .. }
+        | PlayerEvent::TouchMove { .. }
+        | PlayerEvent::TouchEnd { .. } = event
+        {
+            self.handle_touch_event(event);
+            return;
+        }
+
         let prev_is_mouse_down = self.input.is_mouse_down();
         self.input.handle_event(&event);
         let is_mouse_button_changed = self.input.is_mouse_down() != prev_is_mouse_down;
@@ -1109,6 +1151,25 @@ impl Player {
                         text.text_control_input(code, context);
                     }
                 }
+
+                // Keyboard navigation, unless a `keyPress` handler took the key.
+                if let PlayerEvent::KeyDown { key_code, .. } = event {
+                    let tracker = context.focus_tracker;
+                    match key_code {
+                        KeyCode::Tab => {
+                            let reverse = context.input.is_key_down(KeyCode::Shift);
+                            tracker.cycle(context, reverse);
+                        }
+                        KeyCode::Return | KeyCode::Space => tracker.click_focused(context),
+                        _ => {}
+                    }
+                }
+            }
+
+            // Pressing the mouse hides the keyboard focus highlight.
+            if let PlayerEvent::MouseDown { .. } = event {
+                let tracker = context.focus_tracker;
+                tracker.reset_highlight(context);
             }
 
             // Propagate clip events.
@@ -1225,6 +1286,253 @@ impl Player {
         }
     }
 
+    /// Handle a touch screen event.
+    ///
+    /// When `Multitouch.inputMode` is `touchPoint`, AVM2 `TouchEvent`s are
+    /// dispatched to the object under each touch point. Regardless of the
+    /// input mode, the primary touch point is also interpreted as the mouse.
+    fn handle_touch_event(&mut self, event: PlayerEvent) {
+        let (PlayerEvent::TouchBegin { id, x, y }
+        | PlayerEvent::TouchMove { id, x, y }
+        | PlayerEvent::TouchEnd { id, x, y }) = event
+        else {
+            return;
+        };
+
+        let is_primary = self.mutate_with_update_context(|context| {
+            let touch_point = match event {
+                PlayerEvent::TouchBegin { .. } => {
+                    Some(context.touch_tracker.begin(context.gc_context, id))
+                }
+                PlayerEvent::TouchMove { .. } => context.touch_tracker.get(id),
+                _ => context.touch_tracker.end(context.gc_context, id),
+            };
+            let Some(mut touch_point) = touch_point else {
+                return false;
+            };
+
+            if context.is_action_script_3()
+                && context.stage.multitouch_input_mode() == MultitouchInputMode::TouchPoint
+            {
+                let position = context.stage.inverse_view_matrix() * Point::from_pixels(x, y);
+                Self::dispatch_touch_events(context, event, &mut touch_point, position);
+                if !matches!(event, PlayerEvent::TouchEnd { .. }) {
+                    context
+                        .touch_tracker
+                        .update(context.gc_context, touch_point);
+                }
+                Self::run_actions(context);
+            }
+
+            touch_point.is_primary
+        });
+
+        if is_primary {
+            match event {
+                PlayerEvent::TouchBegin { x, y, .. } => {
+                    self.handle_event(PlayerEvent::MouseMove { x, y });
+                    self.handle_event(PlayerEvent::MouseDown {
+                        x,
+                        y,
+                        button: MouseButton::Left,
+                    });
+                }
+                PlayerEvent::TouchMove { x, y, .. } => {
+                    self.handle_event(PlayerEvent::MouseMove { x, y });
+                }
+                PlayerEvent::TouchEnd { x, y, .. } => {
+                    self.handle_event(PlayerEvent::MouseUp {
+                        x,
+                        y,
+                        button: MouseButton::Left,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Dispatch the AVM2 `TouchEvent`s caused by a touch point moving to `position`.
+    fn dispatch_touch_events<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        event: PlayerEvent,
+        touch_point: &mut TouchPoint<'gc>,
+        position: Point<Twips>,
+    ) {
+        let target = run_mouse_pick_at(context, position, false);
+        let target_object = target
+            .map(|target| target.as_displayobject())
+            .unwrap_or_else(|| context.stage.into());
+
+        match event {
+            PlayerEvent::TouchBegin { .. } => {
+                Self::update_touch_over(context, touch_point, target, position);
+                touch_point.begin_target = target;
+                Self::dispatch_touch_event(
+                    context,
+                    "touchBegin",
+                    target_object,
+                    touch_point,
+                    position,
+                    None,
+                    true,
+                );
+            }
+            PlayerEvent::TouchMove { .. } => {
+                Self::dispatch_touch_event(
+                    context,
+                    "touchMove",
+                    target_object,
+                    touch_point,
+                    position,
+                    None,
+                    true,
+                );
+                Self::update_touch_over(context, touch_point, target, position);
+            }
+            PlayerEvent::TouchEnd { .. } => {
+                Self::dispatch_touch_event(
+                    context,
+                    "touchEnd",
+                    target_object,
+                    touch_point,
+                    position,
+                    None,
+                    true,
+                );
+                if target.is_some()
+                    && InteractiveObject::option_ptr_eq(touch_point.begin_target, target)
+                {
+                    Self::dispatch_touch_event(
+                        context,
+                        "touchTap",
+                        target_object,
+                        touch_point,
+                        position,
+                        None,
+                        true,
+                    );
+                }
+                // The touch point no longer hovers over anything once it is lifted.
+                Self::update_touch_over(context, touch_point, None, position);
+            }
+            _ => {}
+        }
+    }
+
+    /// Dispatch the AVM2 over/out events for a touch point that moved onto `new_over`.
+    ///
+    /// This mirrors the rollover logic of mouse events.
+    fn update_touch_over<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        touch_point: &mut TouchPoint<'gc>,
+        new_over: Option<InteractiveObject<'gc>>,
+        position: Point<Twips>,
+    ) {
+        let old_over = touch_point.over_target;
+        if InteractiveObject::option_ptr_eq(old_over, new_over) {
+            return;
+        }
+        touch_point.over_target = new_over;
+
+        let stage: DisplayObject<'gc> = context.stage.into();
+        if let Some(old_over) = old_over {
+            let old_object = old_over.as_displayobject();
+            Self::dispatch_touch_event(
+                context,
+                "touchOut",
+                old_object,
+                touch_point,
+                position,
+                new_over,
+                true,
+            );
+
+            let lca = lowest_common_ancestor(
+                old_object,
+                new_over.map(|o| o.as_displayobject()).unwrap_or(stage),
+            );
+            let mut rollout_target = Some(old_object);
+            while let Some(target) = rollout_target {
+                if DisplayObject::option_ptr_eq(rollout_target, lca) {
+                    break;
+                }
+                Self::dispatch_touch_event(
+                    context,
+                    "touchRollOut",
+                    target,
+                    touch_point,
+                    position,
+                    new_over,
+                    false,
+                );
+                rollout_target = target.parent();
+            }
+        }
+
+        if let Some(new_over) = new_over {
+            let new_object = new_over.as_displayobject();
+            let lca = lowest_common_ancestor(
+                new_object,
+                old_over.map(|o| o.as_displayobject()).unwrap_or(stage),
+            );
+            let mut rollover_target = Some(new_object);
+            while let Some(target) = rollover_target {
+                if DisplayObject::option_ptr_eq(rollover_target, lca) {
+                    break;
+                }
+                Self::dispatch_touch_event(
+                    context,
+                    "touchRollOver",
+                    target,
+                    touch_point,
+                    position,
+                    old_over,
+                    false,
+                );
+                rollover_target = target.parent();
+            }
+
+            Self::dispatch_touch_event(
+                context,
+                "touchOver",
+                new_object,
+                touch_point,
+                position,
+                old_over,
+                true,
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch_touch_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        event_type: &'static str,
+        target: DisplayObject<'gc>,
+        touch_point: &TouchPoint<'gc>,
+        position: Point<Twips>,
+        related_object: Option<InteractiveObject<'gc>>,
+        bubbles: bool,
+    ) {
+        let Avm2Value::Object(avm2_target) = target.object2() else {
+            return;
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let avm2_event = Avm2EventObject::touch_event(
+            &mut activation,
+            event_type,
+            target,
+            touch_point.touch_point_id,
+            touch_point.is_primary,
+            position,
+            related_object,
+            bubbles,
+        );
+        Avm2::dispatch_event(&mut activation.context, avm2_event, avm2_target);
+    }
+
     /// Update dragged object, if any.
     pub fn update_drag(context: &mut UpdateContext<'_, '_>) {
         let mouse_position = *context.mouse_position;
@@ -1681,9 +1989,13 @@ impl Player {
                 is_offscreen: false,
                 use_bitmap_cache: true,
                 stage,
+                scaling_grid: None,
             };
 
             stage.render(&mut render_context);
+            root_data
+                .focus_tracker
+                .render_highlight(&mut render_context);
 
             #[cfg(feature = "egui")]
             {
@@ -1867,6 +2179,7 @@ impl Player {
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let mouse_pressed_object = root_data.mouse_pressed_object;
             let focus_tracker = root_data.focus_tracker;
+            let touch_tracker = root_data.touch_tracker;
 
             #[allow(unused_variables)]
             let (
@@ -1887,6 +2200,7 @@ impl Player {
                 audio_manager,
                 stream_manager,
                 sockets,
+                local_connections,
                 dynamic_root,
             ) = root_data.update_context_params();
 
@@ -1913,6 +2227,9 @@ impl Player {
                 system: &mut self.system,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
+                local_connection: self.local_connection.deref_mut(),
+                file_dialog: self.file_dialog.deref_mut(),
+                print: self.print.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
                 avm1_shared_objects,
@@ -1928,6 +2245,7 @@ impl Player {
                 update_start: Instant::now(),
                 max_execution_duration: self.max_execution_duration,
                 focus_tracker,
+                touch_tracker,
                 times_get_time_called: 0,
                 time_offset: &mut self.time_offset,
                 audio_manager,
@@ -1938,6 +2256,7 @@ impl Player {
                 stub_tracker: &mut self.stub_tracker,
                 stream_manager,
                 sockets,
+                local_connections,
                 dynamic_root,
             };
 
@@ -2075,6 +2394,22 @@ impl Player {
         })
     }
 
+    /// Refill the buffers of playing dynamic sounds, so that they don't run out of samples
+    /// when frames are further apart than the buffered audio lasts.
+    pub fn update_dynamic_sounds(&mut self) {
+        self.has_dynamic_sounds = self.mutate_with_update_context(|context| {
+            AudioManager::request_dynamic_sound_samples(context);
+            context.audio_manager.has_dynamic_sounds()
+        });
+    }
+
+    /// Deliver pending `LocalConnection` messages and statuses.
+    pub fn update_local_connections(&mut self) {
+        self.mutate_with_update_context(|context| {
+            LocalConnections::update_local_connections(context);
+        })
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -2113,6 +2448,11 @@ impl Player {
         &self.log
     }
 
+    /// The stubs encountered by this player so far.
+    pub fn stub_tracker(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
     pub fn max_execution_duration(&self) -> Duration {
         self.max_execution_duration
     }
@@ -2128,6 +2468,21 @@ impl Player {
     }
 }
 
+impl Drop for Player {
+    fn drop(&mut self) {
+        // The `LocalConnection` backend may be shared with other players, which
+        // must not see the connections of this player anymore.
+        let local_connection = self.local_connection.deref_mut();
+        self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+            gc_root
+                .data
+                .write(gc_context)
+                .local_connections
+                .close_all(local_connection);
+        });
+    }
+}
+
 /// Player factory, which can be used to configure the aspects of a Ruffle player.
 pub struct PlayerBuilder {
     movie: Option<SwfMovie>,
@@ -2138,6 +2493,9 @@ pub struct PlayerBuilder {
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
+    local_connection: Option<LocalConnection>,
+    file_dialog: Option<FileDialog>,
+    print: Option<Print>,
     ui: Option<Ui>,
     video: Option<Video>,
 
@@ -2162,6 +2520,11 @@ pub struct PlayerBuilder {
     frame_rate: Option<f64>,
     external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
+
+    // Device fonts
+    device_fonts: Vec<Vec<u8>>,
+    default_fonts: HashMap<DefaultFont, Vec<String>>,
+    fallback_fonts: Vec<String>,
 }
 
 impl PlayerBuilder {
@@ -2179,6 +2542,9 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            local_connection: None,
+            file_dialog: None,
+            print: None,
             ui: None,
             video: None,
 
@@ -2207,6 +2573,41 @@ impl PlayerBuilder {
             frame_rate: None,
             external_interface_providers: vec![],
             fs_command_provider: Box::new(NullFsCommandProvider),
+
+            device_fonts: vec![],
+            default_fonts: HashMap::from([
+                (
+                    DefaultFont::Sans,
+                    vec![
+                        "Arial".to_string(),
+                        "Helvetica".to_string(),
+                        "Liberation Sans".to_string(),
+                        "DejaVu Sans".to_string(),
+                        "Noto Sans".to_string(),
+                    ],
+                ),
+                (
+                    DefaultFont::Serif,
+                    vec![
+                        "Times New Roman".to_string(),
+                        "Times".to_string(),
+                        "Liberation Serif".to_string(),
+                        "DejaVu Serif".to_string(),
+                        "Noto Serif".to_string(),
+                    ],
+                ),
+                (
+                    DefaultFont::Typewriter,
+                    vec![
+                        "Courier New".to_string(),
+                        "Courier".to_string(),
+                        "Liberation Mono".to_string(),
+                        "DejaVu Sans Mono".to_string(),
+                        "Noto Sans Mono".to_string(),
+                    ],
+                ),
+            ]),
+            fallback_fonts: vec![],
         }
     }
 
@@ -2252,6 +2653,32 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the local connection backend of the player.
+    ///
+    /// Players sharing the same registry can talk to each other with `LocalConnection`.
+    #[inline]
+    pub fn with_local_connection(
+        mut self,
+        local_connection: impl 'static + LocalConnectionBackend,
+    ) -> Self {
+        self.local_connection = Some(Box::new(local_connection));
+        self
+    }
+
+    /// Sets the file dialog backend of the player.
+    #[inline]
+    pub fn with_file_dialog(mut self, file_dialog: impl 'static + FileDialogBackend) -> Self {
+        self.file_dialog = Some(Box::new(file_dialog));
+        self
+    }
+
+    /// Sets the print backend of the player.
+    #[inline]
+    pub fn with_print(mut self, print: impl 'static + PrintBackend) -> Self {
+        self.print = Some(Box::new(print));
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2377,6 +2804,31 @@ impl PlayerBuilder {
         self
     }
 
+    /// Adds a TrueType or OpenType font file, or font collection, to be used
+    /// as a device font.
+    ///
+    /// Fonts are picked by their family name, such as `Arial`.
+    pub fn with_device_font(mut self, data: Vec<u8>) -> Self {
+        self.device_fonts.push(data);
+        self
+    }
+
+    /// Sets the names of the device fonts used for a default font such as
+    /// `_sans`, in order of preference.
+    pub fn with_default_font(mut self, font: DefaultFont, names: Vec<String>) -> Self {
+        self.default_fonts.insert(font, names);
+        self
+    }
+
+    /// Sets the names of the device fonts used when a font is unavailable or
+    /// lacks glyphs for some text, in order of preference.
+    ///
+    /// The other device fonts are still searched for glyphs after these.
+    pub fn with_fallback_fonts(mut self, names: Vec<String>) -> Self {
+        self.fallback_fonts = names;
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc gc_arena::Mutation<'gc>,
         player_version: u8,
@@ -2409,6 +2861,7 @@ impl PlayerBuilder {
                         fs_command_provider,
                     ),
                     focus_tracker: FocusTracker::new(gc_context),
+                    touch_tracker: TouchTracker::new(gc_context),
                     library: Library::empty(),
                     load_manager: LoadManager::new(),
                     mouse_hovered_object: None,
@@ -2420,6 +2873,7 @@ impl PlayerBuilder {
                     unbound_text_fields: Vec::new(),
                     stream_manager: StreamManager::new(),
                     sockets: Sockets::empty(),
+                    local_connections: LocalConnections::empty(),
                     dynamic_root,
                 },
             ),
@@ -2449,6 +2903,15 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let local_connection = self
+            .local_connection
+            .unwrap_or_else(|| Box::new(local_connection::MemoryLocalConnectionBackend::new()));
+        let file_dialog = self
+            .file_dialog
+            .unwrap_or_else(|| Box::new(file_dialog::MemoryFileDialogBackend::new()));
+        let print = self
+            .print
+            .unwrap_or_else(|| Box::new(print::NullPrintBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
@@ -2470,6 +2933,9 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                local_connection,
+                file_dialog,
+                print,
                 ui,
                 video,
 
@@ -2486,6 +2952,7 @@ impl PlayerBuilder {
                 start_time: Instant::now(),
                 time_offset: 0,
                 time_til_next_timer: None,
+                has_dynamic_sounds: false,
                 max_execution_duration: self.max_execution_duration,
                 actions_since_timeout_check: 0,
 
@@ -2537,6 +3004,20 @@ impl PlayerBuilder {
             stage.set_forced_scale_mode(context, self.forced_scale_mode);
             stage.post_instantiation(context, None, Instantiator::Movie, false);
             stage.build_matrices(context);
+
+            for data in &self.device_fonts {
+                let font_count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+                for index in 0..font_count {
+                    match Font::from_font_file(context.gc_context, context.renderer, data, index) {
+                        Ok(font) => context.library.register_device_font(font),
+                        Err(e) => tracing::warn!("Unable to load device font: {}", e),
+                    }
+                }
+            }
+            for (font, names) in self.default_fonts {
+                context.library.set_default_font(font, names);
+            }
+            context.library.set_fallback_fonts(self.fallback_fonts);
         });
         player_lock.gc_arena.borrow().mutate(|context, root| {
             let call_stack = root.data.read().avm2.call_stack();
@@ -2590,13 +3071,23 @@ pub struct DragObject<'gc> {
 fn run_mouse_pick<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
     require_button_mode: bool,
+) -> Option<InteractiveObject<'gc>> {
+    let mouse_position = *context.mouse_position;
+    run_mouse_pick_at(context, mouse_position, require_button_mode)
+}
+
+/// Determine the interactive object under the given point, in stage coordinates.
+fn run_mouse_pick_at<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    point: Point<Twips>,
+    require_button_mode: bool,
 ) -> Option<InteractiveObject<'gc>> {
     context.stage.iter_render_list().rev().find_map(|level| {
         level.as_interactive().and_then(|l| {
             if context.is_action_script_3() {
                 let mut res = None;
                 if let Avm2MousePick::Hit(target) =
-                    l.mouse_pick_avm2(context, *context.mouse_position, require_button_mode)
+                    l.mouse_pick_avm2(context, point, require_button_mode)
                 {
                     // Flash Player appears to never target events at the root object
                     if !target.as_displayobject().is_root() {
@@ -2605,7 +3096,7 @@ fn run_mouse_pick<'gc>(
                 }
                 res
             } else {
-                l.mouse_pick_avm1(context, *context.mouse_position, require_button_mode)
+                l.mouse_pick_avm1(context, point, require_button_mode)
             }
         })
     })
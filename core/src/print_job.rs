@@ -0,0 +1,247 @@
+//! Shared logic of the AVM1 and AVM2 `PrintJob` classes, which render
+//! display objects off-screen and hand the pages to the print backend.
+
+use crate::backend::print::{PageFormat, PrintedPage};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::TDisplayObject;
+use crate::prelude::*;
+use crate::string::WStr;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, PixelRegion};
+use ruffle_render::commands::CommandList;
+use ruffle_render::transform::{Transform, TransformStack};
+
+/// How a display object is printed on a page.
+#[derive(Debug, Clone, Default)]
+pub struct PrintPageOptions {
+    /// The area of the display object to print, in its own coordinate space.
+    ///
+    /// If `None`, the bounds of the display object are printed.
+    pub print_area: Option<Rectangle<Twips>>,
+
+    /// Render the page at screen resolution rather than at the resolution of the printer.
+    pub print_as_bitmap: bool,
+
+    /// Scale the printed area to fit the page, instead of printing each
+    /// pixel as a point and cropping what doesn't fit.
+    pub fit_to_page: bool,
+
+    /// The 1-based frame of a movie clip to print, or `None` for its current frame.
+    pub frame: Option<u16>,
+}
+
+/// Render a display object and add it as a page of the active print job.
+///
+/// Returns `false` if nothing could be printed.
+pub fn print_page<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    format: &PageFormat,
+    target: DisplayObject<'gc>,
+    options: &PrintPageOptions,
+) -> bool {
+    // Other frames are printed from a copy of the clip, so that the
+    // displayed clip isn't navigated.
+    let target = match (target.as_movie_clip(), options.frame) {
+        (Some(clip), Some(frame)) if frame != clip.current_frame() => {
+            clip.snapshot_frame(context, frame).into()
+        }
+        _ => target,
+    };
+
+    match render_page(context, format, target, options) {
+        Some(page) => {
+            context.print.add_page(page);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Print the pages of the active print job.
+///
+/// This may ask the user where to print them, so it completes asynchronously.
+pub fn send_print_job(context: &mut UpdateContext<'_, '_>) {
+    let printing = context.print.send();
+    context.navigator.spawn_future(Box::pin(async move {
+        printing.await;
+        Ok(())
+    }));
+}
+
+fn render_page<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    format: &PageFormat,
+    target: DisplayObject<'gc>,
+    options: &PrintPageOptions,
+) -> Option<PrintedPage> {
+    let area = options
+        .print_area
+        .clone()
+        .unwrap_or_else(|| target.bounds());
+    if area.width() <= Twips::ZERO || area.height() <= Twips::ZERO {
+        return None;
+    }
+
+    // A pixel of the content is a point of the page, which is rendered at
+    // the resolution of the printer unless printing as a bitmap.
+    let resolution = if options.print_as_bitmap {
+        1.0
+    } else {
+        format.dpi as f64 / 72.0
+    };
+    let content_scale = if options.fit_to_page {
+        (format.page_width as f64 / area.width().to_pixels())
+            .min(format.page_height as f64 / area.height().to_pixels())
+    } else {
+        1.0
+    };
+    let width = (format.page_width as f64 * resolution).ceil() as u32;
+    let height = (format.page_height as f64 * resolution).ceil() as u32;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let scale = (content_scale * resolution) as f32;
+    let transform = Transform {
+        matrix: Matrix::scale(scale, scale) * Matrix::translate(-area.x_min, -area.y_min),
+        color_transform: Default::default(),
+    };
+
+    // Pages are printed on white paper.
+    let paper = Bitmap::new(
+        width,
+        height,
+        BitmapFormat::Rgba,
+        vec![255; (width * height * 4) as usize],
+    );
+    let handle = match context.renderer.register_bitmap(paper) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::error!("Failed to create a print page: {e}");
+            return None;
+        }
+    };
+
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&transform);
+    let mut cache_draws = vec![];
+    let mut render_context = RenderContext {
+        renderer: context.renderer,
+        commands: CommandList::new(),
+        cache_draws: &mut cache_draws,
+        gc_context: context.gc_context,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        is_offscreen: true,
+        use_bitmap_cache: false,
+        stage: context.stage,
+        scaling_grid: None,
+    };
+
+    // Like `BitmapData.draw`, the transform and mask of the target are ignored.
+    target.render_self(&mut render_context);
+
+    let commands = render_context.commands;
+    let sync_handle = context.renderer.render_offscreen(
+        handle,
+        commands,
+        context.stage.quality(),
+        PixelRegion::for_whole_size(width, height),
+    )?;
+
+    let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+    let result = sync_handle.retrieve_offscreen_texture(Box::new(|buffer, row_length| {
+        for row in buffer.chunks(row_length as usize).take(height as usize) {
+            for pixel in row[..(width * 4) as usize].chunks_exact(4) {
+                pixels.extend_from_slice(&pixel[..3]);
+            }
+        }
+    }));
+    if let Err(e) = result {
+        tracing::error!("Failed to render a print page: {e}");
+        return None;
+    }
+
+    Some(PrintedPage {
+        format: *format,
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Print the frames of a movie clip, as requested by the AVM1 `print` functions.
+///
+/// The frames labeled `#p` are printed, or every frame if there is no such label.
+/// `bounding_box` is the option following the `#` of the print URL:
+/// - `bmovie` prints the area of the frame labeled `#b`, or the stage if
+///   there is none;
+/// - `bmax` prints the area containing every printed frame;
+/// - `bframe` prints the bounds of each frame.
+pub fn print_movie_clip<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: DisplayObject<'gc>,
+    bounding_box: &str,
+    print_as_bitmap: bool,
+) {
+    let Some(format) = context.print.start() else {
+        return;
+    };
+
+    let mut frames = Vec::new();
+    let mut bounds_frame = None;
+    if let Some(clip) = target.as_movie_clip() {
+        for (label, frame) in clip.labels_in_range(1, clip.total_frames() + 1) {
+            if label.eq_ignore_case(WStr::from_units(b"#p")) {
+                frames.push(frame);
+            } else if label.eq_ignore_case(WStr::from_units(b"#b")) {
+                bounds_frame = Some(frame);
+            }
+        }
+        if frames.is_empty() {
+            frames.extend(1..=clip.total_frames());
+        }
+    }
+
+    let print_area = match bounding_box {
+        "bmovie" => match bounds_frame {
+            Some(frame) => Some(frame_bounds(context, target, frame)),
+            None => Some(context.swf.stage_size().clone()),
+        },
+        "bmax" => frames
+            .iter()
+            .map(|&frame| frame_bounds(context, target, frame))
+            .reduce(|a, b| a.union(&b)),
+        _ => None,
+    };
+
+    if frames.is_empty() {
+        // Not a movie clip, print it as it is.
+        frames.push(0);
+    }
+    for frame in frames {
+        let options = PrintPageOptions {
+            print_area: print_area.clone(),
+            print_as_bitmap,
+            fit_to_page: true,
+            frame: (frame > 0).then_some(frame),
+        };
+        print_page(context, &format, target, &options);
+    }
+    send_print_job(context);
+}
+
+/// The bounds of a frame of a movie clip.
+fn frame_bounds<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: DisplayObject<'gc>,
+    frame: u16,
+) -> Rectangle<Twips> {
+    let Some(clip) = target.as_movie_clip() else {
+        return target.bounds();
+    };
+    if frame == clip.current_frame() {
+        return target.bounds();
+    }
+
+    clip.snapshot_frame(context, frame).bounds()
+}
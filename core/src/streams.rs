@@ -1,5 +1,7 @@
 //! NetStream implementation
 
+mod mp4;
+
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, ScriptObject as Avm1ScriptObject,
@@ -18,14 +20,15 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::MovieClip;
 use crate::loader::Error;
+use crate::streams::mp4::{Mp4Codec, Mp4Error, Mp4Movie, Mp4Track, Mp4TrackKind};
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
-    Header as FlvHeader, ScriptData as FlvScriptData, SoundFormat as FlvSoundFormat,
-    SoundRate as FlvSoundRate, SoundSize as FlvSoundSize, SoundType as FlvSoundType, Tag as FlvTag,
-    TagData as FlvTagData, Value as FlvValue, VideoData as FlvVideoData,
-    VideoPacket as FlvVideoPacket,
+    FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
+    SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
+    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
+    Variable as FlvVariable, VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::bitmap::BitmapInfo;
@@ -37,6 +40,10 @@ use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
 
+/// How far ahead of the stream time MP4 audio samples are queued, in
+/// milliseconds.
+const MP4_AUDIO_LOOKAHEAD_MS: f64 = 200.0;
+
 #[derive(Debug, Error)]
 enum NetstreamError {
     #[error("Decoding failed because {0}")]
@@ -47,6 +54,9 @@ enum NetstreamError {
 
     #[error("Unknown codec")]
     UnknownCodec,
+
+    #[error("No playable tracks")]
+    NoPlayableTracks,
 }
 
 impl From<DecodeError> for NetstreamError {
@@ -177,6 +187,27 @@ pub enum NetStreamType {
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
     },
+
+    /// The stream is an MP4 or F4V.
+    Mp4 {
+        /// The sample tables of every track in the file.
+        movie: Mp4Movie,
+
+        /// The index of the video track we are playing, if any.
+        video_track: Option<usize>,
+
+        /// The currently playing video track's stream instance.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the next sample to decode from the video track.
+        next_video_sample: usize,
+
+        /// The index of the audio track we are playing, if any.
+        audio_track: Option<usize>,
+
+        /// The index of the next sample to queue from the audio track.
+        next_audio_sample: usize,
+    },
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -185,6 +216,9 @@ pub struct NetStreamData<'gc> {
     /// All data currently loaded in the stream.
     buffer: Buffer,
 
+    /// Whether the whole source file has been loaded into the buffer.
+    is_complete: bool,
+
     /// The buffer position that we are currently seeking to.
     offset: usize,
 
@@ -244,6 +278,7 @@ impl<'gc> NetStream<'gc> {
             gc_context,
             NetStreamData {
                 buffer: Buffer::new(),
+                is_complete: false,
                 offset: 0,
                 preload_offset: 0,
                 stream_type: None,
@@ -292,6 +327,7 @@ impl<'gc> NetStream<'gc> {
         }
 
         write.buffer = Buffer::new();
+        write.is_complete = false;
         write.offset = 0;
         write.preload_offset = 0;
         write.stream_type = None;
@@ -319,6 +355,14 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Mark the `NetStream`'s buffer as containing the whole source file.
+    ///
+    /// Data that is still missing at this point will never load, so the
+    /// stream is considered invalid rather than waiting for it.
+    pub fn finish_buffer(self, gc_context: &Mutation<'gc>) {
+        self.0.write(gc_context).is_complete = true;
+    }
+
     pub fn report_error(self, _error: Error) {
         //TODO: Report an `asyncError` to AVM1 or 2.
     }
@@ -378,6 +422,60 @@ impl<'gc> NetStream<'gc> {
         StreamManager::toggle_paused(context, self);
     }
 
+    /// Seek to a given time in the stream, in milliseconds.
+    ///
+    /// Playback resumes from the nearest keyframe or sync sample at or before
+    /// the given time.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc>, offset: f64) {
+        let mut write = self.0.write(context.gc_context);
+        let offset = offset.max(0.0);
+
+        let stream_time = if let Some(NetStreamType::Flv { header, .. }) = &write.stream_type {
+            let slice = write.buffer.to_full_slice();
+            let buffer = slice.data();
+            let (position, stream_time) =
+                Self::flv_seek_position(&buffer, header.data_offset as usize, offset);
+            write.offset = position;
+            stream_time
+        } else if let Some(NetStreamType::Mp4 {
+            movie,
+            video_track,
+            next_video_sample,
+            audio_track,
+            next_audio_sample,
+            ..
+        }) = &mut write.stream_type
+        {
+            let mut stream_time = offset;
+            if let Some(track) = video_track.map(|index| &movie.tracks[index]) {
+                *next_video_sample = track.sync_sample_at(stream_time);
+                stream_time = track.sample_time(*next_video_sample);
+            }
+            if let Some(track) = audio_track.map(|index| &movie.tracks[index]) {
+                *next_audio_sample = track.sync_sample_at(stream_time);
+            }
+
+            stream_time
+        } else {
+            return;
+        };
+
+        if let Some(instance) = write.sound_instance {
+            context.audio.stop_sound(instance);
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+
+        write.stream_time = stream_time;
+        write.audio_stream = None;
+        write.sound_instance = None;
+        drop(write);
+
+        self.trigger_status_event(
+            context,
+            &[("code", "NetStream.Seek.Notify"), ("level", "status")],
+        );
+    }
+
     /// Indicates that this `NetStream`'s audio was detached from a `MovieClip` (AVM1)
     pub fn was_detached(self, context: &mut UpdateContext<'_, 'gc>) {
         let mut write = self.0.write(context.gc_context);
@@ -410,6 +508,40 @@ impl<'gc> NetStream<'gc> {
         write.attached_to = Some(clip);
     }
 
+    /// Find the FLV tag to resume playback from when seeking to `time`.
+    ///
+    /// This is the last video keyframe at or before `time`, or the last tag
+    /// at or before it if there is no video. Returns the position of the tag
+    /// in the buffer and its timestamp.
+    fn flv_seek_position(buffer: &[u8], data_offset: usize, time: f64) -> (usize, f64) {
+        let mut reader = FlvReader::from_parts(buffer, data_offset);
+        let mut has_video = false;
+        let mut keyframe = None;
+        let mut last_tag = None;
+
+        loop {
+            let tag_position = reader.stream_position().expect("valid position") as usize;
+            let Ok(tag) = FlvTag::parse(&mut reader) else {
+                break;
+            };
+            let timestamp = tag.timestamp as f64;
+            if timestamp > time {
+                break;
+            }
+
+            if let FlvTagData::Video(video_data) = &tag.data {
+                has_video = true;
+                if video_data.frame_type == FlvFrameType::Keyframe {
+                    keyframe = Some((tag_position, timestamp));
+                }
+            }
+            last_tag = Some((tag_position, timestamp));
+        }
+
+        let position = if has_video { keyframe } else { last_tag };
+        position.unwrap_or((data_offset, 0.0))
+    }
+
     /// Process a parsed FLV audio tag.
     ///
     /// `write` must be an active borrow of the current `NetStream`. `slice`
@@ -797,6 +929,274 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Pick the tracks to play out of a demuxed MP4.
+    ///
+    /// The first video and audio tracks that we can decode are used; video
+    /// tracks are registered with the video backend here.
+    fn mp4_stream_type(
+        context: &mut UpdateContext<'_, 'gc>,
+        movie: Mp4Movie,
+    ) -> Result<NetStreamType, NetstreamError> {
+        let mut video_track = None;
+        let mut video_stream = None;
+        let mut audio_track = None;
+
+        for (index, track) in movie.tracks.iter().enumerate() {
+            match track.kind {
+                Mp4TrackKind::Video { width, height } if video_track.is_none() => {
+                    let Some(codec) = Self::mp4_video_codec(track) else {
+                        tracing::warn!(
+                            "MP4 video track {} has unsupported codec {}",
+                            track.track_id,
+                            track.codec
+                        );
+                        continue;
+                    };
+
+                    match context.video.register_video_stream(
+                        track.samples.len() as u32,
+                        (width, height),
+                        codec,
+                        VideoDeblocking::UseVideoPacketValue,
+                    ) {
+                        Ok(stream_handle) => {
+                            video_track = Some(index);
+                            video_stream = Some(stream_handle);
+                        }
+                        Err(e) => {
+                            tracing::error!("Got error when registering MP4 video stream: {}", e)
+                        }
+                    }
+                }
+                Mp4TrackKind::Audio { .. } if audio_track.is_none() => {
+                    if Self::mp4_sound_format(track).is_some() {
+                        audio_track = Some(index);
+                    } else {
+                        tracing::warn!(
+                            "MP4 audio track {} has unsupported codec {}",
+                            track.track_id,
+                            track.codec
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if video_track.is_none() && audio_track.is_none() {
+            return Err(NetstreamError::NoPlayableTracks);
+        }
+
+        Ok(NetStreamType::Mp4 {
+            movie,
+            video_track,
+            video_stream,
+            next_video_sample: 0,
+            audio_track,
+            next_audio_sample: 0,
+        })
+    }
+
+    /// Build the equivalent of an FLV `onMetaData` script value for an MP4.
+    fn mp4_metadata(movie: &Mp4Movie) -> FlvValue<'static> {
+        let mut metadata = vec![FlvVariable {
+            name: b"duration",
+            data: FlvValue::Number(movie.duration_ms() / 1000.0),
+        }];
+
+        for track in &movie.tracks {
+            match track.kind {
+                Mp4TrackKind::Video { width, height } => {
+                    metadata.push(FlvVariable {
+                        name: b"width",
+                        data: FlvValue::Number(width.into()),
+                    });
+                    metadata.push(FlvVariable {
+                        name: b"height",
+                        data: FlvValue::Number(height.into()),
+                    });
+                }
+                Mp4TrackKind::Audio {
+                    channel_count,
+                    sample_rate,
+                    ..
+                } => {
+                    metadata.push(FlvVariable {
+                        name: b"audiochannels",
+                        data: FlvValue::Number(channel_count.into()),
+                    });
+                    metadata.push(FlvVariable {
+                        name: b"audiosamplerate",
+                        data: FlvValue::Number(sample_rate.into()),
+                    });
+                }
+                Mp4TrackKind::Other => {}
+            }
+        }
+
+        FlvValue::EcmaArray(metadata)
+    }
+
+    /// Determine the video codec of an MP4 track, if it is one we can decode.
+    fn mp4_video_codec(track: &Mp4Track) -> Option<VideoCodec> {
+        match track.codec {
+            Mp4Codec::Vp6 => Some(VideoCodec::Vp6),
+            Mp4Codec::Vp6WithAlpha => Some(VideoCodec::Vp6WithAlpha),
+            _ => None,
+        }
+    }
+
+    /// Determine the sound format of an MP4 track, if it is one we can decode.
+    fn mp4_sound_format(track: &Mp4Track) -> Option<SoundFormat> {
+        let Mp4TrackKind::Audio {
+            channel_count,
+            sample_size,
+            sample_rate,
+        } = track.kind
+        else {
+            return None;
+        };
+
+        let compression = match track.codec {
+            Mp4Codec::Mp3 => AudioCompression::Mp3,
+            Mp4Codec::Raw | Mp4Codec::PcmLittleEndian => AudioCompression::Uncompressed,
+            _ => return None,
+        };
+
+        Some(SoundFormat {
+            compression,
+            sample_rate: sample_rate.try_into().ok()?,
+            is_stereo: channel_count > 1,
+            is_16_bit: sample_size == 16,
+        })
+    }
+
+    /// Decode and queue MP4 samples up to the given stream time.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `slice`
+    /// must reference the underlying backing buffer, and `buffer` must be its
+    /// data.
+    ///
+    /// Returns true once every sample in the playing tracks has been
+    /// processed.
+    fn mp4_tick(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        buffer: &[u8],
+        end_time: f64,
+    ) -> bool {
+        let NetStreamData {
+            stream_type,
+            audio_stream,
+            last_decoded_bitmap,
+            is_complete,
+            ..
+        } = write;
+        let Some(NetStreamType::Mp4 {
+            movie,
+            video_track,
+            video_stream,
+            next_video_sample,
+            audio_track,
+            next_audio_sample,
+        }) = stream_type
+        else {
+            unreachable!()
+        };
+
+        let mut is_finished = true;
+
+        if let (Some(track), Some(video_handle)) =
+            (video_track.map(|index| &movie.tracks[index]), *video_stream)
+        {
+            let codec = Self::mp4_video_codec(track).expect("video track has a supported codec");
+
+            while *next_video_sample < track.samples.len()
+                && track.sample_time(*next_video_sample) < end_time
+            {
+                let frame_id = *next_video_sample as u32;
+                let Some(data) = track.samples[*next_video_sample].data(buffer) else {
+                    if *is_complete {
+                        tracing::error!("MP4 video sample {} is truncated", frame_id);
+                        *next_video_sample += 1;
+                        continue;
+                    }
+
+                    // The sample hasn't loaded yet.
+                    break;
+                };
+
+                let encoded_frame = EncodedFrame {
+                    codec,
+                    data,
+                    frame_id,
+                };
+
+                match context.video.decode_video_stream_frame(
+                    video_handle,
+                    encoded_frame,
+                    context.renderer,
+                ) {
+                    Ok(bitmap_info) => {
+                        *last_decoded_bitmap = Some(bitmap_info);
+                    }
+                    Err(e) => {
+                        tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
+                    }
+                }
+
+                *next_video_sample += 1;
+            }
+
+            is_finished &= *next_video_sample >= track.samples.len();
+        }
+
+        if let Some(track) = audio_track.map(|index| &movie.tracks[index]) {
+            // Like FLV audio tags, we queue up samples ahead of the current
+            // time to avoid audio underruns. This is measured in time rather
+            // than samples, as a PCM sample can be as short as a single frame.
+            while *next_audio_sample < track.samples.len()
+                && track.sample_time(*next_audio_sample) < end_time + MP4_AUDIO_LOOKAHEAD_MS
+            {
+                let Some(data) = track.samples[*next_audio_sample].data(buffer) else {
+                    if *is_complete {
+                        tracing::error!("MP4 audio sample {} is truncated", *next_audio_sample);
+                        *next_audio_sample += 1;
+                        continue;
+                    }
+
+                    // The sample hasn't loaded yet.
+                    break;
+                };
+
+                let (substream, _sound_stream_info) = audio_stream.get_or_insert_with(|| {
+                    let sound_stream_head = SoundStreamInfo {
+                        wrapping: SoundStreamWrapping::Unwrapped,
+                        stream_format: Self::mp4_sound_format(track)
+                            .expect("audio track has a supported codec"),
+                        num_samples_per_block: 0,
+                        latency_seek: 0,
+                    };
+
+                    (Substream::new(slice.buffer().clone()), sound_stream_head)
+                });
+
+                if let Err(e) = substream.append(slice.to_subslice(data)) {
+                    tracing::error!("Error queueing MP4 audio sample: {}", e);
+                    break;
+                }
+
+                *next_audio_sample += 1;
+            }
+
+            is_finished &= *next_audio_sample >= track.samples.len();
+        }
+
+        is_finished
+    }
+
     pub fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
         #![allow(clippy::explicit_auto_deref)] //Erroneous lint
         let mut write = self.0.write(context.gc_context);
@@ -814,8 +1214,32 @@ impl<'gc> NetStream<'gc> {
                 return;
             }
 
-            match buffer.get(0..3) {
-                Some([0x46, 0x4C, 0x56]) => {
+            let mut sniff_error = None;
+            let mut metadata = None;
+
+            if buffer.len() < 8 {
+                if !write.is_complete {
+                    return; //Not enough data to check for an MP4
+                }
+
+                tracing::error!("Stream is too short to play: {} bytes", buffer.len());
+                write.preload_offset = 3;
+                drop(buffer);
+                drop(slice);
+                drop(write);
+                self.trigger_status_event(
+                    context,
+                    &[
+                        ("code", "NetStream.Play.StreamNotFound"),
+                        ("level", "error"),
+                    ],
+                );
+                self.pause(context);
+                return;
+            }
+
+            match &buffer[0..3] {
+                [0x46, 0x4C, 0x56] => {
                     let mut reader = FlvReader::from_parts(&*buffer, write.offset);
                     match FlvHeader::parse(&mut reader) {
                         Ok(header) => {
@@ -836,7 +1260,30 @@ impl<'gc> NetStream<'gc> {
                         }
                     }
                 }
-                Some(magic) => {
+                _ if Mp4Movie::is_mp4(&buffer) => {
+                    match Mp4Movie::parse(&buffer, write.is_complete) {
+                        Ok(movie) => match Self::mp4_stream_type(context, movie) {
+                            Ok(stream_type) => {
+                                if let NetStreamType::Mp4 { movie, .. } = &stream_type {
+                                    metadata = Some(Self::mp4_metadata(movie));
+                                }
+                                write.stream_type = Some(stream_type);
+                            }
+                            Err(e) => {
+                                tracing::error!("MP4 stream cannot be played: {}", e);
+                                write.preload_offset = 3;
+                                sniff_error = Some("NetStream.Play.NoSupportedTrackFound");
+                            }
+                        },
+                        Err(Mp4Error::EndOfData) if !write.is_complete => return,
+                        Err(e) => {
+                            tracing::error!("MP4 parsing failed: {}", e);
+                            write.preload_offset = 3;
+                            sniff_error = Some("NetStream.Play.FileStructureInvalid");
+                        }
+                    }
+                }
+                magic => {
                     //Unrecognized signature
                     //TODO: Fire an error event to AS & stop playing too
                     tracing::error!("Unrecognized file signature: {:?}", magic);
@@ -860,7 +1307,26 @@ impl<'gc> NetStream<'gc> {
                     }
                     return;
                 }
-                None => return, //Data not yet loaded
+            }
+
+            if let Some(code) = sniff_error {
+                drop(buffer);
+                drop(slice);
+                drop(write);
+                self.trigger_status_event(context, &[("code", code), ("level", "error")]);
+                self.pause(context);
+                return;
+            }
+
+            if let Some(metadata) = metadata {
+                // Playback starts on the next tick, as script callbacks may
+                // modify the stream.
+                let avm_object = write.avm_object;
+                drop(buffer);
+                drop(slice);
+                drop(write);
+                let _ = self.handle_script_data(avm_object, context, b"onMetaData", metadata); // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+                return;
             }
         }
 
@@ -940,6 +1406,8 @@ impl<'gc> NetStream<'gc> {
                     write.preload_offset = max(write.offset, write.preload_offset);
                 }
             }
+        } else if matches!(write.stream_type, Some(NetStreamType::Mp4 { .. })) {
+            end_of_video = self.mp4_tick(context, &mut write, &slice, &buffer, end_time);
         }
 
         write.stream_time = end_time;
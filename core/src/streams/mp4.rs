@@ -0,0 +1,790 @@
+//! ISO base media file format (MP4/F4V) demuxer
+//!
+//! This only reads the sample tables out of the `moov` box; sample data is
+//! left in place in the stream buffer, to be picked out by `NetStream` as it
+//! is loaded.
+
+use std::fmt;
+use thiserror::Error;
+
+pub type FourCc = [u8; 4];
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Mp4Error {
+    #[error("the MP4 parser ran out of data")]
+    EndOfData,
+
+    #[error("the MP4 does not contain a {0} box")]
+    MissingBox(&'static str),
+
+    #[error("the MP4 contains a box with an invalid size")]
+    InvalidBoxSize,
+
+    #[error("the MP4 contains an inconsistent sample table")]
+    InvalidSampleTable,
+}
+
+/// A simple big-endian cursor over box contents.
+struct BoxReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BoxReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    fn read(&mut self, count: usize) -> Result<&'a [u8], Mp4Error> {
+        let end = self
+            .position
+            .checked_add(count)
+            .ok_or(Mp4Error::EndOfData)?;
+        let data = self
+            .data
+            .get(self.position..end)
+            .ok_or(Mp4Error::EndOfData)?;
+        self.position = end;
+        Ok(data)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), Mp4Error> {
+        self.read(count).map(|_| ())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Mp4Error> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Mp4Error> {
+        let bytes = self.read(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Mp4Error> {
+        let bytes = self.read(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Mp4Error> {
+        let bytes = self.read(8)?;
+        let mut value = [0; 8];
+        value.copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(value))
+    }
+
+    fn read_fourcc(&mut self) -> Result<FourCc, Mp4Error> {
+        let bytes = self.read(4)?;
+        Ok([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Read the version byte of a full box, skipping the flags.
+    fn read_version(&mut self) -> Result<u8, Mp4Error> {
+        let version = self.read_u8()?;
+        self.skip(3)?;
+        Ok(version)
+    }
+
+    /// Read a 32-bit value in version 0 boxes and a 64-bit one otherwise.
+    fn read_versioned(&mut self, version: u8) -> Result<u64, Mp4Error> {
+        if version == 1 {
+            self.read_u64()
+        } else {
+            Ok(self.read_u32()? as u64)
+        }
+    }
+
+    /// Read the next box header and contents.
+    ///
+    /// Returns `None` once all data has been consumed. A box that extends
+    /// past the end of our data yields `EndOfData`.
+    fn next_box(&mut self) -> Result<Option<(FourCc, &'a [u8])>, Mp4Error> {
+        if self.remaining() == 0 {
+            return Ok(None);
+        }
+
+        let start = self.position;
+        let size = self.read_u32()? as u64;
+        let box_type = self.read_fourcc()?;
+        let size = match size {
+            0 => (self.data.len() - start) as u64,
+            1 => self.read_u64()?,
+            size => size,
+        };
+
+        let header_size = (self.position - start) as u64;
+        if size < header_size {
+            return Err(Mp4Error::InvalidBoxSize);
+        }
+
+        let contents = self.read((size - header_size) as usize)?;
+        Ok(Some((box_type, contents)))
+    }
+
+    /// Find the first child box of a given type.
+    fn find_box(data: &'a [u8], box_type: &FourCc) -> Result<Option<&'a [u8]>, Mp4Error> {
+        let mut reader = BoxReader::new(data);
+        while let Some((child_type, contents)) = reader.next_box()? {
+            if &child_type == box_type {
+                return Ok(Some(contents));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Find a required child box of a given type.
+    fn require_box(data: &'a [u8], box_type: &'static str) -> Result<&'a [u8], Mp4Error> {
+        let fourcc = box_type
+            .as_bytes()
+            .try_into()
+            .expect("box types are four bytes");
+        Self::find_box(data, &fourcc)?.ok_or(Mp4Error::MissingBox(box_type))
+    }
+}
+
+/// The format of the samples in a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mp4Codec {
+    /// H.264 video, which we cannot decode.
+    Avc,
+
+    /// On2 VP6 video, as stored in F4V files.
+    Vp6,
+
+    /// On2 VP6 video with an alpha channel, as stored in F4V files.
+    Vp6WithAlpha,
+
+    /// AAC audio, which we cannot decode.
+    Aac,
+
+    /// MP3 audio, either as a `.mp3` sample entry or within an MPEG-4 audio
+    /// elementary stream.
+    Mp3,
+
+    /// Uncompressed, unsigned 8-bit audio.
+    Raw,
+
+    /// Uncompressed, little-endian 16-bit audio.
+    PcmLittleEndian,
+
+    /// Any other sample format, identified by its sample entry type.
+    Unknown(FourCc),
+}
+
+impl fmt::Display for Mp4Codec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Avc => write!(f, "H.264"),
+            Self::Vp6 => write!(f, "VP6"),
+            Self::Vp6WithAlpha => write!(f, "VP6 with alpha"),
+            Self::Aac => write!(f, "AAC"),
+            Self::Mp3 => write!(f, "MP3"),
+            Self::Raw => write!(f, "8-bit PCM"),
+            Self::PcmLittleEndian => write!(f, "16-bit PCM"),
+            Self::Unknown(format) => write!(f, "'{}'", String::from_utf8_lossy(format)),
+        }
+    }
+}
+
+/// What kind of media a track contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mp4TrackKind {
+    Video {
+        width: u16,
+        height: u16,
+    },
+    Audio {
+        channel_count: u16,
+        sample_size: u16,
+        sample_rate: u32,
+    },
+
+    /// A track with a handler type we do not use, such as hint or text
+    /// tracks.
+    Other,
+}
+
+/// A single sample (frame) of media in a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mp4Sample {
+    /// The absolute offset of the sample data in the file.
+    pub offset: u64,
+
+    /// The size of the sample data in bytes.
+    pub size: u32,
+
+    /// The decoding time of the sample, in units of the track timescale.
+    pub decode_time: u64,
+
+    /// Whether or not the sample can be decoded without any prior samples.
+    pub is_sync: bool,
+}
+
+impl Mp4Sample {
+    /// Get the data of this sample out of the file, if it has loaded.
+    pub fn data<'a>(&self, file: &'a [u8]) -> Option<&'a [u8]> {
+        let start = usize::try_from(self.offset).ok()?;
+        let end = start.checked_add(self.size as usize)?;
+        file.get(start..end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mp4Track {
+    pub track_id: u32,
+
+    /// The number of time units per second in this track.
+    pub timescale: u32,
+
+    /// The duration of the track, in units of the track timescale.
+    pub duration: u64,
+
+    pub kind: Mp4TrackKind,
+    pub codec: Mp4Codec,
+    pub samples: Vec<Mp4Sample>,
+}
+
+impl Mp4Track {
+    /// The decoding time of a given sample, in milliseconds.
+    pub fn sample_time(&self, index: usize) -> f64 {
+        match self.samples.get(index) {
+            Some(sample) if self.timescale > 0 => {
+                sample.decode_time as f64 * 1000.0 / self.timescale as f64
+            }
+            _ => self.duration_ms(),
+        }
+    }
+
+    /// The duration of this track, in milliseconds.
+    pub fn duration_ms(&self) -> f64 {
+        if self.timescale > 0 {
+            self.duration as f64 * 1000.0 / self.timescale as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Find the sample to start decoding from in order to display the given
+    /// time.
+    ///
+    /// This is the last sync sample at or before the time, or the first
+    /// sample if there is none.
+    pub fn sync_sample_at(&self, time: f64) -> usize {
+        let mut sync_sample = 0;
+        for (index, sample) in self.samples.iter().enumerate() {
+            if self.sample_time(index) > time {
+                break;
+            }
+
+            if sample.is_sync {
+                sync_sample = index;
+            }
+        }
+
+        sync_sample
+    }
+
+    fn parse(data: &[u8]) -> Result<Self, Mp4Error> {
+        let mut tkhd = BoxReader::new(BoxReader::require_box(data, "tkhd")?);
+        let version = tkhd.read_version()?;
+        tkhd.read_versioned(version)?; // creation time
+        tkhd.read_versioned(version)?; // modification time
+        let track_id = tkhd.read_u32()?;
+
+        let mdia = BoxReader::require_box(data, "mdia")?;
+
+        let mut mdhd = BoxReader::new(BoxReader::require_box(mdia, "mdhd")?);
+        let version = mdhd.read_version()?;
+        mdhd.read_versioned(version)?; // creation time
+        mdhd.read_versioned(version)?; // modification time
+        let timescale = mdhd.read_u32()?;
+        let duration = mdhd.read_versioned(version)?;
+
+        let mut hdlr = BoxReader::new(BoxReader::require_box(mdia, "hdlr")?);
+        hdlr.read_version()?;
+        hdlr.skip(4)?; // pre-defined
+        let handler_type = hdlr.read_fourcc()?;
+
+        let minf = BoxReader::require_box(mdia, "minf")?;
+        let stbl = BoxReader::require_box(minf, "stbl")?;
+        let (kind, codec) =
+            parse_sample_description(BoxReader::require_box(stbl, "stsd")?, handler_type)?;
+
+        Ok(Self {
+            track_id,
+            timescale,
+            duration,
+            kind,
+            codec,
+            samples: parse_sample_table(stbl)?,
+        })
+    }
+}
+
+/// The demuxed structure of an MP4 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mp4Movie {
+    /// The number of time units per second in the movie header.
+    pub timescale: u32,
+
+    /// The duration of the movie, in units of the movie timescale.
+    pub duration: u64,
+
+    pub tracks: Vec<Mp4Track>,
+}
+
+impl Mp4Movie {
+    /// Check if the given data starts with an ISO base media file.
+    ///
+    /// This does not validate the file, only that the first box is a file
+    /// type box.
+    pub fn is_mp4(data: &[u8]) -> bool {
+        data.get(4..8) == Some(b"ftyp")
+    }
+
+    /// Parse the movie structure of an MP4 file.
+    ///
+    /// The entire `moov` box must be present in `data`, as well as any boxes
+    /// before it. If it is not, `EndOfData` will be returned, and parsing
+    /// should be retried once more of the file has loaded. Once `data` is
+    /// `complete`, a missing `moov` box is reported as `MissingBox` instead.
+    pub fn parse(data: &[u8], complete: bool) -> Result<Self, Mp4Error> {
+        // The movie box may come after the media data, so if we haven't seen
+        // it yet, we have to assume that it hasn't loaded.
+        let moov = match BoxReader::find_box(data, b"moov")? {
+            Some(moov) => moov,
+            None if complete => return Err(Mp4Error::MissingBox("moov")),
+            None => return Err(Mp4Error::EndOfData),
+        };
+
+        let mut mvhd = BoxReader::new(BoxReader::require_box(moov, "mvhd")?);
+        let version = mvhd.read_version()?;
+        mvhd.read_versioned(version)?; // creation time
+        mvhd.read_versioned(version)?; // modification time
+        let timescale = mvhd.read_u32()?;
+        let duration = mvhd.read_versioned(version)?;
+
+        let mut tracks = vec![];
+        let mut reader = BoxReader::new(moov);
+        while let Some((box_type, contents)) = reader.next_box()? {
+            if &box_type == b"trak" {
+                tracks.push(Mp4Track::parse(contents)?);
+            }
+        }
+
+        Ok(Self {
+            timescale,
+            duration,
+            tracks,
+        })
+    }
+
+    /// The duration of the movie, in milliseconds.
+    pub fn duration_ms(&self) -> f64 {
+        if self.timescale > 0 {
+            self.duration as f64 * 1000.0 / self.timescale as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Parse the first sample entry of a sample description box.
+fn parse_sample_description(
+    data: &[u8],
+    handler_type: FourCc,
+) -> Result<(Mp4TrackKind, Mp4Codec), Mp4Error> {
+    let mut stsd = BoxReader::new(data);
+    stsd.read_version()?;
+    let _entry_count = stsd.read_u32()?;
+    let (format, entry) = stsd.next_box()?.ok_or(Mp4Error::MissingBox("stsd"))?;
+
+    let mut entry = BoxReader::new(entry);
+    entry.skip(6)?; // reserved
+    let _data_reference_index = entry.read_u16()?;
+
+    match &handler_type {
+        b"vide" => {
+            entry.skip(16)?; // pre-defined and reserved
+            let width = entry.read_u16()?;
+            let height = entry.read_u16()?;
+            let codec = match &format {
+                b"avc1" | b"avc3" => Mp4Codec::Avc,
+                b"VP6F" => Mp4Codec::Vp6,
+                b"VP6A" => Mp4Codec::Vp6WithAlpha,
+                _ => Mp4Codec::Unknown(format),
+            };
+
+            Ok((Mp4TrackKind::Video { width, height }, codec))
+        }
+        b"soun" => {
+            // QuickTime sound descriptions are versioned; MP4 requires version 0.
+            let version = entry.read_u16()?;
+            entry.skip(6)?; // revision and vendor
+            let channel_count = entry.read_u16()?;
+            let sample_size = entry.read_u16()?;
+            entry.skip(4)?; // compression ID and packet size
+            let sample_rate = entry.read_u32()? >> 16;
+            match version {
+                1 => entry.skip(16)?,
+                2 => entry.skip(36)?,
+                _ => {}
+            }
+
+            let codec = match &format {
+                b".mp3" => Mp4Codec::Mp3,
+                b"raw " => Mp4Codec::Raw,
+                b"sowt" => Mp4Codec::PcmLittleEndian,
+                b"mp4a" => {
+                    let children = entry.read(entry.remaining())?;
+                    match BoxReader::find_box(children, b"esds")? {
+                        Some(esds) => match parse_object_type(esds)? {
+                            // MPEG-1 and MPEG-2 audio (layer 3)
+                            0x69 | 0x6B => Mp4Codec::Mp3,
+                            _ => Mp4Codec::Aac,
+                        },
+                        None => Mp4Codec::Aac,
+                    }
+                }
+                _ => Mp4Codec::Unknown(format),
+            };
+
+            Ok((
+                Mp4TrackKind::Audio {
+                    channel_count,
+                    sample_size,
+                    sample_rate,
+                },
+                codec,
+            ))
+        }
+        _ => Ok((Mp4TrackKind::Other, Mp4Codec::Unknown(format))),
+    }
+}
+
+/// Read the length of an MPEG-4 descriptor.
+fn read_descriptor_length(reader: &mut BoxReader<'_>) -> Result<u32, Mp4Error> {
+    let mut length = 0;
+    for _ in 0..4 {
+        let byte = reader.read_u8()?;
+        length = (length << 7) | (byte & 0x7F) as u32;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok(length)
+}
+
+/// Get the object type indication from an elementary stream descriptor box.
+fn parse_object_type(data: &[u8]) -> Result<u8, Mp4Error> {
+    let mut esds = BoxReader::new(data);
+    esds.read_version()?;
+
+    // ES_Descriptor
+    if esds.read_u8()? != 0x03 {
+        return Err(Mp4Error::MissingBox("esds"));
+    }
+    read_descriptor_length(&mut esds)?;
+    esds.skip(2)?; // ES_ID
+    let flags = esds.read_u8()?;
+    if flags & 0x80 != 0 {
+        esds.skip(2)?; // dependsOn_ES_ID
+    }
+    if flags & 0x40 != 0 {
+        let url_length = esds.read_u8()?;
+        esds.skip(url_length as usize)?;
+    }
+    if flags & 0x20 != 0 {
+        esds.skip(2)?; // OCR_ES_Id
+    }
+
+    // DecoderConfigDescriptor
+    if esds.read_u8()? != 0x04 {
+        return Err(Mp4Error::MissingBox("esds"));
+    }
+    read_descriptor_length(&mut esds)?;
+    esds.read_u8()
+}
+
+/// Build the list of samples in a track from its sample table box.
+fn parse_sample_table(stbl: &[u8]) -> Result<Vec<Mp4Sample>, Mp4Error> {
+    // Sample sizes
+    let mut stsz = BoxReader::new(BoxReader::require_box(stbl, "stsz")?);
+    stsz.read_version()?;
+    let constant_size = stsz.read_u32()?;
+    let sample_count = stsz.read_u32()? as usize;
+    let sizes = if constant_size == 0 {
+        let mut sizes = Vec::with_capacity(sample_count.min(stsz.remaining() / 4));
+        for _ in 0..sample_count {
+            sizes.push(stsz.read_u32()?);
+        }
+        Some(sizes)
+    } else {
+        None
+    };
+
+    // Chunk offsets
+    let chunk_offsets = if let Some(stco) = BoxReader::find_box(stbl, b"stco")? {
+        let mut stco = BoxReader::new(stco);
+        stco.read_version()?;
+        let count = stco.read_u32()? as usize;
+        let mut offsets = Vec::with_capacity(count.min(stco.remaining() / 4));
+        for _ in 0..count {
+            offsets.push(stco.read_u32()? as u64);
+        }
+        offsets
+    } else {
+        let mut co64 = BoxReader::new(BoxReader::require_box(stbl, "co64")?);
+        co64.read_version()?;
+        let count = co64.read_u32()? as usize;
+        let mut offsets = Vec::with_capacity(count.min(co64.remaining() / 8));
+        for _ in 0..count {
+            offsets.push(co64.read_u64()?);
+        }
+        offsets
+    };
+
+    // Sample-to-chunk mapping, as (first chunk, samples per chunk)
+    let mut stsc = BoxReader::new(BoxReader::require_box(stbl, "stsc")?);
+    stsc.read_version()?;
+    let count = stsc.read_u32()? as usize;
+    let mut chunk_runs = Vec::with_capacity(count.min(stsc.remaining() / 12));
+    for _ in 0..count {
+        let first_chunk = stsc.read_u32()?;
+        let samples_per_chunk = stsc.read_u32()?;
+        let _sample_description_index = stsc.read_u32()?;
+        if first_chunk == 0 {
+            return Err(Mp4Error::InvalidSampleTable);
+        }
+        chunk_runs.push((first_chunk as usize - 1, samples_per_chunk as usize));
+    }
+
+    // Decoding times, as (sample count, sample delta)
+    let mut stts = BoxReader::new(BoxReader::require_box(stbl, "stts")?);
+    stts.read_version()?;
+    let count = stts.read_u32()? as usize;
+    let mut time_runs = Vec::with_capacity(count.min(stts.remaining() / 8));
+    for _ in 0..count {
+        time_runs.push((stts.read_u32()?, stts.read_u32()?));
+    }
+
+    // Sync samples; if there is no table, then every sample is a sync sample.
+    let sync_samples = if let Some(stss) = BoxReader::find_box(stbl, b"stss")? {
+        let mut stss = BoxReader::new(stss);
+        stss.read_version()?;
+        let count = stss.read_u32()? as usize;
+        let mut sync_samples = Vec::with_capacity(count.min(stss.remaining() / 4));
+        for _ in 0..count {
+            sync_samples.push(stss.read_u32()?);
+        }
+        Some(sync_samples)
+    } else {
+        None
+    };
+
+    let mut samples = Vec::with_capacity(sizes.as_ref().map(Vec::len).unwrap_or(0));
+    let mut time_runs = time_runs.into_iter();
+    let mut time_run = (0, 0);
+    let mut decode_time = 0u64;
+    let mut sync_samples = sync_samples.map(|s| s.into_iter().peekable());
+
+    'chunks: for (chunk, chunk_offset) in chunk_offsets.iter().enumerate() {
+        let samples_per_chunk = chunk_runs
+            .iter()
+            .rev()
+            .find(|(first_chunk, _)| *first_chunk <= chunk)
+            .map(|(_, samples_per_chunk)| *samples_per_chunk)
+            .ok_or(Mp4Error::InvalidSampleTable)?;
+
+        let mut offset = *chunk_offset;
+        for _ in 0..samples_per_chunk {
+            let index = samples.len();
+            if index >= sample_count {
+                break 'chunks;
+            }
+
+            let size = match &sizes {
+                Some(sizes) => sizes[index],
+                None => constant_size,
+            };
+
+            while time_run.0 == 0 {
+                time_run = time_runs.next().ok_or(Mp4Error::InvalidSampleTable)?;
+            }
+            time_run.0 -= 1;
+
+            let is_sync = match &mut sync_samples {
+                Some(sync_samples) => {
+                    // Sync sample numbers are 1-based.
+                    if sync_samples.peek() == Some(&(index as u32 + 1)) {
+                        sync_samples.next();
+                        true
+                    } else {
+                        false
+                    }
+                }
+                None => true,
+            };
+
+            samples.push(Mp4Sample {
+                offset,
+                size,
+                decode_time,
+                is_sync,
+            });
+
+            offset += size as u64;
+            decode_time += time_run.1 as u64;
+        }
+    }
+
+    if samples.len() < sample_count {
+        return Err(Mp4Error::InvalidSampleTable);
+    }
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::streams::mp4::{Mp4Codec, Mp4Error, Mp4Movie, Mp4Sample, Mp4TrackKind};
+
+    fn make_box(box_type: &[u8; 4], contents: &[u8]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(contents.len() + 8);
+        data.extend_from_slice(&(contents.len() as u32 + 8).to_be_bytes());
+        data.extend_from_slice(box_type);
+        data.extend_from_slice(contents);
+        data
+    }
+
+    fn make_full_box(box_type: &[u8; 4], values: &[u32]) -> Vec<u8> {
+        let mut contents = vec![0; 4];
+        for value in values {
+            contents.extend_from_slice(&value.to_be_bytes());
+        }
+        make_box(box_type, &contents)
+    }
+
+    fn make_video_track() -> Vec<u8> {
+        let tkhd = make_full_box(b"tkhd", &[0, 0, 1, 0, 3000]);
+        let mdhd = make_full_box(b"mdhd", &[0, 0, 1000, 3000]);
+        let hdlr = make_full_box(b"hdlr", &[0, u32::from_be_bytes(*b"vide")]);
+
+        let mut entry = vec![0; 6];
+        entry.extend_from_slice(&1u16.to_be_bytes());
+        entry.extend_from_slice(&[0; 16]);
+        entry.extend_from_slice(&320u16.to_be_bytes());
+        entry.extend_from_slice(&240u16.to_be_bytes());
+        entry.extend_from_slice(&[0; 50]);
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend(make_box(b"VP6F", &entry));
+        let stsd = make_box(b"stsd", &stsd);
+
+        // Three samples of 1 second each, split across two chunks, with only
+        // the first and last being keyframes.
+        let stts = make_full_box(b"stts", &[1, 3, 1000]);
+        let stss = make_full_box(b"stss", &[2, 1, 3]);
+        let stsc = make_full_box(b"stsc", &[2, 1, 2, 1, 2, 1, 1]);
+        let stsz = make_full_box(b"stsz", &[0, 3, 10, 20, 30]);
+        let stco = make_full_box(b"stco", &[2, 100, 500]);
+        let stbl = make_box(b"stbl", &[stsd, stts, stss, stsc, stsz, stco].concat());
+        let minf = make_box(b"minf", &stbl);
+        let mdia = make_box(b"mdia", &[mdhd, hdlr, minf].concat());
+        make_box(b"trak", &[tkhd, mdia].concat())
+    }
+
+    fn make_movie() -> Vec<u8> {
+        let mvhd = make_full_box(b"mvhd", &[0, 0, 600, 1800]);
+        let moov = make_box(b"moov", &[mvhd, make_video_track()].concat());
+        [make_box(b"ftyp", b"f4v \0\0\0\0"), moov].concat()
+    }
+
+    #[test]
+    fn parse_movie() {
+        let data = make_movie();
+        assert!(Mp4Movie::is_mp4(&data));
+
+        let movie = Mp4Movie::parse(&data, true).expect("valid movie");
+        assert_eq!(movie.timescale, 600);
+        assert_eq!(movie.duration_ms(), 3000.0);
+        assert_eq!(movie.tracks.len(), 1);
+
+        let track = &movie.tracks[0];
+        assert_eq!(track.track_id, 1);
+        assert_eq!(track.codec, Mp4Codec::Vp6);
+        assert_eq!(
+            track.kind,
+            Mp4TrackKind::Video {
+                width: 320,
+                height: 240
+            }
+        );
+        assert_eq!(
+            track.samples,
+            vec![
+                Mp4Sample {
+                    offset: 100,
+                    size: 10,
+                    decode_time: 0,
+                    is_sync: true
+                },
+                Mp4Sample {
+                    offset: 110,
+                    size: 20,
+                    decode_time: 1000,
+                    is_sync: false
+                },
+                Mp4Sample {
+                    offset: 500,
+                    size: 30,
+                    decode_time: 2000,
+                    is_sync: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_samples() {
+        let movie = Mp4Movie::parse(&make_movie(), true).expect("valid movie");
+        let track = &movie.tracks[0];
+
+        assert_eq!(track.sync_sample_at(0.0), 0);
+        assert_eq!(track.sync_sample_at(1500.0), 0);
+        assert_eq!(track.sync_sample_at(2000.0), 2);
+        assert_eq!(track.sync_sample_at(10000.0), 2);
+    }
+
+    #[test]
+    fn parse_truncated_movie() {
+        let data = make_movie();
+
+        assert_eq!(
+            Mp4Movie::parse(&data[..data.len() - 1], false),
+            Err(Mp4Error::EndOfData)
+        );
+    }
+
+    #[test]
+    fn parse_unloaded_movie() {
+        let data = make_box(b"ftyp", b"f4v \0\0\0\0");
+
+        assert_eq!(Mp4Movie::parse(&data, false), Err(Mp4Error::EndOfData));
+    }
+
+    #[test]
+    fn parse_movie_without_moov() {
+        let data = make_box(b"ftyp", b"f4v \0\0\0\0");
+
+        assert_eq!(
+            Mp4Movie::parse(&data, true),
+            Err(Mp4Error::MissingBox("moov"))
+        );
+    }
+}
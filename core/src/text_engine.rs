@@ -0,0 +1,740 @@
+//! Line layout for the Flash Text Engine (`flash.text.engine`).
+//!
+//! A `TextBlock` is flattened into runs of text sharing the same format, which
+//! are then broken into lines one at a time, each line consisting of atoms.
+//! Atoms are the indivisible units of a line, which are currently single
+//! characters.
+
+use crate::drawing::Drawing;
+use crate::font::Font;
+use crate::string::{WStr, WString};
+use ruffle_render::shape_utils::DrawCommand;
+use swf::{Color, FillStyle, Point, ShapeRecord, Twips};
+
+/// The distance between the default tab stops, in pixels.
+const DEFAULT_TAB_WIDTH: f64 = 48.0;
+
+/// The formatting of a run of text, as given by an `ElementFormat`.
+#[derive(Debug, Clone)]
+pub struct RunFormat<'gc> {
+    pub font: Font<'gc>,
+
+    /// The size of the text, in pixels.
+    pub size: f64,
+
+    pub color: Color,
+
+    /// The distance the text is moved down from the baseline, in pixels.
+    pub baseline_shift: f64,
+
+    /// The space added before each character, in pixels.
+    pub tracking_left: f64,
+
+    /// The space added after each character, in pixels.
+    pub tracking_right: f64,
+
+    pub kerning: bool,
+}
+
+impl<'gc> RunFormat<'gc> {
+    /// The scale from EM-square coordinates of the font to pixels.
+    fn scale(&self) -> f64 {
+        self.size / f64::from(self.font.scale())
+    }
+
+    fn ascent(&self) -> f64 {
+        self.font
+            .get_baseline_for_height(Twips::from_pixels(self.size))
+            .to_pixels()
+    }
+
+    fn descent(&self) -> f64 {
+        self.font
+            .get_descent_for_height(Twips::from_pixels(self.size))
+            .to_pixels()
+    }
+}
+
+/// A run of text sharing the same format, as given by a `TextElement`.
+#[derive(Debug, Clone)]
+pub struct TextRun<'gc> {
+    pub text: WString,
+    pub format: RunFormat<'gc>,
+}
+
+/// Which lines of a text block are justified, as given by `LineJustification`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJustification {
+    Unjustified,
+    AllButLast,
+    AllButMandatoryBreak,
+    AllIncludingLast,
+}
+
+impl LineJustification {
+    pub fn from_name(name: &WStr) -> Option<Self> {
+        match name.to_utf8_lossy().as_ref() {
+            "unjustified" => Some(Self::Unjustified),
+            "allButLast" => Some(Self::AllButLast),
+            "allButMandatoryBreak" => Some(Self::AllButMandatoryBreak),
+            "allIncludingLast" => Some(Self::AllIncludingLast),
+            _ => None,
+        }
+    }
+}
+
+/// How the lines of a text block are justified, as given by a `TextJustifier`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Justifier {
+    pub line_justification: LineJustification,
+
+    /// Whether extra space is distributed between all characters, rather than
+    /// between words only.
+    pub letter_spacing: bool,
+}
+
+impl Default for Justifier {
+    fn default() -> Self {
+        Self {
+            line_justification: LineJustification::Unjustified,
+            letter_spacing: false,
+        }
+    }
+}
+
+/// How the text following a tab is aligned, as given by `TabAlignment`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabAlignment {
+    Start,
+    Center,
+    End,
+    Decimal,
+}
+
+impl TabAlignment {
+    pub fn from_name(name: &WStr) -> Option<Self> {
+        match name.to_utf8_lossy().as_ref() {
+            "start" => Some(Self::Start),
+            "center" => Some(Self::Center),
+            "end" => Some(Self::End),
+            "decimal" => Some(Self::Decimal),
+            _ => None,
+        }
+    }
+}
+
+/// A tab stop, as given by a `TabStop`.
+#[derive(Debug, Clone)]
+pub struct TabStop {
+    pub alignment: TabAlignment,
+
+    /// The position of the tab stop from the origin of the tab stops, in
+    /// pixels.
+    pub position: f64,
+
+    /// The text to align on, for decimal tab stops.
+    pub decimal_alignment_token: WString,
+}
+
+/// A single character of a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atom {
+    /// The index of the first character of the atom within the text block.
+    pub begin_index: usize,
+
+    /// The index after the last character of the atom within the text block.
+    pub end_index: usize,
+
+    pub character: char,
+
+    /// The index of the run containing the atom.
+    pub run: usize,
+
+    /// The start of the atom from the start of the line, in pixels.
+    pub x: f64,
+
+    /// The width of the atom, in pixels.
+    ///
+    /// This includes the tracking, kerning and justification of the atom.
+    pub width: f64,
+}
+
+/// A line of text laid out by `lay_out_line`.
+#[derive(Debug, Clone)]
+pub struct LineLayout {
+    pub atoms: Vec<Atom>,
+
+    /// The index of the first character of the line within the text block.
+    pub begin_index: usize,
+
+    /// The number of characters in the line, including trailing spaces and
+    /// line breaks.
+    pub raw_text_length: usize,
+
+    /// The distance from the baseline to the top of the line, in pixels.
+    pub ascent: f64,
+
+    /// The distance from the baseline to the bottom of the line, in pixels.
+    pub descent: f64,
+
+    /// The width of the line without its trailing spaces, in pixels.
+    pub text_width: f64,
+
+    /// The width of the line before justification, in pixels.
+    pub unjustified_text_width: f64,
+
+    /// Whether the line had to be broken without a break opportunity.
+    pub is_emergency: bool,
+}
+
+fn is_mandatory_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+fn is_space(c: char) -> bool {
+    c == ' ' || c == '\u{3000}'
+}
+
+/// Ideographic characters, which can be broken before and after.
+fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FFEF}'
+    )
+}
+
+/// Collect the atoms of the paragraph starting at `begin_index`, with their
+/// natural widths.
+fn collect_atoms(runs: &[TextRun<'_>], begin_index: usize) -> Vec<Atom> {
+    let mut atoms = Vec::new();
+    let mut run_start = 0;
+    'runs: for (run_index, run) in runs.iter().enumerate() {
+        let run_end = run_start + run.text.len();
+        if run_end > begin_index {
+            let skip = begin_index.saturating_sub(run_start);
+            let format = &run.format;
+            for (position, c) in run.text[skip..].char_indices() {
+                let len = c.map_or(1, char::len_utf16);
+                let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+                let glyph_advance = format
+                    .font
+                    .get_glyph_for_char(c)
+                    .filter(|_| !c.is_control())
+                    .map_or(0.0, |glyph| {
+                        f64::from(glyph.advance().get()) * format.scale()
+                    });
+                let begin_index = run_start + skip + position;
+                atoms.push(Atom {
+                    begin_index,
+                    end_index: begin_index + len,
+                    character: c,
+                    run: run_index,
+                    x: 0.0,
+                    width: glyph_advance + format.tracking_left + format.tracking_right,
+                });
+
+                if is_mandatory_break(c) {
+                    break 'runs;
+                }
+            }
+        }
+        run_start = run_end;
+    }
+
+    // Apply kerning between the characters of the same run.
+    for i in 1..atoms.len() {
+        let (left, right) = (&atoms[i - 1], &atoms[i]);
+        if left.run == right.run {
+            let format = &runs[left.run].format;
+            if format.kerning {
+                let offset = format
+                    .font
+                    .get_kerning_offset(left.character, right.character);
+                atoms[i - 1].width += f64::from(offset.get()) * format.scale();
+            }
+        }
+    }
+
+    atoms
+}
+
+/// The width of the atoms following a tab which are aligned by its tab stop.
+fn tab_segment_width(atoms: &[Atom], alignment: TabAlignment, token: &WStr) -> f64 {
+    let token = token.chars().next().and_then(Result::ok).unwrap_or('.');
+    atoms
+        .iter()
+        .take_while(|atom| atom.character != '\t' && !is_mandatory_break(atom.character))
+        .take_while(|atom| alignment != TabAlignment::Decimal || atom.character != token)
+        .map(|atom| atom.width)
+        .sum()
+}
+
+/// Position the atoms one after another, expanding tabs to their tab stops.
+///
+/// The line starts `line_offset` pixels after the origin of the tab stops.
+fn position_atoms(atoms: &mut [Atom], tab_stops: &[TabStop], line_offset: f64) {
+    let mut x = line_offset;
+    for i in 0..atoms.len() {
+        if atoms[i].character == '\t' {
+            let tab_stop = tab_stops.iter().find(|tab_stop| tab_stop.position > x);
+            let width = if let Some(tab_stop) = tab_stop {
+                let segment = tab_segment_width(
+                    &atoms[i + 1..],
+                    tab_stop.alignment,
+                    &tab_stop.decimal_alignment_token,
+                );
+                match tab_stop.alignment {
+                    TabAlignment::Start => tab_stop.position - x,
+                    TabAlignment::Center => tab_stop.position - x - segment / 2.0,
+                    TabAlignment::End | TabAlignment::Decimal => tab_stop.position - x - segment,
+                }
+            } else {
+                ((x / DEFAULT_TAB_WIDTH).floor() + 1.0) * DEFAULT_TAB_WIDTH - x
+            };
+            atoms[i].width = width.max(0.0);
+        }
+
+        atoms[i].x = x - line_offset;
+        x += atoms[i].width;
+    }
+}
+
+/// The number of atoms of a line which aren't trailing spaces or line breaks.
+fn content_len(atoms: &[Atom]) -> usize {
+    atoms
+        .iter()
+        .rposition(|atom| !is_space(atom.character) && !is_mandatory_break(atom.character))
+        .map_or(0, |i| i + 1)
+}
+
+/// Spread the atoms of a line to fill the given width.
+fn justify(atoms: &mut [Atom], width: f64, text_width: f64, letter_spacing: bool) {
+    let content_len = content_len(atoms);
+    let extra = width - text_width;
+    if extra <= 0.0 || content_len < 2 {
+        return;
+    }
+
+    let is_gap = |atom: &Atom| letter_spacing || is_space(atom.character);
+    let gaps = atoms[..content_len - 1]
+        .iter()
+        .filter(|atom| is_gap(atom))
+        .count();
+    if gaps == 0 {
+        return;
+    }
+
+    let gap_width = extra / gaps as f64;
+    let mut x = 0.0;
+    for (i, atom) in atoms.iter_mut().enumerate() {
+        if i < content_len - 1 && is_gap(atom) {
+            atom.width += gap_width;
+        }
+        atom.x = x;
+        x += atom.width;
+    }
+}
+
+/// Lay out the line of a text block starting at `begin_index`.
+///
+/// The line is broken at the last break opportunity fitting in `width`, or
+/// at the last fitting atom if there is none. `None` is returned if not even
+/// a single atom fits and `fit_something` isn't set, or if there's no text
+/// left to lay out.
+///
+/// Tabs are expanded to the tab stops as if the line started `line_offset`
+/// pixels after their origin.
+pub fn lay_out_line(
+    runs: &[TextRun<'_>],
+    begin_index: usize,
+    width: f64,
+    line_offset: f64,
+    justifier: Justifier,
+    tab_stops: &[TabStop],
+    fit_something: bool,
+) -> Option<LineLayout> {
+    let mut atoms = collect_atoms(runs, begin_index);
+    if atoms.is_empty() {
+        return None;
+    }
+    position_atoms(&mut atoms, tab_stops, line_offset);
+
+    let mut line_len = atoms.len();
+    let mut is_emergency = false;
+    let mut last_break = None;
+    for (i, atom) in atoms.iter().enumerate() {
+        if is_ideographic(atom.character) && i > 0 {
+            last_break = Some(i);
+        }
+
+        if !is_space(atom.character)
+            && !is_mandatory_break(atom.character)
+            && atom.x + atom.width > width
+        {
+            line_len = match last_break {
+                Some(last_break) => last_break,
+                None if i > 0 => {
+                    is_emergency = true;
+                    i
+                }
+                None if fit_something => {
+                    is_emergency = true;
+                    1
+                }
+                None => return None,
+            };
+            break;
+        }
+
+        if is_space(atom.character)
+            || atom.character == '\t'
+            || atom.character == '-'
+            || is_ideographic(atom.character)
+        {
+            last_break = Some(i + 1);
+        }
+    }
+    atoms.truncate(line_len);
+
+    let last_atom = atoms.last()?;
+    let text_len: usize = runs.iter().map(|run| run.text.len()).sum();
+    let is_last_line = last_atom.end_index >= text_len;
+    let ends_with_break = is_mandatory_break(last_atom.character);
+    let raw_text_length = last_atom.end_index - begin_index;
+
+    let content_len = content_len(&atoms);
+    let text_width = atoms[..content_len]
+        .last()
+        .map_or(0.0, |atom| atom.x + atom.width);
+    let unjustified_text_width = text_width;
+
+    let should_justify = match justifier.line_justification {
+        LineJustification::Unjustified => false,
+        LineJustification::AllButLast => !is_last_line,
+        LineJustification::AllButMandatoryBreak => !is_last_line && !ends_with_break,
+        LineJustification::AllIncludingLast => true,
+    };
+    let text_width = if should_justify && !is_emergency {
+        justify(&mut atoms, width, text_width, justifier.letter_spacing);
+        atoms[..content_len]
+            .last()
+            .map_or(0.0, |atom| atom.x + atom.width)
+    } else {
+        text_width
+    };
+
+    let mut ascent: f64 = 0.0;
+    let mut descent: f64 = 0.0;
+    for atom in &atoms {
+        let format = &runs[atom.run].format;
+        ascent = ascent.max(format.ascent() - format.baseline_shift);
+        descent = descent.max(format.descent() + format.baseline_shift);
+    }
+
+    Some(LineLayout {
+        atoms,
+        begin_index,
+        raw_text_length,
+        ascent,
+        descent,
+        text_width,
+        unjustified_text_width,
+        is_emergency,
+    })
+}
+
+/// Draw the glyphs of a line, with its baseline at `y = 0`.
+pub fn draw_line(drawing: &mut Drawing, runs: &[TextRun<'_>], layout: &LineLayout) {
+    let mut fill_color = None;
+    for atom in &layout.atoms {
+        let format = &runs[atom.run].format;
+        let Some(glyph) = format.font.get_glyph_for_char(atom.character) else {
+            continue;
+        };
+        if glyph.shape_records().is_empty() || atom.character.is_control() {
+            continue;
+        }
+
+        if fill_color.as_ref() != Some(&format.color) {
+            drawing.set_fill_style(Some(FillStyle::Color(format.color)));
+            fill_color = Some(format.color);
+        }
+
+        let scale = format.scale();
+        let origin_x = atom.x + format.tracking_left;
+        let origin_y = format.baseline_shift;
+        let to_point = |point: Point<Twips>| {
+            Point::new(
+                Twips::from_pixels(origin_x + f64::from(point.x.get()) * scale),
+                Twips::from_pixels(origin_y + f64::from(point.y.get()) * scale),
+            )
+        };
+
+        let mut pen = Point::ZERO;
+        for record in glyph.shape_records() {
+            match record {
+                ShapeRecord::StyleChange(style_change) => {
+                    if let Some(move_to) = style_change.move_to {
+                        pen = move_to;
+                        drawing.draw_command(DrawCommand::MoveTo(to_point(pen)));
+                    }
+                }
+                ShapeRecord::StraightEdge { delta } => {
+                    pen += *delta;
+                    drawing.draw_command(DrawCommand::LineTo(to_point(pen)));
+                }
+                ShapeRecord::CurvedEdge {
+                    control_delta,
+                    anchor_delta,
+                } => {
+                    let control = pen + *control_delta;
+                    pen = control + *anchor_delta;
+                    drawing.draw_command(DrawCommand::QuadraticCurveTo {
+                        control: to_point(control),
+                        anchor: to_point(pen),
+                    });
+                }
+            }
+        }
+    }
+
+    if fill_color.is_some() {
+        drawing.set_fill_style(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use gc_arena::rootless_arena;
+    use ruffle_render::backend::{null::NullRenderer, ViewportDimensions};
+
+    fn atom(begin_index: usize, character: char, width: f64) -> Atom {
+        Atom {
+            begin_index,
+            end_index: begin_index + 1,
+            character,
+            run: 0,
+            x: 0.0,
+            width,
+        }
+    }
+
+    /// Atoms of the given characters, each 10 pixels wide except for tabs.
+    fn text_atoms(text: &str) -> Vec<Atom> {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| atom(i, c, if c == '\t' { 0.0 } else { 10.0 }))
+            .collect()
+    }
+
+    fn tab_stop(alignment: TabAlignment, position: f64) -> TabStop {
+        TabStop {
+            alignment,
+            position,
+            decimal_alignment_token: WString::from_utf8("."),
+        }
+    }
+
+    fn positions(atoms: &[Atom]) -> Vec<f64> {
+        atoms.iter().map(|atom| atom.x).collect()
+    }
+
+    fn with_runs<F>(text: &str, callback: F)
+    where
+        F: for<'gc> FnOnce(&[TextRun<'gc>]),
+    {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new(ViewportDimensions {
+                width: 0,
+                height: 0,
+                scale_factor: 1.0,
+            });
+            let font = Player::load_device_font(mc, &mut renderer);
+            let runs = [TextRun {
+                text: WString::from_utf8(text),
+                format: RunFormat {
+                    font,
+                    size: 12.0,
+                    color: Color::BLACK,
+                    baseline_shift: 0.0,
+                    tracking_left: 0.0,
+                    tracking_right: 0.0,
+                    kerning: false,
+                },
+            }];
+
+            callback(&runs);
+        })
+    }
+
+    fn lay_out(runs: &[TextRun<'_>], begin_index: usize, width: f64) -> Option<LineLayout> {
+        lay_out_line(
+            runs,
+            begin_index,
+            width,
+            0.0,
+            Justifier::default(),
+            &[],
+            false,
+        )
+    }
+
+    #[test]
+    fn position_atoms_default_tab_stops() {
+        let mut atoms = text_atoms("a\tb\tc");
+        position_atoms(&mut atoms, &[], 0.0);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 48.0, 58.0, 96.0]);
+        assert_eq!(atoms[1].width, 38.0);
+    }
+
+    #[test]
+    fn position_atoms_line_offset() {
+        let mut atoms = text_atoms("a\tb");
+        position_atoms(&mut atoms, &[], 40.0);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 56.0]);
+
+        let mut atoms = text_atoms("a\tb");
+        position_atoms(&mut atoms, &[tab_stop(TabAlignment::Start, 100.0)], 30.0);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 70.0]);
+    }
+
+    #[test]
+    fn position_atoms_tab_alignments() {
+        let mut atoms = text_atoms("a\tbc");
+        position_atoms(&mut atoms, &[tab_stop(TabAlignment::Start, 100.0)], 0.0);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 100.0, 110.0]);
+
+        let mut atoms = text_atoms("a\tbc");
+        position_atoms(&mut atoms, &[tab_stop(TabAlignment::End, 100.0)], 0.0);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 80.0, 90.0]);
+
+        let mut atoms = text_atoms("a\tbc");
+        position_atoms(&mut atoms, &[tab_stop(TabAlignment::Center, 100.0)], 0.0);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 90.0, 100.0]);
+
+        let mut atoms = text_atoms("a\t12.5");
+        position_atoms(&mut atoms, &[tab_stop(TabAlignment::Decimal, 100.0)], 0.0);
+        assert_eq!(atoms[4].x, 100.0);
+    }
+
+    #[test]
+    fn position_atoms_skips_passed_tab_stops() {
+        let mut atoms = text_atoms("abc\td");
+        position_atoms(
+            &mut atoms,
+            &[
+                tab_stop(TabAlignment::Start, 20.0),
+                tab_stop(TabAlignment::Start, 60.0),
+            ],
+            0.0,
+        );
+        assert_eq!(atoms[4].x, 60.0);
+    }
+
+    #[test]
+    fn justify_spaces() {
+        let mut atoms = text_atoms("a b c");
+        position_atoms(&mut atoms, &[], 0.0);
+        justify(&mut atoms, 70.0, 50.0, false);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 30.0, 40.0, 60.0]);
+    }
+
+    #[test]
+    fn justify_letter_spacing() {
+        let mut atoms = text_atoms("abc");
+        position_atoms(&mut atoms, &[], 0.0);
+        justify(&mut atoms, 50.0, 30.0, true);
+        assert_eq!(positions(&atoms), vec![0.0, 20.0, 40.0]);
+    }
+
+    #[test]
+    fn justify_ignores_trailing_spaces() {
+        let mut atoms = text_atoms("a b ");
+        position_atoms(&mut atoms, &[], 0.0);
+        justify(&mut atoms, 50.0, 30.0, false);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0, 40.0, 50.0]);
+        assert_eq!(atoms[3].width, 10.0);
+    }
+
+    #[test]
+    fn justify_without_gaps() {
+        let mut atoms = text_atoms("ab");
+        position_atoms(&mut atoms, &[], 0.0);
+        justify(&mut atoms, 50.0, 20.0, false);
+        assert_eq!(positions(&atoms), vec![0.0, 10.0]);
+    }
+
+    #[test]
+    fn lay_out_line_breaks_at_space() {
+        with_runs("abcd efgh", |runs| {
+            let full = lay_out(runs, 0, 1000.0).unwrap();
+            assert_eq!(full.raw_text_length, 9);
+            assert!(!full.is_emergency);
+
+            let line = lay_out(runs, 0, full.atoms[7].x).unwrap();
+            assert_eq!(line.begin_index, 0);
+            assert_eq!(line.raw_text_length, 5);
+            assert_eq!(line.text_width, full.atoms[4].x);
+            assert!(!line.is_emergency);
+
+            let line = lay_out(runs, 5, 1000.0).unwrap();
+            assert_eq!(line.begin_index, 5);
+            assert_eq!(line.raw_text_length, 4);
+            assert!(lay_out(runs, 9, 1000.0).is_none());
+        });
+    }
+
+    #[test]
+    fn lay_out_line_mandatory_break() {
+        with_runs("ab\ncd", |runs| {
+            let line = lay_out(runs, 0, 1000.0).unwrap();
+            assert_eq!(line.raw_text_length, 3);
+            assert_eq!(line.text_width, line.atoms[2].x);
+
+            let line = lay_out(runs, 3, 1000.0).unwrap();
+            assert_eq!(line.raw_text_length, 2);
+        });
+    }
+
+    #[test]
+    fn lay_out_line_emergency_break() {
+        with_runs("abcdefgh", |runs| {
+            let full = lay_out(runs, 0, 1000.0).unwrap();
+
+            let line = lay_out(runs, 0, full.atoms[3].x).unwrap();
+            assert_eq!(line.raw_text_length, 3);
+            assert!(line.is_emergency);
+
+            assert!(lay_out(runs, 0, 1.0).is_none());
+            let line = lay_out_line(runs, 0, 1.0, 0.0, Justifier::default(), &[], true).unwrap();
+            assert_eq!(line.raw_text_length, 1);
+            assert!(line.is_emergency);
+        });
+    }
+
+    #[test]
+    fn lay_out_line_justification() {
+        with_runs("ab cd", |runs| {
+            let justifier = Justifier {
+                line_justification: LineJustification::AllIncludingLast,
+                letter_spacing: false,
+            };
+            let line = lay_out_line(runs, 0, 200.0, 0.0, justifier, &[], false).unwrap();
+            assert!((line.text_width - 200.0).abs() < 1e-9);
+            assert!(line.unjustified_text_width < 200.0);
+
+            let justifier = Justifier {
+                line_justification: LineJustification::AllButLast,
+                letter_spacing: false,
+            };
+            let line = lay_out_line(runs, 0, 200.0, 0.0, justifier, &[], false).unwrap();
+            assert_eq!(line.text_width, line.unjustified_text_width);
+        });
+    }
+}
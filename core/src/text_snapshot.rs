@@ -0,0 +1,341 @@
+//! Shared logic of the AVM1 and AVM2 `TextSnapshot` classes, which expose the
+//! static text of a movie clip to scripts.
+
+use crate::display_object::{TDisplayObjectContainer, Text, TextGlyph};
+use crate::library::Library;
+use crate::prelude::*;
+use crate::string::{WStr, WString};
+use gc_arena::Mutation;
+use std::ops::Range;
+
+/// The information about a glyph returned by `TextSnapshot.getTextRunInfo`.
+#[derive(Clone, Debug)]
+pub struct TextRunInfo {
+    /// The index of the glyph in the snapshot.
+    pub index_in_run: usize,
+    pub selected: bool,
+    pub font: String,
+    pub color: Color,
+
+    /// The font size, in pixels.
+    pub height: f64,
+
+    /// The transform of the glyph, relative to the container.
+    ///
+    /// This maps the EM square of the font to the size of the glyph.
+    pub matrix: Matrix,
+
+    /// The corners of the glyph box relative to the container, clockwise from
+    /// the top left one.
+    pub corners: [Point<Twips>; 4],
+}
+
+/// The static texts of a container, as seen by `TextSnapshot`.
+pub struct TextSnapshot<'gc> {
+    /// The glyphs of the static texts, in depth order.
+    glyphs: Vec<(Text<'gc>, TextGlyph<'gc>)>,
+}
+
+impl<'gc> TextSnapshot<'gc> {
+    /// Take a snapshot of the static texts directly inside a container.
+    pub fn new(container: DisplayObject<'gc>, library: &Library<'gc>) -> Self {
+        let mut glyphs = Vec::new();
+        if let Some(container) = container.as_container() {
+            for child in container.iter_render_list() {
+                if let DisplayObject::Text(text) = child {
+                    glyphs.extend(text.glyphs(library).into_iter().map(|glyph| (text, glyph)));
+                }
+            }
+        }
+        Self { glyphs }
+    }
+
+    /// Take a snapshot of a single static text.
+    pub fn of_text(text: Text<'gc>, library: &Library<'gc>) -> Self {
+        let glyphs = text
+            .glyphs(library)
+            .into_iter()
+            .map(|glyph| (text, glyph))
+            .collect();
+        Self { glyphs }
+    }
+
+    /// The number of characters in this snapshot.
+    pub fn char_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Clamp a range of characters given by scripts to this snapshot.
+    fn range(&self, begin_index: i32, end_index: i32) -> Range<usize> {
+        let len = self.glyphs.len();
+        let begin = (begin_index.max(0) as usize).min(len);
+        let end = (end_index.max(0) as usize).clamp(begin, len);
+        begin..end
+    }
+
+    fn write_text<'a>(
+        glyphs: impl Iterator<Item = &'a TextGlyph<'gc>>,
+        include_line_endings: bool,
+    ) -> WString
+    where
+        'gc: 'a,
+    {
+        let mut text = WString::new();
+        for glyph in glyphs {
+            if include_line_endings && glyph.is_line_start && !text.is_empty() {
+                text.push_byte(b'\n');
+            }
+            text.push_char(glyph.character);
+        }
+        text
+    }
+
+    /// The characters from `begin_index` to `end_index`, excluded.
+    ///
+    /// If `include_line_endings` is set, a newline separates each line of text.
+    pub fn text(&self, begin_index: i32, end_index: i32, include_line_endings: bool) -> WString {
+        let range = self.range(begin_index, end_index);
+        Self::write_text(
+            self.glyphs[range].iter().map(|(_, glyph)| glyph),
+            include_line_endings,
+        )
+    }
+
+    /// The characters that are selected.
+    pub fn selected_text(&self, include_line_endings: bool) -> WString {
+        Self::write_text(
+            self.glyphs
+                .iter()
+                .filter(|(text, glyph)| text.is_glyph_selected(glyph.index))
+                .map(|(_, glyph)| glyph),
+            include_line_endings,
+        )
+    }
+
+    /// Find the first occurrence of some text from `begin_index`.
+    pub fn find_text(
+        &self,
+        begin_index: i32,
+        needle: &WStr,
+        case_sensitive: bool,
+    ) -> Option<usize> {
+        let begin = self.range(begin_index, begin_index).start;
+        let haystack = self.text(begin as i32, self.glyphs.len() as i32, false);
+        let index = if case_sensitive {
+            haystack.find(needle)
+        } else {
+            haystack
+                .to_ascii_lowercase()
+                .find(needle.to_ascii_lowercase().as_wstr())
+        }?;
+        Some(begin + index)
+    }
+
+    /// Whether any of the characters from `begin_index` to `end_index` is selected.
+    pub fn is_selected(&self, begin_index: i32, end_index: i32) -> bool {
+        let range = self.range(begin_index, end_index);
+        self.glyphs[range]
+            .iter()
+            .any(|(text, glyph)| text.is_glyph_selected(glyph.index))
+    }
+
+    /// Select or deselect the characters from `begin_index` to `end_index`.
+    pub fn set_selected(
+        &self,
+        gc_context: &Mutation<'gc>,
+        begin_index: i32,
+        end_index: i32,
+        selected: bool,
+    ) {
+        let range = self.range(begin_index, end_index);
+        for (text, glyph) in &self.glyphs[range] {
+            text.set_glyph_selected(gc_context, glyph.index, selected);
+        }
+    }
+
+    /// Set the color highlighting the selected characters of every text.
+    pub fn set_select_color(&self, gc_context: &Mutation<'gc>, color: Color) {
+        let mut last_text: Option<Text<'gc>> = None;
+        for (text, _) in &self.glyphs {
+            let is_new_text = last_text.map_or(true, |last_text| {
+                !DisplayObject::ptr_eq((*text).into(), last_text.into())
+            });
+            if is_new_text {
+                text.set_select_color(gc_context, color);
+                last_text = Some(*text);
+            }
+        }
+    }
+
+    /// The transform from the coordinate space of the text records of a text to the container.
+    fn text_matrix(text: Text<'gc>) -> Matrix {
+        *text.base().matrix() * text.text_transform()
+    }
+
+    /// Find the character closest to a point of the container, if it is no
+    /// further than `max_distance`.
+    pub fn hit_test_text_near_pos(
+        &self,
+        point: Point<Twips>,
+        max_distance: Twips,
+    ) -> Option<usize> {
+        let mut closest = None;
+        for (index, (text, glyph)) in self.glyphs.iter().enumerate() {
+            let bounds = Self::text_matrix(*text) * glyph.bounds();
+            let dx = (bounds.x_min - point.x)
+                .max(point.x - bounds.x_max)
+                .max(Twips::ZERO);
+            let dy = (bounds.y_min - point.y)
+                .max(point.y - bounds.y_max)
+                .max(Twips::ZERO);
+            let distance = dx.get().max(dy.get());
+            if distance <= max_distance.get()
+                && closest.map_or(true, |(_, closest_distance)| distance < closest_distance)
+            {
+                closest = Some((index, distance));
+            }
+        }
+        closest.map(|(index, _)| index)
+    }
+
+    /// Describe the characters from `begin_index` to `end_index`.
+    pub fn text_run_info(&self, begin_index: i32, end_index: i32) -> Vec<TextRunInfo> {
+        let range = self.range(begin_index, end_index);
+        let start = range.start;
+        self.glyphs[range]
+            .iter()
+            .enumerate()
+            .map(|(i, (text, glyph))| {
+                let text_matrix = Self::text_matrix(*text);
+                let em_scale = (glyph.height.to_pixels() / f64::from(glyph.font.scale())) as f32;
+                let matrix = text_matrix
+                    * Matrix {
+                        a: em_scale,
+                        d: em_scale,
+                        tx: glyph.x,
+                        ty: glyph.y,
+                        ..Default::default()
+                    };
+
+                let bounds = glyph.bounds();
+                let corners = [
+                    Point::new(bounds.x_min, bounds.y_min),
+                    Point::new(bounds.x_max, bounds.y_min),
+                    Point::new(bounds.x_max, bounds.y_max),
+                    Point::new(bounds.x_min, bounds.y_max),
+                ]
+                .map(|corner| text_matrix * corner);
+
+                TextRunInfo {
+                    index_in_run: start + i,
+                    selected: text.is_glyph_selected(glyph.index),
+                    font: glyph.font.descriptor().class().to_string(),
+                    color: glyph.color,
+                    height: glyph.height.to_pixels(),
+                    matrix,
+                    corners,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::Font;
+    use crate::player::{Player, PlayerBuilder};
+    use crate::tag_utils::SwfMovie;
+
+    /// Runs a test with a snapshot of a static text showing the given lines,
+    /// with 12 pixel glyphs of the device font.
+    fn with_snapshot<F>(lines: &[&str], test: F)
+    where
+        F: for<'gc> FnOnce(TextSnapshot<'gc>, Font<'gc>),
+    {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| {
+            let font = Player::load_device_font(context.gc_context, context.renderer);
+            let tag = swf::Text {
+                id: 1,
+                bounds: Default::default(),
+                matrix: Default::default(),
+                records: Vec::new(),
+            };
+            let movie = context.swf.clone();
+            let text = Text::from_swf_tag(context, movie, &tag);
+
+            let mut glyphs = Vec::new();
+            for (line, characters) in lines.iter().enumerate() {
+                for (column, character) in characters.chars().enumerate() {
+                    let glyph = TextGlyph {
+                        index: glyphs.len(),
+                        character,
+                        font,
+                        color: Color::BLACK,
+                        height: Twips::from_pixels(12.0),
+                        x: Twips::from_pixels(10.0 * column as f64),
+                        y: Twips::from_pixels(20.0 * (line + 1) as f64),
+                        advance: Twips::from_pixels(10.0),
+                        is_line_start: column == 0,
+                    };
+                    glyphs.push((text, glyph));
+                }
+            }
+            test(TextSnapshot { glyphs }, font);
+        })
+    }
+
+    fn text(snapshot: &TextSnapshot<'_>, begin: i32, end: i32, line_endings: bool) -> String {
+        snapshot.text(begin, end, line_endings).to_string()
+    }
+
+    #[test]
+    fn get_text() {
+        with_snapshot(&["Hello", "World"], |snapshot, _| {
+            assert_eq!(text(&snapshot, 0, 10, false), "HelloWorld");
+            assert_eq!(text(&snapshot, 0, 10, true), "Hello\nWorld");
+            assert_eq!(text(&snapshot, 3, 7, true), "lo\nWo");
+            assert_eq!(text(&snapshot, 5, 7, true), "Wo");
+            assert_eq!(text(&snapshot, -5, 2, false), "He");
+            assert_eq!(text(&snapshot, 8, 100, false), "ld");
+            assert_eq!(text(&snapshot, 6, 2, false), "");
+        });
+    }
+
+    #[test]
+    fn find_text() {
+        with_snapshot(&["Hello", "World"], |snapshot, _| {
+            let find = |begin, needle: &[u8], case_sensitive| {
+                snapshot.find_text(begin, WStr::from_units(needle), case_sensitive)
+            };
+            assert_eq!(find(0, b"World", true), Some(5));
+            assert_eq!(find(0, b"oW", true), Some(4));
+            assert_eq!(find(0, b"o\nW", true), None);
+            assert_eq!(find(5, b"l", true), Some(8));
+            assert_eq!(find(6, b"o", true), Some(6));
+            assert_eq!(find(-3, b"H", true), Some(0));
+            assert_eq!(find(1, b"H", true), None);
+            assert_eq!(find(20, b"d", true), None);
+            assert_eq!(find(0, b"world", true), None);
+            assert_eq!(find(0, b"wORLD", false), Some(5));
+        });
+    }
+
+    #[test]
+    fn text_run_info_uses_font_scale() {
+        with_snapshot(&["Hi"], |snapshot, font| {
+            let info = snapshot.text_run_info(1, 2);
+            assert_eq!(info.len(), 1);
+            assert_eq!(info[0].index_in_run, 1);
+            assert_eq!(info[0].height, 12.0);
+
+            let em_scale = (12.0 / f64::from(font.scale())) as f32;
+            assert_eq!(info[0].matrix.a, em_scale);
+            assert_eq!(info[0].matrix.d, em_scale);
+            assert_eq!(info[0].matrix.tx, Twips::from_pixels(10.0));
+            assert_eq!(info[0].matrix.ty, Twips::from_pixels(20.0));
+        });
+    }
+}
@@ -0,0 +1,372 @@
+use crate::display_object::InteractiveObject;
+use gc_arena::{Collect, GcCell, Mutation};
+
+/// A point of contact with a touch screen.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct TouchPoint<'gc> {
+    /// The identifier given to this touch by the frontend.
+    pub id: u64,
+
+    /// The `touchPointID` exposed to ActionScript.
+    pub touch_point_id: i32,
+
+    /// Whether this is the first touch point of a gesture.
+    /// The primary touch point is also interpreted as the mouse.
+    pub is_primary: bool,
+
+    /// The object this touch point started on, used to detect taps.
+    pub begin_target: Option<InteractiveObject<'gc>>,
+
+    /// The object this touch point is currently over.
+    pub over_target: Option<InteractiveObject<'gc>>,
+}
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+struct TouchTrackerData<'gc> {
+    touch_points: Vec<TouchPoint<'gc>>,
+    next_touch_point_id: i32,
+}
+
+/// A tracker for the points of contact currently on the touch screen.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct TouchTracker<'gc>(GcCell<'gc, TouchTrackerData<'gc>>);
+
+impl<'gc> TouchTracker<'gc> {
+    pub fn new(mc: &Mutation<'gc>) -> Self {
+        Self(GcCell::new(mc, Default::default()))
+    }
+
+    /// Start tracking a new touch point with the given frontend identifier.
+    ///
+    /// If a touch point with the same identifier is already being tracked,
+    /// it is replaced.
+    pub fn begin(self, mc: &Mutation<'gc>, id: u64) -> TouchPoint<'gc> {
+        let mut write = self.0.write(mc);
+        write
+            .touch_points
+            .retain(|touch_point| touch_point.id != id);
+
+        let touch_point = TouchPoint {
+            id,
+            touch_point_id: write.next_touch_point_id,
+            is_primary: write.touch_points.is_empty(),
+            begin_target: None,
+            over_target: None,
+        };
+        write.next_touch_point_id = write.next_touch_point_id.wrapping_add(1);
+        write.touch_points.push(touch_point);
+        touch_point
+    }
+
+    /// Get the touch point with the given frontend identifier, if it is being tracked.
+    pub fn get(self, id: u64) -> Option<TouchPoint<'gc>> {
+        self.0
+            .read()
+            .touch_points
+            .iter()
+            .find(|touch_point| touch_point.id == id)
+            .copied()
+    }
+
+    /// Replace the state of a tracked touch point.
+    pub fn update(self, mc: &Mutation<'gc>, touch_point: TouchPoint<'gc>) {
+        let mut write = self.0.write(mc);
+        if let Some(existing) = write
+            .touch_points
+            .iter_mut()
+            .find(|existing| existing.id == touch_point.id)
+        {
+            *existing = touch_point;
+        }
+    }
+
+    /// Stop tracking the touch point with the given frontend identifier, returning its last state.
+    pub fn end(self, mc: &Mutation<'gc>, id: u64) -> Option<TouchPoint<'gc>> {
+        let mut write = self.0.write(mc);
+        let index = write
+            .touch_points
+            .iter()
+            .position(|touch_point| touch_point.id == id)?;
+        Some(write.touch_points.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::native_function;
+    use crate::avm2::{
+        Activation as Avm2Activation, Error as Avm2Error, Namespace, Object as Avm2Object, QName,
+        TObject as _, Value as Avm2Value,
+    };
+    use crate::backend::ui::{
+        FullscreenError, LanguageIdentifier, MouseCursor, NullUiBackend, UiBackend,
+    };
+    use crate::display_object::MultitouchInputMode;
+    use crate::events::PlayerEvent;
+    use crate::player::{Player, PlayerBuilder};
+    use crate::tag_utils::SwfMovie;
+    use std::cell::RefCell;
+    use swf::{FileAttributes, Header, Tag};
+    use url::Url;
+
+    const TOUCH_EVENT_TYPES: [&str; 6] = [
+        "touchBegin",
+        "touchMove",
+        "touchEnd",
+        "touchTap",
+        "touchOver",
+        "touchOut",
+    ];
+
+    thread_local! {
+        /// The type, `touchPointID` and `isPrimaryTouchPoint` of each dispatched `TouchEvent`.
+        static TOUCH_EVENTS: RefCell<Vec<(String, i32, bool)>> = RefCell::new(Vec::new());
+    }
+
+    /// A UI backend for a device with a touch screen.
+    struct TouchScreenUiBackend(NullUiBackend);
+
+    impl UiBackend for TouchScreenUiBackend {
+        fn mouse_visible(&self) -> bool {
+            self.0.mouse_visible()
+        }
+
+        fn set_mouse_visible(&mut self, visible: bool) {
+            self.0.set_mouse_visible(visible)
+        }
+
+        fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+            self.0.set_mouse_cursor(cursor)
+        }
+
+        fn clipboard_content(&mut self) -> String {
+            self.0.clipboard_content()
+        }
+
+        fn set_clipboard_content(&mut self, content: String) {
+            self.0.set_clipboard_content(content)
+        }
+
+        fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
+            self.0.set_fullscreen(is_full)
+        }
+
+        fn display_root_movie_download_failed_message(&self) {}
+
+        fn message(&self, _message: &str) {}
+
+        fn open_virtual_keyboard(&self) {}
+
+        fn language(&self) -> &LanguageIdentifier {
+            self.0.language()
+        }
+
+        fn display_unsupported_video(&self, _url: Url) {}
+
+        fn max_touch_points(&self) -> u32 {
+            5
+        }
+    }
+
+    /// Constructs an AS3 movie without any tags but its `FileAttributes`.
+    fn as3_movie() -> SwfMovie {
+        let mut data = Vec::new();
+        swf::write_swf(
+            &Header::default_with_swf_version(10),
+            &[Tag::FileAttributes(FileAttributes::IS_ACTION_SCRIPT_3)],
+            &mut data,
+        )
+        .unwrap();
+        SwfMovie::from_data(&data, "file:///movie.swf".into(), None).unwrap()
+    }
+
+    fn record_touch_event<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        _this: Avm2Object<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        let event = args
+            .first()
+            .and_then(|event| event.as_object())
+            .expect("Listener should receive an event");
+        let event_type = event.as_event().unwrap().event_type().to_string();
+        let touch_point_id = event
+            .get_public_property("touchPointID", activation)?
+            .coerce_to_i32(activation)?;
+        let is_primary = event
+            .get_public_property("isPrimaryTouchPoint", activation)?
+            .coerce_to_boolean();
+        TOUCH_EVENTS.with(|events| {
+            events
+                .borrow_mut()
+                .push((event_type, touch_point_id, is_primary))
+        });
+        Ok(Avm2Value::Undefined)
+    }
+
+    /// Records the `TouchEvent`s reaching the stage.
+    fn listen_for_touch_events(player: &mut Player) {
+        player.mutate_with_update_context(|context| {
+            let Avm2Value::Object(stage) = context.stage.object2() else {
+                panic!("Stage should have an AVM2 object");
+            };
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let listener = native_function(&mut activation, record_touch_event, "recordTouchEvent");
+            for event_type in TOUCH_EVENT_TYPES {
+                stage
+                    .call_public_property(
+                        "addEventListener",
+                        &[event_type.into(), listener.into()],
+                        &mut activation,
+                    )
+                    .unwrap();
+            }
+        });
+    }
+
+    fn take_touch_events() -> Vec<(String, i32, bool)> {
+        TOUCH_EVENTS.with(|events| events.take())
+    }
+
+    fn touch_begin(player: &mut Player, id: u64) {
+        player.handle_event(PlayerEvent::TouchBegin {
+            id,
+            x: 10.0,
+            y: 10.0,
+        });
+    }
+
+    fn touch_move(player: &mut Player, id: u64) {
+        player.handle_event(PlayerEvent::TouchMove {
+            id,
+            x: 10.0,
+            y: 10.0,
+        });
+    }
+
+    fn touch_end(player: &mut Player, id: u64) {
+        player.handle_event(PlayerEvent::TouchEnd {
+            id,
+            x: 10.0,
+            y: 10.0,
+        });
+    }
+
+    fn is_mouse_down(player: &mut Player) -> bool {
+        player.mutate_with_update_context(|context| context.input.is_mouse_down())
+    }
+
+    fn get_multitouch_property(player: &mut Player, name: &'static str) -> String {
+        player.mutate_with_update_context(|context| {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let multitouch = multitouch_class(&mut activation);
+            let value = multitouch
+                .get_public_property(name, &mut activation)
+                .unwrap();
+            value.coerce_to_string(&mut activation).unwrap().to_string()
+        })
+    }
+
+    fn set_input_mode(player: &mut Player, input_mode: &'static str) {
+        player.mutate_with_update_context(|context| {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let mut multitouch = multitouch_class(&mut activation);
+            multitouch
+                .set_public_property("inputMode", input_mode.into(), &mut activation)
+                .unwrap();
+        })
+    }
+
+    fn multitouch_class<'gc>(activation: &mut Avm2Activation<'_, 'gc>) -> Avm2Object<'gc> {
+        let ns = Namespace::package("flash.ui", &mut activation.borrow_gc());
+        let domain = activation.domain();
+        domain
+            .get_defined_value(activation, QName::new(ns, "Multitouch"))
+            .unwrap()
+            .as_object()
+            .unwrap()
+    }
+
+    #[test]
+    fn multitouch_without_touch_screen() {
+        let player = PlayerBuilder::new().with_movie(as3_movie()).build();
+        let mut player = player.lock().unwrap();
+        assert_eq!(
+            get_multitouch_property(&mut player, "supportsTouchEvents"),
+            "false"
+        );
+        assert_eq!(get_multitouch_property(&mut player, "maxTouchPoints"), "0");
+    }
+
+    #[test]
+    fn touch_events_in_touch_point_mode() {
+        let player = PlayerBuilder::new()
+            .with_movie(as3_movie())
+            .with_ui(TouchScreenUiBackend(NullUiBackend::new()))
+            .build();
+        let mut player = player.lock().unwrap();
+        assert_eq!(
+            get_multitouch_property(&mut player, "supportsTouchEvents"),
+            "true"
+        );
+        assert_eq!(get_multitouch_property(&mut player, "maxTouchPoints"), "5");
+        listen_for_touch_events(&mut player);
+
+        // In the default gesture mode, touches are only interpreted as the mouse.
+        assert_eq!(get_multitouch_property(&mut player, "inputMode"), "gesture");
+        touch_begin(&mut player, 1);
+        assert!(is_mouse_down(&mut player));
+        touch_end(&mut player, 1);
+        assert!(!is_mouse_down(&mut player));
+        assert_eq!(take_touch_events(), []);
+
+        set_input_mode(&mut player, "touchPoint");
+        assert_eq!(
+            get_multitouch_property(&mut player, "inputMode"),
+            "touchPoint"
+        );
+        touch_begin(&mut player, 1);
+        touch_begin(&mut player, 2);
+        touch_move(&mut player, 2);
+        touch_end(&mut player, 2);
+        // Only the primary touch point is interpreted as the mouse.
+        assert!(is_mouse_down(&mut player));
+        touch_end(&mut player, 1);
+        assert!(!is_mouse_down(&mut player));
+
+        let expected = [
+            ("touchBegin", 1, true),
+            ("touchBegin", 2, false),
+            ("touchMove", 2, false),
+            ("touchEnd", 2, false),
+            ("touchEnd", 1, true),
+        ]
+        .map(|(event_type, id, is_primary)| (event_type.to_string(), id, is_primary));
+        assert_eq!(take_touch_events(), expected);
+    }
+
+    #[test]
+    fn touch_events_in_avm1_movies_are_mouse_events() {
+        let player = PlayerBuilder::new()
+            .with_movie(SwfMovie::empty(10))
+            .with_ui(TouchScreenUiBackend(NullUiBackend::new()))
+            .build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| {
+            context
+                .stage
+                .set_multitouch_input_mode(context.gc_context, MultitouchInputMode::TouchPoint);
+        });
+        listen_for_touch_events(&mut player);
+
+        touch_begin(&mut player, 1);
+        touch_move(&mut player, 1);
+        assert!(is_mouse_down(&mut player));
+        touch_end(&mut player, 1);
+        assert!(!is_mouse_down(&mut player));
+        assert_eq!(take_touch_events(), []);
+    }
+}
@@ -225,6 +225,25 @@ impl App {
                             self.player.handle_event(event);
                             check_redraw = true;
                         }
+                        WindowEvent::Touch(touch) => {
+                            if self.gui.borrow_mut().is_context_menu_visible() {
+                                return;
+                            }
+
+                            use winit::event::TouchPhase;
+                            let id = touch.id;
+                            let x = touch.location.x;
+                            let y = touch.location.y - height_offset;
+                            let event = match touch.phase {
+                                TouchPhase::Started => PlayerEvent::TouchBegin { id, x, y },
+                                TouchPhase::Moved => PlayerEvent::TouchMove { id, x, y },
+                                TouchPhase::Ended | TouchPhase::Cancelled => {
+                                    PlayerEvent::TouchEnd { id, x, y }
+                                }
+                            };
+                            self.player.handle_event(event);
+                            check_redraw = true;
+                        }
                         WindowEvent::MouseWheel { delta, .. } => {
                             use ruffle_core::events::MouseWheelDelta;
                             use winit::event::MouseScrollDelta;
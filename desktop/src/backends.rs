@@ -1,11 +1,15 @@
 mod audio;
 mod external_interface;
+mod file_dialog;
 mod navigator;
+mod print;
 mod storage;
 mod ui;
 
 pub use audio::CpalAudioBackend;
 pub use external_interface::DesktopExternalInterfaceProvider;
+pub use file_dialog::DesktopFileDialogBackend;
 pub use navigator::ExternalNavigatorBackend;
+pub use print::DesktopPrintBackend;
 pub use storage::DiskStorageBackend;
 pub use ui::DesktopUiBackend;
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use rfd::AsyncFileDialog;
+use ruffle_core::backend::file_dialog::{
+    DialogFuture, FileDialogBackend, FileFilter, SaveTarget, SelectedFile,
+};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Opens and saves files through the native dialogs of the system.
+#[derive(Default)]
+pub struct DesktopFileDialogBackend;
+
+impl DesktopFileDialogBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FileDialogBackend for DesktopFileDialogBackend {
+    fn browse(&mut self, filters: &[FileFilter]) -> DialogFuture<SelectedFile> {
+        let mut dialog = AsyncFileDialog::new();
+        for filter in filters {
+            // The dialog expects bare extensions, rather than patterns such as `*.jpg`.
+            let extensions: Vec<_> = filter
+                .extensions
+                .iter()
+                .map(|pattern| pattern.trim_start_matches('*').trim_start_matches('.'))
+                .collect();
+            if extensions.contains(&"") {
+                continue;
+            }
+            dialog = dialog.add_filter(&filter.description, &extensions);
+        }
+
+        Box::pin(async move {
+            let handle = dialog.pick_file().await?;
+            let path = handle.path();
+            let data = match fs::read(path) {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::warn!("Unable to read {}: {}", path.display(), e);
+                    return None;
+                }
+            };
+
+            let mut file = SelectedFile::new(handle.file_name(), data);
+            if let Ok(metadata) = fs::metadata(path) {
+                file.creation_date = metadata.created().ok().map(DateTime::<Utc>::from);
+                file.modification_date = metadata.modified().ok().map(DateTime::<Utc>::from);
+            }
+            Some(file)
+        })
+    }
+
+    fn save(&mut self, default_file_name: &str) -> DialogFuture<Box<dyn SaveTarget>> {
+        let dialog = AsyncFileDialog::new().set_file_name(default_file_name);
+
+        Box::pin(async move {
+            let handle = dialog.save_file().await?;
+            let target: Box<dyn SaveTarget> = Box::new(DiskSaveTarget {
+                path: handle.path().to_owned(),
+            });
+            Some(target)
+        })
+    }
+}
+
+struct DiskSaveTarget {
+    path: PathBuf,
+}
+
+impl SaveTarget for DiskSaveTarget {
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        fs::write(&self.path, data)
+    }
+}
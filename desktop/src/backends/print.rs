@@ -0,0 +1,66 @@
+use rfd::AsyncFileDialog;
+use ruffle_core::backend::print::{
+    pages_to_pdf, PageFormat, PrintBackend, PrintFuture, PrintedPage,
+};
+use std::fs;
+
+struct PrintJob {
+    format: PageFormat,
+    pages: Vec<PrintedPage>,
+}
+
+/// Prints pages to a PDF file picked by the user.
+pub struct DesktopPrintBackend {
+    /// The format of the pages of every print job.
+    format: PageFormat,
+    job: Option<PrintJob>,
+}
+
+impl DesktopPrintBackend {
+    pub fn new(format: PageFormat) -> Self {
+        Self { format, job: None }
+    }
+}
+
+impl PrintBackend for DesktopPrintBackend {
+    fn start(&mut self) -> Option<PageFormat> {
+        // The file to print to is picked once the pages are sent, so that
+        // the movie isn't blocked while the dialog is open.
+        self.job = Some(PrintJob {
+            format: self.format,
+            pages: Vec::new(),
+        });
+        self.page_format()
+    }
+
+    fn page_format(&self) -> Option<PageFormat> {
+        self.job.as_ref().map(|job| job.format)
+    }
+
+    fn add_page(&mut self, page: PrintedPage) {
+        if let Some(job) = &mut self.job {
+            job.pages.push(page);
+        }
+    }
+
+    fn send(&mut self) -> PrintFuture {
+        let Some(job) = self.job.take().filter(|job| !job.pages.is_empty()) else {
+            return Box::pin(std::future::ready(()));
+        };
+
+        let dialog = AsyncFileDialog::new()
+            .set_title("Print to PDF")
+            .add_filter("PDF", &["pdf"])
+            .set_file_name("print.pdf");
+
+        Box::pin(async move {
+            let Some(handle) = dialog.save_file().await else {
+                return;
+            };
+            let path = handle.path();
+            if let Err(e) = fs::write(path, pages_to_pdf(&job.pages)) {
+                tracing::error!("Unable to print to {}: {}", path.display(), e);
+            }
+        })
+    }
+}
@@ -147,4 +147,17 @@ impl UiBackend for DesktopUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &self.language
     }
+
+    #[cfg(windows)]
+    fn max_touch_points(&self) -> u32 {
+        use winapi::um::winuser::{GetSystemMetrics, SM_MAXIMUMTOUCHES};
+        let max_touch_points = unsafe { GetSystemMetrics(SM_MAXIMUMTOUCHES) };
+        max_touch_points.max(0) as u32
+    }
+
+    // winit can't tell whether there's a touch screen on other platforms.
+    #[cfg(not(windows))]
+    fn max_touch_points(&self) -> u32 {
+        0
+    }
 }
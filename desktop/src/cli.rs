@@ -2,11 +2,12 @@ use crate::RUFFLE_VERSION;
 use anyhow::Error;
 use clap::Parser;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
+use ruffle_core::backend::print::PrintOrientation;
 use ruffle_core::config::Letterbox;
 use ruffle_core::{LoadBehavior, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 #[derive(Parser, Debug)]
@@ -127,6 +128,20 @@ pub struct Opt {
     #[clap(long, default_value = "allow")]
     pub open_url_mode: OpenURLMode,
 
+    /// Load the TrueType and OpenType fonts in a directory and its
+    /// subdirectories as device fonts.
+    #[clap(long = "font-dir", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub font_dirs: Vec<PathBuf>,
+
+    /// A device font used when a font is unavailable or lacks glyphs.
+    /// Can be repeated, in order of preference.
+    #[clap(long = "fallback-font", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub fallback_fonts: Vec<String>,
+
+    /// The orientation of the pages printed by the movie.
+    #[clap(long, default_value = "portrait")]
+    pub print_orientation: PrintOrientation,
+
     /// Provide a dummy (completely empty) External Interface to the movie.
     /// This may break some movies that expect an External Interface to be functional,
     /// but may fix others that always require an External Interface.
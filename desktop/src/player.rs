@@ -1,6 +1,6 @@
 use crate::backends::{
-    CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopUiBackend, DiskStorageBackend,
-    ExternalNavigatorBackend,
+    CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopFileDialogBackend,
+    DesktopPrintBackend, DesktopUiBackend, DiskStorageBackend, ExternalNavigatorBackend,
 };
 use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
@@ -10,6 +10,7 @@ use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
 use ruffle_core::backend::audio::AudioBackend;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
+use ruffle_core::backend::print::PageFormat;
 use ruffle_core::config::Letterbox;
 use ruffle_core::{LoadBehavior, Player, PlayerBuilder, PlayerEvent, StageAlign, StageScaleMode};
 use ruffle_render::backend::RenderBackend;
@@ -17,10 +18,13 @@ use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::descriptors::Descriptors;
 use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 use url::Url;
+use walkdir::WalkDir;
 use winit::event_loop::EventLoopProxy;
 use winit::window::Window;
 
@@ -49,6 +53,8 @@ pub struct PlayerOptions {
     pub frame_rate: Option<f64>,
     pub open_url_mode: OpenURLMode,
     pub dummy_external_interface: bool,
+    pub font_dirs: Vec<PathBuf>,
+    pub fallback_fonts: Vec<String>,
 }
 
 impl From<&Opt> for PlayerOptions {
@@ -75,6 +81,8 @@ impl From<&Opt> for PlayerOptions {
             dummy_external_interface: value.dummy_external_interface,
             socket_allowed: HashSet::from_iter(value.socket_allow.iter().cloned()),
             tcp_connections: value.tcp_connections,
+            font_dirs: value.font_dirs.clone(),
+            fallback_fonts: value.fallback_fonts.clone(),
         }
     }
 }
@@ -134,6 +142,12 @@ impl ActivePlayer {
                 builder.with_external_interface(Box::<DesktopExternalInterfaceProvider>::default());
         }
 
+        for font_dir in &opt.font_dirs {
+            for data in read_font_files(font_dir) {
+                builder = builder.with_device_font(data);
+            }
+        }
+
         let max_execution_duration = if opt.max_execution_duration == f64::INFINITY {
             Duration::MAX
         } else {
@@ -144,6 +158,10 @@ impl ActivePlayer {
             .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(DiskStorageBackend::new().expect("Couldn't create storage backend"))
+            .with_file_dialog(DesktopFileDialogBackend::new())
+            .with_print(DesktopPrintBackend::new(
+                PageFormat::default().with_orientation(opt.print_orientation),
+            ))
             .with_ui(
                 DesktopUiBackend::new(window.clone(), opt.open_url_mode)
                     .expect("Couldn't create ui backend"),
@@ -158,7 +176,8 @@ impl ActivePlayer {
             .with_load_behavior(opt.load_behavior)
             .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_player_version(Some(opt.player_version))
-            .with_frame_rate(opt.frame_rate);
+            .with_frame_rate(opt.frame_rate)
+            .with_fallback_fonts(opt.fallback_fonts.clone());
         let player = builder.build();
 
         let name = movie_url
@@ -191,6 +210,43 @@ impl ActivePlayer {
     }
 }
 
+/// Read the TrueType and OpenType font files within a directory and its
+/// subdirectories.
+fn read_font_files(dir: &Path) -> Vec<Vec<u8>> {
+    // Sorting keeps the order in which fonts are searched for glyphs stable.
+    WalkDir::new(dir)
+        .follow_links(true)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                tracing::warn!("Unable to read font directory {}: {}", dir.display(), e);
+                None
+            }
+        })
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .map_or(false, |extension| {
+                        ["ttf", "otf", "ttc", "otc"]
+                            .iter()
+                            .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+                    })
+        })
+        .filter_map(|entry| match fs::read(entry.path()) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::warn!("Unable to read font {}: {}", entry.path().display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
@@ -0,0 +1,84 @@
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+
+/// An audio backend that mixes sound offline, one movie frame at a time.
+///
+/// Nothing is played back; instead, each call to `tick` mixes exactly the amount of audio
+/// that a frame lasts for, so that the output stays in step with the exported frames
+/// regardless of how long rendering takes.
+pub struct CaptureAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+
+    /// The number of frames ticked since the frame rate last changed.
+    frames_mixed: u64,
+
+    /// The number of sample frames mixed since the frame rate last changed.
+    samples_mixed: u64,
+
+    mix_buffer: Vec<f32>,
+    frame_samples: Vec<i16>,
+}
+
+impl CaptureAudioBackend {
+    pub const NUM_CHANNELS: u8 = 2;
+    pub const SAMPLE_RATE: u32 = 44100;
+
+    pub fn new() -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            frame_rate: 1.0,
+            frames_mixed: 0,
+            samples_mixed: 0,
+            mix_buffer: vec![],
+            frame_samples: vec![],
+        }
+    }
+
+    /// The interleaved 16-bit samples that were mixed during the last tick.
+    pub fn frame_samples(&self) -> &[i16] {
+        &self.frame_samples
+    }
+}
+
+impl AudioBackend for CaptureAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = frame_rate;
+        self.frames_mixed = 0;
+        self.samples_mixed = 0;
+    }
+
+    fn tick(&mut self) {
+        // Frame rates rarely divide the sample rate evenly, so the length of each frame is
+        // derived from the total time elapsed. This keeps rounding errors from accumulating.
+        self.frames_mixed += 1;
+        let end = (self.frames_mixed as f64 * f64::from(Self::SAMPLE_RATE) / self.frame_rate)
+            .round() as u64;
+        let num_samples = (end - self.samples_mixed) as usize * usize::from(Self::NUM_CHANNELS);
+        self.samples_mixed = end;
+
+        self.mix_buffer.clear();
+        self.mix_buffer.resize(num_samples, 0.0);
+        self.mixer.mix::<f32>(&mut self.mix_buffer);
+
+        self.frame_samples.clear();
+        self.frame_samples.extend(
+            self.mix_buffer
+                .iter()
+                .map(|sample| (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16),
+        );
+    }
+}
+
+impl Default for CaptureAudioBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
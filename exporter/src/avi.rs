@@ -0,0 +1,373 @@
+use image::RgbaImage;
+use std::io::{self, Seek, SeekFrom, Write};
+
+const AVIF_HASINDEX: u32 = 0x10;
+const AVIF_ISINTERLEAVED: u32 = 0x100;
+const AVIIF_KEYFRAME: u32 = 0x10;
+
+const VIDEO_CHUNK_ID: &[u8; 4] = b"00db";
+const AUDIO_CHUNK_ID: &[u8; 4] = b"01wb";
+
+/// The size in bytes of everything preceding the `movi` list data.
+/// The headers are rewritten in place once the file is finished, so this must never change.
+const HEADER_SIZE: u32 = 12 + 12 + 64 + 12 + 64 + 48 + 12 + 64 + 26 + 12;
+
+struct IndexEntry {
+    id: &'static [u8; 4],
+    flags: u32,
+    offset: u32,
+    size: u32,
+}
+
+/// A writer for AVI files holding uncompressed 24-bit video and 16-bit PCM audio.
+///
+/// Chunks are streamed to the sink as they are written, and the headers are filled in
+/// by `finish`. The format is limited to files of 4 GiB.
+pub struct AviWriter<W: Write + Seek> {
+    sink: W,
+    width: u32,
+    height: u32,
+    frame_rate: f64,
+    num_channels: u16,
+    sample_rate: u32,
+
+    frame_count: u32,
+
+    /// The number of audio sample frames written so far.
+    sample_count: u32,
+
+    largest_chunk: u32,
+
+    /// The size of the `movi` list data written so far.
+    movi_size: u32,
+
+    index: Vec<IndexEntry>,
+
+    /// The size of the `idx1` chunk, once it has been written.
+    index_size: u32,
+}
+
+impl<W: Write + Seek> AviWriter<W> {
+    pub fn new(
+        sink: W,
+        width: u32,
+        height: u32,
+        frame_rate: f64,
+        num_channels: u16,
+        sample_rate: u32,
+    ) -> io::Result<Self> {
+        let mut writer = Self {
+            sink,
+            width,
+            height,
+            frame_rate,
+            num_channels,
+            sample_rate,
+            frame_count: 0,
+            sample_count: 0,
+            largest_chunk: 0,
+            movi_size: 4,
+            index: vec![],
+            index_size: 0,
+        };
+        writer.write_headers()?;
+        Ok(writer)
+    }
+
+    /// Append a video frame.
+    ///
+    /// The image is expected to use straight alpha, and is composited onto black.
+    pub fn write_frame(&mut self, image: &RgbaImage) -> io::Result<()> {
+        if image.dimensions() != (self.width, self.height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Frame size doesn't match the video size",
+            ));
+        }
+
+        // Frames are stored bottom-up, with each row padded to a multiple of 4 bytes.
+        let stride = self.stride();
+        let mut data = Vec::with_capacity(stride * self.height as usize);
+        for row in image.rows().rev() {
+            for pixel in row {
+                let [r, g, b, a] = pixel.0;
+                let a = u16::from(a);
+                for c in [b, g, r] {
+                    data.push(((u16::from(c) * a + 127) / 255) as u8);
+                }
+            }
+            data.resize((data.len() + 3) & !3, 0);
+        }
+
+        self.write_chunk(VIDEO_CHUNK_ID, AVIIF_KEYFRAME, &data)?;
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    /// Append interleaved audio samples.
+    pub fn write_audio(&mut self, samples: &[i16]) -> io::Result<()> {
+        let data: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        self.write_chunk(AUDIO_CHUNK_ID, 0, &data)?;
+        self.sample_count += (samples.len() / usize::from(self.num_channels)) as u32;
+        Ok(())
+    }
+
+    /// Write the index and the final headers, and return the underlying sink.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut index = Vec::with_capacity(self.index.len() * 16 + 8);
+        index.extend_from_slice(b"idx1");
+        index.extend_from_slice(&(self.index.len() as u32 * 16).to_le_bytes());
+        for entry in &self.index {
+            index.extend_from_slice(entry.id);
+            index.extend_from_slice(&entry.flags.to_le_bytes());
+            index.extend_from_slice(&entry.offset.to_le_bytes());
+            index.extend_from_slice(&entry.size.to_le_bytes());
+        }
+        self.sink.write_all(&index)?;
+        self.index_size = index.len() as u32;
+
+        self.sink.seek(SeekFrom::Start(0))?;
+        self.write_headers()?;
+        self.sink.seek(SeekFrom::End(0))?;
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+
+    fn stride(&self) -> usize {
+        (self.width as usize * 3 + 3) & !3
+    }
+
+    fn block_align(&self) -> u16 {
+        self.num_channels * 2
+    }
+
+    fn write_chunk(&mut self, id: &'static [u8; 4], flags: u32, data: &[u8]) -> io::Result<()> {
+        // Leave room for this chunk's header and for the index, which is written last.
+        let padded_size = data.len() as u64 + (data.len() as u64 & 1);
+        let total = u64::from(HEADER_SIZE) + u64::from(self.movi_size) - 4
+            + padded_size
+            + 8
+            + (self.index.len() as u64 + 1) * 16
+            + 8;
+        if total > u64::from(u32::MAX) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "AVI files can't be larger than 4 GiB",
+            ));
+        }
+        let size = data.len() as u32;
+        let padded_size = padded_size as u32;
+
+        self.index.push(IndexEntry {
+            id,
+            flags,
+            offset: self.movi_size,
+            size,
+        });
+        self.sink.write_all(id)?;
+        self.write_u32(size)?;
+        self.sink.write_all(data)?;
+        if size & 1 != 0 {
+            self.sink.write_all(&[0])?;
+        }
+        self.movi_size += padded_size + 8;
+        self.largest_chunk = self.largest_chunk.max(size);
+        Ok(())
+    }
+
+    fn write_headers(&mut self) -> io::Result<()> {
+        let frame_size = (self.stride() * self.height as usize) as u32;
+        let block_align = self.block_align();
+        let bytes_per_second = self.sample_rate * u32::from(block_align);
+        // Express the frame rate as a fraction, which is exact for the 8.8 fixed point rates of SWFs.
+        let rate = (self.frame_rate * 256.0).round() as u32;
+        let scale = 256;
+        let riff_size = HEADER_SIZE - 8 + self.movi_size - 4 + self.index_size;
+
+        self.sink.write_all(b"RIFF")?;
+        self.write_u32(riff_size)?;
+        self.sink.write_all(b"AVI ")?;
+
+        self.sink.write_all(b"LIST")?;
+        self.write_u32(4 + 64 + 12 + 64 + 48 + 12 + 64 + 26)?;
+        self.sink.write_all(b"hdrl")?;
+
+        self.sink.write_all(b"avih")?;
+        self.write_u32(56)?;
+        self.write_u32((1_000_000.0 / self.frame_rate).round() as u32)?; // dwMicroSecPerFrame
+        self.write_u32(
+            ((f64::from(frame_size) * self.frame_rate) as u32).saturating_add(bytes_per_second),
+        )?; // dwMaxBytesPerSec
+        self.write_u32(0)?; // dwPaddingGranularity
+        self.write_u32(AVIF_HASINDEX | AVIF_ISINTERLEAVED)?;
+        self.write_u32(self.frame_count)?;
+        self.write_u32(0)?; // dwInitialFrames
+        self.write_u32(2)?; // dwStreams
+        self.write_u32(self.largest_chunk.max(frame_size))?;
+        self.write_u32(self.width)?;
+        self.write_u32(self.height)?;
+        self.sink.write_all(&[0; 16])?;
+
+        // Video stream
+        self.sink.write_all(b"LIST")?;
+        self.write_u32(4 + 64 + 48)?;
+        self.sink.write_all(b"strl")?;
+
+        self.sink.write_all(b"strh")?;
+        self.write_u32(56)?;
+        self.sink.write_all(b"vids")?;
+        self.sink.write_all(b"DIB ")?;
+        self.write_u32(0)?; // dwFlags
+        self.write_u32(0)?; // wPriority, wLanguage
+        self.write_u32(0)?; // dwInitialFrames
+        self.write_u32(scale)?;
+        self.write_u32(rate)?;
+        self.write_u32(0)?; // dwStart
+        self.write_u32(self.frame_count)?;
+        self.write_u32(frame_size)?;
+        self.write_u32(u32::MAX)?; // dwQuality
+        self.write_u32(0)?; // dwSampleSize
+        self.write_u16(0)?;
+        self.write_u16(0)?;
+        self.write_u16(self.width.min(u16::MAX.into()) as u16)?;
+        self.write_u16(self.height.min(u16::MAX.into()) as u16)?;
+
+        // BITMAPINFOHEADER
+        self.sink.write_all(b"strf")?;
+        self.write_u32(40)?;
+        self.write_u32(40)?;
+        self.write_u32(self.width)?;
+        self.write_u32(self.height)?;
+        self.write_u16(1)?; // biPlanes
+        self.write_u16(24)?; // biBitCount
+        self.write_u32(0)?; // BI_RGB
+        self.write_u32(frame_size)?;
+        self.sink.write_all(&[0; 16])?;
+
+        // Audio stream
+        self.sink.write_all(b"LIST")?;
+        self.write_u32(4 + 64 + 26)?;
+        self.sink.write_all(b"strl")?;
+
+        self.sink.write_all(b"strh")?;
+        self.write_u32(56)?;
+        self.sink.write_all(b"auds")?;
+        self.write_u32(0)?; // fccHandler
+        self.write_u32(0)?; // dwFlags
+        self.write_u32(0)?; // wPriority, wLanguage
+        self.write_u32(0)?; // dwInitialFrames
+        self.write_u32(u32::from(block_align))?;
+        self.write_u32(bytes_per_second)?;
+        self.write_u32(0)?; // dwStart
+        self.write_u32(self.sample_count)?;
+        self.write_u32(bytes_per_second)?;
+        self.write_u32(u32::MAX)?; // dwQuality
+        self.write_u32(u32::from(block_align))?;
+        self.sink.write_all(&[0; 8])?;
+
+        // WAVEFORMATEX
+        self.sink.write_all(b"strf")?;
+        self.write_u32(18)?;
+        self.write_u16(1)?; // WAVE_FORMAT_PCM
+        self.write_u16(self.num_channels)?;
+        self.write_u32(self.sample_rate)?;
+        self.write_u32(bytes_per_second)?;
+        self.write_u16(block_align)?;
+        self.write_u16(16)?;
+        self.write_u16(0)?;
+
+        self.sink.write_all(b"LIST")?;
+        self.write_u32(self.movi_size)?;
+        self.sink.write_all(b"movi")?;
+        Ok(())
+    }
+
+    fn write_u16(&mut self, value: u16) -> io::Result<()> {
+        self.sink.write_all(&value.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, value: u32) -> io::Result<()> {
+        self.sink.write_all(&value.to_le_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use std::io::Cursor;
+
+    /// The offset of the `movi` list type, which index offsets are relative to.
+    const MOVI_OFFSET: usize = HEADER_SIZE as usize - 4;
+
+    fn u32_at(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn chunk_sizes_and_offsets() {
+        let frame = RgbaImage::from_pixel(1, 2, Rgba([255, 0, 0, 255]));
+        let mut writer = AviWriter::new(Cursor::new(Vec::new()), 1, 2, 30.0, 1, 44100).unwrap();
+        writer.write_frame(&frame).unwrap();
+        writer.write_audio(&[1, 2, 3]).unwrap();
+        writer.write_frame(&frame).unwrap();
+        let data = writer.finish().unwrap().into_inner();
+
+        // Each frame has 2 rows of 4 bytes, and the audio chunk holds 6 bytes.
+        let movi_size = 4 + (8 + 8) + (8 + 6) + (8 + 8);
+        let index_size = 8 + 3 * 16;
+        assert_eq!(
+            data.len(),
+            HEADER_SIZE as usize + movi_size - 4 + index_size
+        );
+        assert_eq!(&data[0..4], b"RIFF");
+        assert_eq!(u32_at(&data, 4) as usize, data.len() - 8);
+        assert_eq!(&data[MOVI_OFFSET - 8..MOVI_OFFSET - 4], b"LIST");
+        assert_eq!(u32_at(&data, MOVI_OFFSET - 4) as usize, movi_size);
+        assert_eq!(&data[MOVI_OFFSET..MOVI_OFFSET + 4], b"movi");
+
+        // dwTotalFrames of the main header, and dwLength of the video and audio streams.
+        assert_eq!(u32_at(&data, 48), 2);
+        assert_eq!(u32_at(&data, 140), 2);
+        assert_eq!(u32_at(&data, 264), 3);
+
+        let index = &data[MOVI_OFFSET + movi_size..];
+        assert_eq!(&index[0..4], b"idx1");
+        assert_eq!(u32_at(index, 4), 3 * 16);
+        let expected: [(&[u8; 4], u32, u32, u32); 3] = [
+            (VIDEO_CHUNK_ID, AVIIF_KEYFRAME, 4, 8),
+            (AUDIO_CHUNK_ID, 0, 20, 6),
+            (VIDEO_CHUNK_ID, AVIIF_KEYFRAME, 34, 8),
+        ];
+        for (entry, (id, flags, offset, size)) in index[8..].chunks(16).zip(expected) {
+            assert_eq!(&entry[0..4], id);
+            assert_eq!(u32_at(entry, 4), flags);
+            assert_eq!(u32_at(entry, 8), offset);
+            assert_eq!(u32_at(entry, 12), size);
+
+            // The offsets point at the chunk headers.
+            let chunk = MOVI_OFFSET + offset as usize;
+            assert_eq!(&data[chunk..chunk + 4], id);
+            assert_eq!(u32_at(&data, chunk + 4), size);
+        }
+
+        // Rows are stored bottom-up as padded BGR.
+        assert_eq!(
+            &data[MOVI_OFFSET + 12..MOVI_OFFSET + 20],
+            &[0, 0, 255, 0, 0, 0, 255, 0]
+        );
+    }
+
+    #[test]
+    fn frame_size_must_match() {
+        let mut writer = AviWriter::new(Cursor::new(Vec::new()), 2, 2, 30.0, 2, 44100).unwrap();
+        let frame = RgbaImage::new(1, 2);
+        assert_eq!(
+            writer.write_frame(&frame).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}
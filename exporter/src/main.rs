@@ -1,3 +1,10 @@
+mod audio;
+mod avi;
+mod wav;
+
+use crate::audio::CaptureAudioBackend;
+use crate::avi::AviWriter;
+use crate::wav::WavWriter;
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use image::RgbaImage;
@@ -5,17 +12,18 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
+use ruffle_core::{Player, PlayerBuilder, ViewportDimensions};
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
-use std::fs::create_dir_all;
-use std::io::{self, Write};
+use std::fs::{create_dir_all, remove_file, File};
+use std::io::{self, BufWriter, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Parser, Debug, Copy, Clone)]
@@ -44,6 +52,7 @@ struct Opt {
     /// The default value will either be:
     /// - If given one swf and one frame, the name of the swf + ".png"
     /// - If given one swf and multiple frames, the name of the swf as a directory
+    /// - If given one swf and exporting a video, the name of the swf + ".avi"
     /// - If given multiple swfs, this field is required.
     #[clap(name = "output")]
     output_path: Option<PathBuf>,
@@ -81,18 +90,32 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of using a graphics adapter.
+    /// This is much slower, but works on machines without a GPU.
+    #[clap(long, action)]
+    software: bool,
+
+    /// Export the captured frames and the movie's audio as a single uncompressed AVI file,
+    /// instead of individual images
+    #[clap(long, action)]
+    video: bool,
+
+    /// Save the audio played during the captured frames to this WAV file.
+    /// Only supported when exporting a single swf.
+    #[clap(long)]
+    audio: Option<PathBuf>,
 }
 
-/// Captures a screenshot. The resulting image uses straight alpha
-fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+/// Loads a movie and creates a player to capture it with.
+/// If no `descriptors` are given, the software renderer is used instead of wgpu.
+fn create_player(
+    descriptors: Option<Arc<Descriptors>>,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
-    progress: &Option<ProgressBar>,
     size: SizeOpt,
     skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+    capture_audio: bool,
+) -> Result<Arc<Mutex<Player>>> {
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
 
     if movie.is_action_script_3() && skip_unsupported {
@@ -111,59 +134,192 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
+    let builder = PlayerBuilder::new();
+    let builder = if let Some(descriptors) = descriptors {
+        let target = TextureTarget::new(&descriptors.device, (width, height))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        builder.with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
+    } else {
+        builder.with_renderer(SoftwareRenderBackend::new(ViewportDimensions {
+            width,
+            height,
+            scale_factor: size.scale,
+        }))
+    };
+    let builder = if capture_audio {
+        builder.with_audio(CaptureAudioBackend::new())
+    } else {
+        builder
+    };
+    Ok(builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
-        .build();
+        .build())
+}
+
+/// Renders the current frame of the player and captures it. The resulting image uses straight alpha.
+fn capture_frame(player: &Mutex<Player>, swf_path: &Path, frame: u32) -> Result<RgbaImage> {
+    match catch_unwind(|| {
+        player.lock().unwrap().render();
+        let mut player = player.lock().unwrap();
+        let renderer = player.renderer_mut();
+        if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+            renderer.capture_frame()
+        } else {
+            renderer
+                .downcast_mut::<SoftwareRenderBackend>()
+                .map(|renderer| renderer.capture_frame())
+        }
+    }) {
+        Ok(Some(image)) => Ok(image),
+        Ok(None) => Err(anyhow!(
+            "Unable to capture frame {} of {:?}",
+            frame,
+            swf_path
+        )),
+        Err(e) => Err(anyhow!(
+            "Unable to capture frame {} of {:?}: {:?}",
+            frame,
+            swf_path,
+            e
+        )),
+    }
+}
+
+fn report_frame(progress: &Option<ProgressBar>, swf_path: &Path, frame: u32) {
+    if let Some(progress) = progress {
+        progress.set_message(format!(
+            "{} frame {}",
+            swf_path.file_stem().unwrap().to_string_lossy(),
+            frame
+        ));
+    }
+}
 
+/// Runs a single frame of the player.
+///
+/// `run_frame` doesn't tick the audio backend, so exactly one frame's worth of audio is mixed here.
+/// Audio from skipped frames is discarded, but sounds started there keep playing.
+fn run_frame(player: &Mutex<Player>) {
+    player.lock().unwrap().preload(&mut ExecutionLimit::none());
+
+    player.lock().unwrap().run_frame();
+    player.lock().unwrap().audio_mut().tick();
+}
+
+/// Returns the audio mixed during the last frame of a player created to capture audio.
+fn frame_audio(player: &Mutex<Player>, swf_path: &Path) -> Result<Vec<i16>> {
+    let mut player = player.lock().unwrap();
+    let audio = player
+        .audio_mut()
+        .downcast_mut::<CaptureAudioBackend>()
+        .ok_or_else(|| anyhow!("Unable to capture audio of {:?}", swf_path))?;
+    Ok(audio.frame_samples().to_vec())
+}
+
+/// Captures a screenshot. The resulting image uses straight alpha.
+/// If no `descriptors` are given, the software renderer is used instead of wgpu.
+/// If an `audio_output` is given, the audio mixed during the captured frames is saved there as a WAV file.
+#[allow(clippy::too_many_arguments)]
+fn take_screenshot(
+    descriptors: Option<Arc<Descriptors>>,
+    swf_path: &Path,
+    frames: u32,
+    skipframes: u32,
+    progress: &Option<ProgressBar>,
+    size: SizeOpt,
+    skip_unsupported: bool,
+    audio_output: Option<&Path>,
+) -> Result<Vec<RgbaImage>> {
+    let player = create_player(
+        descriptors,
+        swf_path,
+        size,
+        skip_unsupported,
+        audio_output.is_some(),
+    )?;
+
+    let mut audio_writer = match audio_output {
+        Some(path) => Some(WavWriter::new(
+            BufWriter::new(File::create(path)?),
+            CaptureAudioBackend::NUM_CHANNELS.into(),
+            CaptureAudioBackend::SAMPLE_RATE,
+        )?),
+        None => None,
+    };
     let mut result = Vec::new();
     let totalframes = frames + skipframes;
 
     for i in 0..totalframes {
+        report_frame(progress, swf_path, i);
+
+        run_frame(&player);
+        if i >= skipframes {
+            result.push(capture_frame(&player, swf_path, i)?);
+            if let Some(audio_writer) = &mut audio_writer {
+                audio_writer.write_samples(&frame_audio(&player, swf_path)?)?;
+            }
+        }
+
         if let Some(progress) = &progress {
-            progress.set_message(format!(
-                "{} frame {}",
-                swf_path.file_stem().unwrap().to_string_lossy(),
-                i
-            ));
+            progress.inc(1);
         }
+    }
+
+    if let Some(audio_writer) = audio_writer {
+        audio_writer.finish()?;
+    }
+    Ok(result)
+}
+
+/// Captures a movie as an uncompressed AVI file at the movie's frame rate,
+/// along with all audio mixed while the captured frames were running.
+/// If no `descriptors` are given, the software renderer is used instead of wgpu.
+#[allow(clippy::too_many_arguments)]
+fn capture_video(
+    descriptors: Option<Arc<Descriptors>>,
+    swf_path: &Path,
+    output: &Path,
+    frames: u32,
+    skipframes: u32,
+    progress: &Option<ProgressBar>,
+    size: SizeOpt,
+    skip_unsupported: bool,
+) -> Result<()> {
+    let player = create_player(descriptors, swf_path, size, skip_unsupported, true)?;
+
+    let mut writer = {
+        let mut player = player.lock().unwrap();
+        let dimensions = player.viewport_dimensions();
+        AviWriter::new(
+            BufWriter::new(File::create(output)?),
+            dimensions.width,
+            dimensions.height,
+            player.frame_rate(),
+            CaptureAudioBackend::NUM_CHANNELS.into(),
+            CaptureAudioBackend::SAMPLE_RATE,
+        )?
+    };
+    let totalframes = frames + skipframes;
 
-        player.lock().unwrap().preload(&mut ExecutionLimit::none());
+    for i in 0..totalframes {
+        report_frame(progress, swf_path, i);
 
-        player.lock().unwrap().run_frame();
+        run_frame(&player);
         if i >= skipframes {
-            match catch_unwind(|| {
-                player.lock().unwrap().render();
-                let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
-            }) {
-                Ok(Some(image)) => result.push(image),
-                Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
-                Err(e) => {
-                    return Err(anyhow!(
-                        "Unable to capture frame {} of {:?}: {:?}",
-                        i,
-                        swf_path,
-                        e
-                    ))
-                }
-            }
+            writer.write_frame(&capture_frame(&player, swf_path, i)?)?;
+            writer.write_audio(&frame_audio(&player, swf_path)?)?;
         }
 
         if let Some(progress) = &progress {
             progress.inc(1);
         }
     }
-    Ok(result)
+
+    writer.finish()?;
+    Ok(())
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -196,17 +352,23 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
+        if opt.video {
+            result.set_extension("avi");
+        } else if opt.frames == 1 {
             result.set_extension("png");
         }
         result
     });
 
-    if opt.frames > 1 {
+    if opt.video && output == Path::new("-") {
+        return Err(anyhow!("Videos can't be written to stdout."));
+    }
+
+    if opt.frames > 1 && !opt.video {
         let _ = create_dir_all(&output);
     }
 
@@ -224,6 +386,32 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
+    if opt.video {
+        capture_video(
+            descriptors,
+            &opt.swf,
+            &output,
+            opt.frames,
+            opt.skipframes,
+            &progress,
+            opt.size,
+            opt.skip_unsupported,
+        )?;
+
+        let message = format!(
+            "Saved {} frames of {} to {}",
+            opt.frames,
+            opt.swf.to_string_lossy(),
+            output.to_string_lossy()
+        );
+        if let Some(progress) = progress {
+            progress.finish_with_message(message);
+        } else if !opt.silent {
+            println!("{message}");
+        }
+        return Ok(());
+    }
+
     let frames = take_screenshot(
         descriptors,
         &opt.swf,
@@ -232,6 +420,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         &progress,
         opt.size,
         opt.skip_unsupported,
+        opt.audio.as_deref(),
     )?;
 
     if let Some(progress) = &progress {
@@ -293,7 +482,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -321,7 +510,33 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
+        if opt.video {
+            let mut destination: PathBuf = (&output).into();
+            let mut relative_path = file
+                .path()
+                .strip_prefix(&opt.swf)
+                .unwrap_or_else(|_| file.path())
+                .to_path_buf();
+            relative_path.set_extension("avi");
+            destination.push(relative_path);
+            if let Some(parent) = destination.parent() {
+                let _ = create_dir_all(parent);
+            }
+            if capture_video(
+                descriptors.clone(),
+                file.path(),
+                &destination,
+                opt.frames,
+                opt.skipframes,
+                &progress,
+                opt.size,
+                opt.skip_unsupported,
+            )
+            .is_err()
+            {
+                let _ = remove_file(&destination);
+            }
+        } else if let Ok(frames) = take_screenshot(
             descriptors.clone(),
             file.path(),
             opt.frames,
@@ -329,6 +544,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
             &progress,
             opt.size,
             opt.skip_unsupported,
+            None,
         ) {
             let mut relative_path = file
                 .path()
@@ -401,20 +617,36 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
-        dx12_shader_compiler: wgpu::Dx12Compiler::default(),
-    });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-        opt.graphics.into(),
-        &instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    ))
-    .map_err(|e| anyhow!(e.to_string()))?;
-
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    if opt.audio.is_some() {
+        if opt.video {
+            return Err(anyhow!(
+                "Videos already include audio, --audio can't be used with --video."
+            ));
+        }
+        if !opt.swf.is_file() {
+            return Err(anyhow!(
+                "Audio can only be saved when exporting a single file."
+            ));
+        }
+    }
+    let descriptors = if opt.software {
+        None
+    } else {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: opt.graphics.into(),
+            dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+        });
+        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+            opt.graphics.into(),
+            &instance,
+            None,
+            opt.power.into(),
+            trace_path(&opt),
+        ))
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+        Some(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+    };
 
     if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
@@ -0,0 +1,129 @@
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// The size in bytes of the RIFF, `fmt ` and `data` chunk headers.
+const HEADER_SIZE: u32 = 12 + 24 + 8;
+
+/// A writer for WAV files holding 16-bit PCM audio.
+///
+/// Samples are streamed to the sink as they are written, and the chunk sizes are
+/// filled in by `finish`.
+pub struct WavWriter<W: Write + Seek> {
+    sink: W,
+    num_channels: u16,
+    sample_rate: u32,
+
+    /// The size of the sample data written so far.
+    data_size: u32,
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    pub fn new(sink: W, num_channels: u16, sample_rate: u32) -> io::Result<Self> {
+        let mut writer = Self {
+            sink,
+            num_channels,
+            sample_rate,
+            data_size: 0,
+        };
+        writer.write_header()?;
+        Ok(writer)
+    }
+
+    /// Append interleaved samples.
+    pub fn write_samples(&mut self, samples: &[i16]) -> io::Result<()> {
+        let size = samples.len() as u64 * 2;
+        if u64::from(HEADER_SIZE) + u64::from(self.data_size) + size > u64::from(u32::MAX) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "WAV files can't be larger than 4 GiB",
+            ));
+        }
+
+        let data: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        self.sink.write_all(&data)?;
+        self.data_size += size as u32;
+        Ok(())
+    }
+
+    /// Write the final header and return the underlying sink.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.sink.seek(SeekFrom::Start(0))?;
+        self.write_header()?;
+        self.sink.seek(SeekFrom::End(0))?;
+        self.sink.flush()?;
+        Ok(self.sink)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        let block_align = self.num_channels * 2;
+
+        self.sink.write_all(b"RIFF")?;
+        self.write_u32(HEADER_SIZE - 8 + self.data_size)?;
+        self.sink.write_all(b"WAVE")?;
+
+        self.sink.write_all(b"fmt ")?;
+        self.write_u32(16)?;
+        self.write_u16(1)?; // WAVE_FORMAT_PCM
+        self.write_u16(self.num_channels)?;
+        self.write_u32(self.sample_rate)?;
+        self.write_u32(self.sample_rate * u32::from(block_align))?;
+        self.write_u16(block_align)?;
+        self.write_u16(16)?;
+
+        self.sink.write_all(b"data")?;
+        self.write_u32(self.data_size)?;
+        Ok(())
+    }
+
+    fn write_u16(&mut self, value: u16) -> io::Result<()> {
+        self.sink.write_all(&value.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, value: u32) -> io::Result<()> {
+        self.sink.write_all(&value.to_le_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn u32_at(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn chunk_sizes_after_finish() {
+        let mut writer = WavWriter::new(Cursor::new(Vec::new()), 2, 44100).unwrap();
+        writer.write_samples(&[1, -1, 2, -2]).unwrap();
+        writer.write_samples(&[3, -3]).unwrap();
+        let data = writer.finish().unwrap().into_inner();
+
+        assert_eq!(data.len(), HEADER_SIZE as usize + 12);
+        assert_eq!(&data[0..4], b"RIFF");
+        assert_eq!(u32_at(&data, 4) as usize, data.len() - 8);
+        assert_eq!(&data[8..12], b"WAVE");
+
+        // nAvgBytesPerSec and nBlockAlign of the `fmt ` chunk.
+        assert_eq!(&data[12..16], b"fmt ");
+        assert_eq!(u32_at(&data, 28), 44100 * 4);
+        assert_eq!(&data[32..34], &4u16.to_le_bytes());
+
+        assert_eq!(&data[36..40], b"data");
+        assert_eq!(u32_at(&data, 40), 12);
+        assert_eq!(&data[44..48], &[1, 0, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn empty_file() {
+        let writer = WavWriter::new(Cursor::new(Vec::new()), 1, 22050).unwrap();
+        let data = writer.finish().unwrap().into_inner();
+
+        assert_eq!(data.len(), HEADER_SIZE as usize);
+        assert_eq!(u32_at(&data, 4), HEADER_SIZE - 8);
+        assert_eq!(u32_at(&data, 40), 0);
+    }
+}
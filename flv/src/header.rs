@@ -1,7 +1,8 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
+use crate::writer::FlvWriter;
 use bitflags::bitflags;
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 bitflags! {
     #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -56,12 +57,28 @@ impl Header {
             }
         }
     }
+
+    /// Write an FLV header.
+    ///
+    /// If the data offset points past the end of the header, the space in
+    /// between is filled with zeroes so that the first tag is written at the
+    /// offset.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u24(0x464C56)?;
+        writer.write_u8(self.version)?;
+        writer.write_u8(self.type_flags.bits())?;
+        writer.write_u32(self.data_offset)?;
+
+        let padding = self.data_offset.saturating_sub(9) as usize;
+        writer.write(&vec![0; padding])
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::header::{Header, TypeFlags};
     use crate::reader::FlvReader;
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_header() {
@@ -77,4 +94,24 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn write_header() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_VIDEO,
+            data_offset: 12,
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        header.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        assert_eq!(
+            data,
+            vec![0x46, 0x4C, 0x56, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00]
+        );
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Header::parse(&mut reader), Ok(header));
+    }
 }
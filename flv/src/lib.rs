@@ -5,6 +5,7 @@ mod tag;
 mod video;
 
 mod reader;
+mod writer;
 
 mod error;
 
@@ -15,3 +16,4 @@ pub use script::{ScriptData, Value, Variable};
 pub use sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
 pub use tag::{Tag, TagData};
 pub use video::{CodecId, CommandFrame, FrameType, VideoData, VideoPacket};
+pub use writer::FlvWriter;
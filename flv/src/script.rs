@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::reader::FlvReader;
-use std::io::Seek;
+use crate::writer::FlvWriter;
+use std::io::{Seek, Write};
 
 fn parse_string<'a>(reader: &mut FlvReader<'a>, is_long_string: bool) -> Result<&'a [u8], Error> {
     let length = if is_long_string {
@@ -12,6 +13,31 @@ fn parse_string<'a>(reader: &mut FlvReader<'a>, is_long_string: bool) -> Result<
     reader.read(length as usize)
 }
 
+fn write_string<W: Write>(
+    writer: &mut FlvWriter<W>,
+    string: &[u8],
+    is_long_string: bool,
+) -> Result<(), Error> {
+    if is_long_string {
+        writer.write_u32(u32::try_from(string.len()).map_err(|_| Error::ValueTooLong)?)?;
+    } else {
+        writer.write_u16(u16::try_from(string.len()).map_err(|_| Error::ValueTooLong)?)?;
+    }
+
+    writer.write(string)
+}
+
+fn write_variables<W: Write>(
+    writer: &mut FlvWriter<W>,
+    variables: &[Variable<'_>],
+) -> Result<(), Error> {
+    for variable in variables {
+        variable.write(writer)?;
+    }
+
+    Ok(())
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
 pub enum Value<'a> {
@@ -98,6 +124,64 @@ impl<'a> Value<'a> {
             _ => Err(Error::UnknownValueType),
         }
     }
+
+    /// Write a script value, including its type marker.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        match self {
+            Self::Number(value) => {
+                writer.write_u8(0)?;
+                writer.write_f64(*value)
+            }
+            Self::Boolean(value) => {
+                writer.write_u8(1)?;
+                writer.write_u8(*value as u8)
+            }
+            Self::String(value) => {
+                writer.write_u8(2)?;
+                write_string(writer, value, false)
+            }
+            Self::Object(variables) => {
+                writer.write_u8(3)?;
+                write_variables(writer, variables)?;
+                writer.write_u24(9)
+            }
+            Self::MovieClip(value) => {
+                writer.write_u8(4)?;
+                write_string(writer, value, false)
+            }
+            Self::Null => writer.write_u8(5),
+            Self::Undefined => writer.write_u8(6),
+            Self::Reference(value) => {
+                writer.write_u8(7)?;
+                writer.write_u16(*value)
+            }
+            Self::EcmaArray(variables) => {
+                writer.write_u8(8)?;
+                writer
+                    .write_u32(u32::try_from(variables.len()).map_err(|_| Error::ValueTooLong)?)?;
+                write_variables(writer, variables)?;
+                writer.write_u24(9)
+            }
+            Self::StrictArray(variables) => {
+                writer.write_u8(10)?;
+                writer
+                    .write_u32(u32::try_from(variables.len()).map_err(|_| Error::ValueTooLong)?)?;
+                write_variables(writer, variables)
+            }
+            Self::Date {
+                unix_time,
+                local_offset,
+            } => {
+                writer.write_u8(11)?;
+                writer.write_f64(*unix_time)?;
+                writer.write_i16(*local_offset)
+            }
+            Self::LongString(value) => {
+                writer.write_u8(12)?;
+                write_string(writer, value, true)
+            }
+        }
+    }
 }
 
 /// An individual object in a ScriptData tag.
@@ -118,6 +202,11 @@ impl<'a> Variable<'a> {
             data: Value::parse(reader)?,
         })
     }
+
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        write_string(writer, self.name, false)?;
+        self.data.write(writer)
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -149,12 +238,26 @@ impl<'a> ScriptData<'a> {
             vars.push(Variable::parse(reader)?);
         }
     }
+
+    /// Write a script data structure.
+    ///
+    /// The structure is always written with an object terminator, even though
+    /// the parser does not require one.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        // This is the type marker of the first name string, which `parse`
+        // skips over.
+        writer.write_u8(2)?;
+        write_variables(writer, &self.0)?;
+        writer.write_u24(9)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::reader::FlvReader;
-    use crate::script::{parse_string, ScriptData, Value, Variable};
+    use crate::script::{parse_string, write_string, ScriptData, Value, Variable};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_string() {
@@ -398,4 +501,101 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn write_string_long() {
+        let mut writer = FlvWriter::new(vec![]);
+        write_string(&mut writer, &[0x01, 0x02, 0x03], true).unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            vec![0x00, 0x00, 0x00, 0x03, 0x01, 0x02, 0x03]
+        );
+    }
+
+    #[test]
+    fn write_string_too_long() {
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(
+            write_string(&mut writer, &[0; 0x10000], false),
+            Err(Error::ValueTooLong)
+        );
+    }
+
+    #[test]
+    fn write_value_round_trip() {
+        let values = [
+            Value::Number(12.3),
+            Value::Boolean(true),
+            Value::String(&[0x01, 0x02, 0x03]),
+            Value::Object(vec![Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Undefined,
+            }]),
+            Value::MovieClip(&[0x01, 0x02, 0x03]),
+            Value::Null,
+            Value::Undefined,
+            Value::Reference(0x2438),
+            Value::EcmaArray(vec![Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Null,
+            }]),
+            Value::StrictArray(vec![
+                Variable {
+                    name: &[0x01, 0x02, 0x03],
+                    data: Value::Undefined,
+                },
+                Variable {
+                    name: &[0x01, 0x02, 0x03],
+                    data: Value::Null,
+                },
+            ]),
+            Value::Date {
+                unix_time: 12.3,
+                local_offset: -2,
+            },
+            Value::LongString(&[0x01, 0x02, 0x03]),
+        ];
+
+        for value in values {
+            let mut writer = FlvWriter::new(vec![]);
+            value.write(&mut writer).unwrap();
+            let data = writer.into_inner();
+
+            let mut reader = FlvReader::from_source(&data);
+            assert_eq!(Value::parse(&mut reader), Ok(value));
+        }
+    }
+
+    #[test]
+    fn write_scriptdata() {
+        let script_data = ScriptData(vec![
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Undefined,
+            },
+            Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Null,
+            },
+        ]);
+        let mut writer = FlvWriter::new(vec![]);
+        script_data.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        assert_eq!(
+            data,
+            vec![
+                0x02, 0x00, 0x03, 0x01, 0x02, 0x03, 0x06, 0x00, 0x03, 0x01, 0x02, 0x03, 0x05, 0x00,
+                0x00, 0x09,
+            ]
+        );
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(
+            ScriptData::parse(&mut reader, data.len() as u32),
+            Ok(script_data)
+        );
+    }
 }
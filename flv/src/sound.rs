@@ -1,6 +1,7 @@
 use crate::error::Error;
+use crate::writer::FlvWriter;
 use crate::FlvReader;
-use std::io::Seek;
+use std::io::{Seek, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -167,6 +168,28 @@ impl<'a> AudioData<'a> {
             data,
         })
     }
+
+    /// Write an audio data structure.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        writer.write_u8(
+            (self.format as u8) << 4
+                | (self.rate as u8) << 2
+                | (self.size as u8) << 1
+                | self.sound_type as u8,
+        )?;
+
+        match self.data {
+            AudioDataType::Raw(data) => writer.write(data),
+            AudioDataType::AacSequenceHeader(data) => {
+                writer.write_u8(0)?;
+                writer.write(data)
+            }
+            AudioDataType::AacRaw(data) => {
+                writer.write_u8(1)?;
+                writer.write(data)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +197,7 @@ mod tests {
     use crate::error::Error;
     use crate::reader::FlvReader;
     use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_audiodata() {
@@ -247,4 +271,42 @@ mod tests {
             Err(Error::UnknownAacPacketType(2))
         );
     }
+
+    #[test]
+    fn write_audiodata() {
+        let audio = AudioData {
+            format: SoundFormat::Speex,
+            rate: SoundRate::R44_000,
+            size: SoundSize::Bits16,
+            sound_type: SoundType::Stereo,
+            data: AudioDataType::Raw(&[0x12, 0x34, 0x56, 0x78]),
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        audio.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        assert_eq!(data, vec![0xBF, 0x12, 0x34, 0x56, 0x78]);
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(AudioData::parse(&mut reader, data.len() as u32), Ok(audio));
+    }
+
+    #[test]
+    fn write_audiodata_aac() {
+        let audio = AudioData {
+            format: SoundFormat::Aac,
+            rate: SoundRate::R44_000,
+            size: SoundSize::Bits16,
+            sound_type: SoundType::Stereo,
+            data: AudioDataType::AacRaw(&[0x12, 0x34, 0x56, 0x78]),
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        audio.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        assert_eq!(data, vec![0xAF, 0x01, 0x12, 0x34, 0x56, 0x78]);
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(AudioData::parse(&mut reader, data.len() as u32), Ok(audio));
+    }
 }
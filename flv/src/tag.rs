@@ -3,8 +3,9 @@ use crate::reader::FlvReader;
 use crate::script::ScriptData;
 use crate::sound::AudioData;
 use crate::video::VideoData;
+use crate::writer::FlvWriter;
 
-use std::io::{Seek, SeekFrom};
+use std::io::{Seek, SeekFrom, Write};
 
 #[repr(u8)]
 #[derive(PartialEq, Debug, Clone)]
@@ -125,6 +126,52 @@ impl<'a> Tag<'a> {
 
         Ok(())
     }
+
+    /// Write a single FLV tag structure.
+    ///
+    /// The tag is preceded by a back pointer to the last tag written with
+    /// the same writer, mirroring the layout expected by `parse`. Once all
+    /// tags have been written, `FlvWriter::finish` should be called to write
+    /// the back pointer to the final tag.
+    ///
+    /// Tags containing `TagData::Invalid` cannot be written, as we do not
+    /// retain the data that failed to parse.
+    pub fn write<W: Write>(&self, writer: &mut FlvWriter<W>) -> Result<(), Error> {
+        let mut data = FlvWriter::new(vec![]);
+        let tag_type = match &self.data {
+            TagData::Audio(audio) => {
+                audio.write(&mut data)?;
+                8
+            }
+            TagData::Video(video) => {
+                video.write(&mut data)?;
+                9
+            }
+            TagData::Script(script) => {
+                script.write(&mut data)?;
+                18
+            }
+            TagData::Invalid(_) => return Err(Error::InvalidTagData),
+        };
+        let data = data.into_inner();
+
+        if data.len() > 0xFFFFFF {
+            return Err(Error::ValueTooLong);
+        }
+
+        writer.write_u32(writer.previous_tag_size())?;
+        writer.write_u8(tag_type)?;
+        writer.write_u24(data.len() as u32)?;
+        writer.write_u24(self.timestamp as u32)?;
+        writer.write_u8((self.timestamp as u32 >> 24) as u8)?;
+        writer.write_u24(self.stream_id)?;
+        writer.write(&data)?;
+
+        // The back pointer covers the 11 byte tag header and the tag data.
+        writer.set_previous_tag_size(11 + data.len() as u32);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -135,6 +182,7 @@ mod tests {
     use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
     use crate::tag::{Tag, TagData};
     use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
 
     #[test]
     fn read_tag_sounddata() {
@@ -321,4 +369,79 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn write_tag_sounddata() {
+        let tag = Tag {
+            timestamp: 0,
+            stream_id: 0x5000,
+            data: TagData::Audio(AudioData {
+                format: SoundFormat::Speex,
+                rate: SoundRate::R44_000,
+                size: SoundSize::Bits16,
+                sound_type: SoundType::Stereo,
+                data: AudioDataType::Raw(&[0x12, 0x34, 0x56, 0x78]),
+            }),
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        tag.write(&mut writer).unwrap();
+
+        assert_eq!(writer.previous_tag_size(), 16);
+        assert_eq!(
+            writer.into_inner(),
+            vec![
+                0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50,
+                0x00, 0xBF, 0x12, 0x34, 0x56, 0x78,
+            ]
+        );
+    }
+
+    #[test]
+    fn write_tag_previous_size() {
+        let first = Tag {
+            timestamp: 0x01020304,
+            stream_id: 0,
+            data: TagData::Video(VideoData {
+                frame_type: FrameType::Keyframe,
+                codec_id: CodecId::SorensonH263,
+                data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+            }),
+        };
+        let second = Tag {
+            timestamp: 0x05,
+            stream_id: 0,
+            data: TagData::Script(ScriptData(vec![Variable {
+                name: &[0x01, 0x02, 0x03],
+                data: Value::Undefined,
+            }])),
+        };
+        let mut writer = FlvWriter::new(vec![]);
+        first.write(&mut writer).unwrap();
+        second.write(&mut writer).unwrap();
+        let data = writer.into_inner();
+
+        // The first tag's timestamp is split across the 24-bit field and the
+        // extension byte.
+        assert_eq!(&data[8..12], &[0x02, 0x03, 0x04, 0x01]);
+        // The second tag points back over the 11 byte header and 5 byte body
+        // of the first.
+        assert_eq!(&data[20..24], &[0x00, 0x00, 0x00, 0x10]);
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Tag::parse(&mut reader), Ok(first));
+        assert_eq!(Tag::parse(&mut reader), Ok(second));
+    }
+
+    #[test]
+    fn write_tag_invalid() {
+        let tag = Tag {
+            timestamp: 0,
+            stream_id: 0,
+            data: TagData::Invalid(Error::UnknownTagType(0x20)),
+        };
+        let mut writer = FlvWriter::new(vec![]);
+
+        assert_eq!(tag.write(&mut writer), Err(Error::InvalidTagData));
+        assert!(writer.into_inner().is_empty());
+    }
 }
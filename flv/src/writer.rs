@@ -0,0 +1,165 @@
+use crate::error::Error as FlvError;
+use std::io::Write;
+
+/// A writer that allows muxing an FLV container.
+///
+/// The writer keeps track of the size of the last tag written to it, so that
+/// each tag can be preceded by the correct back pointer.
+pub struct FlvWriter<W: Write> {
+    sink: W,
+
+    previous_tag_size: u32,
+}
+
+impl<W: Write> FlvWriter<W> {
+    pub fn new(sink: W) -> Self {
+        FlvWriter {
+            sink,
+            previous_tag_size: 0,
+        }
+    }
+
+    /// Retrieve the underlying sink without finishing the FLV.
+    pub fn into_inner(self) -> W {
+        self.sink
+    }
+
+    /// Finish writing the FLV and return the underlying sink.
+    ///
+    /// FLV files end with a back pointer to the last tag in the file, which
+    /// is written here.
+    pub fn finish(mut self) -> Result<W, FlvError> {
+        self.write_u32(self.previous_tag_size)?;
+        Ok(self.sink)
+    }
+
+    /// The size of the last tag written, including its header.
+    pub fn previous_tag_size(&self) -> u32 {
+        self.previous_tag_size
+    }
+
+    pub(crate) fn set_previous_tag_size(&mut self, size: u32) {
+        self.previous_tag_size = size;
+    }
+
+    /// Write a buffer of bytes to the sink.
+    pub fn write(&mut self, data: &[u8]) -> Result<(), FlvError> {
+        self.sink.write_all(data)?;
+        Ok(())
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> Result<(), FlvError> {
+        self.write(&[value])
+    }
+
+    pub fn write_u16(&mut self, value: u16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_i16(&mut self, value: i16) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    /// Write the lower 24 bits of a value.
+    pub fn write_u24(&mut self, value: u32) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes()[1..])
+    }
+
+    pub fn write_u32(&mut self, value: u32) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+
+    pub fn write_f64(&mut self, value: f64) -> Result<(), FlvError> {
+        self.write(&value.to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::header::{Header, TypeFlags};
+    use crate::reader::FlvReader;
+    use crate::script::{ScriptData, Value, Variable};
+    use crate::sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
+    use crate::tag::{Tag, TagData};
+    use crate::video::{CodecId, FrameType, VideoData, VideoPacket};
+    use crate::writer::FlvWriter;
+
+    #[test]
+    fn write_integers() {
+        let mut writer = FlvWriter::new(vec![]);
+        writer.write_u8(0x01).unwrap();
+        writer.write_u16(0x0203).unwrap();
+        writer.write_i16(-2).unwrap();
+        writer.write_u24(0xFF040506).unwrap();
+        writer.write_u32(0x0708090A).unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            vec![0x01, 0x02, 0x03, 0xFF, 0xFE, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A]
+        );
+    }
+
+    #[test]
+    fn round_trip_file() {
+        let header = Header {
+            version: 1,
+            type_flags: TypeFlags::HAS_AUDIO | TypeFlags::HAS_VIDEO,
+            data_offset: 9,
+        };
+        let tags = vec![
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Script(ScriptData(vec![Variable {
+                    name: b"onMetaData",
+                    data: Value::EcmaArray(vec![
+                        Variable {
+                            name: b"duration",
+                            data: Value::Number(1.07),
+                        },
+                        Variable {
+                            name: b"stereo",
+                            data: Value::Boolean(false),
+                        },
+                    ]),
+                }])),
+            },
+            Tag {
+                timestamp: 0,
+                stream_id: 0,
+                data: TagData::Video(VideoData {
+                    frame_type: FrameType::Keyframe,
+                    codec_id: CodecId::SorensonH263,
+                    data: VideoPacket::Data(&[0x12, 0x34, 0x56, 0x78]),
+                }),
+            },
+            Tag {
+                timestamp: 0x01234567,
+                stream_id: 0,
+                data: TagData::Audio(AudioData {
+                    format: SoundFormat::MP3,
+                    rate: SoundRate::R44_000,
+                    size: SoundSize::Bits16,
+                    sound_type: SoundType::Stereo,
+                    data: AudioDataType::Raw(&[0x9A, 0xBC]),
+                }),
+            },
+        ];
+
+        let mut writer = FlvWriter::new(vec![]);
+        header.write(&mut writer).unwrap();
+        for tag in &tags {
+            tag.write(&mut writer).unwrap();
+        }
+        let data = writer.finish().unwrap();
+
+        let mut reader = FlvReader::from_source(&data);
+        assert_eq!(Header::parse(&mut reader), Ok(header));
+        for tag in &tags {
+            assert_eq!(Tag::parse(&mut reader).as_ref(), Ok(tag));
+        }
+
+        // The trailing back pointer covers the header and data of the last tag.
+        assert_eq!(&data[data.len() - 4..], &[0x00, 0x00, 0x00, 0x0E]);
+    }
+}
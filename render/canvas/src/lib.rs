@@ -11,6 +11,7 @@ use ruffle_render::bitmap::{
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
@@ -553,6 +554,39 @@ impl CommandHandler for WebCanvasRenderBackend {
         self.clear_color_filter();
     }
 
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        // Canvas can't draw projections, so we use the closest affine transform
+        // at the center of the bitmap.
+        let canvas = &as_bitmap_data(&bitmap).canvas;
+        let half_width = f64::from(canvas.width()) / 2.0;
+        let half_height = f64::from(canvas.height()) / 2.0;
+        let centered = matrix * Matrix3D::translate(half_width, half_height, 0.0);
+        if centered.get(3, 3) <= 0.0 {
+            // The center is behind the viewer.
+            return;
+        }
+        let matrix = centered.to_affine()
+            * Matrix::translate(
+                Twips::from_pixels(-half_width),
+                Twips::from_pixels(-half_height),
+            );
+        self.render_bitmap(
+            bitmap,
+            Transform {
+                matrix,
+                color_transform,
+            },
+            smoothing,
+            PixelSnapping::Never,
+        );
+    }
+
     fn render_stage3d(&mut self, _bitmap: BitmapHandle, _transform: Transform) {
         panic!("Stage3D should not have been created on canvas backend")
     }
@@ -0,0 +1,668 @@
+use crate::blend::source_over;
+use crate::paint::{transform_color, GradientRamp, Paint};
+use crate::raster::{Affine, Rasterizer, Triangle};
+use crate::surface::{premultiply, Surface};
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, RenderBackend, ShapeHandle, ShapeHandleImpl, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, PixelSnapping, RgbaBufRead,
+    SyncHandle,
+};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::error::Error;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::{
+    DrawType as TessDrawType, Gradient as TessGradient, ShapeTessellator, Vertex as TessVertex,
+};
+use ruffle_render::transform::Transform;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use swf::{BlendMode, Color, ColorTransform};
+
+/// A render backend that rasterizes everything on the CPU.
+///
+/// This is slow compared to the GPU backends, but it works everywhere, including machines
+/// without any graphics adapter. The rendered frame can be retrieved with `capture_frame`.
+pub struct SoftwareRenderBackend {
+    shape_tessellator: ShapeTessellator,
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    surface: Surface,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(viewport_dimensions: ViewportDimensions) -> Self {
+        Self {
+            shape_tessellator: ShapeTessellator::new(),
+            surface: Surface::new(
+                viewport_dimensions.width.max(1),
+                viewport_dimensions.height.max(1),
+            ),
+            viewport_dimensions,
+            quality: StageQuality::High,
+        }
+    }
+
+    /// Returns the last rendered frame. The resulting image uses straight alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut bytes = self.surface.to_rgba8();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        image::RgbaImage::from_raw(self.surface.width(), self.surface.height(), bytes)
+            .expect("Surface must be a valid RgbaImage")
+    }
+
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+
+        let draws = lyon_mesh
+            .into_iter()
+            .map(|draw| Draw {
+                draw_type: match draw.draw_type {
+                    TessDrawType::Color => DrawType::Color,
+                    TessDrawType::Gradient(gradient) => DrawType::Gradient(gradient),
+                    TessDrawType::Bitmap(bitmap) => DrawType::Bitmap {
+                        matrix: bitmap.matrix,
+                        handle: bitmap_source.bitmap_handle(bitmap.bitmap_id, self),
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                    },
+                },
+                vertices: draw.vertices,
+                indices: draw.indices,
+                mask_index_count: draw.mask_index_count as usize,
+            })
+            .collect();
+
+        Mesh { draws }
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.surface = Surface::new(dimensions.width.max(1), dimensions.height.max(1));
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        ShapeHandle(Arc::new(self.register_shape_internal(shape, bitmap_source)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        mut bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let entry = as_bitmap(&handle);
+
+        // Draw onto a copy, so that the bitmap may be used as a source while it's being drawn to.
+        let mut target = entry.surface.borrow().clone();
+        let rasterizer = Rasterizer::new(quality);
+        commands.execute(&mut Renderer::new(&mut target, &rasterizer));
+
+        bounds.clamp(target.width(), target.height());
+        entry.surface.borrow_mut().copy_region_from(&target, bounds);
+
+        Some(Box::new(SoftwareSyncHandle {
+            rgba: target.region_to_rgba8(bounds),
+            row_width: bounds.width() * 4,
+        }))
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            if !entry.filters.is_empty() {
+                tracing::warn!("Filters are not supported by the software renderer");
+            }
+            let bitmap = as_bitmap(&entry.handle);
+            let mut target = bitmap.surface.borrow().clone();
+            target.clear(entry.clear);
+            let rasterizer = Rasterizer::new(self.quality);
+            entry
+                .commands
+                .execute(&mut Renderer::new(&mut target, &rasterizer));
+            *bitmap.surface.borrow_mut() = target;
+        }
+
+        self.surface.clear(clear);
+        let rasterizer = Rasterizer::new(self.quality);
+        commands.execute(&mut Renderer::new(&mut self.surface, &rasterizer));
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap {
+            surface: RefCell::new(Surface::new(width, height)),
+        })))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap {
+            surface: RefCell::new(Surface::from_bitmap(bitmap)),
+        })))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let entry = as_bitmap(handle);
+        let mut surface = entry.surface.borrow_mut();
+        if surface.width() != bitmap.width() || surface.height() != bitmap.height() {
+            *surface = Surface::from_bitmap(bitmap);
+        } else {
+            surface.update_region(bitmap, region);
+        }
+        Ok(())
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Renderer: Software\nSurface size: {} x {}\nSurface samples: {} x",
+            self.surface.width(),
+            self.surface.height(),
+            self.quality.sample_count()
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: BitmapHandle,
+    ) -> Result<Box<dyn SyncHandle>, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// Executes a `CommandList` onto a single surface.
+struct Renderer<'a> {
+    target: &'a mut Surface,
+    rasterizer: &'a Rasterizer,
+
+    /// The coverage of each active mask, for every pixel of the target.
+    /// Nested masks are already intersected with their parents.
+    masks: Vec<Vec<f32>>,
+    mask_state: MaskState,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(target: &'a mut Surface, rasterizer: &'a Rasterizer) -> Self {
+        Self {
+            target,
+            rasterizer,
+            masks: Vec::new(),
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    fn fill(&mut self, triangles: &[Triangle], paint: &Paint) {
+        let width = self.target.width();
+        let Some(coverage) = self
+            .rasterizer
+            .rasterize(triangles, width, self.target.height())
+        else {
+            return;
+        };
+
+        match self.mask_state {
+            MaskState::ClearMaskStencil => {}
+            MaskState::DrawMaskStencil => {
+                let last = self.masks.len() - 1;
+                let (parents, current) = self.masks.split_at_mut(last);
+                let parent = parents.last();
+                let current = &mut current[0];
+                for (x, y, amount) in coverage.iter() {
+                    let i = (y * width + x) as usize;
+                    let limit = parent.map_or(1.0, |parent| parent[i]);
+                    current[i] = current[i].max(amount * limit);
+                }
+            }
+            MaskState::NoMask | MaskState::DrawMaskedContent => {
+                let mask = if self.mask_state == MaskState::DrawMaskedContent {
+                    self.masks.last()
+                } else {
+                    None
+                };
+                let pixels = self.target.pixels_mut();
+                for (x, y, amount) in coverage.iter() {
+                    let i = (y * width + x) as usize;
+                    let amount = amount * mask.map_or(1.0, |mask| mask[i]);
+                    if amount <= 0.0 {
+                        continue;
+                    }
+                    let src = paint.color_at(x, y).map(|c| c * amount);
+                    pixels[i] = source_over(src, pixels[i]);
+                }
+            }
+        }
+    }
+
+    fn drawing_mask(&self) -> bool {
+        self.mask_state == MaskState::DrawMaskStencil
+    }
+}
+
+impl CommandHandler for Renderer<'_> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        if self.mask_state == MaskState::ClearMaskStencil {
+            return;
+        }
+
+        let entry = as_bitmap(&bitmap);
+        let texture = entry.surface.borrow();
+        let (width, height) = (texture.width() as f32, texture.height() as f32);
+
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        let matrix = Affine::from_matrix(&matrix);
+        let triangles = quad(&matrix, width, height);
+
+        if self.drawing_mask() {
+            self.fill(&triangles, &Paint::Solid([1.0; 4]));
+            return;
+        }
+
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let texture_matrix = Affine {
+            a: 1.0 / width,
+            d: 1.0 / height,
+            ..Affine::IDENTITY
+        };
+        let paint = Paint::Bitmap {
+            matrix: texture_matrix.then(&inverse),
+            texture: &texture,
+            color_transform: transform.color_transform,
+            smoothed: smoothing,
+            repeating: false,
+        };
+        self.fill(&triangles, &paint);
+    }
+
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        if self.mask_state == MaskState::ClearMaskStencil {
+            return;
+        }
+
+        let entry = as_bitmap(&bitmap);
+        let texture = entry.surface.borrow();
+        let (width, height) = (texture.width() as f64, texture.height() as f64);
+
+        // Parts of the bitmap behind the viewer would need clipping, so we skip those bitmaps.
+        let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)]
+            .map(|(x, y)| matrix.project_point(x, y));
+        let [Some(top_left), Some(top_right), Some(bottom_right), Some(bottom_left)] =
+            corners.map(|corner| corner.map(|(x, y)| (x as f32, y as f32)))
+        else {
+            return;
+        };
+        let triangles = [
+            [top_left, top_right, bottom_right],
+            [top_left, bottom_right, bottom_left],
+        ];
+
+        if self.drawing_mask() {
+            self.fill(&triangles, &Paint::Solid([1.0; 4]));
+            return;
+        }
+
+        let Some(inverse) = matrix.homography().inverse() else {
+            return;
+        };
+        let paint = Paint::ProjectedBitmap {
+            matrix: Matrix3D::scale(1.0 / width, 1.0 / height, 1.0) * inverse,
+            texture: &texture,
+            color_transform,
+            smoothed: smoothing,
+        };
+        self.fill(&triangles, &paint);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.render_bitmap(bitmap, transform, false, PixelSnapping::Never);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        if self.mask_state == MaskState::ClearMaskStencil {
+            return;
+        }
+
+        let matrix = Affine::from_matrix(&transform.matrix);
+        let drawing_mask = self.drawing_mask();
+        let mesh = as_mesh(&shape);
+        for draw in &mesh.draws {
+            // Ignore strokes when drawing a mask.
+            let indices = if drawing_mask {
+                &draw.indices[..draw.mask_index_count.min(draw.indices.len())]
+            } else {
+                &draw.indices[..]
+            };
+            if indices.is_empty() {
+                continue;
+            }
+
+            if drawing_mask {
+                let triangles = transform_triangles(&draw.vertices, indices, &matrix);
+                self.fill(&triangles, &Paint::Solid([1.0; 4]));
+                continue;
+            }
+
+            match &draw.draw_type {
+                DrawType::Color => {
+                    for (color, triangles) in color_runs(&draw.vertices, indices, &matrix) {
+                        let paint =
+                            Paint::Solid(transform_color(color, &transform.color_transform));
+                        self.fill(&triangles, &paint);
+                    }
+                }
+                DrawType::Gradient(gradient) => {
+                    let Some(inverse) = matrix.inverse() else {
+                        continue;
+                    };
+                    let paint = Paint::Gradient {
+                        matrix: Affine::from_texture_matrix(&gradient.matrix).then(&inverse),
+                        ramp: Box::new(GradientRamp::new(gradient, &transform.color_transform)),
+                        gradient_type: gradient.gradient_type,
+                        focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+                        spread: gradient.repeat_mode,
+                    };
+                    let triangles = transform_triangles(&draw.vertices, indices, &matrix);
+                    self.fill(&triangles, &paint);
+                }
+                DrawType::Bitmap {
+                    matrix: bitmap_matrix,
+                    handle,
+                    is_smoothed,
+                    is_repeating,
+                } => {
+                    let Some(handle) = handle else {
+                        tracing::warn!("Tried to render a handleless bitmap");
+                        continue;
+                    };
+                    let Some(inverse) = matrix.inverse() else {
+                        continue;
+                    };
+                    let texture = as_bitmap(handle).surface.borrow();
+                    let paint = Paint::Bitmap {
+                        matrix: Affine::from_texture_matrix(bitmap_matrix).then(&inverse),
+                        texture: &texture,
+                        color_transform: transform.color_transform,
+                        smoothed: *is_smoothed,
+                        repeating: *is_repeating,
+                    };
+                    let triangles = transform_triangles(&draw.vertices, indices, &matrix);
+                    self.fill(&triangles, &paint);
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        if self.mask_state == MaskState::ClearMaskStencil {
+            return;
+        }
+        let triangles = quad(&Affine::from_matrix(&matrix), 1.0, 1.0);
+        let color = if self.drawing_mask() {
+            [1.0; 4]
+        } else {
+            premultiply(color)
+        };
+        self.fill(&triangles, &Paint::Solid(color));
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        let len = self.target.width() as usize * self.target.height() as usize;
+        self.masks.push(vec![0.0; len]);
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::ClearMaskStencil);
+        self.masks.pop();
+        self.mask_state = if self.masks.is_empty() {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn blend(&mut self, commands: CommandList, blend: RenderBlendMode) {
+        match self.mask_state {
+            MaskState::ClearMaskStencil => return,
+            // Blend modes have no effect on the shape of a mask.
+            MaskState::DrawMaskStencil => {
+                commands.execute(self);
+                return;
+            }
+            MaskState::NoMask | MaskState::DrawMaskedContent => {}
+        }
+
+        let blend_mode = match blend {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend mode is not supported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        let mut layer = Surface::new(self.target.width(), self.target.height());
+        commands.execute(&mut Renderer::new(&mut layer, self.rasterizer));
+
+        let mask = if self.mask_state == MaskState::DrawMaskedContent {
+            self.masks.last()
+        } else {
+            None
+        };
+        for (i, (dst, src)) in self
+            .target
+            .pixels_mut()
+            .iter_mut()
+            .zip(layer.pixels())
+            .enumerate()
+        {
+            let amount = mask.map_or(1.0, |mask| mask[i]);
+            if amount > 0.0 {
+                *dst = crate::blend::blend(blend_mode, src.map(|c| c * amount), *dst);
+            }
+        }
+    }
+}
+
+/// Returns the two triangles covering the rectangle `(0, 0, width, height)` after transformation.
+fn quad(matrix: &Affine, width: f32, height: f32) -> [Triangle; 2] {
+    let top_left = matrix.apply(0.0, 0.0);
+    let top_right = matrix.apply(width, 0.0);
+    let bottom_right = matrix.apply(width, height);
+    let bottom_left = matrix.apply(0.0, height);
+    [
+        [top_left, top_right, bottom_right],
+        [top_left, bottom_right, bottom_left],
+    ]
+}
+
+fn transform_triangle(vertices: &[TessVertex], triangle: &[u32], matrix: &Affine) -> Triangle {
+    let point = |i: u32| {
+        let vertex = &vertices[i as usize];
+        matrix.apply(vertex.x, vertex.y)
+    };
+    [point(triangle[0]), point(triangle[1]), point(triangle[2])]
+}
+
+fn transform_triangles(vertices: &[TessVertex], indices: &[u32], matrix: &Affine) -> Vec<Triangle> {
+    indices
+        .chunks_exact(3)
+        .map(|triangle| transform_triangle(vertices, triangle, matrix))
+        .collect()
+}
+
+/// Solid color fills are batched into a single draw by the tessellator.
+/// Splits them back up into runs of triangles sharing the same color.
+fn color_runs(
+    vertices: &[TessVertex],
+    indices: &[u32],
+    matrix: &Affine,
+) -> Vec<(Color, Vec<Triangle>)> {
+    let mut runs: Vec<(Color, Vec<Triangle>)> = Vec::new();
+    for triangle in indices.chunks_exact(3) {
+        let color = vertices[triangle[0] as usize].color;
+        let triangle = transform_triangle(vertices, triangle, matrix);
+        match runs.last_mut() {
+            Some((run_color, triangles)) if *run_color == color => triangles.push(triangle),
+            _ => runs.push((color, vec![triangle])),
+        }
+    }
+    runs
+}
+
+#[derive(Debug)]
+struct SoftwareBitmap {
+    surface: RefCell<Surface>,
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software bitmap")
+}
+
+#[derive(Debug)]
+struct Mesh {
+    draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a software Mesh")
+}
+
+#[derive(Debug)]
+struct Draw {
+    draw_type: DrawType,
+    vertices: Vec<TessVertex>,
+    indices: Vec<u32>,
+    mask_index_count: usize,
+}
+
+#[derive(Debug)]
+enum DrawType {
+    Color,
+    Gradient(TessGradient),
+    Bitmap {
+        matrix: [[f32; 3]; 3],
+        handle: Option<BitmapHandle>,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+struct SoftwareSyncHandle {
+    rgba: Vec<u8>,
+    row_width: u32,
+}
+
+impl Debug for SoftwareSyncHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftwareSyncHandle")
+            .field("row_width", &self.row_width)
+            .finish()
+    }
+}
+
+impl SyncHandle for SoftwareSyncHandle {
+    fn retrieve_offscreen_texture(self: Box<Self>, with_rgba: RgbaBufRead) -> Result<(), Error> {
+        with_rgba(&self.rgba, self.row_width);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::surface::Rgba;
+use swf::BlendMode;
+
+/// Composites `src` over `dst` using normal (source-over) blending.
+#[inline]
+pub fn source_over(src: Rgba, dst: Rgba) -> Rgba {
+    let inv = 1.0 - src[3];
+    [
+        src[0] + dst[0] * inv,
+        src[1] + dst[1] * inv,
+        src[2] + dst[2] * inv,
+        src[3] + dst[3] * inv,
+    ]
+}
+
+/// Composites a layer pixel (`src`) onto its parent (`dst`) with the given blend mode.
+///
+/// These match the formulas used by the wgpu blend shaders, including Flash's special cases.
+pub fn blend(mode: BlendMode, src: Rgba, dst: Rgba) -> Rgba {
+    if src[3] <= 0.0 {
+        return dst;
+    }
+    let [sr, sg, sb, sa] = src;
+    let [dr, dg, db, da] = dst;
+    let over_alpha = sa + da * (1.0 - sa);
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => source_over(src, dst),
+        BlendMode::Add => [
+            (sr + dr).min(1.0),
+            (sg + dg).min(1.0),
+            (sb + db).min(1.0),
+            over_alpha,
+        ],
+        BlendMode::Subtract => [
+            (dr - sr).max(0.0),
+            (dg - sg).max(0.0),
+            (db - sb).max(0.0),
+            over_alpha,
+        ],
+        BlendMode::Screen => [
+            sr + dr * (1.0 - sr),
+            sg + dg * (1.0 - sg),
+            sb + db * (1.0 - sb),
+            over_alpha,
+        ],
+        BlendMode::Alpha => [dr * sa, dg * sa, db * sa, sa * da],
+        BlendMode::Erase => [
+            dr * (1.0 - sa),
+            dg * (1.0 - sa),
+            db * (1.0 - sa),
+            da * (1.0 - sa),
+        ],
+        BlendMode::Multiply if da <= 0.0 => src,
+        BlendMode::Multiply => separable(src, dst, |s, d| s * d),
+        BlendMode::Lighten => separable(src, dst, f32::max),
+        BlendMode::Darken => separable(src, dst, f32::min),
+        BlendMode::Difference => separable(src, dst, |s, d| (d - s).abs()),
+        BlendMode::Invert => separable(src, dst, |_, d| 1.0 - d),
+        BlendMode::Overlay => separable(src, dst, |s, d| hard_light(d, s)),
+        BlendMode::HardLight => separable(src, dst, hard_light),
+    }
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        2.0 * s * d
+    } else {
+        1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+    }
+}
+
+/// Applies a separable blend function on straight colors, then composites the result.
+fn separable(src: Rgba, dst: Rgba, f: impl Fn(f32, f32) -> f32) -> Rgba {
+    let sa = src[3];
+    let da = dst[3];
+    let mut out = [0.0, 0.0, 0.0, sa + da * (1.0 - sa)];
+    for (i, channel) in out.iter_mut().take(3).enumerate() {
+        let mixed = if da > 0.0 {
+            sa * da * f(src[i] / sa, dst[i] / da)
+        } else {
+            0.0
+        };
+        *channel = src[i] * (1.0 - da) + dst[i] * (1.0 - sa) + mixed;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_is_source_over() {
+        let src = [0.25, 0.0, 0.0, 0.5];
+        let dst = [0.0, 0.0, 1.0, 1.0];
+        assert_eq!(blend(BlendMode::Normal, src, dst), [0.25, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn transparent_source_keeps_destination() {
+        let dst = [0.1, 0.2, 0.3, 0.4];
+        assert_eq!(blend(BlendMode::Difference, [0.0; 4], dst), dst);
+        assert_eq!(blend(BlendMode::Erase, [0.0; 4], dst), dst);
+    }
+
+    #[test]
+    fn multiply_onto_transparent_replaces() {
+        let src = [0.5, 0.5, 0.5, 0.5];
+        assert_eq!(blend(BlendMode::Multiply, src, [0.0; 4]), src);
+    }
+
+    #[test]
+    fn erase_and_alpha() {
+        let src = [1.0, 1.0, 1.0, 1.0];
+        let dst = [0.5, 0.5, 0.5, 1.0];
+        assert_eq!(blend(BlendMode::Erase, src, dst), [0.0; 4]);
+        assert_eq!(blend(BlendMode::Alpha, src, dst), dst);
+    }
+}
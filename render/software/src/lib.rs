@@ -0,0 +1,11 @@
+#![deny(clippy::unwrap_used)]
+// Bitmap handles need interior mutability to be updated through a shared `BitmapHandle`.
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod blend;
+mod paint;
+mod raster;
+pub mod surface;
+
+pub use backend::SoftwareRenderBackend;
@@ -0,0 +1,233 @@
+use crate::raster::Affine;
+use crate::surface::{Rgba, Surface, TRANSPARENT};
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::Gradient;
+use swf::{Color, ColorTransform, GradientInterpolation, GradientSpread};
+
+/// Applies a color transform to a straight-alpha color, returning a premultiplied color.
+pub fn transform_color(color: Color, color_transform: &ColorTransform) -> Rgba {
+    let color = [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ];
+    premultiply_normalized(apply_color_transform(color, color_transform))
+}
+
+/// Applies a color transform to a premultiplied color, like the bitmap shaders do.
+pub fn transform_premultiplied(color: Rgba, color_transform: &ColorTransform) -> Rgba {
+    if color[3] <= 0.0 || *color_transform == ColorTransform::IDENTITY {
+        return color;
+    }
+    let a = color[3];
+    let straight = [color[0] / a, color[1] / a, color[2] / a, a];
+    premultiply_normalized(apply_color_transform(straight, color_transform))
+}
+
+fn apply_color_transform(color: Rgba, color_transform: &ColorTransform) -> Rgba {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    std::array::from_fn(|i| (color[i] * mult[i] + add[i]).clamp(0.0, 1.0))
+}
+
+fn premultiply_normalized(color: Rgba) -> Rgba {
+    let a = color[3];
+    [color[0] * a, color[1] * a, color[2] * a, a]
+}
+
+/// The source of color for a single rasterized draw.
+pub enum Paint<'a> {
+    /// A solid, premultiplied color.
+    Solid(Rgba),
+
+    Gradient {
+        /// Maps target pixels to gradient space.
+        matrix: Affine,
+        ramp: Box<GradientRamp>,
+        gradient_type: GradientType,
+        focal_point: f32,
+        spread: GradientSpread,
+    },
+
+    Bitmap {
+        /// Maps target pixels to normalized texture coordinates.
+        matrix: Affine,
+        texture: &'a Surface,
+        color_transform: ColorTransform,
+        smoothed: bool,
+        repeating: bool,
+    },
+
+    ProjectedBitmap {
+        /// Maps target pixels to normalized texture coordinates, with a projection.
+        matrix: Matrix3D,
+        texture: &'a Surface,
+        color_transform: ColorTransform,
+        smoothed: bool,
+    },
+}
+
+impl Paint<'_> {
+    /// Returns the premultiplied color of this paint at the center of the given target pixel.
+    pub fn color_at(&self, x: u32, y: u32) -> Rgba {
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Gradient {
+                matrix,
+                ramp,
+                gradient_type,
+                focal_point,
+                spread,
+            } => {
+                let (u, v) = matrix.apply(px, py);
+                let t = match gradient_type {
+                    GradientType::Linear => u,
+                    GradientType::Radial => {
+                        let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                        (u * u + v * v).sqrt()
+                    }
+                    GradientType::Focal => {
+                        let (u, v) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                        let (dx, dy) = (focal_point - u, -v);
+                        let l = (dx * dx + dy * dy).sqrt();
+                        if l == 0.0 {
+                            0.0
+                        } else {
+                            let (dx, dy) = (dx / l, dy / l);
+                            l / ((1.0 - focal_point * focal_point * dy * dy).sqrt()
+                                + focal_point * dx)
+                        }
+                    }
+                };
+                ramp.color_at(apply_spread(t, *spread))
+            }
+            Paint::Bitmap {
+                matrix,
+                texture,
+                color_transform,
+                smoothed,
+                repeating,
+            } => {
+                // Non-repeating bitmap fills extend their edge pixels over the rest of the shape.
+                let (u, v) = matrix.apply(px, py);
+                transform_premultiplied(
+                    texture.sample(u, v, *smoothed, *repeating),
+                    color_transform,
+                )
+            }
+            Paint::ProjectedBitmap {
+                matrix,
+                texture,
+                color_transform,
+                smoothed,
+            } => match matrix.project_point(px.into(), py.into()) {
+                Some((u, v)) => transform_premultiplied(
+                    texture.sample(u as f32, v as f32, *smoothed, false),
+                    color_transform,
+                ),
+                None => TRANSPARENT,
+            },
+        }
+    }
+}
+
+fn apply_spread(t: f32, spread: GradientSpread) -> f32 {
+    if !t.is_finite() {
+        return 1.0;
+    }
+    match spread {
+        GradientSpread::Pad => t.clamp(0.0, 1.0),
+        GradientSpread::Repeat => t - t.floor(),
+        GradientSpread::Reflect => {
+            let t = t.abs();
+            let fract = t - t.floor();
+            if (t.floor() as i64) % 2 == 0 {
+                fract
+            } else {
+                1.0 - fract
+            }
+        }
+    }
+}
+
+/// A precomputed lookup table of the colors along a gradient, with the color transform baked in.
+pub struct GradientRamp {
+    colors: [Rgba; 256],
+}
+
+impl GradientRamp {
+    pub fn new(gradient: &Gradient, color_transform: &ColorTransform) -> Self {
+        let linear = gradient.interpolation == GradientInterpolation::LinearRgb;
+        let stops: Vec<(f32, Rgba)> = gradient
+            .records
+            .iter()
+            .map(|record| {
+                let mut color = [
+                    f32::from(record.color.r) / 255.0,
+                    f32::from(record.color.g) / 255.0,
+                    f32::from(record.color.b) / 255.0,
+                    f32::from(record.color.a) / 255.0,
+                ];
+                if linear {
+                    for c in &mut color[..3] {
+                        *c = srgb_to_linear(*c);
+                    }
+                }
+                (
+                    f32::from(record.ratio) / 255.0,
+                    apply_color_transform(color, color_transform),
+                )
+            })
+            .collect();
+
+        let mut colors = [TRANSPARENT; 256];
+        for (i, out) in colors.iter_mut().enumerate() {
+            let t = i as f32 / 255.0;
+            let mut color = match stops.iter().position(|(ratio, _)| t <= *ratio) {
+                None => stops.last().map(|(_, color)| *color).unwrap_or(TRANSPARENT),
+                Some(0) => stops[0].1,
+                Some(next) => {
+                    let (ratio1, color1) = stops[next - 1];
+                    let (ratio2, color2) = stops[next];
+                    let a = if ratio2 > ratio1 {
+                        (t - ratio1) / (ratio2 - ratio1)
+                    } else {
+                        0.0
+                    };
+                    std::array::from_fn(|c| color1[c] + (color2[c] - color1[c]) * a)
+                }
+            };
+            if linear {
+                for c in &mut color[..3] {
+                    *c = linear_to_srgb(*c);
+                }
+            }
+            *out = premultiply_normalized(color);
+        }
+
+        Self { colors }
+    }
+
+    fn color_at(&self, t: f32) -> Rgba {
+        self.colors[(t * 255.0).round().clamp(0.0, 255.0) as usize]
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c < 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,284 @@
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+
+/// A 2D affine transform in pixel space, mirroring `ruffle_render::matrix::Matrix`
+/// without rounding the translation to twips.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Affine {
+    pub const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    pub fn from_matrix(matrix: &Matrix) -> Self {
+        Self {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels() as f32,
+            ty: matrix.ty.to_pixels() as f32,
+        }
+    }
+
+    /// Builds a transform from a column-major 3x3 texture matrix, as produced by the tessellator.
+    pub fn from_texture_matrix(matrix: &[[f32; 3]; 3]) -> Self {
+        Self {
+            a: matrix[0][0],
+            b: matrix[0][1],
+            c: matrix[1][0],
+            d: matrix[1][1],
+            tx: matrix[2][0],
+            ty: matrix[2][1],
+        }
+    }
+
+    #[inline]
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    /// Returns `self * other`, i.e. `other` is applied first.
+    pub fn then(&self, other: &Affine) -> Affine {
+        Affine {
+            a: other.a * self.a + other.b * self.c,
+            b: other.a * self.b + other.b * self.d,
+            c: other.c * self.a + other.d * self.c,
+            d: other.c * self.b + other.d * self.d,
+            tx: self.a * other.tx + self.c * other.ty + self.tx,
+            ty: self.b * other.tx + self.d * other.ty + self.ty,
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Affine> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let a = self.d / det;
+        let b = -self.b / det;
+        let c = -self.c / det;
+        let d = self.a / det;
+        Some(Affine {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        })
+    }
+}
+
+pub type Triangle = [(f32, f32); 3];
+
+/// The sampled coverage of a set of triangles over a rectangle of the target.
+///
+/// Each pixel stores a bitmask of which of its sub-samples are covered, so that
+/// triangles sharing an edge never count the same sample twice.
+pub struct Coverage {
+    pub x_min: u32,
+    pub y_min: u32,
+    pub width: u32,
+    pub height: u32,
+    sample_count: u32,
+    masks: Vec<u16>,
+}
+
+impl Coverage {
+    /// Iterates over every pixel with non-zero coverage, yielding `(x, y, coverage)`.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32, f32)> + '_ {
+        let sample_count = self.sample_count as f32;
+        self.masks
+            .iter()
+            .enumerate()
+            .filter(|(_, mask)| **mask != 0)
+            .map(move |(i, mask)| {
+                let i = i as u32;
+                (
+                    self.x_min + i % self.width,
+                    self.y_min + i / self.width,
+                    mask.count_ones() as f32 / sample_count,
+                )
+            })
+    }
+}
+
+/// Converts triangles into per-pixel coverage, using a regular grid of samples per pixel.
+pub struct Rasterizer {
+    offsets: Vec<(f32, f32)>,
+}
+
+impl Rasterizer {
+    pub fn new(quality: StageQuality) -> Self {
+        let grid = match quality.sample_count() {
+            0 | 1 => 1,
+            2 | 4 => 2,
+            _ => 4,
+        };
+        let step = 1.0 / grid as f32;
+        let mut offsets = Vec::with_capacity(grid * grid);
+        for y in 0..grid {
+            for x in 0..grid {
+                offsets.push(((x as f32 + 0.5) * step, (y as f32 + 0.5) * step));
+            }
+        }
+        Self { offsets }
+    }
+
+    /// Rasterizes the given triangles (in target pixel space), clipped to a `width` x `height` target.
+    ///
+    /// Returns `None` if nothing is visible.
+    pub fn rasterize(&self, triangles: &[Triangle], width: u32, height: u32) -> Option<Coverage> {
+        let (mut x_min, mut y_min) = (f32::INFINITY, f32::INFINITY);
+        let (mut x_max, mut y_max) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for (x, y) in triangles.iter().flatten() {
+            x_min = x_min.min(*x);
+            y_min = y_min.min(*y);
+            x_max = x_max.max(*x);
+            y_max = y_max.max(*y);
+        }
+        if !(x_min.is_finite() && y_min.is_finite() && x_max.is_finite() && y_max.is_finite()) {
+            return None;
+        }
+
+        let x_min = x_min.floor().clamp(0.0, width as f32) as u32;
+        let y_min = y_min.floor().clamp(0.0, height as f32) as u32;
+        let x_max = x_max.ceil().clamp(0.0, width as f32) as u32;
+        let y_max = y_max.ceil().clamp(0.0, height as f32) as u32;
+        if x_min >= x_max || y_min >= y_max {
+            return None;
+        }
+
+        let mut coverage = Coverage {
+            x_min,
+            y_min,
+            width: x_max - x_min,
+            height: y_max - y_min,
+            sample_count: self.offsets.len() as u32,
+            masks: vec![0; (x_max - x_min) as usize * (y_max - y_min) as usize],
+        };
+
+        for triangle in triangles {
+            self.rasterize_triangle(triangle, &mut coverage);
+        }
+
+        Some(coverage)
+    }
+
+    fn rasterize_triangle(&self, triangle: &Triangle, coverage: &mut Coverage) {
+        let [p0, p1, p2] = *triangle;
+        let area = edge(p0, p1, p2);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+
+        let tri_x_min = p0.0.min(p1.0).min(p2.0).floor().max(coverage.x_min as f32) as u32;
+        let tri_y_min = p0.1.min(p1.1).min(p2.1).floor().max(coverage.y_min as f32) as u32;
+        let tri_x_max =
+            (p0.0.max(p1.0).max(p2.0).ceil() as u32).min(coverage.x_min + coverage.width);
+        let tri_y_max =
+            (p0.1.max(p1.1).max(p2.1).ceil() as u32).min(coverage.y_min + coverage.height);
+
+        // Normalize the winding so that "inside" always means non-negative edge functions.
+        let (p1, p2) = if area < 0.0 { (p2, p1) } else { (p1, p2) };
+
+        for y in tri_y_min..tri_y_max {
+            let row = ((y - coverage.y_min) * coverage.width) as usize;
+            for x in tri_x_min..tri_x_max {
+                let mut mask = 0u16;
+                for (i, (ox, oy)) in self.offsets.iter().enumerate() {
+                    let p = (x as f32 + ox, y as f32 + oy);
+                    if edge(p0, p1, p) >= 0.0 && edge(p1, p2, p) >= 0.0 && edge(p2, p0, p) >= 0.0 {
+                        mask |= 1 << i;
+                    }
+                }
+                coverage.masks[row + (x - coverage.x_min) as usize] |= mask;
+            }
+        }
+    }
+}
+
+#[inline]
+fn edge(a: (f32, f32), b: (f32, f32), p: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_coverage(coverage: &Coverage) -> f32 {
+        coverage.iter().map(|(_, _, c)| c).sum()
+    }
+
+    #[test]
+    fn shared_edges_are_not_double_counted() {
+        let rasterizer = Rasterizer::new(StageQuality::High16x16);
+        let triangles = [
+            [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)],
+            [(0.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+        ];
+        let coverage = rasterizer
+            .rasterize(&triangles, 20, 20)
+            .expect("Triangles should be visible");
+        assert_eq!(coverage.width, 10);
+        assert_eq!(coverage.height, 10);
+        assert_eq!(total_coverage(&coverage), 100.0);
+        assert!(coverage.iter().all(|(_, _, c)| c == 1.0));
+    }
+
+    #[test]
+    fn partial_pixels_are_antialiased() {
+        let rasterizer = Rasterizer::new(StageQuality::High);
+        let triangles = [
+            [(0.0, 0.0), (1.5, 0.0), (1.5, 1.0)],
+            [(0.0, 0.0), (1.5, 1.0), (0.0, 1.0)],
+        ];
+        let coverage = rasterizer
+            .rasterize(&triangles, 4, 4)
+            .expect("Triangles should be visible");
+        let pixels: Vec<_> = coverage.iter().collect();
+        assert_eq!(pixels, vec![(0, 0, 1.0), (1, 0, 0.5)]);
+    }
+
+    #[test]
+    fn offscreen_triangles_are_clipped() {
+        let rasterizer = Rasterizer::new(StageQuality::Low);
+        let triangles = [[(-10.0, -10.0), (-5.0, -10.0), (-5.0, -5.0)]];
+        assert!(rasterizer.rasterize(&triangles, 4, 4).is_none());
+    }
+
+    #[test]
+    fn affine_inverse_round_trips() {
+        let matrix = Affine {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: 10.0,
+            ty: -4.0,
+        };
+        let inverse = matrix.inverse().expect("Matrix should be invertible");
+        let (x, y) = matrix.apply(3.0, 7.0);
+        let (x, y) = inverse.apply(x, y);
+        assert!((x - 3.0).abs() < 1e-4);
+        assert!((y - 7.0).abs() < 1e-4);
+    }
+}
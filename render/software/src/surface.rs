@@ -0,0 +1,182 @@
+use ruffle_render::bitmap::{Bitmap, PixelRegion};
+use std::fmt::{self, Debug, Formatter};
+use swf::Color;
+
+/// A premultiplied RGBA color, with each component normalized to [0.0, 1.0].
+pub type Rgba = [f32; 4];
+
+pub const TRANSPARENT: Rgba = [0.0, 0.0, 0.0, 0.0];
+
+/// Converts a straight-alpha color into a premultiplied `Rgba`.
+pub fn premultiply(color: Color) -> Rgba {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
+
+/// A CPU-side image that all drawing happens on.
+///
+/// Pixels are stored with premultiplied alpha, the same way the GPU backends store their textures.
+#[derive(Clone)]
+pub struct Surface {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgba>,
+}
+
+impl Surface {
+    /// Creates a new, fully transparent surface.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![TRANSPARENT; width as usize * height as usize],
+        }
+    }
+
+    /// Creates a surface from a bitmap. The bitmap is expected to use premultiplied alpha.
+    pub fn from_bitmap(bitmap: Bitmap) -> Self {
+        let bitmap = bitmap.to_rgba();
+        let pixels = bitmap
+            .data()
+            .chunks_exact(4)
+            .map(|rgba| {
+                [
+                    f32::from(rgba[0]) / 255.0,
+                    f32::from(rgba[1]) / 255.0,
+                    f32::from(rgba[2]) / 255.0,
+                    f32::from(rgba[3]) / 255.0,
+                ]
+            })
+            .collect();
+        Self {
+            width: bitmap.width(),
+            height: bitmap.height(),
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[Rgba] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [Rgba] {
+        &mut self.pixels
+    }
+
+    /// Fills the entire surface with the given straight-alpha color.
+    pub fn clear(&mut self, color: Color) {
+        self.pixels.fill(premultiply(color));
+    }
+
+    /// Copies the given region of a (full size) bitmap into this surface.
+    pub fn update_region(&mut self, bitmap: Bitmap, mut region: PixelRegion) {
+        let source = Surface::from_bitmap(bitmap);
+        region.clamp(self.width.min(source.width), self.height.min(source.height));
+        for y in region.y_min..region.y_max {
+            let start = (y * self.width + region.x_min) as usize;
+            let source_start = (y * source.width + region.x_min) as usize;
+            let len = region.width() as usize;
+            self.pixels[start..start + len]
+                .copy_from_slice(&source.pixels[source_start..source_start + len]);
+        }
+    }
+
+    /// Copies the given region of another surface of the same size into this surface.
+    pub fn copy_region_from(&mut self, source: &Surface, mut region: PixelRegion) {
+        region.clamp(self.width.min(source.width), self.height.min(source.height));
+        for y in region.y_min..region.y_max {
+            let start = (y * self.width + region.x_min) as usize;
+            let source_start = (y * source.width + region.x_min) as usize;
+            let len = region.width() as usize;
+            self.pixels[start..start + len]
+                .copy_from_slice(&source.pixels[source_start..source_start + len]);
+        }
+    }
+
+    /// Returns the premultiplied RGBA8 bytes of the given region, tightly packed.
+    pub fn region_to_rgba8(&self, mut region: PixelRegion) -> Vec<u8> {
+        region.clamp(self.width, self.height);
+        let mut bytes = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+        for y in region.y_min..region.y_max {
+            let start = (y * self.width + region.x_min) as usize;
+            for pixel in &self.pixels[start..start + region.width() as usize] {
+                bytes.extend(pixel.iter().map(|c| to_u8(*c)));
+            }
+        }
+        bytes
+    }
+
+    /// Returns the premultiplied RGBA8 bytes of the entire surface.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.region_to_rgba8(PixelRegion::for_whole_size(self.width, self.height))
+    }
+
+    fn fetch(&self, x: i32, y: i32, repeating: bool) -> Rgba {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let (x, y) = if repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        self.pixels[(y * width + x) as usize]
+    }
+
+    /// Samples this surface at the given normalized texture coordinates.
+    pub fn sample(&self, u: f32, v: f32, smoothed: bool, repeating: bool) -> Rgba {
+        if self.width == 0 || self.height == 0 || !u.is_finite() || !v.is_finite() {
+            return TRANSPARENT;
+        }
+
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !smoothed {
+            return self.fetch(x.floor() as i32, y.floor() as i32, repeating);
+        }
+
+        // Bilinear filtering around the texel centers.
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let (x0, y0) = (x0 as i32, y0 as i32);
+
+        let top_left = self.fetch(x0, y0, repeating);
+        let top_right = self.fetch(x0 + 1, y0, repeating);
+        let bottom_left = self.fetch(x0, y0 + 1, repeating);
+        let bottom_right = self.fetch(x0 + 1, y0 + 1, repeating);
+
+        std::array::from_fn(|i| {
+            let top = top_left[i] + (top_right[i] - top_left[i]) * fx;
+            let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * fx;
+            top + (bottom - top) * fy
+        })
+    }
+}
+
+impl Debug for Surface {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Surface")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
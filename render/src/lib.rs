@@ -6,10 +6,12 @@ pub mod blend;
 pub mod error;
 pub mod filters;
 pub mod matrix;
+pub mod matrix3d;
 pub mod pixel_bender;
 // The `renderdoc` crate doesn't compile on apple platforms
 #[cfg(all(feature = "renderdoc", not(target_vendor = "apple")))]
 pub mod renderdoc;
+pub mod scaling_grid;
 pub mod shader_source;
 pub mod shape_utils;
 pub mod transform;
@@ -0,0 +1,357 @@
+use crate::matrix::Matrix;
+use swf::Twips;
+
+/// A 4x4 transformation matrix, as used by 3D display object transforms and
+/// `flash.geom.Matrix3D`.
+///
+/// Unlike [`Matrix`], translations are in pixels, and values are stored in
+/// column-major order, matching `Matrix3D.rawData`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    /// The value in the given row and column.
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    #[inline]
+    fn set(&mut self, row: usize, column: usize, value: f64) {
+        self.raw_data[column * 4 + row] = value;
+    }
+
+    pub fn translate(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 3, x);
+        matrix.set(1, 3, y);
+        matrix.set(2, 3, z);
+        matrix
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, x);
+        matrix.set(1, 1, y);
+        matrix.set(2, 2, z);
+        matrix
+    }
+
+    /// A rotation around the X axis, by an angle in radians.
+    pub fn rotate_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(1, 1, cos);
+        matrix.set(1, 2, -sin);
+        matrix.set(2, 1, sin);
+        matrix.set(2, 2, cos);
+        matrix
+    }
+
+    /// A rotation around the Y axis, by an angle in radians.
+    pub fn rotate_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, cos);
+        matrix.set(0, 2, sin);
+        matrix.set(2, 0, -sin);
+        matrix.set(2, 2, cos);
+        matrix
+    }
+
+    /// A rotation around the Z axis, by an angle in radians.
+    pub fn rotate_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, cos);
+        matrix.set(0, 1, -sin);
+        matrix.set(1, 0, sin);
+        matrix.set(1, 1, cos);
+        matrix
+    }
+
+    /// A perspective projection onto the `z = 0` plane, as seen by a viewer
+    /// `focal_length` pixels in front of the point `(center_x, center_y)`.
+    ///
+    /// Points on the plane are left in place, and points behind it (with a
+    /// positive `z`) move towards the center.
+    pub fn perspective(focal_length: f64, center_x: f64, center_y: f64) -> Self {
+        let mut projection = Self::IDENTITY;
+        projection.set(3, 2, 1.0 / focal_length);
+        Self::translate(center_x, center_y, 0.0)
+            * projection
+            * Self::translate(-center_x, -center_y, 0.0)
+    }
+
+    /// Builds a matrix from its components, which are applied in the order
+    /// scale, rotation around X, Y and Z, then translation.
+    ///
+    /// Angles are in radians.
+    pub fn recompose(translation: [f64; 3], rotation: [f64; 3], scale: [f64; 3]) -> Self {
+        Self::translate(translation[0], translation[1], translation[2])
+            * Self::rotate_z(rotation[2])
+            * Self::rotate_y(rotation[1])
+            * Self::rotate_x(rotation[0])
+            * Self::scale(scale[0], scale[1], scale[2])
+    }
+
+    /// Splits an affine matrix into the translation, rotation and scale
+    /// passed to [`Matrix3D::recompose`].
+    ///
+    /// Returns `None` if the matrix has a projection, or a zero scale.
+    pub fn decompose(&self) -> Option<([f64; 3], [f64; 3], [f64; 3])> {
+        if self.get(3, 0) != 0.0
+            || self.get(3, 1) != 0.0
+            || self.get(3, 2) != 0.0
+            || self.get(3, 3) != 1.0
+        {
+            return None;
+        }
+
+        let translation = [self.get(0, 3), self.get(1, 3), self.get(2, 3)];
+        let mut columns = [0, 1, 2].map(|column| [0, 1, 2].map(|row| self.get(row, column)));
+        let mut scale = columns.map(|column| column.iter().map(|v| v * v).sum::<f64>().sqrt());
+        if scale.contains(&0.0) {
+            return None;
+        }
+        for (column, scale) in columns.iter_mut().zip(scale) {
+            column.iter_mut().for_each(|v| *v /= scale);
+        }
+
+        // A mirrored matrix can't be expressed with rotations alone.
+        let [x, y, z] = columns;
+        let determinant = x[0] * (y[1] * z[2] - y[2] * z[1]) - y[0] * (x[1] * z[2] - x[2] * z[1])
+            + z[0] * (x[1] * y[2] - x[2] * y[1]);
+        if determinant < 0.0 {
+            scale[0] = -scale[0];
+            columns[0] = x.map(|v| -v);
+        }
+
+        // The rotation is `Rz * Ry * Rx`, whose bottom row is
+        // `[-sin(y), cos(y) * sin(x), cos(y) * cos(x)]`.
+        let [x, y, z] = columns;
+        let rotation_y = (-x[2]).clamp(-1.0, 1.0).asin();
+        let rotation = if rotation_y.cos() > 1e-6 {
+            [y[2].atan2(z[2]), rotation_y, x[1].atan2(x[0])]
+        } else {
+            // Gimbal lock: only the difference of the X and Z rotations matters.
+            [0.0, rotation_y, (-y[0]).atan2(y[1])]
+        };
+        Some((translation, rotation, scale))
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = |row, column| self.get(row, column);
+        let minor = |r: [usize; 3], c: [usize; 3]| {
+            m(r[0], c[0]) * (m(r[1], c[1]) * m(r[2], c[2]) - m(r[1], c[2]) * m(r[2], c[1]))
+                - m(r[0], c[1]) * (m(r[1], c[0]) * m(r[2], c[2]) - m(r[1], c[2]) * m(r[2], c[0]))
+                + m(r[0], c[2]) * (m(r[1], c[0]) * m(r[2], c[1]) - m(r[1], c[1]) * m(r[2], c[0]))
+        };
+        m(0, 0) * minor([1, 2, 3], [1, 2, 3]) - m(0, 1) * minor([1, 2, 3], [0, 2, 3])
+            + m(0, 2) * minor([1, 2, 3], [0, 1, 3])
+            - m(0, 3) * minor([1, 2, 3], [0, 1, 2])
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() <= f64::EPSILON || !det.is_finite() {
+            return None;
+        }
+
+        let mut inverse = Self::IDENTITY;
+        for row in 0..4 {
+            for column in 0..4 {
+                // The inverse is the transposed matrix of cofactors, over the determinant.
+                let rows: Vec<usize> = (0..4).filter(|&r| r != column).collect();
+                let columns: Vec<usize> = (0..4).filter(|&c| c != row).collect();
+                let m = |r: usize, c: usize| self.get(rows[r], columns[c]);
+                let minor = m(0, 0) * (m(1, 1) * m(2, 2) - m(1, 2) * m(2, 1))
+                    - m(0, 1) * (m(1, 0) * m(2, 2) - m(1, 2) * m(2, 0))
+                    + m(0, 2) * (m(1, 0) * m(2, 1) - m(1, 1) * m(2, 0));
+                let sign = if (row + column) & 1 == 0 { 1.0 } else { -1.0 };
+                inverse.set(row, column, sign * minor / det);
+            }
+        }
+        Some(inverse)
+    }
+
+    /// Transforms a point, returning its homogeneous coordinates `[x, y, z, w]`.
+    pub fn transform_point(&self, x: f64, y: f64, z: f64) -> [f64; 4] {
+        [0, 1, 2, 3].map(|row| {
+            self.get(row, 0) * x + self.get(row, 1) * y + self.get(row, 2) * z + self.get(row, 3)
+        })
+    }
+
+    /// Transforms a point on the `z = 0` plane and projects it back onto that
+    /// plane, returning `None` if it ends up behind the viewer.
+    pub fn project_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let [x, y, _, w] = self.transform_point(x, y, 0.0);
+        (w > 0.0).then(|| (x / w, y / w))
+    }
+
+    /// The inverse of [`Matrix3D::project_point`]: finds the point on the
+    /// `z = 0` plane which is projected onto the given point.
+    pub fn unproject_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        self.homography()
+            .inverse()
+            .and_then(|inverse| inverse.project_point(x, y))
+    }
+
+    /// Flattens this matrix into a projective transformation of the `z = 0`
+    /// plane: the input `z` is ignored, and the output `z` is zero.
+    ///
+    /// This is what's needed to draw a flat object, and unlike the full
+    /// matrix, it's invertible for objects seen edge-on.
+    pub fn homography(&self) -> Self {
+        let mut matrix = *self;
+        for i in 0..4 {
+            matrix.set(2, i, 0.0);
+            matrix.set(i, 2, 0.0);
+        }
+        matrix.set(2, 2, 1.0);
+        matrix
+    }
+
+    /// The 2D matrix which best approximates this matrix at the origin,
+    /// for backends which can't draw projections.
+    pub fn to_affine(&self) -> Matrix {
+        let w = self.get(3, 3);
+        let (x0, y0) = (self.get(0, 3) / w, self.get(1, 3) / w);
+        let derivative = |column| {
+            let w_delta = self.get(3, column) / w;
+            (
+                (self.get(0, column) / w - x0 * w_delta) as f32,
+                (self.get(1, column) / w - y0 * w_delta) as f32,
+            )
+        };
+        let (a, b) = derivative(0);
+        let (c, d) = derivative(1);
+        Matrix {
+            a,
+            b,
+            c,
+            d,
+            tx: Twips::from_pixels(x0),
+            ty: Twips::from_pixels(y0),
+        }
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut out = Self::IDENTITY;
+        for row in 0..4 {
+            for column in 0..4 {
+                let value = (0..4).map(|i| self.get(row, i) * rhs.get(i, column)).sum();
+                out.set(row, column, value);
+            }
+        }
+        out
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Matrix> for Matrix3D {
+    fn from(matrix: Matrix) -> Self {
+        let mut out = Self::IDENTITY;
+        out.set(0, 0, matrix.a.into());
+        out.set(1, 0, matrix.b.into());
+        out.set(0, 1, matrix.c.into());
+        out.set(1, 1, matrix.d.into());
+        out.set(0, 3, matrix.tx.to_pixels());
+        out.set(1, 3, matrix.ty.to_pixels());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn assert_matrix_eq(left: Matrix3D, right: Matrix3D) {
+        for (l, r) in left.raw_data.iter().zip(right.raw_data.iter()) {
+            assert_abs_diff_eq!(l, r, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Matrix3D::recompose([10.0, -5.0, 30.0], [0.3, -1.2, 2.0], [2.0, 0.5, 1.5])
+            * Matrix3D::perspective(500.0, 250.0, 200.0);
+        let inverse = matrix.inverse().expect("matrix should be invertible");
+        assert_matrix_eq(matrix * inverse, Matrix3D::IDENTITY);
+        assert_matrix_eq(inverse * matrix, Matrix3D::IDENTITY);
+        assert_eq!(Matrix3D::scale(1.0, 0.0, 1.0).inverse(), None);
+    }
+
+    #[test]
+    fn decompose_round_trip() {
+        let components = ([10.0, -5.0, 30.0], [0.3, -1.2, 2.0], [2.0, 0.5, 1.5]);
+        let matrix = Matrix3D::recompose(components.0, components.1, components.2);
+        let (translation, rotation, scale) =
+            matrix.decompose().expect("matrix should be decomposable");
+        for (l, r) in [translation, rotation, scale]
+            .iter()
+            .flatten()
+            .zip([components.0, components.1, components.2].iter().flatten())
+        {
+            assert_abs_diff_eq!(l, r, epsilon = 1e-9);
+        }
+
+        let mirrored = Matrix3D::scale(-1.0, 1.0, 1.0) * Matrix3D::rotate_y(0.5);
+        let (translation, rotation, scale) =
+            mirrored.decompose().expect("matrix should be decomposable");
+        assert_matrix_eq(Matrix3D::recompose(translation, rotation, scale), mirrored);
+    }
+
+    #[test]
+    fn from_matrix() {
+        let matrix = Matrix::create_box(2.0, 3.0, 0.5, Twips::from_pixels(10.0), Twips::new(7));
+        let matrix_3d = Matrix3D::from(matrix);
+        let (x, y) = matrix_3d
+            .project_point(4.0, -2.0)
+            .expect("point should be in front");
+        let point = matrix * swf::Point::new(Twips::from_pixels(4.0), Twips::from_pixels(-2.0));
+        assert_abs_diff_eq!(x, point.x.to_pixels(), epsilon = 0.05);
+        assert_abs_diff_eq!(y, point.y.to_pixels(), epsilon = 0.05);
+        assert_eq!(matrix_3d.to_affine(), matrix);
+    }
+
+    #[test]
+    fn perspective() {
+        let projection = Matrix3D::perspective(500.0, 100.0, 100.0);
+        // Points on the plane don't move, and points behind it move towards the center.
+        assert_eq!(projection.project_point(300.0, 0.0), Some((300.0, 0.0)));
+        let behind = projection * Matrix3D::translate(0.0, 0.0, 500.0);
+        assert_eq!(behind.project_point(300.0, 0.0), Some((200.0, 50.0)));
+
+        let tilted = projection * Matrix3D::rotate_y(1.0);
+        let (x, y) = tilted
+            .project_point(40.0, 60.0)
+            .expect("point should be in front");
+        let (x, y) = tilted
+            .unproject_point(x, y)
+            .expect("point should be on the plane");
+        assert_abs_diff_eq!(x, 40.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(y, 60.0, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::matrix::Matrix;
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use swf::{Point, Rectangle, Twips};
+
+/// The 9-slice scaling of a display object with a `scale9Grid`.
+///
+/// The grid splits the content of the object into nine regions. When the object
+/// is scaled, the corners keep their size on screen, the edges are only stretched
+/// along their length, and the center region is stretched to fill what is left.
+///
+/// This is done by mapping the content in the local space of the object, so that
+/// it looks right once the scale of the object is applied. Points outside of the
+/// content bounds are mapped as if they were in the nearest corner or edge, and
+/// the content bounds themselves never move.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScalingGrid {
+    x: AxisMapping,
+    y: AxisMapping,
+}
+
+impl ScalingGrid {
+    /// Creates the mapping for an object with the given `scale9Grid`, whose
+    /// content has the given bounds, both in the local space of the object.
+    ///
+    /// Returns `None` if the grid would have no effect, or if the object can't
+    /// be sliced because its content or scale is empty.
+    pub fn new(
+        bounds: &Rectangle<Twips>,
+        grid: &Rectangle<Twips>,
+        scale_x: f64,
+        scale_y: f64,
+    ) -> Option<Self> {
+        if !bounds.is_valid() || !grid.is_valid() {
+            return None;
+        }
+        let grid = Self {
+            x: AxisMapping::new(
+                bounds.x_min.get().into(),
+                bounds.x_max.get().into(),
+                grid.x_min.get().into(),
+                grid.x_max.get().into(),
+                scale_x,
+            )?,
+            y: AxisMapping::new(
+                bounds.y_min.get().into(),
+                bounds.y_max.get().into(),
+                grid.y_min.get().into(),
+                grid.y_max.get().into(),
+                scale_y,
+            )?,
+        };
+        (!grid.x.is_identity() || !grid.y.is_identity()).then_some(grid)
+    }
+
+    /// Maps a point from the content to where it is drawn.
+    pub fn map_point(&self, point: Point<Twips>) -> Point<Twips> {
+        Point::new(
+            Twips::new(self.x.map(point.x.get().into()).round() as i32),
+            Twips::new(self.y.map(point.y.get().into()).round() as i32),
+        )
+    }
+
+    /// Maps a point from where it is drawn back to the content,
+    /// for hit-testing the content as it was defined.
+    pub fn unmap_point(&self, point: Point<Twips>) -> Point<Twips> {
+        Point::new(
+            Twips::new(self.x.unmap(point.x.get().into()).round() as i32),
+            Twips::new(self.y.unmap(point.y.get().into()).round() as i32),
+        )
+    }
+
+    /// Maps a rectangle from the content to where it is drawn.
+    fn map_rectangle(&self, rectangle: &Rectangle<Twips>) -> Rectangle<Twips> {
+        if !rectangle.is_valid() {
+            return rectangle.clone();
+        }
+        // Both axes are mapped by increasing functions, so the corners stay the corners.
+        let min = self.map_point(Point::new(rectangle.x_min, rectangle.y_min));
+        let max = self.map_point(Point::new(rectangle.x_max, rectangle.y_max));
+        Rectangle {
+            x_min: min.x,
+            x_max: max.x,
+            y_min: min.y,
+            y_max: max.y,
+        }
+    }
+
+    /// Slices a shape, which is first transformed into the local space of the
+    /// object by `matrix`. The resulting shape must be drawn without that matrix.
+    ///
+    /// Only the anchors and control points of the shape are mapped, so any edge
+    /// crossing the grid lines is stretched as a whole, like in Flash Player.
+    pub fn map_shape(&self, shape: &mut DistilledShape<'_>, matrix: &Matrix) {
+        for path in &mut shape.paths {
+            let commands = match path {
+                DrawPath::Stroke { commands, .. } => commands,
+                DrawPath::Fill { commands, .. } => commands,
+            };
+            for command in commands {
+                match command {
+                    DrawCommand::MoveTo(point) | DrawCommand::LineTo(point) => {
+                        *point = self.map_point(*matrix * *point);
+                    }
+                    DrawCommand::QuadraticCurveTo { control, anchor } => {
+                        *control = self.map_point(*matrix * *control);
+                        *anchor = self.map_point(*matrix * *anchor);
+                    }
+                    DrawCommand::CubicCurveTo {
+                        control_a,
+                        control_b,
+                        anchor,
+                    } => {
+                        *control_a = self.map_point(*matrix * *control_a);
+                        *control_b = self.map_point(*matrix * *control_b);
+                        *anchor = self.map_point(*matrix * *anchor);
+                    }
+                }
+            }
+        }
+        shape.shape_bounds = self.map_rectangle(&(*matrix * shape.shape_bounds.clone()));
+        shape.edge_bounds = self.map_rectangle(&(*matrix * shape.edge_bounds.clone()));
+    }
+}
+
+/// The mapping of a `ScalingGrid` along one axis, in twips.
+///
+/// The content spans `min..max`, and its center region spans `grid_min..grid_max`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct AxisMapping {
+    min: f64,
+    grid_min: f64,
+    grid_max: f64,
+
+    /// How much the regions before and after the center region are scaled.
+    outer_scale: f64,
+
+    /// How much the center region is scaled.
+    center_scale: f64,
+}
+
+impl AxisMapping {
+    fn new(min: f64, max: f64, grid_min: f64, grid_max: f64, scale: f64) -> Option<Self> {
+        let scale = scale.abs();
+        let size = max - min;
+        if !scale.is_finite() || scale == 0.0 || size <= 0.0 {
+            return None;
+        }
+
+        let grid_min = grid_min.clamp(min, max);
+        let grid_max = grid_max.clamp(grid_min, max);
+        let outer_size = (grid_min - min) + (max - grid_max);
+        let center_size = grid_max - grid_min;
+
+        // The outer regions keep their size on screen, unless the object is too
+        // small to fit them, in which case they fill it and the center vanishes.
+        let outer_scale = if outer_size > size * scale {
+            size / outer_size
+        } else {
+            1.0 / scale
+        };
+        let center_scale = if center_size > 0.0 {
+            (size - outer_size * outer_scale) / center_size
+        } else {
+            1.0
+        };
+
+        Some(Self {
+            min,
+            grid_min,
+            grid_max,
+            outer_scale,
+            center_scale,
+        })
+    }
+
+    fn is_identity(&self) -> bool {
+        self.outer_scale == 1.0 && self.center_scale == 1.0
+    }
+
+    fn map(&self, value: f64) -> f64 {
+        let center_start = self.min + (self.grid_min - self.min) * self.outer_scale;
+        if value < self.grid_min {
+            self.min + (value - self.min) * self.outer_scale
+        } else if value <= self.grid_max {
+            center_start + (value - self.grid_min) * self.center_scale
+        } else {
+            let center_end = center_start + (self.grid_max - self.grid_min) * self.center_scale;
+            center_end + (value - self.grid_max) * self.outer_scale
+        }
+    }
+
+    fn unmap(&self, value: f64) -> f64 {
+        let center_start = self.map(self.grid_min);
+        let center_end = self.map(self.grid_max);
+        if value < center_start {
+            self.min + (value - self.min) / self.outer_scale
+        } else if value <= center_end {
+            if self.center_scale > 0.0 {
+                self.grid_min + (value - center_start) / self.center_scale
+            } else {
+                self.grid_min
+            }
+        } else {
+            self.grid_max + (value - center_end) / self.outer_scale
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rectangle(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::from_pixels_i32(x_min),
+            y_min: Twips::from_pixels_i32(y_min),
+            x_max: Twips::from_pixels_i32(x_max),
+            y_max: Twips::from_pixels_i32(y_max),
+        }
+    }
+
+    fn point(x: i32, y: i32) -> Point<Twips> {
+        Point::new(Twips::from_pixels_i32(x), Twips::from_pixels_i32(y))
+    }
+
+    #[test]
+    fn corners_keep_their_size() {
+        // A 100x100 object with 10px borders, scaled 2x horizontally.
+        let grid = ScalingGrid::new(
+            &rectangle(0, 0, 100, 100),
+            &rectangle(10, 10, 90, 90),
+            2.0,
+            1.0,
+        )
+        .expect("grid should apply");
+
+        // Once scaled 2x, the left border is 10px wide again.
+        assert_eq!(grid.map_point(point(10, 10)), point(5, 10));
+        assert_eq!(grid.map_point(point(90, 90)), point(95, 90));
+        // The bounds don't move.
+        assert_eq!(grid.map_point(point(0, 0)), point(0, 0));
+        assert_eq!(grid.map_point(point(100, 100)), point(100, 100));
+        // The center is stretched to fill the rest.
+        assert_eq!(grid.map_point(point(50, 50)), point(50, 50));
+    }
+
+    #[test]
+    fn unmap_point() {
+        let grid = ScalingGrid::new(
+            &rectangle(-20, 0, 80, 50),
+            &rectangle(0, 10, 60, 40),
+            3.0,
+            0.5,
+        )
+        .expect("grid should apply");
+        for p in [point(-20, 0), point(-5, 5), point(30, 25), point(80, 45)] {
+            assert_eq!(grid.unmap_point(grid.map_point(p)), p);
+        }
+    }
+
+    #[test]
+    fn too_small_for_corners() {
+        // Scaled down to 10px, there's no room for 10px borders on both sides.
+        let grid = ScalingGrid::new(
+            &rectangle(0, 0, 100, 100),
+            &rectangle(10, 10, 90, 90),
+            0.1,
+            1.0,
+        )
+        .expect("grid should apply");
+        assert_eq!(grid.map_point(point(10, 0)), point(50, 0));
+        assert_eq!(grid.map_point(point(90, 0)), point(50, 0));
+        assert_eq!(grid.map_point(point(100, 0)), point(100, 0));
+    }
+
+    #[test]
+    fn unscaled_is_none() {
+        assert_eq!(
+            ScalingGrid::new(
+                &rectangle(0, 0, 100, 100),
+                &rectangle(10, 10, 90, 90),
+                1.0,
+                1.0
+            ),
+            None
+        );
+    }
+}
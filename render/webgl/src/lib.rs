@@ -12,6 +12,7 @@ use ruffle_render::bitmap::{
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error as BitmapError;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
@@ -21,7 +22,7 @@ use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
-use swf::{BlendMode, Color};
+use swf::{BlendMode, Color, ColorTransform};
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -754,6 +755,84 @@ impl WebGlRenderBackend {
         }
     }
 
+    /// Draws a bitmap, with a world matrix that maps the unit square to target pixels.
+    fn draw_bitmap_quad(
+        &mut self,
+        bitmap: &BitmapHandle,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        self.set_stencil_state();
+        let entry = as_registry_data(bitmap);
+        // Adjust the quad draw to use the target bitmap.
+        let quad = &self.bitmap_quad_draws;
+        let draw = &quad[0];
+        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
+            matrix
+        } else {
+            unreachable!()
+        };
+
+        let mult_color = color_transform.mult_rgba_normalized();
+        let add_color = color_transform.add_rgba_normalized();
+
+        self.bind_vertex_array(Some(&draw.vao));
+
+        let program = &self.bitmap_program;
+
+        // Set common render state, while minimizing unnecessary state changes.
+        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
+        // are in the same location between shaders, and avoid changing them unless necessary.
+        if program as *const ShaderProgram != self.active_program {
+            self.gl.use_program(Some(&program.program));
+            self.active_program = program as *const ShaderProgram;
+
+            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
+
+            self.mult_color = None;
+            self.add_color = None;
+        }
+
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
+        if Some(mult_color) != self.mult_color {
+            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
+            self.mult_color = Some(mult_color);
+        }
+        if Some(add_color) != self.add_color {
+            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
+            self.add_color = Some(add_color);
+        }
+
+        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
+
+        // Bind texture.
+        self.gl.active_texture(Gl::TEXTURE0);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
+        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+
+        // Set texture parameters.
+        let filter = if smoothing {
+            Gl::LINEAR as i32
+        } else {
+            Gl::NEAREST as i32
+        };
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+
+        let wrap = Gl::CLAMP_TO_EDGE as i32;
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+
+        // Draw the triangles.
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+    }
+
     fn apply_blend_mode(&mut self, mode: RenderBlendMode) {
         let (blend_op, src_rgb, dst_rgb) = match mode {
             RenderBlendMode::Builtin(BlendMode::Normal) => {
@@ -1178,16 +1257,7 @@ impl CommandHandler for WebGlRenderBackend {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     ) {
-        self.set_stencil_state();
         let entry = as_registry_data(&bitmap);
-        // Adjust the quad draw to use the target bitmap.
-        let quad = &self.bitmap_quad_draws;
-        let draw = &quad[0];
-        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
-            matrix
-        } else {
-            unreachable!()
-        };
 
         // Scale the quad to the bitmap's dimensions.
         let mut matrix = transform.matrix;
@@ -1205,64 +1275,32 @@ impl CommandHandler for WebGlRenderBackend {
                 1.0,
             ],
         ];
+        self.draw_bitmap_quad(
+            &bitmap,
+            world_matrix,
+            &transform.color_transform,
+            smoothing,
+        );
+    }
 
-        let mult_color = transform.color_transform.mult_rgba_normalized();
-        let add_color = transform.color_transform.add_rgba_normalized();
-
-        self.bind_vertex_array(Some(&draw.vao));
-
-        let program = &self.bitmap_program;
-
-        // Set common render state, while minimizing unnecessary state changes.
-        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
-        // are in the same location between shaders, and avoid changing them unless necessary.
-        if program as *const ShaderProgram != self.active_program {
-            self.gl.use_program(Some(&program.program));
-            self.active_program = program as *const ShaderProgram;
-
-            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
-
-            self.mult_color = None;
-            self.add_color = None;
-        }
-
-        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
-        if Some(mult_color) != self.mult_color {
-            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
-            self.mult_color = Some(mult_color);
-        }
-        if Some(add_color) != self.add_color {
-            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
-            self.add_color = Some(add_color);
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        let entry = as_registry_data(&bitmap);
+        // The output depth is flattened to zero, so that the projection doesn't get clipped.
+        let matrix = (matrix * Matrix3D::scale(entry.width as f64, entry.height as f64, 1.0))
+            .homography();
+        let mut world_matrix = [[0.0; 4]; 4];
+        for (column, values) in world_matrix.iter_mut().enumerate() {
+            for (row, value) in values.iter_mut().enumerate() {
+                *value = matrix.get(row, column) as f32;
+            }
         }
-
-        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
-
-        // Bind texture.
-        self.gl.active_texture(Gl::TEXTURE0);
-        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
-        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
-
-        // Set texture parameters.
-        let filter = if smoothing {
-            Gl::LINEAR as i32
-        } else {
-            Gl::NEAREST as i32
-        };
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
-
-        let wrap = Gl::CLAMP_TO_EDGE as i32;
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
-
-        // Draw the triangles.
-        self.gl
-            .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+        self.draw_bitmap_quad(&bitmap, world_matrix, &color_transform, smoothing);
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
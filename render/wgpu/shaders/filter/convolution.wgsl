@@ -0,0 +1,64 @@
+#import filter
+
+struct Filter {
+    // Up to 15x15 matrix values, packed 4 to a vector so that the array has a 16 byte stride.
+    matrix: array<vec4<f32>, 64>,
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    // The region of the source texture that we're filtering, as x_min, y_min, x_max, y_max.
+    source_rect: vec4<i32>,
+    clamp_edges: u32,
+    preserve_alpha: u32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> filter_args: Filter;
+
+@vertex
+fn main_vertex(in: filter::VertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+fn unmultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a <= 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+fn load_source(coord: vec2<i32>) -> vec4<f32> {
+    let rect = filter_args.source_rect;
+    var pos = coord;
+    if (pos.x < rect.x || pos.y < rect.y || pos.x >= rect.z || pos.y >= rect.w) {
+        if (filter_args.clamp_edges == 0u) {
+            return filter_args.default_color;
+        }
+        pos = clamp(pos, rect.xy, rect.zw - vec2<i32>(1));
+    }
+    return unmultiply(textureLoad(texture, pos, 0));
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let center = vec2<i32>(in.position.xy) + filter_args.source_rect.xy;
+    let offset = vec2<i32>(i32(filter_args.matrix_x / 2u), i32(filter_args.matrix_y / 2u));
+
+    var sum = vec4<f32>(0.0);
+    for (var y = 0u; y < filter_args.matrix_y; y += 1u) {
+        for (var x = 0u; x < filter_args.matrix_x; x += 1u) {
+            let index = y * filter_args.matrix_x + x;
+            let weight = filter_args.matrix[index / 4u][index % 4u];
+            let color = load_source(center + vec2<i32>(i32(x), i32(y)) - offset);
+            sum += color * weight;
+        }
+    }
+
+    var color = saturate(sum / filter_args.divisor + vec4<f32>(filter_args.bias / 255.0));
+    if (filter_args.preserve_alpha != 0u) {
+        color.a = textureLoad(texture, center, 0).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
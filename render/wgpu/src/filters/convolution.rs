@@ -0,0 +1,224 @@
+use crate::backend::RenderTargetMode;
+use crate::buffer_pool::TexturePool;
+use crate::descriptors::Descriptors;
+use crate::filters::{FilterSource, VERTEX_BUFFERS_DESCRIPTION_FILTERS};
+use crate::surface::target::CommandTarget;
+use crate::utils::SampleCountMap;
+use bytemuck::{Pod, Zeroable};
+use std::sync::OnceLock;
+use swf::ConvolutionFilter as ConvolutionFilterArgs;
+use wgpu::util::DeviceExt;
+
+/// The largest matrix Flash Player accepts is 15x15.
+const MAX_MATRIX_SIZE: usize = 15 * 15;
+
+/// The matrix is packed into vec4s, rounded up to a size that `bytemuck` understands.
+const MATRIX_VECTORS: usize = 64;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable, PartialEq)]
+struct ConvolutionUniform {
+    matrix: [[f32; 4]; MATRIX_VECTORS],
+    default_color: [f32; 4],
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    source_rect: [i32; 4], // x_min, y_min, x_max, y_max
+    clamp_edges: u32,
+    preserve_alpha: u32,
+    _padding: [u32; 2],
+}
+
+pub struct ConvolutionFilter {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    pipelines: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
+}
+
+impl ConvolutionFilter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<
+                            ConvolutionUniform,
+                        >() as u64),
+                    },
+                    count: None,
+                },
+            ],
+            label: create_debug_label!("Convolution filter binds").as_deref(),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        Self {
+            pipelines: Default::default(),
+            pipeline_layout,
+            bind_group_layout,
+        }
+    }
+
+    fn pipeline(&self, descriptors: &Descriptors, msaa_sample_count: u32) -> &wgpu::RenderPipeline {
+        self.pipelines.get_or_init(msaa_sample_count, || {
+            let label = create_debug_label!("Convolution Filter ({} msaa)", msaa_sample_count);
+            descriptors
+                .device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: label.as_deref(),
+                    layout: Some(&self.pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &descriptors.shaders.convolution_filter,
+                        entry_point: "main_vertex",
+                        buffers: &VERTEX_BUFFERS_DESCRIPTION_FILTERS,
+                    },
+                    primitive: wgpu::PrimitiveState {
+                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        strip_index_format: None,
+                        front_face: wgpu::FrontFace::Ccw,
+                        cull_mode: None,
+                        polygon_mode: wgpu::PolygonMode::default(),
+                        unclipped_depth: false,
+                        conservative: false,
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: msaa_sample_count,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &descriptors.shaders.convolution_filter,
+                        entry_point: "main_fragment",
+                        targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                    }),
+                    multiview: None,
+                })
+        })
+    }
+
+    pub fn apply(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source: &FilterSource,
+        filter: &ConvolutionFilterArgs,
+    ) -> Option<CommandTarget> {
+        let matrix_x = filter.num_matrix_cols as usize;
+        let matrix_y = filter.num_matrix_rows as usize;
+        if matrix_x * matrix_y > MAX_MATRIX_SIZE {
+            return None;
+        }
+
+        let sample_count = source.texture.sample_count();
+        let format = source.texture.format();
+        let pipeline = self.pipeline(descriptors, sample_count);
+
+        let target = CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: source.size.0,
+                height: source.size.1,
+                depth_or_array_layers: 1,
+            },
+            format,
+            sample_count,
+            RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        );
+        let source_view = source.texture.create_view(&Default::default());
+
+        let mut matrix = [[0.0; 4]; MATRIX_VECTORS];
+        for (i, value) in filter.matrix.iter().take(matrix_x * matrix_y).enumerate() {
+            matrix[i / 4][i % 4] = value.to_f32();
+        }
+        // Flash treats a divisor of 0 as 1.
+        let divisor = filter.divisor.to_f32();
+        let divisor = if divisor == 0.0 { 1.0 } else { divisor };
+
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[ConvolutionUniform {
+                    matrix,
+                    default_color: [
+                        f32::from(filter.default_color.r) / 255.0,
+                        f32::from(filter.default_color.g) / 255.0,
+                        f32::from(filter.default_color.b) / 255.0,
+                        f32::from(filter.default_color.a) / 255.0,
+                    ],
+                    bias: filter.bias.to_f32(),
+                    divisor,
+                    matrix_x: matrix_x as u32,
+                    matrix_y: matrix_y as u32,
+                    source_rect: [
+                        source.point.0 as i32,
+                        source.point.1 as i32,
+                        (source.point.0 + source.size.0) as i32,
+                        (source.point.1 + source.size.1) as i32,
+                    ],
+                    clamp_edges: filter.is_clamped() as u32,
+                    preserve_alpha: filter.is_preserve_alpha() as u32,
+                    _padding: Default::default(),
+                }]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let vertices = source.vertices(&descriptors.device);
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: buffer.as_entire_binding(),
+                    },
+                ],
+            });
+        let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: create_debug_label!("Convolution filter").as_deref(),
+            color_attachments: &[target.color_attachments()],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(pipeline);
+
+        render_pass.set_bind_group(0, &filter_group, &[]);
+
+        render_pass.set_vertex_buffer(0, vertices.slice(..));
+        render_pass.set_index_buffer(
+            descriptors.quad.indices.slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
+        render_pass.draw_indexed(0..6, 0, 0..1);
+        drop(render_pass);
+        Some(target)
+    }
+}
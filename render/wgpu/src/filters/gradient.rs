@@ -0,0 +1,384 @@
+use crate::backend::RenderTargetMode;
+use crate::buffer_pool::TexturePool;
+use crate::descriptors::Descriptors;
+use crate::filters::blur::BlurFilter;
+use crate::filters::{
+    FilterSource, VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_BLUR,
+    VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_DOUBLE_BLUR,
+};
+use crate::mesh::create_gradient_texture;
+use crate::surface::target::CommandTarget;
+use crate::utils::SampleCountMap;
+use bytemuck::{Pod, Zeroable};
+use enum_map::{Enum, EnumMap};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, OnceLock};
+use swf::{GradientFilter as GradientFilterArgs, GradientInterpolation, GradientRecord};
+use wgpu::util::DeviceExt;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable, PartialEq)]
+struct GradientFilterUniform {
+    strength: f32,
+    filter_type: u32, // 0 outer, 1 inner, 2 full
+    knockout: u32,    // a wasteful bool, but we need to be aligned anyway
+    _padding: u32,
+}
+
+/// Which of the gradient filters is applied.
+///
+/// Both blur the source and color the result with a gradient, but a glow uses
+/// a single offset blur while a bevel compares a highlight and a shadow.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Enum)]
+enum GradientFilterKind {
+    Glow,
+    Bevel,
+}
+
+impl GradientFilterKind {
+    fn name(self) -> &'static str {
+        match self {
+            GradientFilterKind::Glow => "Gradient glow",
+            GradientFilterKind::Bevel => "Gradient bevel",
+        }
+    }
+
+    fn shader(self, descriptors: &Descriptors) -> &wgpu::ShaderModule {
+        match self {
+            GradientFilterKind::Glow => &descriptors.shaders.gradient_glow_filter,
+            GradientFilterKind::Bevel => &descriptors.shaders.gradient_bevel_filter,
+        }
+    }
+
+    fn vertex_buffers(self) -> &'static [wgpu::VertexBufferLayout<'static>] {
+        match self {
+            GradientFilterKind::Glow => &VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_BLUR,
+            GradientFilterKind::Bevel => &VERTEX_BUFFERS_DESCRIPTION_FILTERS_WITH_DOUBLE_BLUR,
+        }
+    }
+
+    fn vertices(
+        self,
+        descriptors: &Descriptors,
+        source: &FilterSource,
+        filter: &GradientFilterArgs,
+    ) -> wgpu::Buffer {
+        let distance = filter.distance.to_f32();
+        let angle = filter.angle.to_f32();
+        let x = angle.cos() * distance;
+        let y = angle.sin() * distance;
+        match self {
+            // Like drop shadows, gradient glows can be offset from the source.
+            GradientFilterKind::Glow => {
+                source.vertices_with_blur_offset(&descriptors.device, (-x, -y))
+            }
+            GradientFilterKind::Bevel => {
+                source.vertices_with_highlight_and_shadow(&descriptors.device, (x, y))
+            }
+        }
+    }
+}
+
+/// How many gradient textures are kept around, as the same filter is usually
+/// applied again on the next frame.
+const GRADIENT_TEXTURE_CACHE_SIZE: usize = 16;
+
+/// Implements both `GradientGlowFilter` and `GradientBevelFilter`.
+///
+/// They share their bindings and gradient textures, and only have separate
+/// pipelines for their shader and vertices.
+pub struct GradientFilter {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    pipelines: EnumMap<GradientFilterKind, SampleCountMap<OnceLock<wgpu::RenderPipeline>>>,
+    gradient_textures: Mutex<LruCache<Vec<GradientRecord>, wgpu::Texture>>,
+}
+
+impl GradientFilter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<
+                            GradientFilterUniform,
+                        >() as u64),
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+            label: create_debug_label!("Gradient filter binds").as_deref(),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        Self {
+            pipelines: Default::default(),
+            pipeline_layout,
+            bind_group_layout,
+            gradient_textures: Mutex::new(LruCache::new(
+                NonZeroUsize::new(GRADIENT_TEXTURE_CACHE_SIZE).unwrap(),
+            )),
+        }
+    }
+
+    fn pipeline(
+        &self,
+        descriptors: &Descriptors,
+        kind: GradientFilterKind,
+        msaa_sample_count: u32,
+    ) -> &wgpu::RenderPipeline {
+        self.pipelines[kind].get_or_init(msaa_sample_count, || {
+            let label = create_debug_label!("{} filter ({} msaa)", kind.name(), msaa_sample_count);
+            let shader = kind.shader(descriptors);
+            descriptors
+                .device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: label.as_deref(),
+                    layout: Some(&self.pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: shader,
+                        entry_point: "main_vertex",
+                        buffers: kind.vertex_buffers(),
+                    },
+                    primitive: wgpu::PrimitiveState {
+                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        strip_index_format: None,
+                        front_face: wgpu::FrontFace::Ccw,
+                        cull_mode: None,
+                        polygon_mode: wgpu::PolygonMode::default(),
+                        unclipped_depth: false,
+                        conservative: false,
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: msaa_sample_count,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: shader,
+                        entry_point: "main_fragment",
+                        targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                    }),
+                    multiview: None,
+                })
+        })
+    }
+
+    /// Returns a view of the texture for the gradient of a filter, creating it if it isn't cached.
+    fn gradient_view(
+        &self,
+        descriptors: &Descriptors,
+        colors: &[GradientRecord],
+    ) -> wgpu::TextureView {
+        let mut gradient_textures = self
+            .gradient_textures
+            .lock()
+            .expect("Gradient textures shouldn't be poisoned");
+        if let Some(texture) = gradient_textures.get(colors) {
+            return texture.create_view(&Default::default());
+        }
+        let texture = create_gradient_texture(descriptors, colors, GradientInterpolation::Rgb);
+        let view = texture.create_view(&Default::default());
+        gradient_textures.put(colors.to_vec(), texture);
+        view
+    }
+
+    pub fn apply_glow(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source: &FilterSource,
+        filter: &GradientFilterArgs,
+        blur_filter: &BlurFilter,
+    ) -> CommandTarget {
+        self.apply(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source,
+            GradientFilterKind::Glow,
+            filter,
+            blur_filter,
+        )
+    }
+
+    pub fn apply_bevel(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source: &FilterSource,
+        filter: &GradientFilterArgs,
+        blur_filter: &BlurFilter,
+    ) -> CommandTarget {
+        self.apply(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source,
+            GradientFilterKind::Bevel,
+            filter,
+            blur_filter,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn apply(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source: &FilterSource,
+        kind: GradientFilterKind,
+        filter: &GradientFilterArgs,
+        blur_filter: &BlurFilter,
+    ) -> CommandTarget {
+        let sample_count = source.texture.sample_count();
+        let format = source.texture.format();
+        let pipeline = self.pipeline(descriptors, kind, sample_count);
+        let blurred = blur_filter.apply(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source,
+            &filter.inner_blur_filter(),
+        );
+        let blurred_texture = if let Some(blurred) = &blurred {
+            blurred.ensure_cleared(draw_encoder);
+            blurred.color_texture()
+        } else {
+            source.texture
+        };
+        let source_view = source.texture.create_view(&Default::default());
+        let blurred_view = blurred_texture.create_view(&Default::default());
+        let gradient_view = self.gradient_view(descriptors, &filter.colors);
+
+        let target = CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: source.size.0,
+                height: source.size.1,
+                depth_or_array_layers: 1,
+            },
+            format,
+            sample_count,
+            RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        );
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[GradientFilterUniform {
+                    strength: filter.strength.to_f32(),
+                    filter_type: if filter.is_on_top() {
+                        2
+                    } else if filter.is_inner() {
+                        1
+                    } else {
+                        0
+                    },
+                    knockout: if filter.is_knockout() { 1 } else { 0 },
+                    _padding: 0,
+                }]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let vertices = kind.vertices(descriptors, source, filter);
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, false),
+                        ),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(&blurred_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(&gradient_view),
+                    },
+                ],
+            });
+        let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: create_debug_label!("{} filter", kind.name()).as_deref(),
+            color_attachments: &[target.color_attachments()],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(pipeline);
+
+        render_pass.set_bind_group(0, &filter_group, &[]);
+
+        render_pass.set_vertex_buffer(0, vertices.slice(..));
+        render_pass.set_index_buffer(
+            descriptors.quad.indices.slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
+        render_pass.draw_indexed(0..6, 0, 0..1);
+        drop(render_pass);
+        target
+    }
+}
@@ -13,6 +13,7 @@ use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
 use ruffle_render::commands::{Command, RenderBlendMode};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -90,6 +91,12 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 *render_stage3d,
                 *pixel_snapping,
             ),
+            DrawCommand::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => self.render_bitmap_projected(bitmap, matrix, color_transform, *smoothing),
             DrawCommand::RenderTexture {
                 _texture,
                 binds,
@@ -195,7 +202,27 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 1.0,
             ],
         ];
+        self.apply_world_matrix(world_matrix, color_adjustments);
+    }
 
+    /// Like `apply_transform`, but with a projection.
+    /// The output depth is flattened to zero, so that the projection doesn't get clipped.
+    pub fn apply_transform_3d(&mut self, matrix: &Matrix3D, color_adjustments: &ColorTransform) {
+        let matrix = matrix.homography();
+        let mut world_matrix = [[0.0; 4]; 4];
+        for (column, values) in world_matrix.iter_mut().enumerate() {
+            for (row, value) in values.iter_mut().enumerate() {
+                *value = matrix.get(row, column) as f32;
+            }
+        }
+        self.apply_world_matrix(world_matrix, color_adjustments);
+    }
+
+    fn apply_world_matrix(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_adjustments: &ColorTransform,
+    ) {
         if self.descriptors.limits.max_push_constant_size > 0 {
             self.render_pass.set_push_constants(
                 wgpu::ShaderStages::VERTEX_FRAGMENT,
@@ -277,6 +304,47 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         }
     }
 
+    pub fn render_bitmap_projected(
+        &mut self,
+        bitmap: &'frame BitmapHandle,
+        matrix: &Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass
+                .push_debug_group(&format!("render_bitmap_projected {:?}", bitmap.0));
+        }
+        let texture = as_texture(bitmap);
+
+        let descriptors = self.descriptors;
+        let bind = texture.bind_group(
+            smoothing,
+            &descriptors.device,
+            &descriptors.bind_layouts.bitmap,
+            &descriptors.quad,
+            bitmap.clone(),
+            &descriptors.bitmap_samplers,
+        );
+        self.prep_bitmap(&bind.bind_group, TrivialBlend::Normal, false);
+        let matrix = *matrix
+            * Matrix3D::scale(
+                texture.texture.width() as f64,
+                texture.texture.height() as f64,
+                1.0,
+            );
+        self.apply_transform_3d(&matrix, color_transform);
+
+        self.draw(
+            self.descriptors.quad.vertices_pos.slice(..),
+            self.descriptors.quad.indices.slice(..),
+            6,
+        );
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass.pop_debug_group();
+        }
+    }
+
     pub fn render_texture(
         &mut self,
         transform: &Transform,
@@ -435,6 +503,12 @@ pub enum DrawCommand {
         render_stage3d: bool,
         pixel_snapping: PixelSnapping,
     },
+    RenderBitmapProjected {
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    },
     RenderTexture {
         _texture: PoolOrArcTexture,
         binds: wgpu::BindGroup,
@@ -590,6 +664,17 @@ pub fn chunk_blends<'a>(
                 render_stage3d: false,
                 pixel_snapping,
             }),
+            Command::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => current.push(DrawCommand::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            }),
             Command::RenderStage3D { bitmap, transform } => {
                 current.push(DrawCommand::RenderBitmap {
                     bitmap,
@@ -1,8 +1,7 @@
 //! Post-scan analysis
 
 use crate::cli_options::AnalyzeOpt;
-use crate::file_results::{FileResults, Step};
-use std::fs::File;
+use crate::file_results::{read_results, FileResults, Step};
 
 /// Generate and print statistics related to a scan's results
 pub fn analyze(results: impl Iterator<Item = FileResults>) {
@@ -51,21 +50,7 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
-    let file = File::open(opt.input_path)?;
-    let reader = csv::Reader::from_reader(file);
-
-    analyze(reader.into_deserialize::<FileResults>().map(|r| {
-        match r {
-            Ok(fr) => fr,
-            Err(e) => {
-                // Treat unparseable CSV rows as a scanner panic
-                FileResults {
-                    error: Some(format!("{e}")),
-                    ..FileResults::default()
-                }
-            }
-        }
-    }));
+    analyze(read_results(&opt.input_path)?);
 
     Ok(())
 }
@@ -20,6 +20,9 @@ pub enum Mode {
 
     /// Execute a single SWF file and generate a machine-readable report
     ExecuteReport(ExecuteReportOpt),
+
+    /// Compare two previously executed scans and report regressions and improvements
+    Compare(CompareOpt),
 }
 
 #[derive(Parser, Debug)]
@@ -50,3 +53,26 @@ pub struct ExecuteReportOpt {
     #[clap(name = "file")]
     pub input_path: PathBuf,
 }
+
+#[derive(Parser, Debug)]
+pub struct CompareOpt {
+    /// The CSV file of the baseline scan
+    #[clap(name = "old")]
+    pub old_path: PathBuf,
+
+    /// The CSV file of the scan to compare against the baseline
+    #[clap(name = "new")]
+    pub new_path: PathBuf,
+
+    /// The file to store every regression and improvement in CSV format
+    #[clap(short = 'o', long = "output")]
+    pub output_path: Option<PathBuf>,
+
+    /// The file to store a summary of the comparison in JSON format
+    #[clap(short = 's', long = "summary")]
+    pub summary_path: Option<PathBuf>,
+
+    /// Exit with a non-zero status if any regressions were found
+    #[clap(long = "fail-on-regression")]
+    pub fail_on_regression: bool,
+}
@@ -0,0 +1,340 @@
+//! Comparison of two scans
+
+use crate::cli_options::CompareOpt;
+use crate::file_results::{into_hex, read_results, FileResults, Step};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+
+/// A way in which a movie can behave differently between two scans.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// The movie failed to be read, decompressed or parsed.
+    Parse,
+
+    /// The movie logged errors while executing.
+    Execute,
+
+    /// Ruffle panicked, or the scanner crashed.
+    Panic,
+
+    /// A script took longer than the execution limit.
+    Timeout,
+
+    /// The movie hit an unimplemented stub.
+    Stub,
+}
+
+impl Category {
+    fn description(self) -> &'static str {
+        match self {
+            Category::Parse => "fail to read, decompress or parse",
+            Category::Execute => "fail to execute",
+            Category::Panic => "panic or crash the scanner",
+            Category::Timeout => "time out",
+            Category::Stub => "hit",
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Regression,
+    Improvement,
+}
+
+/// A single difference in behaviour of a movie between two scans.
+#[derive(Serialize, Debug, Clone)]
+pub struct Change {
+    /// The file name in the new scan.
+    #[serde(rename = "Filename")]
+    pub name: String,
+
+    /// The SHA256 hash of the SWF file, which is the same in both scans.
+    #[serde(rename = "SHA256 Hash", serialize_with = "into_hex")]
+    pub hash: Vec<u8>,
+
+    #[serde(rename = "Change")]
+    pub direction: Direction,
+
+    #[serde(rename = "Category")]
+    pub category: Category,
+
+    /// The error of the failing scan, or the name of the stub.
+    #[serde(rename = "Detail")]
+    pub detail: Option<String>,
+}
+
+/// Machine-readable totals of a comparison.
+#[derive(Serialize, Debug, Default)]
+pub struct Summary {
+    /// Movies present in both scans.
+    pub matched: usize,
+
+    /// Movies only present in the old scan, including unreadable ones.
+    pub only_in_old: usize,
+
+    /// Movies only present in the new scan, including unreadable ones.
+    pub only_in_new: usize,
+
+    /// The number of regressions in each category. Stubs are counted once
+    /// per movie and stub.
+    pub regressions: BTreeMap<Category, usize>,
+
+    /// The number of improvements in each category.
+    pub improvements: BTreeMap<Category, usize>,
+}
+
+impl Summary {
+    pub fn total_regressions(&self) -> usize {
+        self.regressions.values().sum()
+    }
+}
+
+/// How a movie failed a scan, if it did.
+///
+/// A movie only has one failure; panics and timeouts take priority over the
+/// step they happened in.
+fn failure(result: &FileResults) -> Option<Category> {
+    let error = result.error.as_deref().unwrap_or_default();
+    if matches!(result.progress, Step::Start)
+        || error.contains("PANIC")
+        || error.contains("panicked at")
+    {
+        Some(Category::Panic)
+    } else if error.contains("has taken too long to execute") {
+        Some(Category::Timeout)
+    } else if matches!(result.progress, Step::Read | Step::Decompress | Step::Parse) {
+        Some(Category::Parse)
+    } else if matches!(result.progress, Step::Execute) {
+        Some(Category::Execute)
+    } else {
+        None
+    }
+}
+
+/// Compare the results of two scans, matching movies by their hash.
+///
+/// A movie which fails differently in the new scan is reported as a
+/// regression in its new failure. The stubs of a movie which panics in
+/// either scan aren't compared.
+pub fn compare(
+    old_results: impl Iterator<Item = FileResults>,
+    new_results: impl Iterator<Item = FileResults>,
+) -> (Vec<Change>, Summary) {
+    let mut summary = Summary::default();
+
+    // Unreadable movies have no hash, and copies of a movie are only compared once.
+    let mut old_by_hash = HashMap::new();
+    for result in old_results {
+        if result.hash.is_empty() {
+            summary.only_in_old += 1;
+        } else {
+            old_by_hash.entry(result.hash.clone()).or_insert(result);
+        }
+    }
+
+    let mut changes = Vec::new();
+    let mut seen = HashSet::new();
+    for new in new_results {
+        if new.hash.is_empty() {
+            summary.only_in_new += 1;
+            continue;
+        }
+        if !seen.insert(new.hash.clone()) {
+            continue;
+        }
+        let Some(old) = old_by_hash.get(&new.hash) else {
+            summary.only_in_new += 1;
+            continue;
+        };
+        summary.matched += 1;
+
+        let change = |direction, category, detail| Change {
+            name: new.name.clone(),
+            hash: new.hash.clone(),
+            direction,
+            category,
+            detail,
+        };
+
+        let (old_failure, new_failure) = (failure(old), failure(&new));
+        match (old_failure, new_failure) {
+            (_, Some(category)) if old_failure != Some(category) => {
+                changes.push(change(Direction::Regression, category, new.error.clone()));
+            }
+            (Some(category), None) => {
+                changes.push(change(Direction::Improvement, category, old.error.clone()));
+            }
+            _ => {}
+        }
+
+        // The stubs hit before a panic can't be compared to a full run.
+        if old_failure == Some(Category::Panic) || new_failure == Some(Category::Panic) {
+            continue;
+        }
+
+        let old_stubs: BTreeSet<_> = old.stubs.iter().collect();
+        let new_stubs: BTreeSet<_> = new.stubs.iter().collect();
+        for stub in new_stubs.difference(&old_stubs) {
+            changes.push(change(
+                Direction::Regression,
+                Category::Stub,
+                Some(stub.to_string()),
+            ));
+        }
+        for stub in old_stubs.difference(&new_stubs) {
+            changes.push(change(
+                Direction::Improvement,
+                Category::Stub,
+                Some(stub.to_string()),
+            ));
+        }
+    }
+    summary.only_in_old += old_by_hash.len() - summary.matched;
+
+    for change in &changes {
+        let counts = match change.direction {
+            Direction::Regression => &mut summary.regressions,
+            Direction::Improvement => &mut summary.improvements,
+        };
+        *counts.entry(change.category).or_default() += 1;
+    }
+
+    (changes, summary)
+}
+
+/// Print the totals of a comparison.
+fn print_summary(summary: &Summary) {
+    println!(
+        "Compared {} swf files present in both scans.",
+        summary.matched
+    );
+    if summary.only_in_old > 0 || summary.only_in_new > 0 {
+        println!(
+            "{} swf files were only in the old scan, and {} only in the new scan.",
+            summary.only_in_old, summary.only_in_new
+        );
+    }
+
+    for (title, counts, change) in [
+        ("Regressions", &summary.regressions, "now"),
+        ("Improvements", &summary.improvements, "no longer"),
+    ] {
+        println!();
+        println!("{title}:");
+        if counts.is_empty() {
+            println!("    none");
+        }
+        for (category, count) in counts {
+            let description = category.description();
+            match category {
+                Category::Stub => println!("{count:>8} stubs are {change} {description}"),
+                _ => println!("{count:>8} movies {change} {description}"),
+            }
+        }
+    }
+    println!();
+}
+
+pub fn compare_main(opt: CompareOpt) -> Result<(), std::io::Error> {
+    let (changes, summary) = compare(read_results(&opt.old_path)?, read_results(&opt.new_path)?);
+
+    if let Some(output_path) = &opt.output_path {
+        let mut writer = csv::Writer::from_path(output_path)?;
+        for change in &changes {
+            writer.serialize(change)?;
+        }
+        writer.flush()?;
+    }
+
+    if let Some(summary_path) = &opt.summary_path {
+        serde_json::to_writer_pretty(File::create(summary_path)?, &summary)?;
+    }
+
+    print_summary(&summary);
+
+    if opt.fail_on_regression && summary.total_regressions() > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(hash: u8, progress: Step, error: Option<&str>, stubs: &[&str]) -> FileResults {
+        FileResults {
+            hash: vec![hash],
+            progress,
+            error: error.map(str::to_string),
+            stubs: stubs.iter().map(|stub| stub.to_string()).collect(),
+            ..FileResults::new("movie.swf")
+        }
+    }
+
+    fn categories(changes: &[Change]) -> Vec<(Direction, Category)> {
+        changes
+            .iter()
+            .map(|change| (change.direction, change.category))
+            .collect()
+    }
+
+    #[test]
+    fn stubs_are_compared() {
+        let old = result(1, Step::Complete, None, &["a", "b"]);
+        let new = result(1, Step::Complete, None, &["b", "c"]);
+        let (changes, summary) = compare([old].into_iter(), [new].into_iter());
+
+        assert_eq!(
+            categories(&changes),
+            [
+                (Direction::Regression, Category::Stub),
+                (Direction::Improvement, Category::Stub)
+            ]
+        );
+        assert_eq!(changes[0].detail.as_deref(), Some("c"));
+        assert_eq!(changes[1].detail.as_deref(), Some("a"));
+        assert_eq!(summary.matched, 1);
+    }
+
+    #[test]
+    fn panic_is_a_regression_without_stubs() {
+        let old = result(1, Step::Complete, None, &["a", "b"]);
+        let new = result(1, Step::Execute, Some("PANIC: oops"), &["a"]);
+        let (changes, summary) = compare([old].into_iter(), [new].into_iter());
+
+        assert_eq!(
+            categories(&changes),
+            [(Direction::Regression, Category::Panic)]
+        );
+        assert_eq!(changes[0].detail.as_deref(), Some("PANIC: oops"));
+        assert_eq!(summary.total_regressions(), 1);
+        assert!(summary.improvements.is_empty());
+    }
+
+    #[test]
+    fn new_panic_after_other_failure_is_a_regression() {
+        let old = result(1, Step::Execute, Some("Error: oops"), &["a"]);
+        let new = result(1, Step::Execute, Some("panicked at core/src/lib.rs"), &[]);
+        let (changes, summary) = compare([old].into_iter(), [new].into_iter());
+
+        assert_eq!(
+            categories(&changes),
+            [(Direction::Regression, Category::Panic)]
+        );
+        assert_eq!(summary.total_regressions(), 1);
+    }
+
+    #[test]
+    fn panic_in_both_scans_is_not_a_regression() {
+        let old = result(1, Step::Start, None, &["a"]);
+        let new = result(1, Step::Start, None, &["b"]);
+        let (changes, summary) = compare([old].into_iter(), [new].into_iter());
+
+        assert!(changes.is_empty());
+        assert_eq!(summary.total_regressions(), 0);
+    }
+}
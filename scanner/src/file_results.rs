@@ -7,6 +7,8 @@ use serde::ser::Error as SerError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::fmt::Write;
+use std::fs::File;
+use std::path::Path;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum AvmType {
@@ -126,6 +128,17 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// The unimplemented stubs hit while executing the movie.
+    ///
+    /// Scans made before this column existed are read as hitting no stubs.
+    #[serde(
+        rename = "Stubs",
+        default,
+        serialize_with = "into_lines",
+        deserialize_with = "from_lines"
+    )]
+    pub stubs: Vec<String>,
 }
 
 impl Default for FileResults {
@@ -153,12 +166,29 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            stubs: vec![],
         }
     }
 }
 
+/// Read the results of a scan from a CSV file.
+///
+/// Rows which can't be read are treated as a scanner panic.
+pub fn read_results(path: &Path) -> Result<impl Iterator<Item = FileResults>, std::io::Error> {
+    let file = File::open(path)?;
+    let reader = csv::Reader::from_reader(file);
+
+    Ok(reader.into_deserialize::<FileResults>().map(|r| match r {
+        Ok(fr) => fr,
+        Err(e) => FileResults {
+            error: Some(format!("{e}")),
+            ..FileResults::default()
+        },
+    }))
+}
+
 /// Formats data as capital hex
-fn into_hex<S>(hash: &[u8], s: S) -> Result<S::Ok, S::Error>
+pub fn into_hex<S>(hash: &[u8], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -207,3 +237,24 @@ where
 
     d.deserialize_str(HexVisitor())
 }
+
+/// Formats a list as one item per line
+fn into_lines<S>(items: &[String], s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&items.join("\n"))
+}
+
+/// Parses lines into a list, ignoring empty lines
+fn from_lines<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let lines = String::deserialize(d)?;
+    Ok(lines
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
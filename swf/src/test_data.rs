@@ -2445,6 +2445,14 @@ pub fn avm1_tests() -> Vec<Avm1TestData> {
             }),
             vec![0x9F, 1, 0, 0],
         ),
+        (
+            4,
+            Action::GotoFrame2(GotoFrame2 {
+                set_playing: true,
+                scene_offset: 0,
+            }),
+            vec![0x9F, 1, 0, 0b01],
+        ),
         (
             4,
             Action::GotoFrame2(GotoFrame2 {
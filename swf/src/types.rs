@@ -803,7 +803,7 @@ impl GradientInterpolation {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct GradientRecord {
     pub ratio: u8,
     pub color: Color,
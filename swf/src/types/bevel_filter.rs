@@ -49,27 +49,43 @@ impl BevelFilter {
     }
 
     pub fn calculate_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
-        let mut result = self.inner_blur_filter().calculate_dest_rect(source_rect);
-        let distance = self.distance.to_f64();
-        let angle = self.angle.to_f64();
-        let x = Twips::from_pixels(angle.cos() * distance);
-        let y = Twips::from_pixels(angle.sin() * distance);
-        if x < Twips::ZERO {
-            result.x_min += x;
-            result.x_max -= x;
-        } else {
-            result.x_max += x;
-            result.x_min -= x;
-        }
-        if y < Twips::ZERO {
-            result.y_min += y;
-            result.y_max -= y;
-        } else {
-            result.y_max += y;
-            result.y_min -= y;
-        }
-        result
+        bevel_dest_rect(
+            &self.inner_blur_filter(),
+            self.angle,
+            self.distance,
+            source_rect,
+        )
+    }
+}
+
+/// Calculates the bounds of a blur offset by `distance` in both directions along `angle`,
+/// as the highlight and shadow of a bevel are.
+pub(crate) fn bevel_dest_rect(
+    blur_filter: &BlurFilter,
+    angle: Fixed16,
+    distance: Fixed16,
+    source_rect: Rectangle<Twips>,
+) -> Rectangle<Twips> {
+    let mut result = blur_filter.calculate_dest_rect(source_rect);
+    let distance = distance.to_f64();
+    let angle = angle.to_f64();
+    let x = Twips::from_pixels(angle.cos() * distance);
+    let y = Twips::from_pixels(angle.sin() * distance);
+    if x < Twips::ZERO {
+        result.x_min += x;
+        result.x_max -= x;
+    } else {
+        result.x_max += x;
+        result.x_min -= x;
+    }
+    if y < Twips::ZERO {
+        result.y_min += y;
+        result.y_max -= y;
+    } else {
+        result.y_max += y;
+        result.y_min -= y;
     }
+    result
 }
 
 bitflags! {
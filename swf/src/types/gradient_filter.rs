@@ -1,4 +1,5 @@
-use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord};
+use super::bevel_filter::bevel_dest_rect;
+use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord, Rectangle, Twips};
 use bitflags::bitflags;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -46,6 +47,15 @@ impl GradientFilter {
             flags: BlurFilterFlags::from_passes(self.num_passes()),
         }
     }
+
+    pub fn calculate_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
+        bevel_dest_rect(
+            &self.inner_blur_filter(),
+            self.angle,
+            self.distance,
+            source_rect,
+        )
+    }
 }
 
 bitflags! {
@@ -67,3 +77,67 @@ impl GradientFilterFlags {
         flags
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BevelFilter, BevelFilterFlags, Color};
+
+    fn source_rect() -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::ZERO,
+            x_max: Twips::from_pixels(100.0),
+            y_min: Twips::ZERO,
+            y_max: Twips::from_pixels(50.0),
+        }
+    }
+
+    #[test]
+    fn dest_rect_extends_both_sides_of_the_offset() {
+        let filter = GradientFilter {
+            colors: vec![],
+            blur_x: Fixed16::ZERO,
+            blur_y: Fixed16::ZERO,
+            angle: Fixed16::ZERO,
+            distance: Fixed16::from_f64(5.0),
+            strength: Fixed8::ONE,
+            flags: GradientFilterFlags::from_passes(1),
+        };
+        assert_eq!(
+            filter.calculate_dest_rect(source_rect()),
+            Rectangle {
+                x_min: Twips::from_pixels(-5.0),
+                x_max: Twips::from_pixels(105.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(50.0),
+            }
+        );
+    }
+
+    #[test]
+    fn dest_rect_matches_bevel() {
+        let filter = GradientFilter {
+            colors: vec![],
+            blur_x: Fixed16::from_f64(4.0),
+            blur_y: Fixed16::from_f64(2.0),
+            angle: Fixed16::from_f64(-2.0),
+            distance: Fixed16::from_f64(8.0),
+            strength: Fixed8::ONE,
+            flags: GradientFilterFlags::from_passes(3),
+        };
+        let bevel = BevelFilter {
+            shadow_color: Color::BLACK,
+            highlight_color: Color::WHITE,
+            blur_x: filter.blur_x,
+            blur_y: filter.blur_y,
+            angle: filter.angle,
+            distance: filter.distance,
+            strength: filter.strength,
+            flags: BevelFilterFlags::from_passes(3),
+        };
+        assert_eq!(
+            filter.calculate_dest_rect(source_rect()),
+            bevel.calculate_dest_rect(source_rect())
+        );
+    }
+}
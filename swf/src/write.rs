@@ -81,6 +81,11 @@ pub fn write_swf_raw_tags<W: Write>(header: &Header, tags: &[u8], mut output: W)
     Ok(())
 }
 
+/// Writes a single tag, including its header, to the output stream.
+pub fn write_tag<W: Write>(tag: &Tag<'_>, swf_version: u8, output: W) -> Result<()> {
+    Writer::new(output, swf_version).write_tag(tag)
+}
+
 #[cfg(feature = "flate2")]
 fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
     use flate2::write::ZlibEncoder;
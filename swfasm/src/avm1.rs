@@ -0,0 +1,549 @@
+//! AVM1 bytecode, as found in `DoAction` tags.
+//!
+//! Each action is a line named after its `Action` variant. Branches refer to
+//! labels, and the bodies of functions, `try` and `with` blocks are nested.
+//! Actions which don't survive being read and written again by the `swf` crate
+//! are kept as `raw` hex.
+
+use crate::text::{hex, parse_hex, quote, Node, Token};
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU8;
+use swf::avm1::read::Reader;
+use swf::avm1::types::*;
+use swf::avm1::write::Writer;
+use swf::SwfStr;
+
+#[derive(Default)]
+struct Disassembler {
+    version: u8,
+
+    /// The last constant pool, used to annotate `Push` actions.
+    constant_pool: Vec<Vec<u8>>,
+
+    /// Labels are numbered across the whole action list.
+    next_label: usize,
+}
+
+/// Disassemble a list of actions.
+pub fn disassemble(data: &[u8], version: u8) -> Vec<Node> {
+    let mut disassembler = Disassembler {
+        version,
+        ..Default::default()
+    };
+    disassembler.block(data)
+}
+
+impl Disassembler {
+    fn block(&mut self, data: &[u8]) -> Vec<Node> {
+        // Split the data into actions, keeping the bytes of each one.
+        let mut actions = Vec::new();
+        let mut reader = Reader::new(data, self.version);
+        while !reader.get_ref().is_empty() {
+            let start = data.len() - reader.get_ref().len();
+            match reader.read_action() {
+                Ok(action) => {
+                    let end = data.len() - reader.get_ref().len();
+                    actions.push((start, Some(action), &data[start..end]));
+                }
+                Err(_) => {
+                    actions.push((start, None, &data[start..]));
+                    break;
+                }
+            }
+        }
+
+        // Branches to the start of an action, or to the end of the block, get a label.
+        let mut labels = BTreeMap::new();
+        for (start, action, bytes) in &actions {
+            if let Some(Action::If(If { offset }) | Action::Jump(Jump { offset })) = action {
+                let target = (start + bytes.len()) as isize + *offset as isize;
+                let is_boundary = target == data.len() as isize
+                    || actions
+                        .iter()
+                        .any(|(start, _, _)| *start as isize == target);
+                if is_boundary {
+                    labels.insert(target as usize, String::new());
+                }
+            }
+        }
+        for label in labels.values_mut() {
+            *label = format!("L{}", self.next_label);
+            self.next_label += 1;
+        }
+
+        let mut nodes = Vec::with_capacity(actions.len());
+        for (start, action, bytes) in actions {
+            if let Some(label) = labels.get(&start) {
+                nodes.push(Node::new(format!("{label}:")));
+            }
+            let end = start + bytes.len();
+            let node = match action {
+                Some(action) if self.round_trips(&action, bytes) => {
+                    self.action(&action, end, &labels)
+                }
+                _ => Node::new("raw").arg(hex(bytes)),
+            };
+            nodes.push(node);
+        }
+        if let Some(label) = labels.get(&data.len()) {
+            nodes.push(Node::new(format!("{label}:")));
+        }
+        nodes
+    }
+
+    /// Whether an action is written back to the same bytes.
+    fn round_trips(&self, action: &Action, bytes: &[u8]) -> bool {
+        let mut written = Vec::with_capacity(bytes.len());
+        Writer::new(&mut written, self.version)
+            .write_action(action)
+            .is_ok()
+            && written == bytes
+    }
+
+    fn action(&mut self, action: &Action, end: usize, labels: &BTreeMap<usize, String>) -> Node {
+        let branch = |offset: i16| {
+            let target = end as isize + offset as isize;
+            usize::try_from(target)
+                .ok()
+                .and_then(|target| labels.get(&target))
+                .cloned()
+                .unwrap_or_else(|| offset.to_string())
+        };
+
+        match action {
+            Action::ConstantPool(pool) => {
+                self.constant_pool = pool.strings.iter().map(|s| s.as_bytes().to_vec()).collect();
+                let mut node = Node::new("ConstantPool");
+                for string in &pool.strings {
+                    node = node.string(string.as_bytes());
+                }
+                node
+            }
+            Action::DefineFunction(function) => {
+                let mut node = Node::new("DefineFunction").string(function.name.as_bytes());
+                for param in &function.params {
+                    node = node.string(param.as_bytes());
+                }
+                node.block().with_children(self.block(function.actions))
+            }
+            Action::DefineFunction2(function) => {
+                let mut node = Node::new("DefineFunction2")
+                    .string(function.name.as_bytes())
+                    .arg(function.register_count)
+                    .block();
+                if !function.flags.is_empty() {
+                    node.push(Node::new("flags").args(function.flags.iter_names().map(|f| f.0)));
+                }
+                for param in &function.params {
+                    node.push(
+                        Node::new("param")
+                            .string(param.name.as_bytes())
+                            .arg(param.register_index.map_or(0, NonZeroU8::get)),
+                    );
+                }
+                node.with_children(self.block(function.actions))
+            }
+            Action::GetUrl(get_url) => Node::new("GetUrl")
+                .string(get_url.url.as_bytes())
+                .string(get_url.target.as_bytes()),
+            Action::GetUrl2(get_url) => {
+                let method = match get_url.send_vars_method() {
+                    SendVarsMethod::None => "none",
+                    SendVarsMethod::Get => "get",
+                    SendVarsMethod::Post => "post",
+                };
+                let mut node = Node::new("GetUrl2").arg(method);
+                if get_url.is_target_sprite() {
+                    node = node.arg("load_target");
+                }
+                if get_url.is_load_vars() {
+                    node = node.arg("load_variables");
+                }
+                node
+            }
+            Action::GotoFrame(goto) => Node::new("GotoFrame").arg(goto.frame),
+            Action::GotoFrame2(goto) => {
+                let node =
+                    Node::new("GotoFrame2").arg(if goto.set_playing { "play" } else { "stop" });
+                if goto.scene_offset != 0 {
+                    node.arg(goto.scene_offset)
+                } else {
+                    node
+                }
+            }
+            Action::GotoLabel(goto) => Node::new("GotoLabel").string(goto.label.as_bytes()),
+            Action::If(action) => Node::new("If").arg(branch(action.offset)),
+            Action::Jump(action) => Node::new("Jump").arg(branch(action.offset)),
+            Action::Push(push) => {
+                let mut node = Node::new("Push");
+                let mut constants = Vec::new();
+                for value in &push.values {
+                    node = match *value {
+                        Value::Undefined => node.arg("undefined"),
+                        Value::Null => node.arg("null"),
+                        Value::Bool(value) => node.arg(value),
+                        Value::Int(value) => node.arg(value),
+                        Value::Float(value) => node.arg(format!("f:{value:?}")),
+                        Value::Double(value) => node.arg(format!("d:{value:?}")),
+                        Value::Str(string) => node.string(string.as_bytes()),
+                        Value::Register(register) => node.arg(format!("r:{register}")),
+                        Value::ConstantPool(index) => {
+                            if let Some(string) = self.constant_pool.get(index as usize) {
+                                constants.push(format!("c:{index} = {}", quote(string)));
+                            }
+                            node.arg(format!("c:{index}"))
+                        }
+                    };
+                }
+                if constants.is_empty() {
+                    node
+                } else {
+                    node.comment(constants.join(", "))
+                }
+            }
+            Action::SetTarget(target) => Node::new("SetTarget").string(target.target.as_bytes()),
+            Action::StoreRegister(store) => Node::new("StoreRegister").arg(store.register),
+            Action::Try(action) => {
+                let mut node = Node::new("Try").child(
+                    Node::new("try")
+                        .block()
+                        .with_children(self.block(action.try_body)),
+                );
+                if let Some((var, body)) = &action.catch_body {
+                    let catch = match var {
+                        CatchVar::Var(name) => Node::new("catch").string(name.as_bytes()),
+                        CatchVar::Register(register) => {
+                            Node::new("catch").arg(format!("r:{register}"))
+                        }
+                    };
+                    node.push(catch.block().with_children(self.block(body)));
+                }
+                if let Some(body) = action.finally_body {
+                    node.push(Node::new("finally").block().with_children(self.block(body)));
+                }
+                node
+            }
+            Action::WaitForFrame(wait) => Node::new("WaitForFrame")
+                .arg(wait.frame)
+                .arg(wait.num_actions_to_skip),
+            Action::WaitForFrame2(wait) => Node::new("WaitForFrame2").arg(wait.num_actions_to_skip),
+            Action::With(with) => Node::new("With")
+                .block()
+                .with_children(self.block(with.actions)),
+            Action::Unknown(unknown) => {
+                let mut bytes = Vec::new();
+                let _ = Writer::new(&mut bytes, self.version).write_action(action);
+                Node::new("raw")
+                    .arg(hex(&bytes))
+                    .comment(format!("unknown action 0x{:02x}", unknown.opcode))
+            }
+            // The remaining actions have no payload, and are named after their variant.
+            action => Node::new(format!("{action:?}")),
+        }
+    }
+}
+
+/// Assemble a list of actions.
+pub fn assemble(nodes: &[Node], version: u8) -> Result<Vec<u8>> {
+    let opcodes = small_opcodes(version);
+    assemble_block(nodes, version, &opcodes)
+}
+
+/// The opcodes of the actions without payload, by name.
+fn small_opcodes(version: u8) -> HashMap<String, u8> {
+    (0..0x80)
+        .filter_map(
+            |opcode| match Reader::new(&[opcode], version).read_action() {
+                Ok(Action::Unknown(_)) | Err(_) => None,
+                Ok(action) => Some((format!("{action:?}"), opcode)),
+            },
+        )
+        .collect()
+}
+
+pub fn label_definition(node: &Node) -> Option<&str> {
+    match node.tokens.as_slice() {
+        [Token::Word(word)] => word.strip_suffix(':'),
+        _ => None,
+    }
+}
+
+fn assemble_block(nodes: &[Node], version: u8, opcodes: &HashMap<String, u8>) -> Result<Vec<u8>> {
+    // Assemble each action with unresolved branches, which have a fixed size.
+    let mut actions = Vec::with_capacity(nodes.len());
+    let mut labels = HashMap::new();
+    let mut position = 0;
+    for node in nodes {
+        if node.tokens.is_empty() {
+            continue;
+        }
+        if let Some(label) = label_definition(node) {
+            if labels.insert(label.to_string(), position).is_some() {
+                return Err(node.error(format!("duplicate label `{label}`")));
+            }
+            continue;
+        }
+        let bytes = assemble_action(node, version, opcodes, 0)?;
+        position += bytes.len();
+        actions.push((node, bytes));
+    }
+
+    // Then resolve the branches.
+    let mut data = Vec::with_capacity(position);
+    for (node, mut bytes) in actions {
+        if matches!(node.keyword(), "If" | "Jump") {
+            let end = data.len() + bytes.len();
+            let target = node.word(0)?;
+            let offset = match target.parse::<i16>() {
+                Ok(offset) => offset,
+                Err(_) => {
+                    let target = *labels
+                        .get(target)
+                        .ok_or_else(|| node.error(format!("unknown label `{target}`")))?;
+                    i16::try_from(target as isize - end as isize)
+                        .map_err(|_| node.error("branch out of range"))?
+                }
+            };
+            bytes = assemble_action(node, version, opcodes, offset)?;
+        }
+        data.extend(bytes);
+    }
+    Ok(data)
+}
+
+fn swf_str(bytes: &[u8]) -> &SwfStr {
+    SwfStr::from_bytes(bytes)
+}
+
+fn register(node: &Node, word: &str) -> Result<u8> {
+    word.strip_prefix("r:")
+        .and_then(|register| register.parse().ok())
+        .ok_or_else(|| node.error(format!("invalid register `{word}`")))
+}
+
+fn assemble_action(
+    node: &Node,
+    version: u8,
+    opcodes: &HashMap<String, u8>,
+    branch_offset: i16,
+) -> Result<Vec<u8>> {
+    let keyword = node.keyword();
+    if keyword == "raw" {
+        return parse_hex(node.word(0)?).map_err(|e| node.error(e));
+    }
+    if let Some(&opcode) = opcodes.get(keyword) {
+        return Ok(vec![opcode]);
+    }
+
+    // Nested blocks are assembled before the action borrows them.
+    let strings = |skip: usize| -> Result<Vec<&SwfStr>> {
+        node.arguments()
+            .iter()
+            .skip(skip)
+            .map(|token| match token {
+                Token::Str(bytes) => Ok(swf_str(bytes)),
+                Token::Word(word) => Err(node.error(format!("expected a string, found `{word}`"))),
+            })
+            .collect()
+    };
+    let body = |nodes: &[Node]| assemble_block(nodes, version, opcodes);
+
+    let mut bytes = Vec::new();
+    let mut writer = Writer::new(&mut bytes, version);
+    match keyword {
+        "ConstantPool" => writer.write_action(&Action::ConstantPool(ConstantPool {
+            strings: strings(0)?,
+        }))?,
+        "DefineFunction" => {
+            let actions = body(node.block_children()?)?;
+            writer.write_action(&Action::DefineFunction(DefineFunction {
+                name: swf_str(node.bytes(0)?),
+                params: strings(1)?,
+                actions: &actions,
+            }))?
+        }
+        "DefineFunction2" => {
+            let children = node.block_children()?;
+            let mut flags = FunctionFlags::empty();
+            let mut params = Vec::new();
+            let mut header_len = 0;
+            for child in children {
+                match child.keyword() {
+                    "flags" => {
+                        for token in child.arguments() {
+                            let name = token.to_string();
+                            flags |= FunctionFlags::from_name(&name)
+                                .ok_or_else(|| child.error(format!("unknown flag `{name}`")))?;
+                        }
+                    }
+                    "param" => params.push(FunctionParam {
+                        name: swf_str(child.bytes(0)?),
+                        register_index: NonZeroU8::new(child.parse(1)?),
+                    }),
+                    _ => break,
+                }
+                header_len += 1;
+            }
+            let actions = body(&children[header_len..])?;
+            writer.write_action(&Action::DefineFunction2(DefineFunction2 {
+                name: swf_str(node.bytes(0)?),
+                register_count: node.parse(1)?,
+                params,
+                flags,
+                actions: &actions,
+            }))?
+        }
+        "GetUrl" => writer.write_action(&Action::GetUrl(GetUrl {
+            url: swf_str(node.bytes(0)?),
+            target: swf_str(node.bytes(1)?),
+        }))?,
+        "GetUrl2" => {
+            let method = match node.word(0)? {
+                "none" => SendVarsMethod::None,
+                "get" => SendVarsMethod::Get,
+                "post" => SendVarsMethod::Post,
+                method => return Err(node.error(format!("unknown method `{method}`"))),
+            };
+            // The flags can't all be set through `GetUrl2`, so write the action by hand.
+            let mut flags = method as u8;
+            if node.has_flag("load_target") {
+                flags |= 1 << 6;
+            }
+            if node.has_flag("load_variables") {
+                flags |= 1 << 7;
+            }
+            return Ok(vec![0x9a, 1, 0, flags]);
+        }
+        "GotoFrame" => writer.write_action(&Action::GotoFrame(GotoFrame {
+            frame: node.parse(0)?,
+        }))?,
+        "GotoFrame2" => writer.write_action(&Action::GotoFrame2(GotoFrame2 {
+            set_playing: match node.word(0)? {
+                "play" => true,
+                "stop" => false,
+                word => {
+                    return Err(node.error(format!("expected `play` or `stop`, found `{word}`")))
+                }
+            },
+            scene_offset: if node.arguments().len() > 1 {
+                node.parse(1)?
+            } else {
+                0
+            },
+        }))?,
+        "GotoLabel" => writer.write_action(&Action::GotoLabel(GotoLabel {
+            label: swf_str(node.bytes(0)?),
+        }))?,
+        "If" => writer.write_action(&Action::If(If {
+            offset: branch_offset,
+        }))?,
+        "Jump" => writer.write_action(&Action::Jump(Jump {
+            offset: branch_offset,
+        }))?,
+        "Push" => {
+            let mut values = Vec::with_capacity(node.arguments().len());
+            for token in node.arguments() {
+                let value = match token {
+                    Token::Str(bytes) => Value::Str(swf_str(bytes)),
+                    Token::Word(word) => push_value(node, word)?,
+                };
+                values.push(value);
+            }
+            writer.write_action(&Action::Push(Push { values }))?
+        }
+        "SetTarget" => writer.write_action(&Action::SetTarget(SetTarget {
+            target: swf_str(node.bytes(0)?),
+        }))?,
+        "StoreRegister" => writer.write_action(&Action::StoreRegister(StoreRegister {
+            register: node.parse(0)?,
+        }))?,
+        "Try" => {
+            let try_body = body(node.field("try")?.block_children()?)?;
+            let catch = match node.find("catch") {
+                Some(catch) => {
+                    let var = match catch.arguments().first() {
+                        Some(Token::Str(name)) => CatchVar::Var(swf_str(name)),
+                        Some(Token::Word(word)) => CatchVar::Register(register(catch, word)?),
+                        None => return Err(catch.error("missing catch variable")),
+                    };
+                    Some((var, body(catch.block_children()?)?))
+                }
+                None => None,
+            };
+            let finally_body = match node.find("finally") {
+                Some(finally) => Some(body(finally.block_children()?)?),
+                None => None,
+            };
+            writer.write_action(&Action::Try(Try {
+                try_body: &try_body,
+                catch_body: catch.as_ref().map(|(var, body)| (var.clone(), &body[..])),
+                finally_body: finally_body.as_deref(),
+            }))?
+        }
+        "WaitForFrame" => writer.write_action(&Action::WaitForFrame(WaitForFrame {
+            frame: node.parse(0)?,
+            num_actions_to_skip: node.parse(1)?,
+        }))?,
+        "WaitForFrame2" => writer.write_action(&Action::WaitForFrame2(WaitForFrame2 {
+            num_actions_to_skip: node.parse(0)?,
+        }))?,
+        "With" => {
+            let actions = body(node.block_children()?)?;
+            writer.write_action(&Action::With(With { actions: &actions }))?
+        }
+        keyword => return Err(node.error(format!("unknown action `{keyword}`"))),
+    }
+    Ok(bytes)
+}
+
+fn push_value<'a>(node: &Node, word: &str) -> Result<Value<'a>> {
+    let invalid = || node.error(format!("invalid value `{word}`"));
+    Ok(match word {
+        "undefined" => Value::Undefined,
+        "null" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => match word.split_once(':') {
+            Some(("f", value)) => Value::Float(value.parse().map_err(|_| invalid())?),
+            Some(("d", value)) => Value::Double(value.parse().map_err(|_| invalid())?),
+            Some(("r", value)) => Value::Register(value.parse().map_err(|_| invalid())?),
+            Some(("c", value)) => Value::ConstantPool(value.parse().map_err(|_| invalid())?),
+            Some(_) => return Err(invalid()),
+            None => Value::Int(word.parse().map_err(|_| invalid())?),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn round_trip_branches_and_functions() {
+        let nodes = text::parse(
+            r#"
+            ConstantPool "a" "b"
+        L0:
+            Push c:0 1 d:1.5 f:-2 r:1 "x" undefined
+            If L1
+            DefineFunction2 "f" 2 {
+                flags PRELOAD_THIS
+                param "x" 1
+                Push r:1
+                Return
+            }
+            Jump L0
+        L1:
+            GotoFrame2 play
+            End
+            "#,
+        )
+        .unwrap();
+        let data = assemble(&nodes, 10).unwrap();
+        let disassembled = disassemble(&data, 10);
+        assert_eq!(assemble(&disassembled, 10).unwrap(), data);
+        assert!(!text::print(&disassembled).contains("raw"));
+    }
+}
@@ -0,0 +1,1351 @@
+//! AVM2 bytecode, as found in `DoAbc` tags.
+//!
+//! The ABC file is listed in the order it's stored in: the constant pools,
+//! followed by methods, metadata, instances, classes, scripts and method
+//! bodies. Entries refer to each other by index, as they do in the file, and
+//! comments give the index and name of each entry. Instructions are named after
+//! their `Op` variant, and instructions which don't survive being read and
+//! written again by the `swf` crate are kept as `raw` hex.
+
+use crate::avm1::label_definition;
+use crate::tags::{flags_node, parse_flags};
+use crate::text::{hex, parse_hex, quote, Node, Token};
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap};
+use swf::avm2::read::Reader;
+use swf::avm2::types::*;
+use swf::avm2::write::Writer;
+use swf::extensions::ReadSwfExt;
+
+/// Disassemble an ABC file.
+///
+/// Returns `None` if the file can't be read, or if its text form doesn't
+/// assemble back to the same bytes.
+pub fn disassemble(data: &[u8]) -> Option<Vec<Node>> {
+    let abc = Reader::new(data).read().ok()?;
+    let nodes = Disassembler { abc: &abc }.abc();
+    match assemble(&nodes) {
+        Ok(assembled) if assembled == data => Some(nodes),
+        _ => None,
+    }
+}
+
+/// Assemble an ABC file.
+pub fn assemble(nodes: &[Node]) -> Result<Vec<u8>> {
+    let mut abc = AbcFile {
+        major_version: 0,
+        minor_version: 0,
+        constant_pool: ConstantPool {
+            ints: vec![],
+            uints: vec![],
+            doubles: vec![],
+            strings: vec![],
+            namespaces: vec![],
+            namespace_sets: vec![],
+            multinames: vec![],
+        },
+        methods: vec![],
+        metadata: vec![],
+        instances: vec![],
+        classes: vec![],
+        scripts: vec![],
+        method_bodies: vec![],
+    };
+    for node in nodes {
+        match node.keyword() {
+            "" => {}
+            "version" => {
+                abc.major_version = node.parse(0)?;
+                abc.minor_version = node.parse(1)?;
+            }
+            "constants" => assemble_constants(node, &mut abc.constant_pool)?,
+            "method" => abc.methods.push(assemble_method(node)?),
+            "metadata" => abc.metadata.push(Metadata {
+                name: index(node, 0)?,
+                items: node
+                    .block_children()?
+                    .iter()
+                    .filter(|child| !child.tokens.is_empty())
+                    .map(|item| {
+                        Ok(MetadataItem {
+                            key: index(item, 0)?,
+                            value: index(item, 1)?,
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            }),
+            "instance" => abc.instances.push(assemble_instance(node)?),
+            "class" => abc.classes.push(Class {
+                init_method: index(node.field("init")?, 0)?,
+                traits: assemble_traits(node)?,
+            }),
+            "script" => abc.scripts.push(Script {
+                init_method: index(node.field("init")?, 0)?,
+                traits: assemble_traits(node)?,
+            }),
+            "body" => abc.method_bodies.push(assemble_body(node)?),
+            keyword => return Err(node.error(format!("unknown entry `{keyword}`"))),
+        }
+    }
+
+    let mut data = Vec::new();
+    Writer::new(&mut data)
+        .write(abc)
+        .map_err(|e| anyhow!("{e}"))?;
+    Ok(data)
+}
+
+struct Disassembler<'a> {
+    abc: &'a AbcFile,
+}
+
+impl Disassembler<'_> {
+    fn abc(&self) -> Vec<Node> {
+        let abc = self.abc;
+        let mut nodes = vec![
+            Node::new("version")
+                .arg(abc.major_version)
+                .arg(abc.minor_version),
+            self.constants(),
+        ];
+
+        for (i, method) in abc.methods.iter().enumerate() {
+            let mut node = Node::new("method")
+                .block()
+                .child(Node::new("name").arg(method.name.0))
+                .child(
+                    Node::new("return_type")
+                        .arg(method.return_type.0)
+                        .comment(self.multiname_name(&method.return_type)),
+                );
+            for param in &method.params {
+                let mut param_node = Node::new("param").arg(param.kind.0);
+                if let Some(name) = &param.name {
+                    param_node = param_node.arg("name").arg(name.0);
+                }
+                if let Some(value) = &param.default_value {
+                    param_node = param_node.arg("default").args(default_value_words(value));
+                }
+                node.push(param_node.comment(self.multiname_name(&param.kind)));
+            }
+            if !method.flags.is_empty() {
+                node.push(flags_node(method.flags));
+            }
+            nodes.push(node.comment(self.entry_comment(i, self.string(&method.name))));
+        }
+
+        for (i, metadata) in abc.metadata.iter().enumerate() {
+            let node = Node::new("metadata")
+                .arg(metadata.name.0)
+                .block()
+                .with_children(
+                    metadata
+                        .items
+                        .iter()
+                        .map(|item| Node::new("item").arg(item.key.0).arg(item.value.0)),
+                );
+            nodes.push(node.comment(self.entry_comment(i, self.string(&metadata.name))));
+        }
+
+        for (i, instance) in abc.instances.iter().enumerate() {
+            let mut node = Node::new("instance")
+                .block()
+                .child(Node::new("name").arg(instance.name.0))
+                .child(
+                    Node::new("super_name")
+                        .arg(instance.super_name.0)
+                        .comment(self.multiname_name(&instance.super_name)),
+                );
+            let flags = [
+                (instance.is_sealed, "sealed"),
+                (instance.is_final, "final"),
+                (instance.is_interface, "interface"),
+            ];
+            if flags.iter().any(|(set, _)| *set) {
+                node.push(
+                    Node::new("flags").args(flags.iter().filter(|(set, _)| *set).map(|f| f.1)),
+                );
+            }
+            if let Some(namespace) = &instance.protected_namespace {
+                node.push(Node::new("protected_namespace").arg(namespace.0));
+            }
+            for interface in &instance.interfaces {
+                node.push(
+                    Node::new("interface")
+                        .arg(interface.0)
+                        .comment(self.multiname_name(interface)),
+                );
+            }
+            node.push(Node::new("init").arg(instance.init_method.0));
+            node = node.with_children(self.traits(&instance.traits));
+            let name = self.multiname_name(&instance.name);
+            nodes.push(node.comment(self.entry_comment(i, Some(&name))));
+        }
+
+        for (i, class) in abc.classes.iter().enumerate() {
+            let node = Node::new("class")
+                .block()
+                .child(Node::new("init").arg(class.init_method.0))
+                .with_children(self.traits(&class.traits));
+            let name = abc
+                .instances
+                .get(i)
+                .map(|instance| self.multiname_name(&instance.name));
+            nodes.push(node.comment(self.entry_comment(i, name.as_deref())));
+        }
+
+        for (i, script) in abc.scripts.iter().enumerate() {
+            let node = Node::new("script")
+                .block()
+                .child(Node::new("init").arg(script.init_method.0))
+                .with_children(self.traits(&script.traits));
+            nodes.push(node.comment(self.entry_comment(i, None)));
+        }
+
+        for body in &abc.method_bodies {
+            nodes.push(self.body(body));
+        }
+        nodes
+    }
+
+    fn constants(&self) -> Node {
+        let pool = &self.abc.constant_pool;
+        let mut node = Node::new("constants").block();
+
+        // Index 0 of each pool is implicit, so entries start at 1.
+        for (i, value) in pool.ints.iter().enumerate() {
+            node.push(Node::new("int").arg(value).comment(format!("#{}", i + 1)));
+        }
+        for (i, value) in pool.uints.iter().enumerate() {
+            node.push(Node::new("uint").arg(value).comment(format!("#{}", i + 1)));
+        }
+        for (i, value) in pool.doubles.iter().enumerate() {
+            node.push(
+                Node::new("double")
+                    .arg(format!("{value:?}"))
+                    .comment(format!("#{}", i + 1)),
+            );
+        }
+        for (i, value) in pool.strings.iter().enumerate() {
+            node.push(
+                Node::new("string")
+                    .string(value.as_bytes())
+                    .comment(format!("#{}", i + 1)),
+            );
+        }
+        for (i, namespace) in pool.namespaces.iter().enumerate() {
+            let (kind, name) = namespace_kind(namespace);
+            node.push(
+                Node::new("namespace")
+                    .arg(kind)
+                    .arg(name.0)
+                    .comment(format!(
+                        "#{} = {}",
+                        i + 1,
+                        quote(self.string(name).unwrap_or_default().as_bytes())
+                    )),
+            );
+        }
+        for (i, set) in pool.namespace_sets.iter().enumerate() {
+            node.push(
+                Node::new("namespace_set")
+                    .args(set.iter().map(|namespace| namespace.0))
+                    .comment(format!("#{}", i + 1)),
+            );
+        }
+        for (i, multiname) in pool.multinames.iter().enumerate() {
+            let name = self.multiname_name(&Index::new(i as u32 + 1));
+            node.push(multiname_node(multiname).comment(format!("#{} = {name}", i + 1)));
+        }
+        node
+    }
+
+    fn traits(&self, traits: &[Trait]) -> Vec<Node> {
+        traits
+            .iter()
+            .map(|t| {
+                let (kind, id, index) = match &t.kind {
+                    TraitKind::Slot {
+                        slot_id, type_name, ..
+                    } => ("slot", slot_id, type_name.0),
+                    TraitKind::Const {
+                        slot_id, type_name, ..
+                    } => ("const", slot_id, type_name.0),
+                    TraitKind::Method { disp_id, method } => ("method", disp_id, method.0),
+                    TraitKind::Getter { disp_id, method } => ("getter", disp_id, method.0),
+                    TraitKind::Setter { disp_id, method } => ("setter", disp_id, method.0),
+                    TraitKind::Class { slot_id, class } => ("class", slot_id, class.0),
+                    TraitKind::Function { slot_id, function } => ("function", slot_id, function.0),
+                };
+                let mut node = Node::new("trait")
+                    .arg(kind)
+                    .arg(t.name.0)
+                    .arg(id)
+                    .arg(index);
+                if let TraitKind::Slot {
+                    value: Some(value), ..
+                }
+                | TraitKind::Const {
+                    value: Some(value), ..
+                } = &t.kind
+                {
+                    node = node.arg("value").args(default_value_words(value));
+                }
+                if t.is_final {
+                    node = node.arg("final");
+                }
+                if t.is_override {
+                    node = node.arg("override");
+                }
+                if !t.metadata.is_empty() {
+                    node = node
+                        .arg("metadata")
+                        .args(t.metadata.iter().map(|metadata| metadata.0));
+                }
+                node.comment(self.multiname_name(&t.name))
+            })
+            .collect()
+    }
+
+    fn body(&self, body: &MethodBody) -> Node {
+        let code = &body.code;
+
+        // Split the code into instructions, keeping the bytes of each one.
+        let mut ops = Vec::new();
+        let mut reader = Reader::new(code);
+        while !reader.as_slice().is_empty() {
+            let start = code.len() - reader.as_slice().len();
+            match reader.read_op() {
+                Ok(op) => {
+                    let bytes = &code[start..code.len() - reader.as_slice().len()];
+                    let op = Some(op).filter(|op| assemble_op_bytes(op).is_ok_and(|b| b == bytes));
+                    ops.push((start, op, bytes));
+                }
+                Err(_) => {
+                    ops.push((start, None, &code[start..]));
+                    break;
+                }
+            }
+        }
+
+        // Branches and exception handlers pointing to the start of an
+        // instruction, or to the end of the code, get a label.
+        let mut targets = Vec::new();
+        for (start, op, bytes) in &ops {
+            let end = (start + bytes.len()) as i64;
+            match op {
+                Some(Op::LookupSwitch {
+                    default_offset,
+                    case_offsets,
+                }) => targets.extend(
+                    std::iter::once(default_offset)
+                        .chain(case_offsets.iter())
+                        .map(|offset| *start as i64 + *offset as i64),
+                ),
+                Some(op) => targets.extend(branch_offset(op).map(|offset| end + offset as i64)),
+                None => {}
+            }
+        }
+        for exception in &body.exceptions {
+            targets.extend(
+                [
+                    exception.from_offset,
+                    exception.to_offset,
+                    exception.target_offset,
+                ]
+                .map(i64::from),
+            );
+        }
+        let mut labels = BTreeMap::new();
+        for target in targets {
+            let is_boundary = target == code.len() as i64
+                || ops.iter().any(|(start, _, _)| *start as i64 == target);
+            if is_boundary {
+                labels.insert(target as usize, String::new());
+            }
+        }
+        for (i, label) in labels.values_mut().enumerate() {
+            *label = format!("L{i}");
+        }
+
+        let mut code_node = Node::new("code").block();
+        for (start, op, bytes) in &ops {
+            if let Some(label) = labels.get(start) {
+                code_node.push(Node::new(format!("{label}:")));
+            }
+            let end = start + bytes.len();
+            code_node.push(match op {
+                Some(op @ Op::LookupSwitch { .. }) => self.op(op, *start, &labels),
+                Some(op) => self.op(op, end, &labels),
+                None => Node::new("raw").arg(hex(bytes)),
+            });
+        }
+        if let Some(label) = labels.get(&code.len()) {
+            code_node.push(Node::new(format!("{label}:")));
+        }
+
+        let offset = |offset: u32| {
+            labels
+                .get(&(offset as usize))
+                .cloned()
+                .unwrap_or_else(|| offset.to_string())
+        };
+        let method_name = self
+            .abc
+            .methods
+            .get(body.method.0 as usize)
+            .and_then(|method| self.string(&method.name))
+            .filter(|name| !name.is_empty())
+            .map(|name| quote(name.as_bytes()));
+        let mut node = Node::new("body")
+            .block()
+            .child(Node::new("method").arg(body.method.0))
+            .child(Node::new("max_stack").arg(body.max_stack))
+            .child(Node::new("num_locals").arg(body.num_locals))
+            .child(Node::new("init_scope_depth").arg(body.init_scope_depth))
+            .child(Node::new("max_scope_depth").arg(body.max_scope_depth))
+            .child(code_node);
+        for exception in &body.exceptions {
+            node.push(
+                Node::new("exception")
+                    .arg(offset(exception.from_offset))
+                    .arg(offset(exception.to_offset))
+                    .arg(offset(exception.target_offset))
+                    .arg(exception.type_name.0)
+                    .arg(exception.variable_name.0)
+                    .comment(format!(
+                        "{} {}",
+                        self.multiname_name(&exception.type_name),
+                        self.multiname_name(&exception.variable_name)
+                    )),
+            );
+        }
+        node = node.with_children(self.traits(&body.traits));
+        match method_name {
+            Some(name) => node.comment(name),
+            None => node,
+        }
+    }
+
+    /// An instruction whose branches are relative to `base`.
+    fn op(&self, op: &Op, base: usize, labels: &BTreeMap<usize, String>) -> Node {
+        let context = PrintContext { base, labels };
+        let (mut node, names) = print_op(op, &context, self);
+        if let Op::LookupSwitch {
+            default_offset,
+            case_offsets,
+        } = op
+        {
+            node = Node::new("LookupSwitch")
+                .arg(default_offset.print(&context))
+                .args(case_offsets.iter().map(|offset| offset.print(&context)));
+        }
+        if names.is_empty() {
+            node
+        } else {
+            node.comment(names.join(", "))
+        }
+    }
+
+    fn entry_comment(&self, index: usize, name: Option<&str>) -> String {
+        match name {
+            Some(name) if !name.is_empty() => format!("#{index} {name}"),
+            _ => format!("#{index}"),
+        }
+    }
+
+    fn string(&self, index: &Index<String>) -> Option<&str> {
+        let strings = &self.abc.constant_pool.strings;
+        let string = strings.get(index.0.checked_sub(1)? as usize)?;
+        Some(string)
+    }
+
+    fn namespace_name(&self, index: &Index<Namespace>) -> String {
+        let namespaces = &self.abc.constant_pool.namespaces;
+        match index
+            .0
+            .checked_sub(1)
+            .and_then(|i| namespaces.get(i as usize))
+        {
+            Some(namespace) => self
+                .string(namespace_kind(namespace).1)
+                .unwrap_or_default()
+                .to_string(),
+            None => "*".to_string(),
+        }
+    }
+
+    /// A readable name of a multiname, such as `flash.display::Sprite`.
+    fn multiname_name(&self, index: &Index<Multiname>) -> String {
+        self.multiname_name_nested(index, 0)
+    }
+
+    fn multiname_name_nested(&self, index: &Index<Multiname>, depth: usize) -> String {
+        let multinames = &self.abc.constant_pool.multinames;
+        let Some(multiname) = index
+            .0
+            .checked_sub(1)
+            .and_then(|i| multinames.get(i as usize))
+        else {
+            return "*".to_string();
+        };
+        let name = |name| self.string(name).unwrap_or("*").to_string();
+        match multiname {
+            Multiname::QName {
+                namespace,
+                name: local,
+            }
+            | Multiname::QNameA {
+                namespace,
+                name: local,
+            } => {
+                let namespace = self.namespace_name(namespace);
+                if namespace.is_empty() {
+                    name(local)
+                } else {
+                    format!("{namespace}::{}", name(local))
+                }
+            }
+            Multiname::RTQName { name: local }
+            | Multiname::RTQNameA { name: local }
+            | Multiname::Multiname { name: local, .. }
+            | Multiname::MultinameA { name: local, .. } => name(local),
+            Multiname::RTQNameL
+            | Multiname::RTQNameLA
+            | Multiname::MultinameL { .. }
+            | Multiname::MultinameLA { .. } => "[runtime]".to_string(),
+            // Type names can refer to themselves, so only a few levels are shown.
+            Multiname::TypeName { .. } if depth > 2 => "...".to_string(),
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|parameter| self.multiname_name_nested(parameter, depth + 1))
+                    .collect();
+                format!(
+                    "{}.<{}>",
+                    self.multiname_name_nested(base_type, depth + 1),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+}
+
+fn namespace_kind(namespace: &Namespace) -> (&'static str, &Index<String>) {
+    match namespace {
+        Namespace::Namespace(name) => ("namespace", name),
+        Namespace::Package(name) => ("package", name),
+        Namespace::PackageInternal(name) => ("package_internal", name),
+        Namespace::Protected(name) => ("protected", name),
+        Namespace::Explicit(name) => ("explicit", name),
+        Namespace::StaticProtected(name) => ("static_protected", name),
+        Namespace::Private(name) => ("private", name),
+    }
+}
+
+fn multiname_node(multiname: &Multiname) -> Node {
+    let node = Node::new("multiname");
+    match multiname {
+        Multiname::QName { namespace, name } => node.arg("qname").arg(namespace.0).arg(name.0),
+        Multiname::QNameA { namespace, name } => node.arg("qname_a").arg(namespace.0).arg(name.0),
+        Multiname::RTQName { name } => node.arg("rtqname").arg(name.0),
+        Multiname::RTQNameA { name } => node.arg("rtqname_a").arg(name.0),
+        Multiname::RTQNameL => node.arg("rtqname_l"),
+        Multiname::RTQNameLA => node.arg("rtqname_la"),
+        Multiname::Multiname {
+            namespace_set,
+            name,
+        } => node.arg("multiname").arg(namespace_set.0).arg(name.0),
+        Multiname::MultinameA {
+            namespace_set,
+            name,
+        } => node.arg("multiname_a").arg(namespace_set.0).arg(name.0),
+        Multiname::MultinameL { namespace_set } => node.arg("multiname_l").arg(namespace_set.0),
+        Multiname::MultinameLA { namespace_set } => node.arg("multiname_la").arg(namespace_set.0),
+        Multiname::TypeName {
+            base_type,
+            parameters,
+        } => node
+            .arg("typename")
+            .arg(base_type.0)
+            .args(parameters.iter().map(|parameter| parameter.0)),
+    }
+}
+
+fn default_value_words(value: &DefaultValue) -> Vec<String> {
+    let (kind, index) = match value {
+        DefaultValue::Undefined => ("undefined", None),
+        DefaultValue::True => ("true", None),
+        DefaultValue::False => ("false", None),
+        DefaultValue::Null => ("null", None),
+        DefaultValue::Int(index) => ("int", Some(index.0)),
+        DefaultValue::Uint(index) => ("uint", Some(index.0)),
+        DefaultValue::Double(index) => ("double", Some(index.0)),
+        DefaultValue::String(index) => ("string", Some(index.0)),
+        DefaultValue::Namespace(index) => ("namespace", Some(index.0)),
+        DefaultValue::Package(index) => ("package", Some(index.0)),
+        DefaultValue::PackageInternal(index) => ("package_internal", Some(index.0)),
+        DefaultValue::Protected(index) => ("protected", Some(index.0)),
+        DefaultValue::Explicit(index) => ("explicit", Some(index.0)),
+        DefaultValue::StaticProtected(index) => ("static_protected", Some(index.0)),
+        DefaultValue::Private(index) => ("private", Some(index.0)),
+    };
+    std::iter::once(kind.to_string())
+        .chain(index.map(|index| index.to_string()))
+        .collect()
+}
+
+/// Parse a default value starting at argument `start`, returning it and the
+/// number of arguments it used.
+fn parse_default_value(node: &Node, start: usize) -> Result<(DefaultValue, usize)> {
+    let kind = node.word(start)?;
+    let value = match kind {
+        "undefined" => DefaultValue::Undefined,
+        "true" => DefaultValue::True,
+        "false" => DefaultValue::False,
+        "null" => DefaultValue::Null,
+        _ => {
+            let index = node.parse(start + 1)?;
+            let value = match kind {
+                "int" => DefaultValue::Int(Index::new(index)),
+                "uint" => DefaultValue::Uint(Index::new(index)),
+                "double" => DefaultValue::Double(Index::new(index)),
+                "string" => DefaultValue::String(Index::new(index)),
+                "namespace" => DefaultValue::Namespace(Index::new(index)),
+                "package" => DefaultValue::Package(Index::new(index)),
+                "package_internal" => DefaultValue::PackageInternal(Index::new(index)),
+                "protected" => DefaultValue::Protected(Index::new(index)),
+                "explicit" => DefaultValue::Explicit(Index::new(index)),
+                "static_protected" => DefaultValue::StaticProtected(Index::new(index)),
+                "private" => DefaultValue::Private(Index::new(index)),
+                _ => return Err(node.error(format!("unknown value kind `{kind}`"))),
+            };
+            return Ok((value, 2));
+        }
+    };
+    Ok((value, 1))
+}
+
+fn index<T>(node: &Node, argument: usize) -> Result<Index<T>> {
+    Ok(Index::new(node.parse(argument)?))
+}
+
+fn assemble_constants(node: &Node, pool: &mut ConstantPool) -> Result<()> {
+    for child in node.block_children()? {
+        match child.keyword() {
+            "" => {}
+            "int" => pool.ints.push(child.parse(0)?),
+            "uint" => pool.uints.push(child.parse(0)?),
+            "double" => pool.doubles.push(child.parse(0)?),
+            "string" => pool.strings.push(
+                String::from_utf8(child.bytes(0)?.to_vec())
+                    .map_err(|_| child.error("strings must be valid UTF-8"))?,
+            ),
+            "namespace" => {
+                let name = index(child, 1)?;
+                pool.namespaces.push(match child.word(0)? {
+                    "namespace" => Namespace::Namespace(name),
+                    "package" => Namespace::Package(name),
+                    "package_internal" => Namespace::PackageInternal(name),
+                    "protected" => Namespace::Protected(name),
+                    "explicit" => Namespace::Explicit(name),
+                    "static_protected" => Namespace::StaticProtected(name),
+                    "private" => Namespace::Private(name),
+                    kind => return Err(child.error(format!("unknown namespace kind `{kind}`"))),
+                });
+            }
+            "namespace_set" => pool.namespace_sets.push(
+                (0..child.arguments().len())
+                    .map(|i| index(child, i))
+                    .collect::<Result<_>>()?,
+            ),
+            "multiname" => pool.multinames.push(match child.word(0)? {
+                "qname" => Multiname::QName {
+                    namespace: index(child, 1)?,
+                    name: index(child, 2)?,
+                },
+                "qname_a" => Multiname::QNameA {
+                    namespace: index(child, 1)?,
+                    name: index(child, 2)?,
+                },
+                "rtqname" => Multiname::RTQName {
+                    name: index(child, 1)?,
+                },
+                "rtqname_a" => Multiname::RTQNameA {
+                    name: index(child, 1)?,
+                },
+                "rtqname_l" => Multiname::RTQNameL,
+                "rtqname_la" => Multiname::RTQNameLA,
+                "multiname" => Multiname::Multiname {
+                    namespace_set: index(child, 1)?,
+                    name: index(child, 2)?,
+                },
+                "multiname_a" => Multiname::MultinameA {
+                    namespace_set: index(child, 1)?,
+                    name: index(child, 2)?,
+                },
+                "multiname_l" => Multiname::MultinameL {
+                    namespace_set: index(child, 1)?,
+                },
+                "multiname_la" => Multiname::MultinameLA {
+                    namespace_set: index(child, 1)?,
+                },
+                "typename" => Multiname::TypeName {
+                    base_type: index(child, 1)?,
+                    parameters: (2..child.arguments().len())
+                        .map(|i| index(child, i))
+                        .collect::<Result<_>>()?,
+                },
+                kind => return Err(child.error(format!("unknown multiname kind `{kind}`"))),
+            }),
+            keyword => return Err(child.error(format!("unknown constant `{keyword}`"))),
+        }
+    }
+    Ok(())
+}
+
+fn assemble_method(node: &Node) -> Result<Method> {
+    let mut params = Vec::new();
+    for param in node.block_children()? {
+        if param.keyword() != "param" {
+            continue;
+        }
+        let mut method_param = MethodParam {
+            kind: index(param, 0)?,
+            name: None,
+            default_value: None,
+        };
+        let mut i = 1;
+        while i < param.arguments().len() {
+            match param.word(i)? {
+                "name" => {
+                    method_param.name = Some(index(param, i + 1)?);
+                    i += 2;
+                }
+                "default" => {
+                    let (value, len) = parse_default_value(param, i + 1)?;
+                    method_param.default_value = Some(value);
+                    i += 1 + len;
+                }
+                word => return Err(param.error(format!("unexpected `{word}`"))),
+            }
+        }
+        params.push(method_param);
+    }
+
+    Ok(Method {
+        name: index(node.field("name")?, 0)?,
+        params,
+        return_type: index(node.field("return_type")?, 0)?,
+        flags: match node.find("flags") {
+            Some(flags) => parse_flags(flags)?,
+            None => MethodFlags::empty(),
+        },
+    })
+}
+
+fn assemble_instance(node: &Node) -> Result<Instance> {
+    let flags = node.find("flags");
+    let has_flag = |flag| flags.is_some_and(|flags| flags.has_flag(flag));
+    Ok(Instance {
+        name: index(node.field("name")?, 0)?,
+        super_name: index(node.field("super_name")?, 0)?,
+        is_sealed: has_flag("sealed"),
+        is_final: has_flag("final"),
+        is_interface: has_flag("interface"),
+        protected_namespace: node
+            .find("protected_namespace")
+            .map(|namespace| index(namespace, 0))
+            .transpose()?,
+        interfaces: node
+            .children()
+            .iter()
+            .filter(|child| child.keyword() == "interface")
+            .map(|interface| index(interface, 0))
+            .collect::<Result<_>>()?,
+        init_method: index(node.field("init")?, 0)?,
+        traits: assemble_traits(node)?,
+    })
+}
+
+fn assemble_traits(node: &Node) -> Result<Vec<Trait>> {
+    node.children()
+        .iter()
+        .filter(|child| child.keyword() == "trait")
+        .map(|node| {
+            let id = node.parse(2)?;
+            let kind = match node.word(0)? {
+                "slot" | "const" => {
+                    let value = if node.arguments().len() > 4 && node.word(4)? == "value" {
+                        Some(parse_default_value(node, 5)?.0)
+                    } else {
+                        None
+                    };
+                    if node.word(0)? == "slot" {
+                        TraitKind::Slot {
+                            slot_id: id,
+                            type_name: index(node, 3)?,
+                            value,
+                        }
+                    } else {
+                        TraitKind::Const {
+                            slot_id: id,
+                            type_name: index(node, 3)?,
+                            value,
+                        }
+                    }
+                }
+                "method" => TraitKind::Method {
+                    disp_id: id,
+                    method: index(node, 3)?,
+                },
+                "getter" => TraitKind::Getter {
+                    disp_id: id,
+                    method: index(node, 3)?,
+                },
+                "setter" => TraitKind::Setter {
+                    disp_id: id,
+                    method: index(node, 3)?,
+                },
+                "class" => TraitKind::Class {
+                    slot_id: id,
+                    class: index(node, 3)?,
+                },
+                "function" => TraitKind::Function {
+                    slot_id: id,
+                    function: index(node, 3)?,
+                },
+                kind => return Err(node.error(format!("unknown trait kind `{kind}`"))),
+            };
+            let metadata = match node
+                .arguments()
+                .iter()
+                .position(|token| matches!(token, Token::Word(word) if word == "metadata"))
+            {
+                Some(position) => (position + 1..node.arguments().len())
+                    .map(|i| index(node, i))
+                    .collect::<Result<_>>()?,
+                None => vec![],
+            };
+            Ok(Trait {
+                name: index(node, 1)?,
+                kind,
+                metadata,
+                is_final: node.has_flag("final"),
+                is_override: node.has_flag("override"),
+            })
+        })
+        .collect()
+}
+
+fn assemble_body(node: &Node) -> Result<MethodBody> {
+    let (code, labels) = assemble_code(node.field("code")?.block_children()?)?;
+    let offset = |node: &Node, argument: usize| -> Result<u32> {
+        let word = node.word(argument)?;
+        match labels.get(word) {
+            Some(&position) => Ok(position as u32),
+            None => node.parse(argument),
+        }
+    };
+    Ok(MethodBody {
+        method: index(node.field("method")?, 0)?,
+        max_stack: node.field("max_stack")?.parse(0)?,
+        num_locals: node.field("num_locals")?.parse(0)?,
+        init_scope_depth: node.field("init_scope_depth")?.parse(0)?,
+        max_scope_depth: node.field("max_scope_depth")?.parse(0)?,
+        code,
+        exceptions: node
+            .children()
+            .iter()
+            .filter(|child| child.keyword() == "exception")
+            .map(|exception| {
+                Ok(Exception {
+                    from_offset: offset(exception, 0)?,
+                    to_offset: offset(exception, 1)?,
+                    target_offset: offset(exception, 2)?,
+                    type_name: index(exception, 3)?,
+                    variable_name: index(exception, 4)?,
+                })
+            })
+            .collect::<Result<_>>()?,
+        traits: assemble_traits(node)?,
+    })
+}
+
+/// Assemble code, returning it and the positions of its labels.
+fn assemble_code(nodes: &[Node]) -> Result<(Vec<u8>, HashMap<String, usize>)> {
+    // Assemble each instruction with unresolved branches, which have a fixed size.
+    let mut ops = Vec::with_capacity(nodes.len());
+    let mut labels = HashMap::new();
+    let mut position = 0;
+    for node in nodes {
+        if node.tokens.is_empty() {
+            continue;
+        }
+        if let Some(label) = label_definition(node) {
+            if labels.insert(label.to_string(), position).is_some() {
+                return Err(node.error(format!("duplicate label `{label}`")));
+            }
+            continue;
+        }
+        let context = ParseContext {
+            base: 0,
+            labels: None,
+        };
+        let len = assemble_instruction(node, &context)?.len();
+        ops.push((node, position, len));
+        position += len;
+    }
+
+    // Then resolve the branches.
+    let mut code = Vec::with_capacity(position);
+    for (node, start, len) in ops {
+        let base = if node.keyword() == "LookupSwitch" {
+            start
+        } else {
+            start + len
+        };
+        let context = ParseContext {
+            base,
+            labels: Some(&labels),
+        };
+        code.extend(assemble_instruction(node, &context)?);
+    }
+    Ok((code, labels))
+}
+
+fn assemble_instruction(node: &Node, context: &ParseContext) -> Result<Vec<u8>> {
+    if node.keyword() == "raw" {
+        return parse_hex(node.word(0)?).map_err(|e| node.error(e));
+    }
+    let op = parse_op(node, context)?;
+    assemble_op_bytes(&op).map_err(|e| node.error(e))
+}
+
+fn assemble_op_bytes(op: &Op) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    Writer::new(&mut bytes)
+        .write_op(op)
+        .map_err(|e| anyhow!("{e}"))?;
+    Ok(bytes)
+}
+
+/// The offset of a branch instruction, other than `LookupSwitch`.
+fn branch_offset(op: &Op) -> Option<i32> {
+    match *op {
+        Op::IfEq { offset }
+        | Op::IfFalse { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNe { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfTrue { offset }
+        | Op::Jump { offset } => Some(offset),
+        _ => None,
+    }
+}
+
+/// Branches are relative to the end of their instruction, except for
+/// `LookupSwitch`, which is relative to its start.
+struct PrintContext<'a> {
+    base: usize,
+    labels: &'a BTreeMap<usize, String>,
+}
+
+/// As `PrintContext`, with labels unknown before the first pass.
+struct ParseContext<'a> {
+    base: usize,
+    labels: Option<&'a HashMap<String, usize>>,
+}
+
+/// An operand of an instruction.
+trait Operand: Sized {
+    fn print(&self, context: &PrintContext) -> String;
+
+    fn parse(word: &str, context: &ParseContext) -> Option<Self>;
+
+    /// What the operand refers to, for comments.
+    fn annotate(&self, _disassembler: &Disassembler) -> Option<String> {
+        None
+    }
+}
+
+macro_rules! impl_number_operand {
+    ($($type:ty),*) => {
+        $(impl Operand for $type {
+            fn print(&self, _context: &PrintContext) -> String {
+                self.to_string()
+            }
+
+            fn parse(word: &str, _context: &ParseContext) -> Option<Self> {
+                word.parse().ok()
+            }
+        })*
+    };
+}
+
+impl_number_operand!(u8, i16, u32, bool);
+
+/// Branch offsets, which are the only `i32` operands.
+impl Operand for i32 {
+    fn print(&self, context: &PrintContext) -> String {
+        let target = context.base as i64 + *self as i64;
+        usize::try_from(target)
+            .ok()
+            .and_then(|target| context.labels.get(&target))
+            .cloned()
+            .unwrap_or_else(|| self.to_string())
+    }
+
+    fn parse(word: &str, context: &ParseContext) -> Option<Self> {
+        if let Ok(offset) = word.parse() {
+            return Some(offset);
+        }
+        match context.labels {
+            Some(labels) => {
+                let target = *labels.get(word)? as i64;
+                i32::try_from(target - context.base as i64).ok()
+            }
+            None => Some(0),
+        }
+    }
+}
+
+/// Entries which can be named in comments.
+trait Named: Sized {
+    fn name(_index: &Index<Self>, _disassembler: &Disassembler) -> Option<String> {
+        None
+    }
+}
+
+impl Named for Multiname {
+    fn name(index: &Index<Self>, disassembler: &Disassembler) -> Option<String> {
+        Some(disassembler.multiname_name(index))
+    }
+}
+
+impl Named for String {
+    fn name(index: &Index<Self>, disassembler: &Disassembler) -> Option<String> {
+        Some(quote(disassembler.string(index)?.as_bytes()))
+    }
+}
+
+impl Named for Namespace {
+    fn name(index: &Index<Self>, disassembler: &Disassembler) -> Option<String> {
+        Some(quote(disassembler.namespace_name(index).as_bytes()))
+    }
+}
+
+macro_rules! impl_named_constant {
+    ($($type:ty => $pool:ident),*) => {
+        $(impl Named for $type {
+            fn name(index: &Index<Self>, disassembler: &Disassembler) -> Option<String> {
+                let pool = &disassembler.abc.constant_pool.$pool;
+                Some(format!("{:?}", pool.get(index.0.checked_sub(1)? as usize)?))
+            }
+        })*
+    };
+}
+
+impl_named_constant!(i32 => ints, u32 => uints, f64 => doubles);
+
+impl Named for Method {}
+impl Named for Class {}
+impl Named for Exception {}
+
+impl<T: Named> Operand for Index<T> {
+    fn print(&self, _context: &PrintContext) -> String {
+        self.0.to_string()
+    }
+
+    fn parse(word: &str, _context: &ParseContext) -> Option<Self> {
+        word.parse().ok().map(Index::new)
+    }
+
+    fn annotate(&self, disassembler: &Disassembler) -> Option<String> {
+        T::name(self, disassembler)
+    }
+}
+
+fn operand<T: Operand>(node: &Node, argument: &mut usize, context: &ParseContext) -> Result<T> {
+    let word = node.word(*argument)?;
+    *argument += 1;
+    T::parse(word, context).ok_or_else(|| node.error(format!("invalid operand `{word}`")))
+}
+
+/// Defines the text form of every instruction other than `LookupSwitch`.
+macro_rules! ops {
+    ($($variant:ident $({ $($field:ident),* })?,)*) => {
+        /// An instruction and the names of what it refers to. `LookupSwitch`
+        /// is left to the caller.
+        fn print_op(
+            op: &Op,
+            #[allow(unused_variables)] context: &PrintContext,
+            #[allow(unused_variables)] disassembler: &Disassembler,
+        ) -> (Node, Vec<String>) {
+            #[allow(unused_mut)]
+            let mut names = Vec::new();
+            let node = match op {
+                $(Op::$variant $({ $($field),* })? => {
+                    #[allow(unused_mut)]
+                    let mut node = Node::new(stringify!($variant));
+                    $($(
+                        node = node.arg($field.print(context));
+                        names.extend($field.annotate(disassembler));
+                    )*)?
+                    node
+                })*
+                Op::LookupSwitch { .. } => Node::new("LookupSwitch"),
+            };
+            (node, names)
+        }
+
+        fn parse_op(node: &Node, context: &ParseContext) -> Result<Op> {
+            let mut argument = 0;
+            let op = match node.keyword() {
+                $(stringify!($variant) => Op::$variant $({
+                    $($field: operand(node, &mut argument, context)?),*
+                })?,)*
+                "LookupSwitch" => {
+                    let default_offset = operand(node, &mut argument, context)?;
+                    let mut case_offsets = Vec::new();
+                    while argument < node.arguments().len() {
+                        case_offsets.push(operand(node, &mut argument, context)?);
+                    }
+                    if case_offsets.is_empty() {
+                        return Err(node.error("`LookupSwitch` needs at least one case"));
+                    }
+                    Op::LookupSwitch {
+                        default_offset,
+                        case_offsets: case_offsets.into(),
+                    }
+                }
+                keyword => return Err(node.error(format!("unknown instruction `{keyword}`"))),
+            };
+            if argument < node.arguments().len() {
+                return Err(node.error("too many operands"));
+            }
+            Ok(op)
+        }
+    };
+}
+
+ops! {
+    Add,
+    AddI,
+    ApplyType { num_types },
+    AsType { type_name },
+    AsTypeLate,
+    BitAnd,
+    BitNot,
+    BitOr,
+    BitXor,
+    Bkpt,
+    BkptLine { line_num },
+    Call { num_args },
+    CallMethod { index, num_args },
+    CallProperty { index, num_args },
+    CallPropLex { index, num_args },
+    CallPropVoid { index, num_args },
+    CallStatic { index, num_args },
+    CallSuper { index, num_args },
+    CallSuperVoid { index, num_args },
+    CheckFilter,
+    Coerce { index },
+    CoerceA,
+    CoerceB,
+    CoerceD,
+    CoerceI,
+    CoerceO,
+    CoerceS,
+    CoerceU,
+    Construct { num_args },
+    ConstructProp { index, num_args },
+    ConstructSuper { num_args },
+    ConvertB,
+    ConvertD,
+    ConvertI,
+    ConvertO,
+    ConvertS,
+    ConvertU,
+    Debug { is_local_register, register_name, register },
+    DebugFile { file_name },
+    DebugLine { line_num },
+    DecLocal { index },
+    DecLocalI { index },
+    Decrement,
+    DecrementI,
+    DeleteProperty { index },
+    Divide,
+    Dup,
+    Dxns { index },
+    DxnsLate,
+    Equals,
+    EscXAttr,
+    EscXElem,
+    FindDef { index },
+    FindProperty { index },
+    FindPropStrict { index },
+    GetDescendants { index },
+    GetGlobalScope,
+    GetGlobalSlot { index },
+    GetLex { index },
+    GetLocal { index },
+    GetOuterScope { index },
+    GetProperty { index },
+    GetScopeObject { index },
+    GetSlot { index },
+    GetSuper { index },
+    GreaterEquals,
+    GreaterThan,
+    HasNext,
+    HasNext2 { object_register, index_register },
+    IfEq { offset },
+    IfFalse { offset },
+    IfGe { offset },
+    IfGt { offset },
+    IfLe { offset },
+    IfLt { offset },
+    IfNe { offset },
+    IfNge { offset },
+    IfNgt { offset },
+    IfNle { offset },
+    IfNlt { offset },
+    IfStrictEq { offset },
+    IfStrictNe { offset },
+    IfTrue { offset },
+    In,
+    IncLocal { index },
+    IncLocalI { index },
+    Increment,
+    IncrementI,
+    InitProperty { index },
+    InstanceOf,
+    IsType { index },
+    IsTypeLate,
+    Jump { offset },
+    Kill { index },
+    Label,
+    LessEquals,
+    LessThan,
+    Lf32,
+    Lf64,
+    Li16,
+    Li32,
+    Li8,
+    LShift,
+    Modulo,
+    Multiply,
+    MultiplyI,
+    Negate,
+    NegateI,
+    NewActivation,
+    NewArray { num_args },
+    NewCatch { index },
+    NewClass { index },
+    NewFunction { index },
+    NewObject { num_args },
+    NextName,
+    NextValue,
+    Nop,
+    Not,
+    Pop,
+    PopScope,
+    PushByte { value },
+    PushConstant { value },
+    PushDouble { value },
+    PushFalse,
+    PushInt { value },
+    PushNamespace { value },
+    PushNaN,
+    PushNull,
+    PushScope,
+    PushShort { value },
+    PushString { value },
+    PushTrue,
+    PushUint { value },
+    PushUndefined,
+    PushWith,
+    ReturnValue,
+    ReturnVoid,
+    RShift,
+    SetGlobalSlot { index },
+    SetLocal { index },
+    SetProperty { index },
+    SetSlot { index },
+    SetSuper { index },
+    Sf32,
+    Sf64,
+    Si16,
+    Si32,
+    Si8,
+    StrictEquals,
+    Subtract,
+    SubtractI,
+    Swap,
+    Sxi1,
+    Sxi16,
+    Sxi8,
+    Throw,
+    TypeOf,
+    Timestamp,
+    URShift,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn round_trip_branches() {
+        let text = r#"
+version 46 16
+constants {
+    int 7
+    string "f"
+    namespace package 1
+    multiname qname 1 1
+}
+method {
+    name 1
+    return_type 0
+    param 1 default true
+    flags HAS_OPTIONAL
+}
+body {
+    method 0
+    max_stack 2
+    num_locals 2
+    init_scope_depth 0
+    max_scope_depth 1
+    code {
+    L0:
+        GetLocal 1
+        IfFalse L1
+        PushByte 1
+        LookupSwitch L1 L0 L1
+        Jump L0
+    L1:
+        raw 47
+    }
+    exception L0 L1 L1 0 0
+    trait slot 1 1 0 value int 1 final metadata 0
+}
+"#;
+        let data = assemble(&text::parse(text).unwrap()).unwrap();
+        let nodes = disassemble(&data).expect("the ABC file should round trip");
+        assert_eq!(assemble(&nodes).unwrap(), data);
+        assert_eq!(text::print(&nodes).matches("L1").count(), 6);
+    }
+}
@@ -0,0 +1,81 @@
+//! Converts SWF files to an editable text form, and back again.
+
+mod avm1;
+mod avm2;
+mod movie;
+mod shape;
+mod tags;
+mod text;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[clap(version, about, author)]
+struct Opt {
+    #[clap(subcommand)]
+    mode: Mode,
+}
+
+#[derive(Parser, Debug)]
+enum Mode {
+    /// Convert a SWF file to text
+    Disassemble(DisassembleOpt),
+
+    /// Convert text produced by `disassemble` back to a SWF file
+    Assemble(AssembleOpt),
+}
+
+#[derive(Parser, Debug)]
+struct DisassembleOpt {
+    /// The SWF file to disassemble
+    #[clap(name = "swf")]
+    input_path: PathBuf,
+
+    /// Where to write the text, instead of standard output
+    #[clap(short = 'o', long = "output")]
+    output_path: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+struct AssembleOpt {
+    /// The text file to assemble
+    #[clap(name = "text")]
+    input_path: PathBuf,
+
+    /// Where to write the SWF file. Defaults to the input path with a `.swf` extension
+    #[clap(short = 'o', long = "output")]
+    output_path: Option<PathBuf>,
+}
+
+fn disassemble(opt: DisassembleOpt) -> Result<()> {
+    let data = std::fs::read(&opt.input_path)
+        .with_context(|| format!("Couldn't read {}", opt.input_path.display()))?;
+    let text = text::print(&movie::disassemble(&data)?);
+    match opt.output_path {
+        Some(path) => std::fs::write(&path, text)
+            .with_context(|| format!("Couldn't write {}", path.display()))?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+fn assemble(opt: AssembleOpt) -> Result<()> {
+    let text = std::fs::read_to_string(&opt.input_path)
+        .with_context(|| format!("Couldn't read {}", opt.input_path.display()))?;
+    let swf = movie::assemble(&text::parse(&text)?)?;
+    let output_path = opt
+        .output_path
+        .unwrap_or_else(|| opt.input_path.with_extension("swf"));
+    std::fs::write(&output_path, swf)
+        .with_context(|| format!("Couldn't write {}", output_path.display()))?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Opt::parse().mode {
+        Mode::Disassemble(opt) => disassemble(opt),
+        Mode::Assemble(opt) => assemble(opt),
+    }
+}
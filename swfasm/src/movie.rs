@@ -0,0 +1,275 @@
+//! The SWF header and tag lists.
+//!
+//! Each tag is a node named after its tag code, followed by `raw` if its body
+//! is dumped as hex, and by `long_header` if it was written with a long header
+//! although its body is short. Tags are only disassembled into their
+//! structured form if that form assembles back to the same bytes, so that
+//! assembling a disassembled SWF always gives back the same tag stream.
+
+use crate::tags;
+use crate::text::{hex_lines, parse_hex_lines, Node};
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+use swf::{Compression, Fixed8, Header, Rectangle, TagCode, Twips};
+
+/// Tags with a body at least this long always use a long header.
+const LONG_HEADER_LENGTH: usize = 0b111111;
+
+/// A tag as stored in a tag list.
+struct TagRecord<'a> {
+    code: u16,
+    long_header: bool,
+    body: &'a [u8],
+}
+
+/// Disassemble a SWF file.
+pub fn disassemble(data: &[u8]) -> Result<Vec<Node>> {
+    let swf_buf = swf::decompress_swf(data).map_err(|e| anyhow!("invalid SWF: {e}"))?;
+    let header = swf_buf.header.swf_header();
+
+    let mut nodes = vec![disassemble_header(header)];
+    let expected_len = header_len(header)? + swf_buf.data.len();
+    if swf_buf.header.uncompressed_len() as usize != expected_len {
+        nodes.push(Node::comment_line(format!(
+            "warning: the header declares {} uncompressed bytes, but the assembled SWF will have {expected_len}",
+            swf_buf.header.uncompressed_len()
+        )));
+    }
+    nodes.extend(disassemble_tags(&swf_buf.data, header.version));
+    Ok(nodes)
+}
+
+/// Assemble a SWF file.
+pub fn assemble(nodes: &[Node]) -> Result<Vec<u8>> {
+    let (header_node, tag_nodes) = match nodes.iter().position(|node| !node.tokens.is_empty()) {
+        Some(index) if nodes[index].keyword() == "header" => (&nodes[index], &nodes[index + 1..]),
+        _ => bail!("the document must start with a `header` block"),
+    };
+    let header = assemble_header(header_node)?;
+    let tags = assemble_tags(tag_nodes, header.version)?;
+
+    let mut swf = Vec::new();
+    swf::write::write_swf_raw_tags(&header, &tags, &mut swf).map_err(|e| anyhow!("{e}"))?;
+    Ok(swf)
+}
+
+/// The length of the uncompressed header, including the signature.
+fn header_len(header: &Header) -> Result<usize> {
+    let header = Header {
+        compression: Compression::None,
+        ..header.clone()
+    };
+    let mut bytes = Vec::new();
+    swf::write::write_swf_raw_tags(&header, &[], &mut bytes).map_err(|e| anyhow!("{e}"))?;
+    Ok(bytes.len())
+}
+
+fn disassemble_header(header: &Header) -> Node {
+    let compression = match header.compression {
+        Compression::None => "none",
+        Compression::Zlib => "zlib",
+        Compression::Lzma => "lzma",
+    };
+    Node::new("header")
+        .child(Node::new("compression").arg(compression))
+        .child(Node::new("version").arg(header.version))
+        .child(rectangle_node("stage_size", &header.stage_size))
+        .child(Node::new("frame_rate").arg(header.frame_rate.to_f64()))
+        .child(Node::new("num_frames").arg(header.num_frames))
+}
+
+fn assemble_header(node: &Node) -> Result<Header> {
+    let compression = node.field("compression")?;
+    Ok(Header {
+        compression: match compression.word(0)? {
+            "none" => Compression::None,
+            "zlib" => Compression::Zlib,
+            "lzma" => Compression::Lzma,
+            word => return Err(compression.error(format!("unknown compression `{word}`"))),
+        },
+        version: node.field("version")?.parse(0)?,
+        stage_size: parse_rectangle(node.field("stage_size")?)?,
+        frame_rate: Fixed8::from_f64(node.field("frame_rate")?.parse(0)?),
+        num_frames: node.field("num_frames")?.parse(0)?,
+    })
+}
+
+/// A rectangle, as its minimum and maximum coordinates in twips.
+pub fn rectangle_node(keyword: &str, rectangle: &Rectangle<Twips>) -> Node {
+    Node::new(keyword).args([
+        rectangle.x_min.get(),
+        rectangle.x_max.get(),
+        rectangle.y_min.get(),
+        rectangle.y_max.get(),
+    ])
+}
+
+pub fn parse_rectangle(node: &Node) -> Result<Rectangle<Twips>> {
+    Ok(Rectangle {
+        x_min: Twips::new(node.parse(0)?),
+        x_max: Twips::new(node.parse(1)?),
+        y_min: Twips::new(node.parse(2)?),
+        y_max: Twips::new(node.parse(3)?),
+    })
+}
+
+/// Split a tag list into tags, and trailing bytes which don't form a whole tag.
+fn read_tag_records(mut data: &[u8]) -> (Vec<TagRecord<'_>>, &[u8]) {
+    let mut records = Vec::new();
+    while data.len() >= 2 {
+        let code_and_length = u16::from_le_bytes([data[0], data[1]]);
+        let code = code_and_length >> 6;
+        let (length, header_len) = match (code_and_length & 0b111111) as usize {
+            LONG_HEADER_LENGTH if data.len() >= 6 => (
+                u32::from_le_bytes([data[2], data[3], data[4], data[5]]) as usize,
+                6,
+            ),
+            LONG_HEADER_LENGTH => break,
+            length => (length, 2),
+        };
+        let Some(body) = data.get(header_len..header_len + length) else {
+            break;
+        };
+        records.push(TagRecord {
+            code,
+            long_header: header_len == 6,
+            body,
+        });
+        data = &data[header_len + length..];
+    }
+    (records, data)
+}
+
+/// Disassemble a tag list.
+pub fn disassemble_tags(data: &[u8], version: u8) -> Vec<Node> {
+    let (records, trailing) = read_tag_records(data);
+    let mut nodes: Vec<Node> = records
+        .iter()
+        .map(|record| disassemble_tag(record, version))
+        .collect();
+    if !trailing.is_empty() {
+        nodes.push(Node::new("trailing_data").with_children(hex_lines(trailing)));
+    }
+    nodes
+}
+
+fn disassemble_tag(record: &TagRecord, version: u8) -> Node {
+    let name = tag_name(record.code);
+    let (mut node, comment) = match tags::disassemble(record.code, record.body, version) {
+        Ok(Some(node)) => match tags::assemble(record.code, &node, version) {
+            Ok(body) if body == record.body => (node, None),
+            _ => (
+                raw_tag(&name, record.body),
+                Some("the structured form of this tag doesn't assemble to the same bytes"),
+            ),
+        },
+        Ok(None) => (raw_tag(&name, record.body), None),
+        Err(_) => (
+            raw_tag(&name, record.body),
+            Some("this tag could not be read"),
+        ),
+    };
+    if record.long_header && record.body.len() < LONG_HEADER_LENGTH {
+        node.tokens
+            .insert(1, crate::text::Token::Word("long_header".to_string()));
+    }
+    if let Some(comment) = comment {
+        node = node.comment(comment);
+    }
+    node
+}
+
+fn raw_tag(name: &str, body: &[u8]) -> Node {
+    let node = Node::new(name);
+    if body.is_empty() {
+        node
+    } else {
+        node.arg("raw").with_children(hex_lines(body))
+    }
+}
+
+/// Assemble a tag list.
+pub fn assemble_tags(nodes: &[Node], version: u8) -> Result<Vec<u8>> {
+    let codes = tag_codes();
+    let mut data = Vec::new();
+    for node in nodes {
+        if node.tokens.is_empty() {
+            continue;
+        }
+        if node.keyword() == "trailing_data" {
+            data.extend(parse_hex_lines(node.children())?);
+            continue;
+        }
+
+        let code = match codes.get(node.keyword()) {
+            Some(&code) => code,
+            None => node
+                .keyword()
+                .parse()
+                .map_err(|_| node.error(format!("unknown tag `{}`", node.keyword())))?,
+        };
+        let body = if node.has_flag("raw") || node.children.is_none() {
+            parse_hex_lines(node.children())?
+        } else {
+            tags::assemble(code, node, version)
+                .map_err(|e| anyhow!("in tag `{}`: {e}", node.keyword()))?
+        };
+
+        if node.has_flag("long_header") || body.len() >= LONG_HEADER_LENGTH {
+            data.extend((code << 6 | LONG_HEADER_LENGTH as u16).to_le_bytes());
+            data.extend((body.len() as u32).to_le_bytes());
+        } else {
+            data.extend((code << 6 | body.len() as u16).to_le_bytes());
+        }
+        data.extend(body);
+    }
+    Ok(data)
+}
+
+/// The name of a tag code, or the code itself if it's unknown.
+fn tag_name(code: u16) -> String {
+    match TagCode::from_u16(code) {
+        Some(code) => format!("{code:?}"),
+        None => code.to_string(),
+    }
+}
+
+fn tag_codes() -> HashMap<String, u16> {
+    (0..1 << 10)
+        .filter_map(|code| TagCode::from_u16(code).map(|tag| (format!("{tag:?}"), code)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    /// Every test SWF of the `swf` crate must survive a round trip through text.
+    #[test]
+    fn round_trip_test_swfs() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../swf/tests/swfs");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension() != Some(OsStr::new("swf")) {
+                continue;
+            }
+            let data = std::fs::read(&path).unwrap();
+            let Ok(swf_buf) = swf::decompress_swf(&data[..]) else {
+                continue;
+            };
+
+            let text = text::print(&disassemble(&data).unwrap());
+            let assembled = assemble(&text::parse(&text).unwrap()).unwrap();
+            let reassembled = swf::decompress_swf(&assembled[..]).unwrap();
+            assert_eq!(
+                reassembled.data,
+                swf_buf.data,
+                "{} doesn't round trip",
+                path.display()
+            );
+        }
+    }
+}
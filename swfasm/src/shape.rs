@@ -0,0 +1,371 @@
+//! Shapes, as found in `DefineShape` tags.
+//!
+//! Coordinates are in twips. Edges are relative to the current position,
+//! like in the SWF format.
+
+use crate::movie::{parse_rectangle, rectangle_node};
+use crate::tags::{color_string, flags_node, parse_color, parse_flags};
+use crate::text::Node;
+use anyhow::Result;
+use swf::{
+    FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Matrix, Point, PointDelta, Shape, ShapeFlag,
+    ShapeRecord, ShapeStyles, StyleChangeData, Twips,
+};
+
+pub fn disassemble(shape: &Shape) -> Vec<Node> {
+    let mut nodes = vec![
+        Node::new("id").arg(shape.id),
+        rectangle_node("bounds", &shape.shape_bounds),
+    ];
+    if shape.version >= 4 {
+        nodes.push(rectangle_node("edge_bounds", &shape.edge_bounds));
+        nodes.push(flags_node(shape.flags));
+    }
+    nodes.extend(styles(&shape.styles, shape.version));
+
+    for record in &shape.shape {
+        nodes.push(match record {
+            ShapeRecord::StyleChange(style_change) => {
+                let mut node = Node::new("style");
+                if let Some(move_to) = &style_change.move_to {
+                    node = node.arg("move").arg(move_to.x.get()).arg(move_to.y.get());
+                }
+                if let Some(fill_style) = style_change.fill_style_0 {
+                    node = node.arg("fill0").arg(fill_style);
+                }
+                if let Some(fill_style) = style_change.fill_style_1 {
+                    node = node.arg("fill1").arg(fill_style);
+                }
+                if let Some(line_style) = style_change.line_style {
+                    node = node.arg("line").arg(line_style);
+                }
+                if let Some(new_styles) = &style_change.new_styles {
+                    node = node
+                        .block()
+                        .with_children(styles(new_styles, shape.version));
+                }
+                node
+            }
+            ShapeRecord::StraightEdge { delta } => {
+                Node::new("edge").arg(delta.dx.get()).arg(delta.dy.get())
+            }
+            ShapeRecord::CurvedEdge {
+                control_delta,
+                anchor_delta,
+            } => Node::new("curve").args([
+                control_delta.dx.get(),
+                control_delta.dy.get(),
+                anchor_delta.dx.get(),
+                anchor_delta.dy.get(),
+            ]),
+        });
+    }
+    nodes
+}
+
+pub fn assemble(node: &Node, version: u8) -> Result<Shape> {
+    let shape_bounds = parse_rectangle(node.field("bounds")?)?;
+    let (edge_bounds, flags) = if version >= 4 {
+        (
+            parse_rectangle(node.field("edge_bounds")?)?,
+            parse_flags(node.field("flags")?)?,
+        )
+    } else {
+        (shape_bounds.clone(), ShapeFlag::HAS_NON_SCALING_STROKES)
+    };
+
+    let children = node.block_children()?;
+    let mut records = Vec::new();
+    for child in children {
+        let twips = |index| child.parse(index).map(Twips::new);
+        match child.keyword() {
+            "style" => {
+                let mut style_change = StyleChangeData {
+                    move_to: None,
+                    fill_style_0: None,
+                    fill_style_1: None,
+                    line_style: None,
+                    new_styles: None,
+                };
+                let mut index = 0;
+                while index < child.arguments().len() {
+                    match child.word(index)? {
+                        "move" => {
+                            style_change.move_to =
+                                Some(Point::new(twips(index + 1)?, twips(index + 2)?));
+                            index += 3;
+                            continue;
+                        }
+                        "fill0" => style_change.fill_style_0 = Some(child.parse(index + 1)?),
+                        "fill1" => style_change.fill_style_1 = Some(child.parse(index + 1)?),
+                        "line" => style_change.line_style = Some(child.parse(index + 1)?),
+                        word => return Err(child.error(format!("unexpected `{word}`"))),
+                    }
+                    index += 2;
+                }
+                if let Some(new_styles) = &child.children {
+                    style_change.new_styles = Some(parse_styles(new_styles, version)?);
+                }
+                records.push(ShapeRecord::StyleChange(Box::new(style_change)));
+            }
+            "edge" => records.push(ShapeRecord::StraightEdge {
+                delta: PointDelta::new(twips(0)?, twips(1)?),
+            }),
+            "curve" => records.push(ShapeRecord::CurvedEdge {
+                control_delta: PointDelta::new(twips(0)?, twips(1)?),
+                anchor_delta: PointDelta::new(twips(2)?, twips(3)?),
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(Shape {
+        version,
+        id: node.field("id")?.parse(0)?,
+        shape_bounds,
+        edge_bounds,
+        flags,
+        styles: parse_styles(children, version)?,
+        shape: records,
+    })
+}
+
+fn styles(styles: &ShapeStyles, version: u8) -> Vec<Node> {
+    let fills = styles
+        .fill_styles
+        .iter()
+        .map(|fill_style| fill_style_node(fill_style, version));
+    let lines = styles.line_styles.iter().map(|line_style| {
+        let mut node = Node::new("line").arg(line_style.width().get());
+        if let FillStyle::Color(color) = line_style.fill_style() {
+            node = node.arg(color_string(color, version >= 3));
+        }
+        if version >= 4 {
+            node = node
+                .arg("caps")
+                .arg(cap_name(line_style.start_cap()))
+                .arg(cap_name(line_style.end_cap()))
+                .arg("join");
+            node = match line_style.join_style() {
+                LineJoinStyle::Round => node.arg("round"),
+                LineJoinStyle::Bevel => node.arg("bevel"),
+                LineJoinStyle::Miter(limit) => node.arg("miter").arg(limit.to_f64()),
+            };
+            for (flag, set) in [
+                ("no_close", !line_style.allow_close()),
+                ("no_hscale", !line_style.allow_scale_x()),
+                ("no_vscale", !line_style.allow_scale_y()),
+                ("pixel_hinting", line_style.is_pixel_hinted()),
+            ] {
+                if set {
+                    node = node.arg(flag);
+                }
+            }
+            if !matches!(line_style.fill_style(), FillStyle::Color(_)) {
+                node = node.child(fill_style_node(line_style.fill_style(), version));
+            }
+        }
+        node
+    });
+    fills.chain(lines).collect()
+}
+
+/// Parse the `fill` and `line` nodes of a list.
+fn parse_styles(nodes: &[Node], version: u8) -> Result<ShapeStyles> {
+    let mut styles = ShapeStyles {
+        fill_styles: Vec::new(),
+        line_styles: Vec::new(),
+    };
+    for node in nodes {
+        match node.keyword() {
+            "fill" => styles.fill_styles.push(parse_fill_style(node)?),
+            "line" => {
+                let mut line_style = LineStyle::new().with_width(Twips::new(node.parse(0)?));
+                let mut index = 1;
+                while index < node.arguments().len() {
+                    let word = node.word(index)?;
+                    index += 1;
+                    line_style = match word {
+                        "caps" => {
+                            index += 2;
+                            line_style
+                                .with_start_cap(parse_cap(node, node.word(index - 2)?)?)
+                                .with_end_cap(parse_cap(node, node.word(index - 1)?)?)
+                        }
+                        "join" => {
+                            index += 1;
+                            line_style.with_join_style(match node.word(index - 1)? {
+                                "round" => LineJoinStyle::Round,
+                                "bevel" => LineJoinStyle::Bevel,
+                                "miter" => {
+                                    index += 1;
+                                    LineJoinStyle::Miter(Fixed8::from_f64(node.parse(index - 1)?))
+                                }
+                                word => return Err(node.error(format!("unknown join `{word}`"))),
+                            })
+                        }
+                        "no_close" => line_style.with_allow_close(false),
+                        "no_hscale" => line_style.with_allow_scale_x(false),
+                        "no_vscale" => line_style.with_allow_scale_y(false),
+                        "pixel_hinting" => line_style.with_is_pixel_hinted(true),
+                        color if color.starts_with('#') => {
+                            line_style.with_color(parse_color(node, color)?)
+                        }
+                        word => return Err(node.error(format!("unexpected `{word}`"))),
+                    };
+                }
+                if version >= 4 {
+                    if let Some(fill) = node.find("fill") {
+                        line_style = line_style.with_fill_style(parse_fill_style(fill)?);
+                    }
+                }
+                styles.line_styles.push(line_style);
+            }
+            _ => {}
+        }
+    }
+    Ok(styles)
+}
+
+fn fill_style_node(fill_style: &FillStyle, version: u8) -> Node {
+    let with_alpha = version >= 3;
+    match fill_style {
+        FillStyle::Color(color) => Node::new("fill")
+            .arg("solid")
+            .arg(color_string(color, with_alpha)),
+        FillStyle::LinearGradient(gradient) => {
+            gradient_node(Node::new("fill").arg("linear"), gradient, with_alpha)
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_node(Node::new("fill").arg("radial"), gradient, with_alpha)
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_node(
+            Node::new("fill").arg("focal").arg(focal_point.to_f64()),
+            gradient,
+            with_alpha,
+        ),
+        FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            let mut node = Node::new("fill").arg("bitmap").arg(id);
+            if *is_smoothed {
+                node = node.arg("smoothed");
+            }
+            if *is_repeating {
+                node = node.arg("repeating");
+            }
+            node.child(matrix_node(matrix))
+        }
+    }
+}
+
+fn gradient_node(node: Node, gradient: &Gradient, with_alpha: bool) -> Node {
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linear_rgb",
+    };
+    node.child(matrix_node(&gradient.matrix))
+        .child(Node::new("spread").arg(spread))
+        .child(Node::new("interpolation").arg(interpolation))
+        .with_children(gradient.records.iter().map(|record| {
+            Node::new("stop")
+                .arg(record.ratio)
+                .arg(color_string(&record.color, with_alpha))
+        }))
+}
+
+fn parse_fill_style(node: &Node) -> Result<FillStyle> {
+    let gradient = || -> Result<Gradient> {
+        let spread = node.field("spread")?;
+        let interpolation = node.field("interpolation")?;
+        Ok(Gradient {
+            matrix: parse_matrix(node.field("matrix")?)?,
+            spread: match spread.word(0)? {
+                "pad" => GradientSpread::Pad,
+                "reflect" => GradientSpread::Reflect,
+                "repeat" => GradientSpread::Repeat,
+                word => return Err(spread.error(format!("unknown spread `{word}`"))),
+            },
+            interpolation: match interpolation.word(0)? {
+                "rgb" => GradientInterpolation::Rgb,
+                "linear_rgb" => GradientInterpolation::LinearRgb,
+                word => return Err(interpolation.error(format!("unknown interpolation `{word}`"))),
+            },
+            records: node
+                .children()
+                .iter()
+                .filter(|child| child.keyword() == "stop")
+                .map(|stop| {
+                    Ok(GradientRecord {
+                        ratio: stop.parse(0)?,
+                        color: parse_color(stop, stop.word(1)?)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })
+    };
+
+    Ok(match node.word(0)? {
+        "solid" => FillStyle::Color(parse_color(node, node.word(1)?)?),
+        "linear" => FillStyle::LinearGradient(gradient()?),
+        "radial" => FillStyle::RadialGradient(gradient()?),
+        "focal" => FillStyle::FocalGradient {
+            gradient: gradient()?,
+            focal_point: Fixed8::from_f64(node.parse(1)?),
+        },
+        "bitmap" => FillStyle::Bitmap {
+            id: node.parse(1)?,
+            matrix: parse_matrix(node.field("matrix")?)?,
+            is_smoothed: node.has_flag("smoothed"),
+            is_repeating: node.has_flag("repeating"),
+        },
+        word => return Err(node.error(format!("unknown fill style `{word}`"))),
+    })
+}
+
+fn matrix_node(matrix: &Matrix) -> Node {
+    Node::new("matrix")
+        .args([matrix.a, matrix.b, matrix.c, matrix.d].map(Fixed16::to_f64))
+        .arg(matrix.tx.get())
+        .arg(matrix.ty.get())
+}
+
+fn parse_matrix(node: &Node) -> Result<Matrix> {
+    Ok(Matrix {
+        a: Fixed16::from_f64(node.parse(0)?),
+        b: Fixed16::from_f64(node.parse(1)?),
+        c: Fixed16::from_f64(node.parse(2)?),
+        d: Fixed16::from_f64(node.parse(3)?),
+        tx: Twips::new(node.parse(4)?),
+        ty: Twips::new(node.parse(5)?),
+    })
+}
+
+fn cap_name(cap: LineCapStyle) -> &'static str {
+    match cap {
+        LineCapStyle::Round => "round",
+        LineCapStyle::None => "none",
+        LineCapStyle::Square => "square",
+    }
+}
+
+fn parse_cap(node: &Node, word: &str) -> Result<LineCapStyle> {
+    match word {
+        "round" => Ok(LineCapStyle::Round),
+        "none" => Ok(LineCapStyle::None),
+        "square" => Ok(LineCapStyle::Square),
+        word => Err(node.error(format!("unknown cap `{word}`"))),
+    }
+}
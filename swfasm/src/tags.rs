@@ -0,0 +1,287 @@
+//! The structured form of tags.
+//!
+//! Tags which aren't listed here are dumped as hex.
+
+use crate::movie::{assemble_tags, disassemble_tags};
+use crate::text::{hex_lines, parse_hex_lines, Node};
+use crate::{avm1, avm2, shape};
+use anyhow::{anyhow, bail, Result};
+use bitflags::{Bits, Flags};
+use std::fmt::Debug;
+use swf::read::Reader;
+use swf::{Color, FileAttributes, FrameLabel, SwfStr, SymbolClassLink, Tag, TagCode};
+
+/// Disassemble the body of a tag.
+///
+/// Returns `None` if the tag has no structured form.
+pub fn disassemble(code: u16, body: &[u8], version: u8) -> Result<Option<Node>> {
+    let Some(tag_code) = TagCode::from_u16(code) else {
+        return Ok(None);
+    };
+    let node = Node::new(format!("{tag_code:?}")).block();
+
+    // Tags containing code or other tags are disassembled from their bytes.
+    match tag_code {
+        TagCode::DoAction => return Ok(Some(node.with_children(avm1::disassemble(body, version)))),
+        TagCode::DoInitAction => {
+            let (id, actions) = split_u16(body)?;
+            return Ok(Some(
+                node.child(Node::new("sprite").arg(id))
+                    .with_children(avm1::disassemble(actions, version)),
+            ));
+        }
+        TagCode::DoAbc => return Ok(Some(node.child(abc_node(body)))),
+        TagCode::DefineSprite => {
+            let (id, rest) = split_u16(body)?;
+            let (num_frames, tags) = split_u16(rest)?;
+            return Ok(Some(
+                node.child(Node::new("id").arg(id))
+                    .child(Node::new("num_frames").arg(num_frames))
+                    .with_children(disassemble_tags(tags, version)),
+            ));
+        }
+        _ => {}
+    }
+
+    let mut record = Vec::with_capacity(body.len() + 6);
+    record.extend((code << 6 | 0b111111).to_le_bytes());
+    record.extend((body.len() as u32).to_le_bytes());
+    record.extend(body);
+    let tag = Reader::new(&record, version)
+        .read_tag()
+        .map_err(|e| anyhow!("{e}"))?;
+
+    let node = match tag {
+        Tag::DefineShape(shape) => node.with_children(shape::disassemble(&shape)),
+        Tag::DoAbc2(do_abc) => node
+            .child(flags_node(do_abc.flags))
+            .child(Node::new("name").string(do_abc.name.as_bytes()))
+            .child(abc_node(do_abc.data)),
+        Tag::ExportAssets(assets) => node.with_children(assets.iter().map(|asset| {
+            Node::new("asset")
+                .arg(asset.id)
+                .string(asset.name.as_bytes())
+        })),
+        Tag::FileAttributes(attributes) => node.child(flags_node(attributes)),
+        Tag::FrameLabel(label) => {
+            let node = node.child(Node::new("label").string(label.label.as_bytes()));
+            if label.is_anchor {
+                node.child(Node::new("anchor"))
+            } else {
+                node
+            }
+        }
+        Tag::Metadata(metadata) => node.child(Node::new("xml").string(metadata.as_bytes())),
+        Tag::ScriptLimits {
+            max_recursion_depth,
+            timeout_in_seconds,
+        } => node
+            .child(Node::new("max_recursion_depth").arg(max_recursion_depth))
+            .child(Node::new("timeout_in_seconds").arg(timeout_in_seconds)),
+        Tag::SetBackgroundColor(color) => {
+            node.child(Node::new("color").arg(color_string(&color, false)))
+        }
+        Tag::SymbolClass(symbols) => node.with_children(symbols.iter().map(|symbol| {
+            Node::new("symbol")
+                .arg(symbol.id)
+                .string(symbol.class_name.as_bytes())
+        })),
+        _ => return Ok(None),
+    };
+    Ok(Some(node))
+}
+
+/// Assemble the body of a tag from its structured form.
+pub fn assemble(code: u16, node: &Node, version: u8) -> Result<Vec<u8>> {
+    let tag_code = TagCode::from_u16(code)
+        .ok_or_else(|| node.error("only known tags have a structured form"))?;
+    let children = node.block_children()?;
+
+    match tag_code {
+        TagCode::DoAction => return avm1::assemble(children, version),
+        TagCode::DoInitAction => {
+            let Some((sprite, actions)) = children.split_first() else {
+                return Err(node.error("missing `sprite`"));
+            };
+            if sprite.keyword() != "sprite" {
+                return Err(sprite.error("expected `sprite`"));
+            }
+            let mut body = sprite.parse::<u16>(0)?.to_le_bytes().to_vec();
+            body.extend(avm1::assemble(actions, version)?);
+            return Ok(body);
+        }
+        TagCode::DoAbc => return assemble_abc(node.field("abc")?),
+        TagCode::DefineSprite => {
+            let mut body = node.field("id")?.parse::<u16>(0)?.to_le_bytes().to_vec();
+            body.extend(node.field("num_frames")?.parse::<u16>(0)?.to_le_bytes());
+            let tags: Vec<Node> = children
+                .iter()
+                .filter(|child| !matches!(child.keyword(), "id" | "num_frames"))
+                .cloned()
+                .collect();
+            body.extend(assemble_tags(&tags, version)?);
+            return Ok(body);
+        }
+        _ => {}
+    }
+
+    // Strings and data are borrowed by the tag, so they are built first.
+    let abc;
+    let tag = match tag_code {
+        TagCode::DefineShape
+        | TagCode::DefineShape2
+        | TagCode::DefineShape3
+        | TagCode::DefineShape4 => {
+            let shape_version = match tag_code {
+                TagCode::DefineShape => 1,
+                TagCode::DefineShape2 => 2,
+                TagCode::DefineShape3 => 3,
+                _ => 4,
+            };
+            Tag::DefineShape(shape::assemble(node, shape_version)?)
+        }
+        TagCode::DoAbc2 => {
+            abc = assemble_abc(node.field("abc")?)?;
+            Tag::DoAbc2(swf::DoAbc2 {
+                flags: parse_flags(node.field("flags")?)?,
+                name: SwfStr::from_bytes(node.field("name")?.bytes(0)?),
+                data: &abc,
+            })
+        }
+        TagCode::ExportAssets => Tag::ExportAssets(
+            children
+                .iter()
+                .map(|child| {
+                    Ok(swf::ExportedAsset {
+                        id: child.parse(0)?,
+                        name: SwfStr::from_bytes(child.bytes(1)?),
+                    })
+                })
+                .collect::<Result<_>>()?,
+        ),
+        TagCode::FileAttributes => {
+            Tag::FileAttributes(parse_flags::<FileAttributes>(node.field("flags")?)?)
+        }
+        TagCode::FrameLabel => Tag::FrameLabel(FrameLabel {
+            label: SwfStr::from_bytes(node.field("label")?.bytes(0)?),
+            is_anchor: node.find("anchor").is_some(),
+        }),
+        TagCode::Metadata => Tag::Metadata(SwfStr::from_bytes(node.field("xml")?.bytes(0)?)),
+        TagCode::ScriptLimits => Tag::ScriptLimits {
+            max_recursion_depth: node.field("max_recursion_depth")?.parse(0)?,
+            timeout_in_seconds: node.field("timeout_in_seconds")?.parse(0)?,
+        },
+        TagCode::SetBackgroundColor => {
+            let color = node.field("color")?;
+            Tag::SetBackgroundColor(parse_color(color, color.word(0)?)?)
+        }
+        TagCode::SymbolClass => Tag::SymbolClass(
+            children
+                .iter()
+                .map(|child| {
+                    Ok(SymbolClassLink {
+                        id: child.parse(0)?,
+                        class_name: SwfStr::from_bytes(child.bytes(1)?),
+                    })
+                })
+                .collect::<Result<_>>()?,
+        ),
+        _ => bail!("`{tag_code:?}` has no structured form"),
+    };
+
+    let mut record = Vec::new();
+    swf::write::write_tag(&tag, version, &mut record).map_err(|e| anyhow!("{e}"))?;
+    // Strip the header written with the tag.
+    let header_len = if record[0] & 0b111111 == 0b111111 {
+        6
+    } else {
+        2
+    };
+    Ok(record.split_off(header_len))
+}
+
+fn split_u16(data: &[u8]) -> Result<(u16, &[u8])> {
+    match data {
+        [a, b, rest @ ..] => Ok((u16::from_le_bytes([*a, *b]), rest)),
+        _ => bail!("unexpected end of tag"),
+    }
+}
+
+/// ABC bytecode, dumped as hex if it doesn't round trip.
+fn abc_node(data: &[u8]) -> Node {
+    let node = Node::new("abc").block();
+    match avm2::disassemble(data) {
+        Some(nodes) => node.with_children(nodes),
+        None => node
+            .arg("raw")
+            .with_children(hex_lines(data))
+            .comment("this bytecode doesn't survive a round trip through the `swf` crate"),
+    }
+}
+
+fn assemble_abc(node: &Node) -> Result<Vec<u8>> {
+    if node.has_flag("raw") {
+        parse_hex_lines(node.children())
+    } else {
+        avm2::assemble(node.block_children()?)
+    }
+}
+
+/// The names of a set of flags.
+pub fn flags_node<F: Flags>(flags: F) -> Node
+where
+    F::Bits: Debug,
+{
+    let mut node = Node::new("flags");
+    for (name, _) in flags.iter_names() {
+        node = node.arg(name);
+    }
+    // Bits without a name are kept as a number.
+    let unnamed = flags.bits() & !F::all().bits();
+    if unnamed != F::Bits::EMPTY {
+        node = node.arg(format!("{unnamed:?}"));
+    }
+    node
+}
+
+pub fn parse_flags<F: Flags>(node: &Node) -> Result<F>
+where
+    F::Bits: std::str::FromStr,
+{
+    let mut flags = F::empty();
+    for index in 0..node.arguments().len() {
+        let word = node.word(index)?;
+        let flag = match F::from_name(word) {
+            Some(flag) => flag,
+            None => F::from_bits_retain(
+                word.parse()
+                    .map_err(|_| node.error(format!("unknown flag `{word}`")))?,
+            ),
+        };
+        flags.insert(flag);
+    }
+    Ok(flags)
+}
+
+/// A color as `#rrggbb`, or `#rrggbbaa` with the alpha channel.
+pub fn color_string(color: &Color, with_alpha: bool) -> String {
+    if with_alpha {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            color.r, color.g, color.b, color.a
+        )
+    } else {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    }
+}
+
+pub fn parse_color(node: &Node, word: &str) -> Result<Color> {
+    let invalid = || node.error(format!("invalid color `{word}`"));
+    let hex = word.strip_prefix('#').ok_or_else(invalid)?;
+    let value = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+    match hex.len() {
+        6 => Ok(Color::from_rgb(value, 255)),
+        8 => Ok(Color::from_rgb(value >> 8, value as u8)),
+        _ => Err(invalid()),
+    }
+}
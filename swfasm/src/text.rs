@@ -0,0 +1,453 @@
+//! The text format of disassembled SWFs.
+//!
+//! A document is a tree of nodes, one per line. Each node is a list of tokens,
+//! optionally followed by a block of child nodes between braces:
+//!
+//! ```text
+//! DefineSprite {
+//!     id 1
+//!     num_frames 1
+//!     ShowFrame
+//! }
+//! ```
+//!
+//! Tokens are either bare words, or strings between double quotes. Everything
+//! after a `;` outside of a string is a comment, and is ignored when assembling.
+
+use anyhow::{anyhow, bail, Result};
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+
+/// The number of bytes per line of hex dumps.
+const HEX_BYTES_PER_LINE: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Word(String),
+    Str(Vec<u8>),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => f.write_str(word),
+            Token::Str(bytes) => f.write_str(&quote(bytes)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Node {
+    pub tokens: Vec<Token>,
+    pub children: Option<Vec<Node>>,
+    pub comment: Option<String>,
+
+    /// The line this node was parsed from, used for error messages.
+    pub line: usize,
+}
+
+impl Node {
+    pub fn new(keyword: impl Display) -> Self {
+        Self {
+            tokens: vec![Token::Word(keyword.to_string())],
+            ..Default::default()
+        }
+    }
+
+    /// A line containing only a comment.
+    pub fn comment_line(comment: impl Into<String>) -> Self {
+        Self {
+            comment: Some(comment.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Append a word token.
+    pub fn arg(mut self, value: impl Display) -> Self {
+        self.tokens.push(Token::Word(value.to_string()));
+        self
+    }
+
+    /// Append a word token for each of the values.
+    pub fn args<T: Display>(mut self, values: impl IntoIterator<Item = T>) -> Self {
+        for value in values {
+            self.tokens.push(Token::Word(value.to_string()));
+        }
+        self
+    }
+
+    /// Append a quoted string token.
+    pub fn string(mut self, bytes: impl AsRef<[u8]>) -> Self {
+        self.tokens.push(Token::Str(bytes.as_ref().to_vec()));
+        self
+    }
+
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Open a block, even if no children are added to it.
+    pub fn block(mut self) -> Self {
+        self.children.get_or_insert_with(Vec::new);
+        self
+    }
+
+    pub fn child(mut self, node: Node) -> Self {
+        self.push(node);
+        self
+    }
+
+    pub fn with_children(mut self, nodes: impl IntoIterator<Item = Node>) -> Self {
+        self.children.get_or_insert_with(Vec::new).extend(nodes);
+        self
+    }
+
+    pub fn push(&mut self, node: Node) {
+        self.children.get_or_insert_with(Vec::new).push(node);
+    }
+
+    /// The first word of the node, or an empty string for comment lines.
+    pub fn keyword(&self) -> &str {
+        match self.tokens.first() {
+            Some(Token::Word(word)) => word,
+            _ => "",
+        }
+    }
+
+    /// The tokens following the keyword.
+    pub fn arguments(&self) -> &[Token] {
+        self.tokens.get(1..).unwrap_or_default()
+    }
+
+    pub fn error(&self, message: impl Display) -> anyhow::Error {
+        anyhow!("line {}: {}", self.line, message)
+    }
+
+    fn argument(&self, index: usize) -> Result<&Token> {
+        self.arguments().get(index).ok_or_else(|| {
+            self.error(format!(
+                "missing argument {} of `{}`",
+                index + 1,
+                self.keyword()
+            ))
+        })
+    }
+
+    /// The argument at `index`, which must be a word.
+    pub fn word(&self, index: usize) -> Result<&str> {
+        match self.argument(index)? {
+            Token::Word(word) => Ok(word),
+            Token::Str(_) => Err(self.error(format!(
+                "argument {} of `{}` must not be a string",
+                index + 1,
+                self.keyword()
+            ))),
+        }
+    }
+
+    /// The argument at `index`, parsed from a word.
+    pub fn parse<T: FromStr>(&self, index: usize) -> Result<T> {
+        let word = self.word(index)?;
+        word.parse()
+            .map_err(|_| self.error(format!("invalid value `{word}`")))
+    }
+
+    /// The argument at `index`, which must be a quoted string.
+    pub fn bytes(&self, index: usize) -> Result<&[u8]> {
+        match self.argument(index)? {
+            Token::Str(bytes) => Ok(bytes),
+            Token::Word(word) => Err(self.error(format!("expected a string, found `{word}`"))),
+        }
+    }
+
+    /// The children of the node, which must have a block.
+    pub fn block_children(&self) -> Result<&[Node]> {
+        self.children
+            .as_deref()
+            .ok_or_else(|| self.error(format!("`{}` must have a block", self.keyword())))
+    }
+
+    /// The children of the node, or nothing if it has no block.
+    pub fn children(&self) -> &[Node] {
+        self.children.as_deref().unwrap_or_default()
+    }
+
+    /// The first child with the given keyword.
+    pub fn find(&self, keyword: &str) -> Option<&Node> {
+        self.children()
+            .iter()
+            .find(|child| child.keyword() == keyword)
+    }
+
+    /// The first child with the given keyword, which must exist.
+    pub fn field(&self, keyword: &str) -> Result<&Node> {
+        self.find(keyword)
+            .ok_or_else(|| self.error(format!("missing `{keyword}` in `{}`", self.keyword())))
+    }
+
+    /// Whether the arguments contain the given word.
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.arguments()
+            .iter()
+            .any(|token| matches!(token, Token::Word(word) if word == flag))
+    }
+}
+
+/// Print a document, indenting blocks by four spaces.
+pub fn print(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        print_node(&mut out, node, 0);
+    }
+    out
+}
+
+fn print_node(out: &mut String, node: &Node, depth: usize) {
+    let indent = "    ".repeat(depth);
+    let mut line = indent.clone();
+    for (i, token) in node.tokens.iter().enumerate() {
+        if i > 0 {
+            line.push(' ');
+        }
+        let _ = write!(line, "{token}");
+    }
+    match &node.children {
+        Some(children) if children.is_empty() => line.push_str(" {}"),
+        Some(_) => line.push_str(" {"),
+        None => {}
+    }
+    if let Some(comment) = &node.comment {
+        if !node.tokens.is_empty() {
+            line.push_str("  ");
+        }
+        line.push_str("; ");
+        line.push_str(comment);
+    }
+    out.push_str(line.trim_end());
+    out.push('\n');
+
+    if let Some(children) = node.children.as_ref().filter(|c| !c.is_empty()) {
+        for child in children {
+            print_node(out, child, depth + 1);
+        }
+        out.push_str(&indent);
+        out.push_str("}\n");
+    }
+}
+
+/// Parse a document. Comments are dropped.
+pub fn parse(text: &str) -> Result<Vec<Node>> {
+    // The blocks being parsed, innermost last.
+    let mut stack: Vec<Node> = vec![Node::default().block()];
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let mut tokens = tokenize(line).map_err(|e| anyhow!("line {line_number}: {e}"))?;
+        if tokens.is_empty() {
+            continue;
+        }
+
+        if tokens == [Token::Word("}".to_string())] {
+            if stack.len() < 2 {
+                bail!("line {line_number}: unexpected `}}`");
+            }
+            let node = stack.pop().unwrap();
+            stack.last_mut().unwrap().push(node);
+            continue;
+        }
+
+        let mut node = Node {
+            line: line_number,
+            ..Default::default()
+        };
+        match tokens.last() {
+            Some(Token::Word(word)) if word == "{" => {
+                tokens.pop();
+                node.tokens = tokens;
+                node.children = Some(Vec::new());
+                stack.push(node);
+            }
+            Some(Token::Word(word)) if word == "{}" => {
+                tokens.pop();
+                node.tokens = tokens;
+                node.children = Some(Vec::new());
+                stack.last_mut().unwrap().push(node);
+            }
+            _ => {
+                node.tokens = tokens;
+                stack.last_mut().unwrap().push(node);
+            }
+        }
+    }
+
+    if stack.len() > 1 {
+        let unclosed = stack.pop().unwrap();
+        return Err(unclosed.error(format!("unclosed block `{}`", unclosed.keyword())));
+    }
+    Ok(stack.pop().unwrap().children.unwrap_or_default())
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == ';' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut bytes = Vec::new();
+            loop {
+                let Some((_, c)) = chars.next() else {
+                    bail!("unterminated string");
+                };
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next().map(|(_, c)| c) {
+                        Some('\\') => bytes.push(b'\\'),
+                        Some('"') => bytes.push(b'"'),
+                        Some('n') => bytes.push(b'\n'),
+                        Some('r') => bytes.push(b'\r'),
+                        Some('t') => bytes.push(b'\t'),
+                        Some('x') => {
+                            let mut digits = String::new();
+                            for _ in 0..2 {
+                                if let Some((_, c)) = chars.next() {
+                                    digits.push(c);
+                                }
+                            }
+                            let byte = u8::from_str_radix(&digits, 16)
+                                .map_err(|_| anyhow!("invalid escape `\\x{digits}`"))?;
+                            bytes.push(byte);
+                        }
+                        Some(c) => bail!("invalid escape `\\{c}`"),
+                        None => bail!("unterminated string"),
+                    },
+                    c => {
+                        let mut buf = [0; 4];
+                        bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                    }
+                }
+            }
+            tokens.push(Token::Str(bytes));
+        } else {
+            let mut end = line.len();
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_whitespace() || c == ';' || c == '"' {
+                    end = i;
+                    break;
+                }
+                chars.next();
+            }
+            tokens.push(Token::Word(line[start..end].to_string()));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Quote a string. Valid UTF-8 is kept as is, other bytes are escaped.
+pub fn quote(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() + 2);
+    out.push('"');
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, &[][..]),
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                let invalid_len = e.error_len().unwrap_or(after.len());
+                let valid = std::str::from_utf8(valid).unwrap_or_default();
+                (valid, &after[..invalid_len])
+            }
+        };
+        for c in valid.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() && (c as u32) < 0x80 => {
+                    let _ = write!(out, "\\x{:02x}", c as u32);
+                }
+                c => out.push(c),
+            }
+        }
+        for byte in invalid {
+            let _ = write!(out, "\\x{byte:02x}");
+        }
+        rest = &rest[valid.len() + invalid.len()..];
+    }
+    out.push('"');
+    out
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+pub fn parse_hex(hex: &str) -> Result<Vec<u8>> {
+    let invalid = || anyhow!("invalid hex data `{hex}`");
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+            match pair.len() {
+                2 => u8::from_str_radix(pair, 16).map_err(|_| invalid()),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+/// Dump bytes as lines of hex.
+pub fn hex_lines(bytes: &[u8]) -> Vec<Node> {
+    bytes
+        .chunks(HEX_BYTES_PER_LINE)
+        .map(|chunk| Node::new(hex(chunk)))
+        .collect()
+}
+
+/// Parse the bytes dumped by `hex_lines`.
+pub fn parse_hex_lines(nodes: &[Node]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for node in nodes {
+        for token in &node.tokens {
+            match token {
+                Token::Word(word) => {
+                    bytes.extend(parse_hex(word).map_err(|e| node.error(e))?);
+                }
+                Token::Str(_) => return Err(node.error("expected hex data")),
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_round_trip() {
+        let bytes = b"tab\there \"quoted\" \\ \xc3\xa9t\xc3\xa9 \xff\x00".to_vec();
+        let quoted = quote(&bytes);
+        assert_eq!(quoted, "\"tab\\there \\\"quoted\\\" \\\\ été \\xff\\x00\"");
+        assert_eq!(tokenize(&quoted).unwrap(), vec![Token::Str(bytes)]);
+    }
+
+    #[test]
+    fn parse_blocks_and_comments() {
+        let nodes = parse("a 1 \"x ; y\" ; comment\nb {\n    c {}\n}\n").unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].word(0).unwrap(), "1");
+        assert_eq!(nodes[0].bytes(1).unwrap(), b"x ; y");
+        assert_eq!(nodes[1].block_children().unwrap()[0].keyword(), "c");
+        assert_eq!(print(&nodes), "a 1 \"x ; y\"\nb {\n    c {}\n}\n");
+    }
+}
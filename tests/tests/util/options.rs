@@ -160,27 +160,40 @@ impl PlayerOptions {
         };
 
         if let Some(render_options) = &self.with_renderer {
+            use ruffle_render_software::SoftwareRenderBackend;
             use ruffle_render_wgpu::backend::WgpuRenderBackend;
             use ruffle_render_wgpu::target::TextureTarget;
 
+            let quality = match render_options.sample_count {
+                16 => StageQuality::High16x16,
+                8 => StageQuality::High8x8,
+                4 => StageQuality::High,
+                2 => StageQuality::Medium,
+                _ => StageQuality::Low,
+            };
+
             if let Some(descriptors) = wgpu_descriptors() {
                 if render_options.is_supported(&descriptors.adapter) {
                     let target = TextureTarget::new(&descriptors.device, (width, height))
                         .map_err(|e| anyhow!(e.to_string()))?;
 
-                    player_builder = player_builder
-                        .with_quality(match render_options.sample_count {
-                            16 => StageQuality::High16x16,
-                            8 => StageQuality::High8x8,
-                            4 => StageQuality::High,
-                            2 => StageQuality::Medium,
-                            _ => StageQuality::Low,
-                        })
-                        .with_renderer(
-                            WgpuRenderBackend::new(descriptors.clone(), target)
-                                .map_err(|e| anyhow!(e.to_string()))?,
-                        );
+                    player_builder = player_builder.with_quality(quality).with_renderer(
+                        WgpuRenderBackend::new(descriptors.clone(), target)
+                            .map_err(|e| anyhow!(e.to_string()))?,
+                    );
                 }
+            } else {
+                // Without a graphics adapter, we can still render on the CPU.
+                let scale_factor = self
+                    .viewport_dimensions
+                    .map(|dimensions| dimensions.scale_factor)
+                    .unwrap_or(1.0);
+                let renderer = SoftwareRenderBackend::new(ViewportDimensions {
+                    width,
+                    height,
+                    scale_factor,
+                });
+                player_builder = player_builder.with_quality(quality).with_renderer(renderer);
             }
         }
 
@@ -236,12 +249,12 @@ impl ImageComparison {
         actual_image: image::RgbaImage,
         expected_image: image::RgbaImage,
         test_path: &Path,
-        adapter_info: wgpu::AdapterInfo,
+        renderer_name: &str,
         known_failure: bool,
     ) -> Result<()> {
         use anyhow::Context;
 
-        let suffix = format!("{}-{:?}", std::env::consts::OS, adapter_info.backend);
+        let suffix = format!("{}-{}", std::env::consts::OS, renderer_name);
 
         let save_actual_image = || {
             if !known_failure {
@@ -10,6 +10,7 @@ use ruffle_core::backend::audio::{
 };
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::NullExecutor;
+use ruffle_core::backend::print::MemoryPrintBackend;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
 use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
 use ruffle_core::impl_audio_mixer_backend;
@@ -20,7 +21,6 @@ use ruffle_input_format::{
     AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
     TextControlCode as InputTextControlCode,
 };
-use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_socket_format::SocketEvent;
 use std::cell::RefCell;
 use std::path::Path;
@@ -116,6 +116,7 @@ pub fn run_swf(
     let builder = PlayerBuilder::new()
         .with_log(log.clone())
         .with_navigator(navigator)
+        .with_print(MemoryPrintBackend::default())
         .with_max_execution_duration(Duration::from_secs(300))
         .with_fs_commands(Box::new(fs_command_provider))
         .with_viewport_dimensions(
@@ -135,12 +136,6 @@ pub fn run_swf(
 
     let mut images = test.options.image_comparisons.clone();
 
-    let wgpu_descriptors = if cfg!(feature = "imgtests") && !images.is_empty() {
-        crate::util::environment::wgpu_descriptors()
-    } else {
-        None
-    };
-
     before_start(player.clone())?;
 
     if test.options.num_frames.is_none() && test.options.num_ticks.is_none() {
@@ -186,6 +181,7 @@ pub fn run_swf(
         } else {
             player.lock().unwrap().run_frame();
             player.lock().unwrap().update_timers(frame_time);
+            player.lock().unwrap().update_local_connections();
             player.lock().unwrap().audio_mut().tick();
         }
         remaining_iterations -= 1;
@@ -205,7 +201,6 @@ pub fn run_swf(
                         capture_and_compare_image(
                             base_path,
                             &player,
-                            wgpu_descriptors,
                             &name,
                             image_comparison,
                             test.options.known_failure,
@@ -277,7 +272,6 @@ pub fn run_swf(
             capture_and_compare_image(
                 base_path,
                 &player,
-                wgpu_descriptors,
                 &name,
                 image_comparison,
                 test.options.known_failure,
@@ -297,7 +291,6 @@ pub fn run_swf(
         capture_and_compare_image(
             base_path,
             &player,
-            wgpu_descriptors,
             &name,
             image_comparison,
             test.options.known_failure,
@@ -327,7 +320,6 @@ pub fn run_swf(
 fn capture_and_compare_image(
     _base_path: &Path,
     _player: &Arc<Mutex<Player>>,
-    _wgpu_descriptors: Option<&Arc<Descriptors>>,
     _name: &String,
     _image_comparison: ImageComparison,
     known_failure: bool,
@@ -347,26 +339,43 @@ fn capture_and_compare_image(
 fn capture_and_compare_image(
     base_path: &Path,
     player: &Arc<Mutex<Player>>,
-    wgpu_descriptors: Option<&Arc<Descriptors>>,
     name: &String,
     image_comparison: ImageComparison,
     known_failure: bool,
 ) -> Result<()> {
     use anyhow::Context;
+    use ruffle_render_software::SoftwareRenderBackend;
     use ruffle_render_wgpu::backend::WgpuRenderBackend;
     use ruffle_render_wgpu::target::TextureTarget;
 
-    if let Some(wgpu_descriptors) = wgpu_descriptors {
-        let mut player_lock = player.lock().unwrap();
-        player_lock.render();
-        let renderer = player_lock
-            .renderer_mut()
-            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-            .unwrap();
-
-        let actual_image = renderer.capture_frame().expect("Failed to capture image");
-
-        let expected_image_path = base_path.join(format!("{name}.expected.png"));
+    let mut player_lock = player.lock().unwrap();
+    player_lock.render();
+    let renderer = player_lock.renderer_mut();
+    // The software renderer doesn't match wgpu pixel for pixel, so its
+    // captures are compared against expected images of its own.
+    let capture =
+        if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+            let backend = renderer.descriptors().adapter.get_info().backend;
+            let image = renderer.capture_frame().expect("Failed to capture image");
+            Some((
+                image,
+                format!("{backend:?}"),
+                format!("{name}.expected.png"),
+            ))
+        } else {
+            renderer
+                .downcast_mut::<SoftwareRenderBackend>()
+                .map(|renderer| {
+                    (
+                        renderer.capture_frame(),
+                        "software".to_string(),
+                        format!("{name}.expected-software.png"),
+                    )
+                })
+        };
+
+    if let Some((actual_image, renderer_name, expected_image_name)) = capture {
+        let expected_image_path = base_path.join(expected_image_name);
         if expected_image_path.is_file() {
             let expected_image = image::open(&expected_image_path)
                 .context("Failed to open expected image")?
@@ -377,7 +386,7 @@ fn capture_and_compare_image(
                 actual_image,
                 expected_image,
                 base_path,
-                wgpu_descriptors.adapter.get_info(),
+                &renderer_name,
                 known_failure,
             )?;
         } else if !known_failure {
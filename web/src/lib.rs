@@ -663,9 +663,16 @@ impl Ruffle {
             // Create mouse move handler.
             let mouse_move_callback = Closure::new(move |js_event: PointerEvent| {
                 let _ = ruffle.with_instance(move |instance| {
-                    let event = PlayerEvent::MouseMove {
-                        x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                        y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
+                    let x = f64::from(js_event.offset_x()) * instance.device_pixel_ratio;
+                    let y = f64::from(js_event.offset_y()) * instance.device_pixel_ratio;
+                    let event = if js_event.pointer_type() == "touch" {
+                        PlayerEvent::TouchMove {
+                            id: js_event.pointer_id() as u64,
+                            x,
+                            y,
+                        }
+                    } else {
+                        PlayerEvent::MouseMove { x, y }
                     };
                     let _ = instance.with_core_mut(|core| {
                         core.handle_event(event);
@@ -727,16 +734,25 @@ impl Ruffle {
                             .unchecked_ref::<Element>()
                             .set_pointer_capture(js_event.pointer_id());
                     }
-                    let device_pixel_ratio = instance.device_pixel_ratio;
-                    let event = PlayerEvent::MouseDown {
-                        x: f64::from(js_event.offset_x()) * device_pixel_ratio,
-                        y: f64::from(js_event.offset_y()) * device_pixel_ratio,
-                        button: match js_event.button() {
-                            0 => MouseButton::Left,
-                            1 => MouseButton::Middle,
-                            2 => MouseButton::Right,
-                            _ => MouseButton::Unknown,
-                        },
+                    let x = f64::from(js_event.offset_x()) * instance.device_pixel_ratio;
+                    let y = f64::from(js_event.offset_y()) * instance.device_pixel_ratio;
+                    let event = if js_event.pointer_type() == "touch" {
+                        PlayerEvent::TouchBegin {
+                            id: js_event.pointer_id() as u64,
+                            x,
+                            y,
+                        }
+                    } else {
+                        PlayerEvent::MouseDown {
+                            x,
+                            y,
+                            button: match js_event.button() {
+                                0 => MouseButton::Left,
+                                1 => MouseButton::Middle,
+                                2 => MouseButton::Right,
+                                _ => MouseButton::Unknown,
+                            },
+                        }
                     };
                     let _ = instance.with_core_mut(|core| {
                         core.handle_event(event);
@@ -798,15 +814,25 @@ impl Ruffle {
                             .unchecked_ref::<Element>()
                             .release_pointer_capture(js_event.pointer_id());
                     }
-                    let event = PlayerEvent::MouseUp {
-                        x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                        y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
-                        button: match js_event.button() {
-                            0 => MouseButton::Left,
-                            1 => MouseButton::Middle,
-                            2 => MouseButton::Right,
-                            _ => MouseButton::Unknown,
-                        },
+                    let x = f64::from(js_event.offset_x()) * instance.device_pixel_ratio;
+                    let y = f64::from(js_event.offset_y()) * instance.device_pixel_ratio;
+                    let event = if js_event.pointer_type() == "touch" {
+                        PlayerEvent::TouchEnd {
+                            id: js_event.pointer_id() as u64,
+                            x,
+                            y,
+                        }
+                    } else {
+                        PlayerEvent::MouseUp {
+                            x,
+                            y,
+                            button: match js_event.button() {
+                                0 => MouseButton::Left,
+                                1 => MouseButton::Middle,
+                                2 => MouseButton::Right,
+                                _ => MouseButton::Unknown,
+                            },
+                        }
                     };
                     let _ = instance.with_core_mut(|core| {
                         core.handle_event(event);
@@ -824,6 +850,13 @@ impl Ruffle {
                     mouse_up_callback.as_ref().unchecked_ref(),
                 )
                 .warn_on_error();
+            // A cancelled pointer, such as a touch turned into a scroll, is released too.
+            canvas
+                .add_event_listener_with_callback(
+                    "pointercancel",
+                    mouse_up_callback.as_ref().unchecked_ref(),
+                )
+                .warn_on_error();
             instance.mouse_up_callback = Some(mouse_up_callback);
 
             // Create mouse wheel handler.
@@ -1296,6 +1329,12 @@ impl Drop for RuffleInstance {
                     mouse_up_callback.as_ref().unchecked_ref(),
                 )
                 .warn_on_error();
+            self.canvas
+                .remove_event_listener_with_callback(
+                    "pointercancel",
+                    mouse_up_callback.as_ref().unchecked_ref(),
+                )
+                .warn_on_error();
         }
         if let Some(mouse_wheel_callback) = self.mouse_wheel_callback.take() {
             self.canvas
@@ -141,6 +141,12 @@ impl UiBackend for WebUiBackend {
         &self.language
     }
 
+    fn max_touch_points(&self) -> u32 {
+        web_sys::window().map_or(0, |window| {
+            window.navigator().max_touch_points().max(0) as u32
+        })
+    }
+
     fn display_unsupported_video(&self, url: Url) {
         self.js_player.display_unsupported_video(url.as_str());
     }